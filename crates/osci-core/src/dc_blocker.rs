@@ -0,0 +1,89 @@
+/// One-pole DC-blocking high-pass filter.
+///
+/// `y[n] = x[n] - x[n-1] + r * y[n-1]`, with the pole `r` placed so the
+/// -3 dB point sits at the cutoff. A constant offset decays away with a time
+/// constant of `1 / (2π · cutoff)` seconds while everything well above the
+/// cutoff passes almost untouched.
+#[derive(Debug, Clone)]
+pub struct DcBlocker {
+    cutoff_hz: f32,
+    coeff: f32,
+    last_input: f32,
+    last_output: f32,
+}
+
+impl DcBlocker {
+    pub fn new(cutoff_hz: f32, sample_rate: f32) -> Self {
+        Self {
+            cutoff_hz,
+            coeff: pole(cutoff_hz, sample_rate),
+            last_input: 0.0,
+            last_output: 0.0,
+        }
+    }
+
+    /// Recompute the pole for a new sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.coeff = pole(self.cutoff_hz, sample_rate);
+    }
+
+    /// Forget the filter history.
+    pub fn reset(&mut self) {
+        self.last_input = 0.0;
+        self.last_output = 0.0;
+    }
+
+    /// Filter one sample.
+    pub fn next(&mut self, input: f32) -> f32 {
+        let output = input - self.last_input + self.coeff * self.last_output;
+        self.last_input = input;
+        self.last_output = output;
+        output
+    }
+
+    /// Filter `samples` in place.
+    pub fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            *sample = self.next(*sample);
+        }
+    }
+}
+
+fn pole(cutoff_hz: f32, sample_rate: f32) -> f32 {
+    if sample_rate <= 0.0 {
+        return 1.0;
+    }
+    (-std::f32::consts::TAU * cutoff_hz / sample_rate).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_offset_decays_to_zero() {
+        let mut blocker = DcBlocker::new(5.0, 48000.0);
+        let mut signal = vec![0.5f32; 48000];
+        blocker.process(&mut signal);
+
+        let mean = |s: &[f32]| s.iter().sum::<f32>() / s.len() as f32;
+        assert!(mean(&signal[..4800]) > 0.1);
+        // After 1 s (about 30 time constants) the offset is gone
+        assert!(mean(&signal[43200..]).abs() < 1e-3, "{}", mean(&signal[43200..]));
+    }
+
+    #[test]
+    fn test_passes_audio_band_signal() {
+        let sample_rate = 48000.0;
+        let mut blocker = DcBlocker::new(5.0, sample_rate);
+        let tone: Vec<f32> = (0..48000)
+            .map(|i| 0.25 + (std::f32::consts::TAU * 100.0 * i as f32 / sample_rate).sin())
+            .collect();
+        let mut filtered = tone.clone();
+        blocker.process(&mut filtered);
+
+        // Once settled, only the offset has been removed
+        let peak = filtered[24000..].iter().fold(0.0f32, |acc, s| acc.max(s.abs()));
+        assert!((peak - 1.0).abs() < 0.01, "peak {peak}");
+    }
+}
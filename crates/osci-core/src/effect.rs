@@ -25,6 +25,38 @@ pub trait EffectApplication: Send + Sync {
         frequency: f32,
     ) -> Point;
 
+    /// Process a whole block of samples with constant parameter values.
+    ///
+    /// `outputs[i]` receives the result for `inputs[i]`; only the shorter of
+    /// the two slices is processed. The default calls [`apply`](Self::apply)
+    /// once per sample with a zero external input. Cheap stateless effects
+    /// override this with a tight loop that hoists per-block work (such as
+    /// trigonometry) out of the inner loop. Overrides must produce exactly the
+    /// same output as the per-sample path.
+    fn apply_block(
+        &mut self,
+        inputs: &[Point],
+        outputs: &mut [Point],
+        values: &[f32],
+        sample_rate: f32,
+        frequency: f32,
+    ) {
+        for (i, (input, output)) in inputs.iter().zip(outputs.iter_mut()).enumerate() {
+            *output = self.apply(i, *input, Point::ZERO, values, sample_rate, frequency);
+        }
+    }
+
+    /// Restart any random number generator the effect uses from `seed`.
+    ///
+    /// Effects without randomness ignore this, which is the default.
+    fn set_seed(&mut self, _seed: u64) {}
+
+    /// Clear any state carried between samples, such as a delay line, so
+    /// the effect behaves like a freshly constructed one.
+    ///
+    /// Stateless effects have nothing to clear, which is the default.
+    fn reset(&mut self) {}
+
     /// Clone this effect application for per-voice instances.
     fn clone_effect(&self) -> Box<dyn EffectApplication>;
 
@@ -103,6 +103,32 @@ impl Env {
         )
     }
 
+    /// ADSR envelope with a silent delay before the attack and a hold at the
+    /// peak before the decay.
+    ///
+    /// Levels: [0, 0, level, level, level*sustain_level, 0]
+    /// Times: [delay, attack, hold, decay, release]
+    /// Release node at index 4 (sustains until note off).
+    #[allow(clippy::too_many_arguments)]
+    pub fn dahdsr(
+        delay_time: f64,
+        attack_time: f64,
+        hold_time: f64,
+        decay_time: f64,
+        sustain_level: f64,
+        release_time: f64,
+        level: f64,
+        curve: f32,
+    ) -> Self {
+        Self::new(
+            vec![0.0, 0.0, level, level, level * sustain_level, 0.0],
+            vec![delay_time, attack_time, hold_time, decay_time, release_time],
+            vec![EnvCurve::numerical(curve); 5],
+            4,
+            -1,
+        )
+    }
+
     /// Attack-Sustain-Release envelope.
     pub fn asr(
         attack_time: f64,
@@ -348,6 +374,28 @@ mod tests {
         assert!((env.duration() - 1.31).abs() < 0.001);
     }
 
+    #[test]
+    fn test_dahdsr_stages() {
+        let env = Env::dahdsr(0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 1.0, -4.0);
+        assert_eq!(env.levels, vec![0.0, 0.0, 1.0, 1.0, 0.5, 0.0]);
+        assert_eq!(env.times, vec![0.1, 0.2, 0.3, 0.4, 0.6]);
+        assert_eq!(env.release_node, 4);
+        assert!((env.duration() - 1.6).abs() < 1e-9);
+
+        // Silent through the delay
+        assert_eq!(env.lookup(0.05), 0.0);
+        // Rising during the attack
+        let mid_attack = env.lookup(0.2);
+        assert!(mid_attack > 0.0 && mid_attack < 1.0);
+        // Flat at full level across the hold plateau
+        assert!((env.lookup(0.35) - 1.0).abs() < 1e-6);
+        assert!((env.lookup(0.55) - 1.0).abs() < 1e-6);
+        // Settled at the sustain level once the decay ends
+        assert!((env.lookup(1.0) - 0.5).abs() < 1e-6);
+        // Silent again at the end of the release
+        assert!(env.lookup(1.6).abs() < 1e-6);
+    }
+
     #[test]
     fn test_level_scale() {
         let env = Env::adsr(0.01, 0.3, 0.5, 1.0, 1.0, -4.0);
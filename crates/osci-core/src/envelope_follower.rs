@@ -0,0 +1,142 @@
+/// Amplitude envelope follower for driving visuals from audio.
+///
+/// A one-pole peak follower: the rectified input is tracked with the attack
+/// time constant while it rises and the release time constant while it
+/// falls, so after one time constant of a step the level has covered
+/// `1 - 1/e` (about 63%) of the distance. It does no audio I/O of its own;
+/// feed it samples one at a time with [`EnvelopeFollower::next`] or a block
+/// at a time with [`EnvelopeFollower::process`].
+#[derive(Debug, Clone)]
+pub struct EnvelopeFollower {
+    attack_ms: f32,
+    release_ms: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    level: f32,
+}
+
+impl EnvelopeFollower {
+    pub fn new(attack_ms: f32, release_ms: f32, sample_rate: f32) -> Self {
+        Self {
+            attack_ms,
+            release_ms,
+            attack_coeff: time_constant_coeff(attack_ms, sample_rate),
+            release_coeff: time_constant_coeff(release_ms, sample_rate),
+            level: 0.0,
+        }
+    }
+
+    /// Recompute the smoothing coefficients for a new sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.attack_coeff = time_constant_coeff(self.attack_ms, sample_rate);
+        self.release_coeff = time_constant_coeff(self.release_ms, sample_rate);
+    }
+
+    /// Drop the tracked level back to silence.
+    pub fn reset(&mut self) {
+        self.level = 0.0;
+    }
+
+    /// The current envelope level.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Feed one sample and return the updated level.
+    pub fn next(&mut self, input: f32) -> f32 {
+        let input = input.abs();
+        let coeff = if input > self.level {
+            self.attack_coeff
+        } else {
+            self.release_coeff
+        };
+        self.level = input + coeff * (self.level - input);
+        self.level
+    }
+
+    /// Follow `input`, writing the level after each sample into `output`.
+    ///
+    /// Processes `min(input.len(), output.len())` samples and returns the
+    /// level at the end of the block.
+    pub fn process(&mut self, input: &[f32], output: &mut [f32]) -> f32 {
+        for (out, &sample) in output.iter_mut().zip(input) {
+            *out = self.next(sample);
+        }
+        self.level
+    }
+}
+
+/// Smoothed amplitude of `input`, starting from silence.
+pub fn follow_envelope(input: &[f32], attack_ms: f32, release_ms: f32, sample_rate: f32) -> Vec<f32> {
+    let mut follower = EnvelopeFollower::new(attack_ms, release_ms, sample_rate);
+    let mut output = vec![0.0; input.len()];
+    follower.process(input, &mut output);
+    output
+}
+
+/// One-pole coefficient that covers `1 - 1/e` of a step in `time_ms`.
+fn time_constant_coeff(time_ms: f32, sample_rate: f32) -> f32 {
+    let samples = time_ms * 0.001 * sample_rate;
+    if samples <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / samples).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_MINUS_INV_E: f32 = 0.632_120_6;
+    const INV_E: f32 = 0.367_879_44;
+
+    #[test]
+    fn test_attack_time_constant() {
+        // 10 ms attack at 48 kHz: 480 samples per time constant
+        let step = vec![1.0f32; 4800];
+        let env = follow_envelope(&step, 10.0, 500.0, 48000.0);
+
+        assert!((env[479] - ONE_MINUS_INV_E).abs() < 0.005, "got {}", env[479]);
+        // Three time constants in, 1 - e^-3 of the step is covered
+        assert!((env[1439] - (1.0 - INV_E.powi(3))).abs() < 0.005);
+        assert!(env.windows(2).all(|w| w[1] >= w[0]));
+    }
+
+    #[test]
+    fn test_release_time_constant() {
+        let mut follower = EnvelopeFollower::new(0.0, 20.0, 48000.0);
+        assert_eq!(follower.next(1.0), 1.0);
+
+        // 20 ms release at 48 kHz: 960 samples per time constant
+        let silence = vec![0.0f32; 1920];
+        let mut env = vec![0.0f32; silence.len()];
+        follower.process(&silence, &mut env);
+
+        assert!((env[959] - INV_E).abs() < 0.005, "got {}", env[959]);
+        assert!((env[1919] - INV_E * INV_E).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_follows_rectified_transient() {
+        let mut follower = EnvelopeFollower::new(1.0, 100.0, 48000.0);
+
+        // A negative-going 1 ms burst still rises by one attack time constant
+        let hit = vec![-1.0f32; 48];
+        let mut env = vec![0.0f32; hit.len()];
+        let peak = follower.process(&hit, &mut env);
+        assert!((peak - ONE_MINUS_INV_E).abs() < 0.01, "peak {peak}");
+
+        // Ten time constants later it has effectively reached the input
+        let sustain = vec![1.0f32; 480];
+        let mut env = vec![0.0f32; sustain.len()];
+        let held = follower.process(&sustain, &mut env);
+        assert!(held > 0.999);
+    }
+
+    #[test]
+    fn test_zero_times_follow_instantly() {
+        let env = follow_envelope(&[0.5, -0.25, 0.0], 0.0, 0.0, 44100.0);
+        assert_eq!(env, vec![0.5, 0.25, 0.0]);
+    }
+}
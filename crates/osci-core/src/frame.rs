@@ -31,11 +31,11 @@ impl Frame {
         self.recompute_length();
     }
 
-    /// Normalize all shapes to fit within given dimensions.
-    pub fn normalize_to(&mut self, width: f32, height: f32) {
-        crate::shape::normalize_shapes_to(&mut self.shapes, width, height);
-        self.remove_out_of_bounds();
-        self.recompute_length();
+    /// Normalize all shapes to fit within given dimensions; see
+    /// [`normalize_shapes_to`](crate::shape::normalize_shapes_to).
+    pub fn normalize_to(&mut self, width: f32, height: f32, keep_aspect: bool) {
+        crate::shape::normalize_shapes_to(&mut self.shapes, width, height, keep_aspect);
+        self.clip_to_bounds(Point::xy(-1.0, -1.0), Point::xy(1.0, 1.0));
     }
 
     /// Remove shapes whose endpoints are entirely out of bounds [-1, 1].
@@ -68,12 +68,80 @@ impl Frame {
         }
     }
 
+    /// Clip lines to the box from `min` to `max` in X and Y.
+    ///
+    /// Lines entirely outside the box are removed and lines crossing its
+    /// edge are cut back to the part inside, keeping their depth and colour,
+    /// so the beam doesn't trace off-screen transits. Other shapes are left
+    /// as they are.
+    pub fn clip_to_bounds(&mut self, min: Point, max: Point) {
+        self.shapes.retain_mut(|shape| {
+            if shape.shape_type() != "Line" {
+                return true;
+            }
+            let start = shape.next_vector(0.0);
+            let end = shape.next_vector(1.0);
+            match clip_segment(start, end, min, max) {
+                None => false,
+                Some((t0, t1)) if t0 > 0.0 || t1 < 1.0 => {
+                    *shape = Box::new(clipped_line(shape.next_vector(t0), shape.next_vector(t1)));
+                    true
+                }
+                Some(_) => true,
+            }
+        });
+        self.recompute_length();
+    }
+
     /// Clone all shapes in this frame.
     pub fn clone_shapes(&self) -> Vec<Box<dyn Shape>> {
         self.shapes.iter().map(|s| s.clone_shape()).collect()
     }
 }
 
+/// Liang–Barsky clipping of the segment `start`–`end` against the box from
+/// `min` to `max`. Returns the range of progress along the segment that lies
+/// inside, or `None` if none of it does.
+fn clip_segment(start: Point, end: Point, min: Point, max: Point) -> Option<(f32, f32)> {
+    let dx = end.x - start.x;
+    let dy = end.y - start.y;
+    let edges = [
+        (-dx, start.x - min.x),
+        (dx, max.x - start.x),
+        (-dy, start.y - min.y),
+        (dy, max.y - start.y),
+    ];
+
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    for (p, q) in edges {
+        if p == 0.0 {
+            // Parallel to this edge, so wholly on one side of it
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    (t0 <= t1).then_some((t0, t1))
+}
+
+/// A line between two points taken from a clipped line.
+fn clipped_line(start: Point, end: Point) -> Line {
+    let line = Line::from_points(start, end);
+    // Uncoloured lines carry their depth in every colour channel
+    if start.r == start.z && start.g == start.z && start.b == start.z {
+        line
+    } else {
+        line.with_colour(start.r, start.g, start.b)
+    }
+}
+
 impl Clone for Frame {
     fn clone(&self) -> Self {
         Self {
@@ -82,3 +150,52 @@ impl Clone for Frame {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ends(frame: &Frame) -> Vec<(Point, Point)> {
+        frame.shapes.iter().map(|s| (s.next_vector(0.0), s.next_vector(1.0))).collect()
+    }
+
+    fn clip(line: Line) -> Frame {
+        let mut frame = Frame::new(vec![Box::new(line)]);
+        frame.clip_to_bounds(Point::xy(-1.0, -1.0), Point::xy(1.0, 1.0));
+        frame
+    }
+
+    #[test]
+    fn test_clip_keeps_lines_inside() {
+        let line = Line::new_3d(-0.5, -0.5, 0.2, 0.5, 0.25, 0.8);
+        let frame = clip(line.clone());
+        assert_eq!(ends(&frame), ends(&Frame::new(vec![Box::new(line)])));
+    }
+
+    #[test]
+    fn test_clip_removes_lines_outside() {
+        // Beside the box, and crossing a corner's diagonal without touching it
+        let mut frame = Frame::new(vec![
+            Box::new(Line::new_2d(1.5, -2.0, 1.5, 2.0)),
+            Box::new(Line::new_2d(0.5, 2.0, 2.0, 0.5)),
+        ]);
+        frame.clip_to_bounds(Point::xy(-1.0, -1.0), Point::xy(1.0, 1.0));
+        assert!(frame.shapes.is_empty());
+        assert_eq!(frame.total_length, 0.0);
+    }
+
+    #[test]
+    fn test_clip_cuts_lines_crossing_the_edge() {
+        let frame = clip(Line::new_3d(-2.0, 0.0, 0.0, 2.0, 0.0, 1.0));
+        let (start, end) = ends(&frame)[0];
+        assert!(start.approx_eq(&Point::new(-1.0, 0.0, 0.25)));
+        assert!(end.approx_eq(&Point::new(1.0, 0.0, 0.75)));
+        assert!((frame.total_length - 4.25f32.sqrt()).abs() < 1e-3);
+
+        // One end inside, and the colour is kept
+        let frame = clip(Line::new_2d(0.0, 0.0, 0.0, 3.0).with_colour(1.0, 0.0, 0.0));
+        let (start, end) = ends(&frame)[0];
+        assert!(start.approx_eq(&Point::xy(0.0, 0.0).with_colour(1.0, 0.0, 0.0)));
+        assert!(end.approx_eq(&Point::xy(0.0, 1.0).with_colour(1.0, 0.0, 0.0)));
+    }
+}
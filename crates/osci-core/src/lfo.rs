@@ -13,6 +13,11 @@ pub struct LfoState {
     pub start_percent: f32,
     pub end_percent: f32,
     pub rng_state: u32,
+    /// Smooth-noise segment endpoints, normalized to [0, 1] of the LFO range.
+    #[serde(skip)]
+    pub noise_from: f32,
+    #[serde(skip)]
+    pub noise_to: f32,
 }
 
 impl Default for LfoState {
@@ -24,6 +29,8 @@ impl Default for LfoState {
             start_percent: 0.0,
             end_percent: 100.0,
             rng_state: 0x12345678,
+            noise_from: 0.5,
+            noise_to: 0.5,
         }
     }
 }
@@ -53,6 +60,22 @@ impl LfoState {
                 let rnd = (self.rng_state & 0x00FFFFFF) as f32 / 16777215.0;
                 rnd * lfo_range + lfo_min
             }
+            LfoType::SmoothNoise => {
+                if sample_rate > 0.0 {
+                    self.phase += self.rate / sample_rate;
+                    if self.phase >= 1.0 {
+                        self.phase -= 1.0;
+                        self.rng_state ^= self.rng_state << 13;
+                        self.rng_state ^= self.rng_state >> 17;
+                        self.rng_state ^= self.rng_state << 5;
+                        self.noise_from = self.noise_to;
+                        self.noise_to = (self.rng_state & 0x00FFFFFF) as f32 / 16777215.0;
+                    }
+                }
+                let t = self.phase;
+                let eased = t * t * (3.0 - 2.0 * t);
+                (self.noise_from + (self.noise_to - self.noise_from) * eased) * lfo_range + lfo_min
+            }
             _ => {
                 // Advance phase
                 if sample_rate > 0.0 {
@@ -108,6 +131,8 @@ impl LfoState {
     pub fn reset(&mut self) {
         self.phase = 0.0;
         self.rng_state = 0x12345678;
+        self.noise_from = 0.5;
+        self.noise_to = 0.5;
     }
 }
 
@@ -1,15 +1,21 @@
 pub mod point;
 pub mod shape;
 pub mod frame;
+pub mod morph;
 pub mod effect;
 pub mod parameter;
 pub mod envelope;
 pub mod lfo;
+pub mod envelope_follower;
+pub mod dc_blocker;
 
 pub use point::Point;
-pub use shape::{Shape, Line, CubicBezierCurve, QuadraticBezierCurve, CircleArc};
+pub use shape::{Shape, NormalizeMode, Coloured, Line, CubicBezierCurve, QuadraticBezierCurve, CircleArc, Reversed};
 pub use frame::Frame;
+pub use morph::ShapeMorph;
 pub use effect::{EffectApplication, EffectContext};
-pub use parameter::{EffectParameter, LfoType};
+pub use parameter::{derive_seed, randomize_parameters, EffectParameter, LfoType, SmoothCurve};
 pub use envelope::{Env, EnvCurve, EnvCurveType};
 pub use lfo::LfoState;
+pub use envelope_follower::EnvelopeFollower;
+pub use dc_blocker::DcBlocker;
@@ -0,0 +1,198 @@
+use crate::point::Point;
+use crate::shape::{Line, Shape};
+
+/// Segments each curve is split into when morphing shape by shape.
+pub const MORPH_CURVE_SEGMENTS: usize = 16;
+
+/// Points both frames are resampled to when their shapes don't line up.
+pub const MORPH_RESAMPLE_POINTS: usize = 512;
+
+/// Vertex positions of two frames paired up for interpolating between them.
+///
+/// When both frames have the same number of shapes of the same types,
+/// each shape morphs into its partner: lines by their end points, curves
+/// as polylines of [`MORPH_CURVE_SEGMENTS`] segments. Otherwise both
+/// frames are resampled by arc length to [`MORPH_RESAMPLE_POINTS`] points
+/// and morphed as one polyline.
+///
+/// Building a morph resamples both frames, so do it off the audio thread;
+/// tracing one with [`ShapeMorph::point_at`] doesn't allocate.
+pub struct ShapeMorph {
+    /// Matching polylines for the start and end of the morph.
+    paths: Vec<(Vec<Point>, Vec<Point>)>,
+    /// Every segment of `paths`, in drawing order.
+    segments: Vec<MorphSegment>,
+}
+
+/// One pair of matching segments, with how far through each frame it ends.
+struct MorphSegment {
+    from: (Point, Point),
+    to: (Point, Point),
+    // Fraction of the first and second frame's length drawn by the segment's end
+    from_end: f32,
+    to_end: f32,
+}
+
+impl ShapeMorph {
+    pub fn new(from: &[Box<dyn Shape>], to: &[Box<dyn Shape>]) -> Self {
+        let paths = morph_paths(from, to);
+        let segments = morph_segments(&paths);
+        Self { paths, segments }
+    }
+
+    /// The morph `t` of the way from the first frame (0) to the second (1).
+    pub fn frame_at(&self, t: f32) -> Vec<Box<dyn Shape>> {
+        let mut frame = Vec::new();
+        self.fill_at(t, &mut frame);
+        frame
+    }
+
+    /// Like [`ShapeMorph::frame_at`], reusing `frame`'s allocation.
+    pub fn fill_at(&self, t: f32, frame: &mut Vec<Box<dyn Shape>>) {
+        let t = t.clamp(0.0, 1.0);
+        frame.clear();
+        for (from, to) in &self.paths {
+            let mut previous: Option<Point> = None;
+            for (a, b) in from.iter().zip(to) {
+                let point = *a + (*b - *a) * t;
+                if let Some(start) = previous {
+                    frame.push(Box::new(Line::from_points(start, point)));
+                }
+                previous = Some(point);
+            }
+        }
+    }
+
+    /// The point `progress` of the way along the morph `t` of the way from
+    /// the first frame to the second, where `progress` runs from 0 to 1
+    /// over one drawing of the frame.
+    pub fn point_at(&self, progress: f32, t: f32) -> Point {
+        let t = t.clamp(0.0, 1.0);
+        let end = |segment: &MorphSegment| segment.from_end + (segment.to_end - segment.from_end) * t;
+        let index = self
+            .segments
+            .partition_point(|segment| end(segment) < progress)
+            .min(self.segments.len().saturating_sub(1));
+        let Some(segment) = self.segments.get(index) else {
+            return Point::ZERO;
+        };
+
+        let start = if index > 0 { end(&self.segments[index - 1]) } else { 0.0 };
+        let span = end(segment) - start;
+        let f = if span > 0.0 { ((progress - start) / span).clamp(0.0, 1.0) } else { 0.0 };
+        let a = segment.from.0 + (segment.from.1 - segment.from.0) * f;
+        let b = segment.to.0 + (segment.to.1 - segment.to.0) * f;
+        a + (b - a) * t
+    }
+}
+
+/// Pair up polylines for morphing `from` into `to`.
+fn morph_paths(from: &[Box<dyn Shape>], to: &[Box<dyn Shape>]) -> Vec<(Vec<Point>, Vec<Point>)> {
+    let matched = from.len() == to.len()
+        && from.iter().zip(to).all(|(a, b)| a.shape_type() == b.shape_type());
+    if matched {
+        from.iter()
+            .zip(to)
+            .map(|(a, b)| {
+                let segments = if a.shape_type() == "Line" { 1 } else { MORPH_CURVE_SEGMENTS };
+                (sample_shape(a.as_ref(), segments), sample_shape(b.as_ref(), segments))
+            })
+            .collect()
+    } else {
+        vec![(
+            resample_frame(from, MORPH_RESAMPLE_POINTS),
+            resample_frame(to, MORPH_RESAMPLE_POINTS),
+        )]
+    }
+}
+
+/// Flatten `paths` into segments, each knowing how far along both frames
+/// it ends. Frames with no length are drawn at an even pace instead.
+fn morph_segments(paths: &[(Vec<Point>, Vec<Point>)]) -> Vec<MorphSegment> {
+    let mut segments: Vec<MorphSegment> = paths
+        .iter()
+        .flat_map(|(from, to)| {
+            from.windows(2).zip(to.windows(2)).map(|(a, b)| MorphSegment {
+                from: (a[0], a[1]),
+                to: (b[0], b[1]),
+                from_end: (a[1] - a[0]).magnitude(),
+                to_end: (b[1] - b[0]).magnitude(),
+            })
+        })
+        .collect();
+
+    let count = segments.len() as f32;
+    let from_total: f32 = segments.iter().map(|s| s.from_end).sum();
+    let to_total: f32 = segments.iter().map(|s| s.to_end).sum();
+    let (mut from_drawn, mut to_drawn) = (0.0, 0.0);
+    for segment in &mut segments {
+        from_drawn += if from_total > 0.0 { segment.from_end / from_total } else { 1.0 / count };
+        to_drawn += if to_total > 0.0 { segment.to_end / to_total } else { 1.0 / count };
+        segment.from_end = from_drawn;
+        segment.to_end = to_drawn;
+    }
+    segments
+}
+
+fn sample_shape(shape: &dyn Shape, segments: usize) -> Vec<Point> {
+    (0..=segments)
+        .map(|i| shape.next_vector(i as f32 / segments as f32))
+        .collect()
+}
+
+/// `count` points spaced evenly along the whole frame by arc length.
+fn resample_frame(frame: &[Box<dyn Shape>], count: usize) -> Vec<Point> {
+    let total: f32 = frame.iter().map(|s| s.length()).sum();
+    if frame.is_empty() {
+        return vec![Point::ZERO; count];
+    }
+
+    let mut points = Vec::with_capacity(count);
+    let mut shape = 0;
+    let mut shape_start = 0.0;
+    for i in 0..count {
+        let distance = total * i as f32 / (count - 1).max(1) as f32;
+        // Move on to the shape containing this distance
+        while shape + 1 < frame.len() && shape_start + frame[shape].length() < distance {
+            shape_start += frame[shape].length();
+            shape += 1;
+        }
+        let length = frame[shape].length();
+        let t = if length > 0.0 { (distance - shape_start) / length } else { 0.0 };
+        points.push(frame[shape].next_vector(t.clamp(0.0, 1.0)));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_at_traces_the_blended_frame() {
+        let from: Vec<Box<dyn Shape>> = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0)),
+            Box::new(Line::new_2d(1.0, 0.0, 1.0, 1.0)),
+        ];
+        let to: Vec<Box<dyn Shape>> = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 0.0, -1.0)),
+            Box::new(Line::new_2d(0.0, -1.0, -1.0, -1.0)),
+        ];
+        let morph = ShapeMorph::new(&from, &to);
+
+        for t in [0.0, 0.5, 1.0] {
+            let frame = morph.frame_at(t);
+            assert!(morph.point_at(0.0, t).approx_eq(&frame[0].next_vector(0.0)));
+            assert!(morph.point_at(0.25, t).approx_eq(&frame[0].next_vector(0.5)));
+            assert!(morph.point_at(0.5, t).approx_eq(&frame[0].next_vector(1.0)));
+            assert!(morph.point_at(0.75, t).approx_eq(&frame[1].next_vector(0.5)));
+            assert!(morph.point_at(1.0, t).approx_eq(&frame[1].next_vector(1.0)));
+        }
+    }
+
+    #[test]
+    fn test_point_at_empty_morph_is_origin() {
+        let morph = ShapeMorph::new(&[], &[]);
+        assert_eq!(morph.point_at(0.5, 0.5), Point::ZERO);
+    }
+}
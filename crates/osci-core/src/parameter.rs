@@ -19,6 +19,8 @@ pub enum LfoType {
     Sawtooth = 6,
     ReverseSawtooth = 7,
     Noise = 8,
+    /// Value noise: eases between random targets, one per LFO cycle.
+    SmoothNoise = 9,
 }
 
 impl LfoType {
@@ -32,6 +34,7 @@ impl LfoType {
             6 => LfoType::Sawtooth,
             7 => LfoType::ReverseSawtooth,
             8 => LfoType::Noise,
+            9 => LfoType::SmoothNoise,
             _ => LfoType::Static,
         }
     }
@@ -46,6 +49,51 @@ impl LfoType {
             LfoType::Sawtooth => "Sawtooth",
             LfoType::ReverseSawtooth => "Reverse Sawtooth",
             LfoType::Noise => "Noise",
+            LfoType::SmoothNoise => "Smooth Noise",
+        }
+    }
+}
+
+/// How a static parameter eases towards a new value.
+///
+/// Each sample the value moves `smooth_value_change` (scaled to the sample
+/// rate) of the way to its target, times a factor depending on how far off
+/// it still is, as a fraction of the parameter's range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SmoothCurve {
+    /// The same fraction of the remaining distance every sample.
+    #[default]
+    Linear,
+    /// Quick while far from the target, settling in more gradually.
+    Exponential,
+    /// Gentle to start, then speeding up to land on the target.
+    Logarithmic,
+}
+
+impl SmoothCurve {
+    pub const ALL: [SmoothCurve; 3] = [
+        SmoothCurve::Linear,
+        SmoothCurve::Exponential,
+        SmoothCurve::Logarithmic,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SmoothCurve::Linear => "Linear",
+            SmoothCurve::Exponential => "Exponential",
+            SmoothCurve::Logarithmic => "Logarithmic",
+        }
+    }
+
+    /// Factor on the smoothing weight at `distance` from the target, as a
+    /// fraction of the range. The curves cross linear at a half and a
+    /// quarter of the range respectively. Exponential never drops below a
+    /// quarter of linear's speed, so it still settles in reasonable time.
+    fn factor(&self, distance: f32) -> f32 {
+        match self {
+            SmoothCurve::Linear => 1.0,
+            SmoothCurve::Exponential => (2.0 * distance).max(0.25),
+            SmoothCurve::Logarithmic => 0.5 / distance.sqrt(),
         }
     }
 }
@@ -104,18 +152,33 @@ pub struct EffectParameter {
     pub lfo_start_percent: f32,
     pub lfo_end_percent: f32,
     pub lfo_enabled: bool,
+    /// When set, the LFO swings around `value` instead of spanning the
+    /// start/end percentages; the span sets the depth.
+    #[serde(default)]
+    pub lfo_bipolar: bool,
 
     // Smoothing
     pub smooth_value_change: f32,
+    #[serde(default)]
+    pub smooth_curve: SmoothCurve,
 
     // Audio-thread state (not serialized directly)
     #[serde(skip)]
     pub phase: f32,
     #[serde(skip)]
     pub rng_state: u32,
+    /// Smooth-noise segment endpoints, normalized to [0, 1] of the LFO range.
+    #[serde(skip)]
+    pub noise_from: f32,
+    #[serde(skip)]
+    pub noise_to: f32,
 
     // Sidechain
     pub sidechain_enabled: bool,
+
+    /// Keeps the value fixed when the chain is randomized.
+    #[serde(default)]
+    pub locked: bool,
 }
 
 impl EffectParameter {
@@ -141,10 +204,15 @@ impl EffectParameter {
             lfo_start_percent: 0.0,
             lfo_end_percent: 100.0,
             lfo_enabled: true,
+            lfo_bipolar: false,
             smooth_value_change: SMOOTHING_SPEED_CONSTANT,
+            smooth_curve: SmoothCurve::Linear,
             phase: 0.0,
             rng_state: 0x12345678,
+            noise_from: 0.5,
+            noise_to: 0.5,
             sidechain_enabled: false,
+            locked: false,
         }
     }
 
@@ -182,13 +250,27 @@ impl EffectParameter {
     pub fn reset_to_default(&mut self) {
         self.value = self.default_value;
         self.smooth_value_change = SMOOTHING_SPEED_CONSTANT;
+        self.smooth_curve = SmoothCurve::Linear;
         self.lfo_type = LfoType::Static;
         self.lfo_rate = 1.0;
         self.lfo_start_percent = 0.0;
         self.lfo_end_percent = 100.0;
+        self.lfo_bipolar = false;
         self.sidechain_enabled = false;
         self.phase = 0.0;
         self.rng_state = 0x12345678;
+        self.noise_from = 0.5;
+        self.noise_to = 0.5;
+    }
+
+    /// Restart the Noise and Smooth Noise LFOs from `seed`.
+    ///
+    /// The noise state is not saved with the parameter, so a fresh parameter
+    /// always starts from the same fixed seed; this picks a different stream.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng_state = xorshift_seed(seed);
+        self.noise_from = 0.5;
+        self.noise_to = 0.5;
     }
 
     /// Compute the LFO range in parameter units.
@@ -200,6 +282,51 @@ impl EffectParameter {
     }
 }
 
+/// Derive an independent seed for sub-stream `stream` of `seed`.
+///
+/// Uses the SplitMix64 finalizer, so neighbouring streams (voice 0 and
+/// voice 1, say) get unrelated seeds rather than ones a bit apart.
+pub fn derive_seed(seed: u64, stream: u64) -> u64 {
+    let mut z = seed.wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9E3779B97F4A7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Fold a 64-bit seed into a starting state for xorshift32, which gets
+/// stuck on zero.
+pub fn xorshift_seed(seed: u64) -> u32 {
+    ((seed ^ (seed >> 32)) as u32).max(1)
+}
+
+/// Set every parameter to a random value in its `[min, max]` range.
+///
+/// The same `seed` always produces the same values, so a result worth
+/// keeping can be found again. With `respect_locks` set, locked parameters
+/// keep their values. Values snap to the parameter's step.
+pub fn randomize_parameters<'a>(
+    params: impl IntoIterator<Item = &'a mut EffectParameter>,
+    seed: u32,
+    respect_locks: bool,
+) {
+    // xorshift32 gets stuck on zero
+    let mut state = seed.max(1);
+    for param in params {
+        if respect_locks && param.locked {
+            continue;
+        }
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        let rnd = (state & 0x00FFFFFF) as f32 / 16777215.0;
+        let mut value = param.min + rnd * (param.max - param.min);
+        if param.step > 0.0 {
+            value = param.min + ((value - param.min) / param.step).round() * param.step;
+        }
+        param.value = value.clamp(param.min.min(param.max), param.min.max(param.max));
+    }
+}
+
 /// Animate a block of parameter values, computing per-sample values with
 /// LFO modulation and smoothing.
 ///
@@ -226,6 +353,7 @@ pub fn animate_parameter(
             svc * (192000.0 / sample_rate) * 0.001
         };
 
+        let curve = param.smooth_curve;
         let use_sidechain = param.sidechain_enabled;
         let static_target = if use_sidechain { 0.0 } else { param.value };
 
@@ -245,8 +373,12 @@ pub fn animate_parameter(
                 let diff = (*current_value - target).abs();
                 if diff < EFFECT_SNAP_THRESHOLD {
                     *current_value = target;
-                } else {
+                } else if curve == SmoothCurve::Linear {
                     *current_value += weight * (target - *current_value);
+                } else {
+                    let distance = if range != 0.0 { (diff / range.abs()).min(1.0) } else { 1.0 };
+                    let step = (weight * curve.factor(distance)).min(1.0);
+                    *current_value += step * (target - *current_value);
                 }
             }
             output[i] = *current_value;
@@ -271,6 +403,24 @@ pub fn animate_parameter(
                     output[i] = rnd * lfo_range + lfo_min;
                 }
             }
+            LfoType::SmoothNoise => {
+                for out in output.iter_mut() {
+                    param.phase += phase_inc;
+                    if param.phase >= 1.0 {
+                        param.phase -= 1.0;
+                        // xorshift32 PRNG picks the next target
+                        param.rng_state ^= param.rng_state << 13;
+                        param.rng_state ^= param.rng_state >> 17;
+                        param.rng_state ^= param.rng_state << 5;
+                        param.noise_from = param.noise_to;
+                        param.noise_to = (param.rng_state & 0x00FFFFFF) as f32 / 16777215.0;
+                    }
+                    let t = param.phase;
+                    let eased = t * t * (3.0 - 2.0 * t); // smoothstep
+                    let v = param.noise_from + (param.noise_to - param.noise_from) * eased;
+                    *out = v * lfo_range + lfo_min;
+                }
+            }
             _ => {
                 // Phase ramp
                 for i in 0..block_size {
@@ -332,6 +482,17 @@ pub fn animate_parameter(
             }
         }
 
+        if param.lfo_bipolar {
+            // Re-center the swing on the parked value: half the percent span
+            // either side of it, kept inside the parameter range
+            let center = (lfo_min + lfo_max) * 0.5;
+            let offset = param.value - center;
+            let (lo, hi) = (param.min.min(param.max), param.min.max(param.max));
+            for v in output.iter_mut() {
+                *v = (*v + offset).clamp(lo, hi);
+            }
+        }
+
         *current_value = output[block_size - 1];
     }
 }
@@ -342,7 +503,7 @@ mod tests {
 
     #[test]
     fn test_lfo_type_roundtrip() {
-        for i in 1..=8 {
+        for i in 1..=9 {
             let t = LfoType::from_i32(i);
             assert_eq!(t as i32, i);
         }
@@ -366,6 +527,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_exponential_smoothing_is_quick_then_gradual() {
+        let settle = |curve: SmoothCurve| {
+            let mut param = EffectParameter::new("Test", "Test", "test", 1.0, 0.0, 1.0);
+            param.smooth_curve = curve;
+            let mut output = vec![0.0f32; 44100];
+            let mut current = 0.0;
+            animate_parameter(&mut param, &mut output, 44100.0, &mut current, None);
+            output
+        };
+        let linear = settle(SmoothCurve::Linear);
+        let exponential = settle(SmoothCurve::Exponential);
+        let logarithmic = settle(SmoothCurve::Logarithmic);
+
+        // About a tenth of linear's time constant in, then three of them
+        let (early, late) = (76, 2300);
+        assert!(exponential[early] > linear[early], "{} vs {}", exponential[early], linear[early]);
+        assert!(exponential[late] < linear[late], "{} vs {}", exponential[late], linear[late]);
+        assert!(logarithmic[early] < linear[early]);
+        // Every curve lands on the target within a second
+        for output in [&linear, &exponential, &logarithmic] {
+            assert!(output.windows(2).all(|w| w[1] >= w[0]));
+            assert_eq!(output.last(), Some(&1.0));
+        }
+    }
+
     #[test]
     fn test_animate_sine_lfo() {
         let mut param = EffectParameter::new("Test", "Test", "test", 0.5, 0.0, 1.0);
@@ -381,6 +568,119 @@ mod tests {
         assert!(max > 0.9);
     }
 
+    #[test]
+    fn test_animate_smooth_noise_is_continuous_and_aperiodic() {
+        let mut param = EffectParameter::new("Test", "Test", "test", 0.5, 0.0, 1.0);
+        param.lfo_type = LfoType::SmoothNoise;
+        param.lfo_rate = 10.0;
+        let sample_rate = 1000.0;
+        let mut output = vec![0.0f32; 20000];
+        let mut current = 0.5;
+        animate_parameter(&mut param, &mut output, sample_rate, &mut current, None);
+
+        // Smoothstep's slope peaks at 1.5, so one sample moves at most
+        // 1.5 * range * rate / sample_rate
+        let max_step = 1.5 * 10.0 / sample_rate + 1e-4;
+        for pair in output.windows(2) {
+            assert!((pair[1] - pair[0]).abs() <= max_step);
+        }
+
+        // One LFO cycle is 100 samples; the wave must not repeat cycle to cycle
+        let period = 100;
+        let repeats = (0..output.len() - period)
+            .filter(|&i| (output[i] - output[i + period]).abs() < 1e-3)
+            .count();
+        assert!(repeats < output.len() / 10);
+
+        let min = output.iter().cloned().fold(f32::MAX, f32::min);
+        let max = output.iter().cloned().fold(f32::MIN, f32::max);
+        assert!(min >= 0.0 && max <= 1.0);
+        assert!(max - min > 0.5);
+    }
+
+    #[test]
+    fn test_bipolar_lfo_centers_on_value() {
+        let mut absolute = EffectParameter::new("Test", "Test", "test", 0.7, 0.0, 1.0);
+        absolute.lfo_type = LfoType::Sine;
+        absolute.lfo_rate = 1.0;
+        absolute.lfo_start_percent = 0.0;
+        absolute.lfo_end_percent = 40.0;
+        let mut bipolar = absolute.clone();
+        bipolar.lfo_bipolar = true;
+
+        let mut abs_out = vec![0.0f32; 44100];
+        let mut bi_out = vec![0.0f32; 44100];
+        let (mut c1, mut c2) = (0.0, 0.0);
+        animate_parameter(&mut absolute, &mut abs_out, 44100.0, &mut c1, None);
+        animate_parameter(&mut bipolar, &mut bi_out, 44100.0, &mut c2, None);
+
+        // Absolute mode spans 0%..40% of the range regardless of the value
+        let abs_min = abs_out.iter().cloned().fold(f32::MAX, f32::min);
+        let abs_max = abs_out.iter().cloned().fold(f32::MIN, f32::max);
+        assert!(abs_min < 0.01 && (abs_max - 0.4).abs() < 0.01);
+
+        // Bipolar mode has the same depth, shifted to swing around 0.7
+        let bi_min = bi_out.iter().cloned().fold(f32::MAX, f32::min);
+        let bi_max = bi_out.iter().cloned().fold(f32::MIN, f32::max);
+        assert!((bi_min - 0.5).abs() < 0.01 && (bi_max - 0.9).abs() < 0.01);
+        for (a, b) in abs_out.iter().zip(&bi_out) {
+            assert!((b - a - 0.5).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_bipolar_lfo_clamps_to_range() {
+        let mut param = EffectParameter::new("Test", "Test", "test", 0.9, 0.0, 1.0);
+        param.lfo_type = LfoType::Triangle;
+        param.lfo_rate = 1.0;
+        param.lfo_end_percent = 60.0;
+        param.lfo_bipolar = true;
+
+        let mut output = vec![0.0f32; 44100];
+        let mut current = 0.0;
+        animate_parameter(&mut param, &mut output, 44100.0, &mut current, None);
+
+        let min = output.iter().cloned().fold(f32::MAX, f32::min);
+        let max = output.iter().cloned().fold(f32::MIN, f32::max);
+        assert!((min - 0.6).abs() < 0.01);
+        assert_eq!(max, 1.0);
+    }
+
+    #[test]
+    fn test_randomize_skips_locked_parameters() {
+        let make = || {
+            let mut params: Vec<EffectParameter> = (0..8)
+                .map(|i| EffectParameter::new("P", "", format!("p{i}"), 0.5, 0.0, 1.0))
+                .collect();
+            for p in params.iter_mut().step_by(2) {
+                p.locked = true;
+            }
+            params
+        };
+
+        let mut params = make();
+        randomize_parameters(params.iter_mut(), 42, true);
+        for p in &params {
+            if p.locked {
+                assert_eq!(p.value, 0.5);
+            } else {
+                assert_ne!(p.value, 0.5);
+                assert!((0.0..=1.0).contains(&p.value));
+            }
+        }
+
+        // Same seed, same values
+        let mut again = make();
+        randomize_parameters(again.iter_mut(), 42, true);
+        let values = |ps: &[EffectParameter]| ps.iter().map(|p| p.value).collect::<Vec<_>>();
+        assert_eq!(values(&params), values(&again));
+
+        // Ignoring locks changes everything
+        let mut all = make();
+        randomize_parameters(all.iter_mut(), 42, false);
+        assert!(all.iter().all(|p| p.value != 0.5));
+    }
+
     #[test]
     fn test_atomic_f32() {
         let a = AtomicF32::new(3.14);
@@ -1,4 +1,5 @@
 use crate::point::Point;
+use std::any::Any;
 
 /// A drawable shape that can be sampled at any drawing progress [0, 1].
 ///
@@ -20,8 +21,23 @@ pub trait Shape: Send + Sync {
     /// Clone this shape into a boxed trait object.
     fn clone_shape(&self) -> Box<dyn Shape>;
 
+    /// Copy this shape into `target`, reusing its box if it already holds a
+    /// shape of the same kind.
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        *target = self.clone_shape();
+    }
+
+    /// This shape as [`Any`], so [`Shape::clone_into_box`] can tell what a
+    /// box holds.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
     /// Shape type name for debugging.
     fn shape_type(&self) -> &'static str;
+
+    /// A copy of this shape traced from end to start.
+    fn reversed(&self) -> Box<dyn Shape> {
+        Box::new(Reversed::new(self.clone_shape()))
+    }
 }
 
 /// Compute total path length of a collection of shapes.
@@ -29,6 +45,136 @@ pub fn total_length(shapes: &[Box<dyn Shape>]) -> f32 {
     shapes.iter().map(|s| s.length()).sum()
 }
 
+/// Reverse a path: draw the shapes in the opposite order, each from end to start.
+pub fn reverse_shapes(shapes: &mut [Box<dyn Shape>]) {
+    shapes.reverse();
+    for shape in shapes.iter_mut() {
+        *shape = shape.reversed();
+    }
+}
+
+/// Copy `source` into `target`, reusing `target`'s boxes wherever they hold
+/// shapes of the same kind, so refilling a frame with one of the same
+/// structure doesn't allocate.
+pub fn clone_shapes_into(source: &[Box<dyn Shape>], target: &mut Vec<Box<dyn Shape>>) {
+    target.truncate(source.len());
+    let reused = target.len();
+    for (shape, slot) in source.iter().zip(target.iter_mut()) {
+        shape.clone_into_box(slot);
+    }
+    target.extend(source[reused..].iter().map(|s| s.clone_shape()));
+}
+
+/// [`Shape::clone_into_box`] for plain shapes that are copied whole.
+fn clone_into_box_of<S: Shape + Clone + 'static>(shape: &S, target: &mut Box<dyn Shape>) {
+    match target.as_any_mut().downcast_mut::<S>() {
+        Some(existing) => existing.clone_from(shape),
+        None => *target = Box::new(shape.clone()),
+    }
+}
+
+/// A copy of shape `index` of `shapes` on its own, for stepping through an
+/// import one stroke at a time. Empty if `index` is out of range.
+pub fn isolate_shape(shapes: &[Box<dyn Shape>], index: usize) -> Vec<Box<dyn Shape>> {
+    shapes.get(index).map(|s| s.clone_shape()).into_iter().collect()
+}
+
+/// Gap below which one line is treated as continuing from the previous one.
+const CHAIN_EPSILON: f32 = 1e-6;
+
+/// Collapse near-collinear runs of lines with Ramer–Douglas–Peucker.
+///
+/// Consecutive lines that join end to start form a polyline, which is
+/// replaced by the fewest of its own vertices that keep every dropped vertex
+/// within `epsilon` of the simplified path. Curves, arcs and points pass
+/// through untouched and break the run, as do gaps between lines. An
+/// `epsilon` of zero or less returns the shapes unchanged.
+pub fn simplify_shapes(shapes: Vec<Box<dyn Shape>>, epsilon: f32) -> Vec<Box<dyn Shape>> {
+    if epsilon <= 0.0 {
+        return shapes;
+    }
+
+    let mut simplified: Vec<Box<dyn Shape>> = Vec::with_capacity(shapes.len());
+    let mut run: Vec<Point> = Vec::new();
+    let mut run_colour = None;
+    for shape in shapes {
+        if shape.shape_type() != "Line" {
+            flush_polyline(&mut run, epsilon, run_colour, &mut simplified);
+            simplified.push(shape);
+            continue;
+        }
+        let (start, end) = (shape.next_vector(0.0), shape.next_vector(1.0));
+        let colour = explicit_colour(&start);
+        let joined = colour == run_colour
+            && run.last().is_some_and(|last| (start - *last).magnitude() <= CHAIN_EPSILON);
+        if !joined {
+            flush_polyline(&mut run, epsilon, run_colour, &mut simplified);
+            run_colour = colour;
+            run.push(start);
+        }
+        run.push(end);
+    }
+    flush_polyline(&mut run, epsilon, run_colour, &mut simplified);
+    simplified
+}
+
+/// The colour of a point unless it is the default one following `z`.
+fn explicit_colour(point: &Point) -> Option<[f32; 3]> {
+    let legacy = point.r == point.z && point.g == point.z && point.b == point.z;
+    (!legacy).then_some([point.r, point.g, point.b])
+}
+
+/// Simplify the polyline through `points` into `out` as lines drawn in
+/// `colour`, leaving `points` empty.
+fn flush_polyline(
+    points: &mut Vec<Point>,
+    epsilon: f32,
+    colour: Option<[f32; 3]>,
+    out: &mut Vec<Box<dyn Shape>>,
+) {
+    if points.len() < 2 {
+        points.clear();
+        return;
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let farthest = (first + 1..last)
+            .map(|i| (i, distance_to_segment(points[i], points[first], points[last])))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, distance)) = farthest {
+            if distance > epsilon {
+                keep[i] = true;
+                stack.push((first, i));
+                stack.push((i, last));
+            }
+        }
+    }
+
+    let kept: Vec<Point> = points.iter().zip(&keep).filter(|(_, k)| **k).map(|(p, _)| *p).collect();
+    for pair in kept.windows(2) {
+        let mut line = Line::from_points(pair[0], pair[1]);
+        line.colour = colour;
+        out.push(Box::new(line));
+    }
+    points.clear();
+}
+
+/// Distance from `point` to the segment `a`–`b`.
+fn distance_to_segment(point: Point, a: Point, b: Point) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.inner_product(&ab);
+    let t = if length_sq > 0.0 {
+        ((point - a).inner_product(&ab) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (point - (a + ab * t)).magnitude()
+}
+
 /// Normalize shapes to fit within [-1, 1] coordinate range.
 pub fn normalize_shapes(shapes: &mut [Box<dyn Shape>]) {
     let h = shapes_height(shapes);
@@ -51,17 +197,92 @@ pub fn normalize_shapes(shapes: &mut [Box<dyn Shape>]) {
     }
 }
 
-/// Normalize shapes to fit within a given width/height.
-pub fn normalize_shapes_to(shapes: &mut [Box<dyn Shape>], width: f32, height: f32) {
+/// How imported shapes are fitted into the [-1, 1] square.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NormalizeMode {
+    /// [`normalize_shapes`]: anchored to the shapes' maximum endpoint, as
+    /// osci-render does.
+    #[default]
+    Anchored,
+    /// [`normalize_shapes_centered`]: the bounding box is centred on the
+    /// origin.
+    Centered,
+}
+
+/// Samples taken along each shape when measuring its bounds.
+const BOUNDS_SAMPLES: usize = 16;
+
+/// Bounding box of a set of shapes as `(min, max)` corners, found by
+/// sampling each shape along its length. `None` if there are no shapes.
+pub fn shapes_bounds(shapes: &[Box<dyn Shape>]) -> Option<(Point, Point)> {
+    let mut bounds: Option<(Point, Point)> = None;
+    for shape in shapes {
+        for i in 0..=BOUNDS_SAMPLES {
+            let v = shape.next_vector(i as f32 / BOUNDS_SAMPLES as f32);
+            let (min, max) = bounds.get_or_insert((v, v));
+            min.x = min.x.min(v.x);
+            min.y = min.y.min(v.y);
+            max.x = max.x.max(v.x);
+            max.y = max.y.max(v.y);
+        }
+    }
+    bounds
+}
+
+/// Normalize shapes so their bounding box is centred on the origin and its
+/// longer side spans [-1, 1].
+///
+/// Unlike [`normalize_shapes`] this doesn't flip Y, and symmetric art such
+/// as a circle lands exactly in the middle of the screen.
+pub fn normalize_shapes_centered(shapes: &mut [Box<dyn Shape>]) {
+    if let Some((min, max)) = shapes_bounds(shapes) {
+        fit_bounds_centered(shapes, min, max);
+    }
+}
+
+/// Scale and move shapes so the box from `min` to `max` is centred on the
+/// origin with its longer side spanning [-1, 1].
+///
+/// Fitting every frame of an animation to their combined bounds keeps it
+/// from jumping around between frames.
+pub fn fit_bounds_centered(shapes: &mut [Box<dyn Shape>], min: Point, max: Point) {
+    let max_dim = (max.x - min.x).max(max.y - min.y);
+    if max_dim <= 0.0 {
+        return;
+    }
+
+    let scale = 2.0 / max_dim;
+    let centre = Point::xy((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+    for shape in shapes.iter_mut() {
+        shape.translate(-centre.x, -centre.y, 0.0);
+        shape.scale(scale, scale, scale);
+    }
+}
+
+/// Normalize shapes laid out in a `width` × `height` box with Y pointing
+/// down (image pixels) to fit within [-1, 1].
+///
+/// With `keep_aspect` the box is scaled uniformly until its longer side
+/// spans [-1, 1] and centred, so non-square art is letterboxed rather than
+/// distorted. Without it each axis is stretched to fill [-1, 1] on its own.
+pub fn normalize_shapes_to(shapes: &mut [Box<dyn Shape>], width: f32, height: f32, keep_aspect: bool) {
     let max_dim = width.max(height);
 
     if max_dim == 0.0 {
         return;
     }
 
+    let (scale_x, scale_y) = if keep_aspect {
+        (2.0 / max_dim, 2.0 / max_dim)
+    } else if width > 0.0 && height > 0.0 {
+        (2.0 / width, 2.0 / height)
+    } else {
+        return;
+    };
+
     for shape in shapes.iter_mut() {
-        shape.scale(2.0 / max_dim, -2.0 / max_dim, 2.0 / max_dim);
-        shape.translate(-1.0, 1.0, 0.0);
+        shape.scale(scale_x, -scale_y, 2.0 / max_dim);
+        shape.translate(-width * scale_x / 2.0, height * scale_y / 2.0, 0.0);
     }
 
     remove_out_of_bounds(shapes);
@@ -120,13 +341,16 @@ pub fn max_vector(shapes: &[Box<dyn Shape>]) -> Point {
 fn remove_out_of_bounds(shapes: &mut [Box<dyn Shape>]) {
     // Note: we operate on a Vec in frame.rs; this is a helper for normalize_shapes_to.
     // Since we can't easily resize a slice, this is a no-op on slices.
-    // The actual removal happens in Frame::remove_out_of_bounds.
+    // The actual clipping happens in Frame::clip_to_bounds.
     let _ = shapes;
 }
 
 // --- Concrete shape implementations ---
 
 /// A line segment between two 3D points.
+///
+/// With a colour set, every point along the line carries that RGB colour;
+/// without one the colour follows `z` as in [`Point::new`].
 #[derive(Debug, Clone)]
 pub struct Line {
     pub x1: f32,
@@ -135,20 +359,27 @@ pub struct Line {
     pub x2: f32,
     pub y2: f32,
     pub z2: f32,
+    pub colour: Option<[f32; 3]>,
     cached_length: Option<f32>,
 }
 
 impl Line {
     pub fn new_2d(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
-        Self { x1, y1, z1: 0.0, x2, y2, z2: 0.0, cached_length: None }
+        Self { x1, y1, z1: 0.0, x2, y2, z2: 0.0, colour: None, cached_length: None }
     }
 
     pub fn new_3d(x1: f32, y1: f32, z1: f32, x2: f32, y2: f32, z2: f32) -> Self {
-        Self { x1, y1, z1, x2, y2, z2, cached_length: None }
+        Self { x1, y1, z1, x2, y2, z2, colour: None, cached_length: None }
     }
 
     pub fn from_points(p1: Point, p2: Point) -> Self {
-        Self { x1: p1.x, y1: p1.y, z1: p1.z, x2: p2.x, y2: p2.y, z2: p2.z, cached_length: None }
+        Self::new_3d(p1.x, p1.y, p1.z, p2.x, p2.y, p2.z)
+    }
+
+    /// Draw the line in the RGB colour `(r, g, b)`.
+    pub fn with_colour(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.colour = Some([r, g, b]);
+        self
     }
 
     pub fn compute_length(x1: f32, y1: f32, z1: f32, x2: f32, y2: f32, z2: f32) -> f32 {
@@ -161,11 +392,15 @@ impl Line {
 
 impl Shape for Line {
     fn next_vector(&self, drawing_progress: f32) -> Point {
-        Point::new(
+        let point = Point::new(
             self.x1 + (self.x2 - self.x1) * drawing_progress,
             self.y1 + (self.y2 - self.y1) * drawing_progress,
             self.z1 + (self.z2 - self.z1) * drawing_progress,
-        )
+        );
+        match self.colour {
+            Some([r, g, b]) => point.with_colour(r, g, b),
+            None => point,
+        }
     }
 
     fn scale(&mut self, x: f32, y: f32, z: f32) {
@@ -191,6 +426,14 @@ impl Shape for Line {
         Box::new(self.clone())
     }
 
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        clone_into_box_of(self, target);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn shape_type(&self) -> &'static str {
         "Line"
     }
@@ -258,6 +501,14 @@ impl Shape for CubicBezierCurve {
         Box::new(self.clone())
     }
 
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        clone_into_box_of(self, target);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn shape_type(&self) -> &'static str {
         "CubicBezierCurve"
     }
@@ -280,6 +531,73 @@ impl QuadraticBezierCurve {
             inner: CubicBezierCurve::new(x1, y1, cx2, cy2, cx3, cy3, x3, y3),
         }
     }
+
+    /// The original quadratic control point, recovered from the elevated cubic.
+    ///
+    /// Degree elevation commutes with scaling and translation, so this stays
+    /// valid after the curve is transformed.
+    fn control_point(&self) -> (f32, f32) {
+        let c = &self.inner;
+        ((3.0 * c.x2 - c.x1) * 0.5, (3.0 * c.y2 - c.y1) * 0.5)
+    }
+}
+
+/// Arc length of the quadratic Bezier `p0 → p1 → p2`.
+///
+/// Uses the closed-form integral of the curve's speed. When the curve folds
+/// back on itself along a straight line the logarithm in that solution is
+/// singular, so a fine polyline estimate is used instead.
+pub fn quadratic_bezier_length(x0: f32, y0: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    let (x0, y0, x1, y1, x2, y2) = (x0 as f64, y0 as f64, x1 as f64, y1 as f64, x2 as f64, y2 as f64);
+
+    // B'(t) = 2 (b + a t); |B'(t)|^2 = A t^2 + B t + C
+    let (ax, ay) = (x0 - 2.0 * x1 + x2, y0 - 2.0 * y1 + y2);
+    let (bx, by) = (2.0 * (x1 - x0), 2.0 * (y1 - y0));
+    let a = 4.0 * (ax * ax + ay * ay);
+    let b = 4.0 * (ax * bx + ay * by);
+    let c = bx * bx + by * by;
+
+    // Control point on the chord midpoint: constant speed, a straight line
+    if a < 1e-12 {
+        return c.sqrt() as f32;
+    }
+
+    let s_abc = 2.0 * (a + b + c).sqrt();
+    let a_2 = a.sqrt();
+    let a_32 = 2.0 * a * a_2;
+    let c_2 = 2.0 * c.sqrt();
+    let ba = b / a_2;
+    let log_arg = (2.0 * a_2 + ba + s_abc) / (ba + c_2);
+
+    let length = (a_32 * s_abc
+        + a_2 * b * (s_abc - c_2)
+        + (4.0 * c * a - b * b) * log_arg.ln())
+        / (4.0 * a_32);
+
+    if length.is_finite() && log_arg > 0.0 {
+        length as f32
+    } else {
+        quadratic_polyline_length(x0, y0, x1, y1, x2, y2, 256) as f32
+    }
+}
+
+/// Length of the quadratic Bezier approximated by `segments` straight lines.
+fn quadratic_polyline_length(x0: f64, y0: f64, x1: f64, y1: f64, x2: f64, y2: f64, segments: usize) -> f64 {
+    let point = |t: f64| {
+        let mt = 1.0 - t;
+        (
+            mt * mt * x0 + 2.0 * mt * t * x1 + t * t * x2,
+            mt * mt * y0 + 2.0 * mt * t * y1 + t * t * y2,
+        )
+    };
+    let mut length = 0.0;
+    let mut prev = point(0.0);
+    for i in 1..=segments {
+        let next = point(i as f64 / segments as f64);
+        length += ((next.0 - prev.0).powi(2) + (next.1 - prev.1).powi(2)).sqrt();
+        prev = next;
+    }
+    length
 }
 
 impl Shape for QuadraticBezierCurve {
@@ -296,13 +614,23 @@ impl Shape for QuadraticBezierCurve {
     }
 
     fn length(&self) -> f32 {
-        self.inner.length()
+        let (cx, cy) = self.control_point();
+        let c = &self.inner;
+        quadratic_bezier_length(c.x1, c.y1, cx, cy, c.x4, c.y4)
     }
 
     fn clone_shape(&self) -> Box<dyn Shape> {
         Box::new(self.clone())
     }
 
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        clone_into_box_of(self, target);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn shape_type(&self) -> &'static str {
         "QuadraticBezierCurve"
     }
@@ -367,20 +695,42 @@ impl Shape for CircleArc {
         Box::new(self.clone())
     }
 
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        clone_into_box_of(self, target);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn shape_type(&self) -> &'static str {
         "Arc"
     }
 }
 
 /// A single-point "shape" that always returns the same point.
+///
+/// By default it has no length, so a renderer passes straight over it. Give
+/// it a `dwell_length` to have the beam rest on the point for as long as it
+/// would take to draw a line that long, which shows up as a bright dot.
 #[derive(Debug, Clone)]
 pub struct PointShape {
     pub point: Point,
+    /// Length the point counts as when dividing the frame's drawing time.
+    pub dwell_length: f32,
 }
 
 impl PointShape {
     pub fn new(point: Point) -> Self {
-        Self { point }
+        Self { point, dwell_length: 0.0 }
+    }
+
+    /// A point the beam dwells on for `dwell_length` worth of drawing time.
+    pub fn with_dwell(point: Point, dwell_length: f32) -> Self {
+        Self {
+            point,
+            dwell_length: dwell_length.max(0.0),
+        }
     }
 }
 
@@ -398,18 +748,136 @@ impl Shape for PointShape {
     }
 
     fn length(&self) -> f32 {
-        0.0
+        self.dwell_length
     }
 
     fn clone_shape(&self) -> Box<dyn Shape> {
         Box::new(self.clone())
     }
 
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        clone_into_box_of(self, target);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
     fn shape_type(&self) -> &'static str {
         "Point"
     }
 }
 
+/// A shape traced in the opposite direction.
+///
+/// `next_vector(t)` samples the wrapped shape at `1 - t`. Reversing it again
+/// hands back the original shape rather than nesting another wrapper.
+pub struct Reversed {
+    inner: Box<dyn Shape>,
+}
+
+impl Reversed {
+    pub fn new(inner: Box<dyn Shape>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Shape for Reversed {
+    fn next_vector(&self, drawing_progress: f32) -> Point {
+        self.inner.next_vector(1.0 - drawing_progress)
+    }
+
+    fn scale(&mut self, x: f32, y: f32, z: f32) {
+        self.inner.scale(x, y, z);
+    }
+
+    fn translate(&mut self, x: f32, y: f32, z: f32) {
+        self.inner.translate(x, y, z);
+    }
+
+    fn length(&self) -> f32 {
+        self.inner.length()
+    }
+
+    fn clone_shape(&self) -> Box<dyn Shape> {
+        Box::new(Reversed::new(self.inner.clone_shape()))
+    }
+
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        match target.as_any_mut().downcast_mut::<Reversed>() {
+            Some(existing) => self.inner.clone_into_box(&mut existing.inner),
+            None => *target = self.clone_shape(),
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn shape_type(&self) -> &'static str {
+        self.inner.shape_type()
+    }
+
+    fn reversed(&self) -> Box<dyn Shape> {
+        self.inner.clone_shape()
+    }
+}
+
+/// A shape drawn in a fixed RGB colour, for shapes with no colour of
+/// their own such as curves.
+pub struct Coloured {
+    inner: Box<dyn Shape>,
+    colour: [f32; 3],
+}
+
+impl Coloured {
+    pub fn new(inner: Box<dyn Shape>, r: f32, g: f32, b: f32) -> Self {
+        Self { inner, colour: [r, g, b] }
+    }
+}
+
+impl Shape for Coloured {
+    fn next_vector(&self, drawing_progress: f32) -> Point {
+        let [r, g, b] = self.colour;
+        self.inner.next_vector(drawing_progress).with_colour(r, g, b)
+    }
+
+    fn scale(&mut self, x: f32, y: f32, z: f32) {
+        self.inner.scale(x, y, z);
+    }
+
+    fn translate(&mut self, x: f32, y: f32, z: f32) {
+        self.inner.translate(x, y, z);
+    }
+
+    fn length(&self) -> f32 {
+        self.inner.length()
+    }
+
+    fn clone_shape(&self) -> Box<dyn Shape> {
+        let [r, g, b] = self.colour;
+        Box::new(Coloured::new(self.inner.clone_shape(), r, g, b))
+    }
+
+    fn clone_into_box(&self, target: &mut Box<dyn Shape>) {
+        match target.as_any_mut().downcast_mut::<Coloured>() {
+            Some(existing) => {
+                existing.colour = self.colour;
+                self.inner.clone_into_box(&mut existing.inner);
+            }
+            None => *target = self.clone_shape(),
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn shape_type(&self) -> &'static str {
+        self.inner.shape_type()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,6 +907,35 @@ mod tests {
         assert!((end.y - 1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_quadratic_bezier_length_matches_polyline() {
+        let curves = [
+            (0.0, 0.0, 0.5, 1.0, 1.0, 0.0),
+            (-1.0, -1.0, 1.0, -1.0, 1.0, 1.0),
+            (0.0, 0.0, 0.5, 0.0, 1.0, 0.0),
+            (0.0, 0.0, 3.0, 0.2, -1.0, 0.0),
+            // Collinear with the control point past the end: folds back on itself
+            (0.0, 0.0, 2.0, 0.0, 1.0, 0.0),
+        ];
+        for (x0, y0, x1, y1, x2, y2) in curves {
+            let mut curve = QuadraticBezierCurve::new(x0, y0, x1, y1, x2, y2);
+            let expected =
+                quadratic_polyline_length(x0 as f64, y0 as f64, x1 as f64, y1 as f64, x2 as f64, y2 as f64, 10_000);
+            let length = curve.length() as f64;
+            assert!(
+                (length - expected).abs() <= expected * 0.01,
+                "curve {:?}: length {length}, polyline {expected}",
+                (x0, y0, x1, y1, x2, y2)
+            );
+
+            // Still exact once the curve has been transformed
+            curve.scale(2.0, 2.0, 1.0);
+            curve.translate(0.3, -0.4, 0.0);
+            let scaled = curve.length() as f64;
+            assert!((scaled - 2.0 * expected).abs() <= expected * 0.02);
+        }
+    }
+
     #[test]
     fn test_circle_arc() {
         let arc = CircleArc::new(0.0, 0.0, 1.0, 1.0, 0.0, std::f32::consts::TAU);
@@ -456,4 +953,132 @@ mod tests {
         let total = total_length(&shapes);
         assert!((total - 15.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_point_dwell_length() {
+        let p = Point::new(0.3, -0.2, 0.0);
+        assert_eq!(PointShape::new(p).length(), 0.0);
+
+        let dot = PointShape::with_dwell(p, 0.05);
+        assert_eq!(dot.length(), 0.05);
+        let q = dot.next_vector(0.7);
+        assert_eq!((q.x, q.y), (0.3, -0.2));
+    }
+
+    #[test]
+    fn test_reversed_line_starts_at_original_end() {
+        let line = Line::new_2d(-1.0, 0.5, 1.0, -0.5);
+        let reversed = line.reversed();
+
+        assert_eq!(reversed.next_vector(0.0), line.next_vector(1.0));
+        assert_eq!(reversed.next_vector(1.0), line.next_vector(0.0));
+        assert_eq!(reversed.next_vector(0.25), line.next_vector(0.75));
+        assert!((reversed.length() - line.length()).abs() < 1e-6);
+
+        // Reversing twice gets the original direction back
+        assert_eq!(reversed.reversed().next_vector(0.0), line.next_vector(0.0));
+    }
+
+    #[test]
+    fn test_reverse_shapes_flips_order_and_direction() {
+        let mut shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0)),
+            Box::new(Line::new_2d(1.0, 0.0, 1.0, 1.0)),
+        ];
+        reverse_shapes(&mut shapes);
+
+        assert_eq!(shapes[0].next_vector(0.0), Point::xy(1.0, 1.0));
+        assert_eq!(shapes[0].next_vector(1.0), Point::xy(1.0, 0.0));
+        assert_eq!(shapes[1].next_vector(1.0), Point::xy(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_simplify_collapses_straight_polyline() {
+        // 1000 points along a line with sub-tolerance wobble
+        let points: Vec<Point> = (0..=1000)
+            .map(|i| {
+                let t = i as f32 / 1000.0;
+                let wobble = if i % 2 == 0 { 1e-4 } else { -1e-4 };
+                Point::new(-1.0 + 2.0 * t, 0.5 * t + wobble, 0.0)
+            })
+            .collect();
+        let mut shapes: Vec<Box<dyn Shape>> = points
+            .windows(2)
+            .map(|p| Box::new(Line::from_points(p[0], p[1])) as Box<dyn Shape>)
+            .collect();
+        // A curve after the run is kept as is
+        shapes.push(Box::new(QuadraticBezierCurve::new(1.0, 0.5, 1.0, 1.0, 0.0, 1.0)));
+
+        let simplified = simplify_shapes(shapes, 1e-3);
+        assert_eq!(simplified.len(), 2);
+        assert_eq!(simplified[0].shape_type(), "Line");
+        assert!(simplified[0].next_vector(0.0).approx_eq(&points[0]));
+        assert!(simplified[0].next_vector(1.0).approx_eq(&points[1000]));
+        assert_eq!(simplified[1].shape_type(), "QuadraticBezierCurve");
+    }
+
+    #[test]
+    fn test_simplify_keeps_corners() {
+        let square: Vec<Box<dyn Shape>> = vec![
+            Box::new(Line::new_2d(-0.5, -0.5, 0.0, -0.5)),
+            Box::new(Line::new_2d(0.0, -0.5, 0.5, -0.5)),
+            Box::new(Line::new_2d(0.5, -0.5, 0.5, 0.5)),
+            Box::new(Line::new_2d(0.5, 0.5, -0.5, 0.5)),
+            // Not joined to the previous line, so not merged with it
+            Box::new(Line::new_2d(-0.6, 0.5, -0.6, -0.5)),
+        ];
+        let simplified = simplify_shapes(square, 0.01);
+        assert_eq!(simplified.len(), 4);
+        assert!((total_length(&simplified) - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_centered_normalization_keeps_circle_centred() {
+        let centre_of = |shapes: &[Box<dyn Shape>]| {
+            let (min, max) = shapes_bounds(shapes).unwrap();
+            (Point::xy((min.x + max.x) / 2.0, (min.y + max.y) / 2.0), max.x - min.x)
+        };
+
+        let mut centred: Vec<Box<dyn Shape>> =
+            vec![Box::new(CircleArc::new(0.0, 0.0, 0.5, 0.5, 0.0, std::f32::consts::TAU))];
+        normalize_shapes_centered(&mut centred);
+        let (centre, width) = centre_of(&centred);
+        assert!(centre.magnitude() < 1e-4, "centre {centre:?}");
+        assert!((width - 2.0).abs() < 1e-3, "width {width}");
+
+        // An off-centre circle is moved to the middle too
+        let mut offset: Vec<Box<dyn Shape>> =
+            vec![Box::new(CircleArc::new(0.3, -0.2, 0.4, 0.4, 0.0, std::f32::consts::TAU))];
+        normalize_shapes_centered(&mut offset);
+        let (centre, width) = centre_of(&offset);
+        assert!(centre.magnitude() < 1e-4, "centre {centre:?}");
+        assert!((width - 2.0).abs() < 1e-3, "width {width}");
+    }
+
+    #[test]
+    fn test_normalize_to_letterboxes_wide_art() {
+        // The outline of a 200 × 100 image
+        let rect = || -> Vec<Box<dyn Shape>> {
+            vec![
+                Box::new(Line::new_2d(0.0, 0.0, 200.0, 0.0)),
+                Box::new(Line::new_2d(200.0, 0.0, 200.0, 100.0)),
+                Box::new(Line::new_2d(200.0, 100.0, 0.0, 100.0)),
+                Box::new(Line::new_2d(0.0, 100.0, 0.0, 0.0)),
+            ]
+        };
+
+        let mut fit = rect();
+        normalize_shapes_to(&mut fit, 200.0, 100.0, true);
+        let (min, max) = shapes_bounds(&fit).unwrap();
+        assert!(min.approx_eq(&Point::xy(-1.0, -0.5)), "{min:?}");
+        assert!(max.approx_eq(&Point::xy(1.0, 0.5)), "{max:?}");
+        // Image row 0 is the top edge
+        assert!((fit[0].next_vector(0.0).y - 0.5).abs() < 1e-6);
+
+        let mut stretched = rect();
+        normalize_shapes_to(&mut stretched, 200.0, 100.0, false);
+        let (min, max) = shapes_bounds(&stretched).unwrap();
+        assert!(min.approx_eq(&Point::xy(-1.0, -1.0)), "{min:?}");
+        assert!(max.approx_eq(&Point::xy(1.0, 1.0)), "{max:?}");
+    }
 }
@@ -6,7 +6,7 @@ use osci_effects::scale::ScaleEffect;
 
 fn bench_rotate_512(c: &mut Criterion) {
     let mut effect = Rotate::new();
-    let values = [0.25_f32, 0.5, 0.0];
+    let values = [0.25_f32, 0.5, 0.0, 0.0];
     let input = Point::new(0.5, 0.3, 0.1);
     let ext = Point::ZERO;
 
@@ -53,5 +53,25 @@ fn bench_scale_512(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_rotate_512, bench_smooth_512, bench_scale_512);
+fn bench_rotate_block_512(c: &mut Criterion) {
+    let mut effect = Rotate::new();
+    let values = [0.25_f32, 0.5, 0.0, 0.0];
+    let inputs = vec![Point::new(0.5, 0.3, 0.1); 512];
+    let mut outputs = vec![Point::ZERO; 512];
+
+    c.bench_function("rotate_block_512_samples", |b| {
+        b.iter(|| {
+            effect.apply_block(&inputs, &mut outputs, &values, 44100.0, 440.0);
+            black_box(&outputs);
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_rotate_512,
+    bench_rotate_block_512,
+    bench_smooth_512,
+    bench_scale_512
+);
 criterion_main!(benches);
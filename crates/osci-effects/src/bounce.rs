@@ -80,6 +80,10 @@ impl EffectApplication for BounceEffect {
         output
     }
 
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
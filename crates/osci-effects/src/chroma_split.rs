@@ -0,0 +1,72 @@
+use osci_core::{EffectApplication, Point};
+use std::f32::consts::TAU;
+
+/// Chroma split effect — velocity-driven RGB separation for a CRT-glitch look.
+///
+/// A single beam sample can only sit at one position, so the channels cannot
+/// literally be drawn apart. Instead the colour is modulated by the local
+/// direction of travel: while the beam moves along the split angle red is
+/// boosted and blue suppressed, and the reverse when moving against it. On
+/// screen this reads as red fringes on one side of each stroke and blue on
+/// the other, as if the channels were offset. The position is unchanged.
+#[derive(Debug, Clone)]
+pub struct ChromaSplitEffect {
+    previous: Point,
+}
+
+impl ChromaSplitEffect {
+    pub fn new() -> Self {
+        Self {
+            previous: Point::ZERO,
+        }
+    }
+}
+
+impl EffectApplication for ChromaSplitEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) -> Point {
+        let amount = values[0].clamp(0.0, 1.0);
+        let angle = values[1] * TAU;
+
+        let dx = input.x - self.previous.x;
+        let dy = input.y - self.previous.y;
+        self.previous = input;
+
+        let speed = (dx * dx + dy * dy).sqrt();
+        if speed < 1e-9 || amount == 0.0 {
+            return input;
+        }
+
+        // How closely the direction of travel matches the split axis, in [-1, 1]
+        let alignment = ((dx * angle.cos() + dy * angle.sin()) / speed).clamp(-1.0, 1.0);
+        let shift = amount * alignment;
+
+        Point::with_rgb(
+            input.x,
+            input.y,
+            input.z,
+            input.r * (1.0 + shift),
+            input.g * (1.0 - 0.5 * shift.abs()),
+            input.b * (1.0 - shift),
+        )
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Chroma Split"
+    }
+}
@@ -0,0 +1,49 @@
+use osci_core::{EffectApplication, Point};
+
+/// Color crush effect — posterizes the beam colour by quantizing each channel.
+///
+/// The colour-domain counterpart of BitCrush: `r`, `g` and `b` are each
+/// rounded to the nearest of Levels evenly spaced steps from 0 to 1, so two
+/// levels leave every channel fully off or fully on. Values outside `[0, 1]`
+/// snap to the same step grid. The position is unchanged.
+#[derive(Debug, Clone)]
+pub struct ColorCrushEffect;
+
+impl ColorCrushEffect {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl EffectApplication for ColorCrushEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) -> Point {
+        let levels = values[0].round().clamp(2.0, 16.0);
+        let steps = levels - 1.0;
+        let crush = |c: f32| (c * steps).round() / steps;
+
+        Point::with_rgb(
+            input.x,
+            input.y,
+            input.z,
+            crush(input.r),
+            crush(input.g),
+            crush(input.b),
+        )
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Color Crush"
+    }
+}
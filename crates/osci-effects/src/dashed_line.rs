@@ -13,6 +13,8 @@ pub struct DashedLineEffect {
     buffer: Vec<Point>,
     buffer_index: usize,
     frame_phase: f64,
+    // Length of the buffer written since the last reset
+    written: usize,
 }
 
 impl DashedLineEffect {
@@ -21,6 +23,7 @@ impl DashedLineEffect {
             buffer: vec![Point::ZERO; MAX_BUFFER],
             buffer_index: 0,
             frame_phase: 0.0,
+            written: 0,
         }
     }
 }
@@ -48,6 +51,7 @@ impl EffectApplication for DashedLineEffect {
 
         let buffer_size = self.buffer.len();
         self.buffer[self.buffer_index] = input;
+        self.written = self.written.max(self.buffer_index + 1);
 
         let mut sample_pos = self.buffer_index as f64
             - dash_length_samples * dash_phase * (1.0 - dash_coverage as f64);
@@ -88,6 +92,13 @@ impl EffectApplication for DashedLineEffect {
         result
     }
 
+    fn reset(&mut self) {
+        self.buffer[..self.written].fill(Point::ZERO);
+        self.buffer_index = 0;
+        self.frame_phase = 0.0;
+        self.written = 0;
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -106,6 +117,8 @@ pub struct TraceEffect {
     buffer: Vec<Point>,
     buffer_index: usize,
     frame_phase: f64,
+    // Length of the buffer written since the last reset
+    written: usize,
 }
 
 impl TraceEffect {
@@ -114,6 +127,7 @@ impl TraceEffect {
             buffer: vec![Point::ZERO; MAX_BUFFER],
             buffer_index: 0,
             frame_phase: 0.0,
+            written: 0,
         }
     }
 }
@@ -141,6 +155,7 @@ impl EffectApplication for TraceEffect {
 
         let buffer_size = self.buffer.len();
         self.buffer[self.buffer_index] = input;
+        self.written = self.written.max(self.buffer_index + 1);
 
         let mut sample_pos = self.buffer_index as f64
             - dash_length_samples * dash_phase * (1.0 - dash_coverage as f64);
@@ -181,6 +196,13 @@ impl EffectApplication for TraceEffect {
         result
     }
 
+    fn reset(&mut self) {
+        self.buffer[..self.written].fill(Point::ZERO);
+        self.buffer_index = 0;
+        self.frame_phase = 0.0;
+        self.written = 0;
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -12,6 +12,9 @@ pub struct DelayEffect {
     head: usize,
     position: usize,
     samples_since_last_delay: usize,
+    // Length of the buffer written since the last reset, so a reset only
+    // clears what was used
+    written: usize,
 }
 
 impl DelayEffect {
@@ -21,6 +24,7 @@ impl DelayEffect {
             head: 0,
             position: 0,
             samples_since_last_delay: 0,
+            written: 0,
         }
     }
 }
@@ -69,6 +73,7 @@ impl EffectApplication for DelayEffect {
         );
 
         self.delay_buffer[self.head] = vector;
+        self.written = self.written.max(self.head + 1);
         self.head += 1;
         self.position += 1;
         self.samples_since_last_delay += 1;
@@ -76,6 +81,14 @@ impl EffectApplication for DelayEffect {
         vector
     }
 
+    fn reset(&mut self) {
+        self.delay_buffer[..self.written].fill(Point::ZERO);
+        self.head = 0;
+        self.position = 0;
+        self.samples_since_last_delay = 0;
+        self.written = 0;
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
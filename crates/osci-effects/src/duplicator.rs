@@ -46,6 +46,10 @@ impl EffectApplication for DuplicatorEffect {
         (1.0 - spread) * input + spread * offset
     }
 
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -39,3 +39,17 @@ impl EffectApplication for FrequencyEffect {
         "Frequency"
     }
 }
+
+/// Cap a drawing frequency at the scan rate limit `max_frequency`.
+///
+/// Tracing a frame faster than a few kHz turns the output into a smear that
+/// can't be heard or seen and is hard on speakers. Frequencies at or below
+/// the limit pass through unchanged; `None` leaves every frequency alone.
+/// A separate visual draw rate is unaffected, so the frame can still refresh
+/// quickly while the pitch stays in range.
+pub fn limit_frequency(frequency: f64, max_frequency: Option<f64>) -> f64 {
+    match max_frequency {
+        Some(max) => frequency.min(max),
+        None => frequency,
+    }
+}
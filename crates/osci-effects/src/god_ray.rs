@@ -4,15 +4,18 @@ use osci_core::{EffectApplication, Point};
 ///
 /// Applies random brightness/scale modulation to each sample,
 /// producing god-ray-like streaking effects. Uses a simple LCG
-/// random number generator for deterministic noise.
+/// random number generator for deterministic noise. A reset goes back to
+/// the start of the stream last seeded.
 #[derive(Debug, Clone)]
 pub struct GodRayEffect {
+    seed: u64,
     rng_state: u64,
 }
 
 impl GodRayEffect {
     pub fn new() -> Self {
         Self {
+            seed: 123456789,
             rng_state: 123456789,
         }
     }
@@ -61,6 +64,15 @@ impl EffectApplication for GodRayEffect {
         )
     }
 
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng_state = seed;
+    }
+
+    fn reset(&mut self) {
+        self.rng_state = self.seed;
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -0,0 +1,99 @@
+use osci_core::parameter::xorshift_seed;
+use osci_core::{EffectApplication, Point};
+
+/// Jitter effect — frequency-locked positional noise for organic shimmer.
+///
+/// Random XY offsets are drawn a fixed number of times per drawing cycle
+/// (from 1 at Detail 0 up to 64 at Detail 1) and linearly interpolated, so
+/// the noise always has the same character relative to the shape regardless
+/// of pitch. Uses the same xorshift32 generator as the Noise LFO. Each
+/// instance carries its own state, but a clone copies it and so repeats the
+/// original's stream: voices only get independent streams once each is given
+/// its own seed with [`set_seed`](EffectApplication::set_seed), as a synth
+/// seed does. A reset goes back to the start of the stream last seeded.
+#[derive(Debug, Clone)]
+pub struct JitterEffect {
+    seed_state: u32,
+    rng_state: u32,
+    phase: f32,
+    from: (f32, f32),
+    to: (f32, f32),
+}
+
+impl JitterEffect {
+    pub fn new() -> Self {
+        Self {
+            seed_state: 0x12345678,
+            rng_state: 0x12345678,
+            phase: 0.0,
+            from: (0.0, 0.0),
+            to: (0.0, 0.0),
+        }
+    }
+
+    /// xorshift32 PRNG returning a value in [-1, 1].
+    fn next_random(&mut self) -> f32 {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 17;
+        self.rng_state ^= self.rng_state << 5;
+        (self.rng_state & 0x00FFFFFF) as f32 / 16777215.0 * 2.0 - 1.0
+    }
+}
+
+impl EffectApplication for JitterEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        sample_rate: f32,
+        frequency: f32,
+    ) -> Point {
+        let amount = values[0].max(0.0);
+        let detail = values[1].clamp(0.0, 1.0);
+
+        let points_per_cycle = 1.0 + detail * 63.0;
+        if sample_rate > 0.0 {
+            self.phase += (frequency * points_per_cycle / sample_rate).max(0.0);
+        }
+        if self.phase >= 1.0 {
+            self.phase = self.phase.fract();
+            self.from = self.to;
+            self.to = (self.next_random(), self.next_random());
+        }
+
+        let dx = self.from.0 + (self.to.0 - self.from.0) * self.phase;
+        let dy = self.from.1 + (self.to.1 - self.from.1) * self.phase;
+
+        Point::with_rgb(
+            input.x + dx * amount,
+            input.y + dy * amount,
+            input.z,
+            input.r,
+            input.g,
+            input.b,
+        )
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed_state = xorshift_seed(seed);
+        self.rng_state = self.seed_state;
+    }
+
+    fn reset(&mut self) {
+        *self = Self {
+            rng_state: self.seed_state,
+            seed_state: self.seed_state,
+            ..Self::new()
+        };
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Jitter"
+    }
+}
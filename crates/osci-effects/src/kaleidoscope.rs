@@ -111,6 +111,10 @@ impl EffectApplication for KaleidoscopeEffect {
         output
     }
 
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -28,3 +28,12 @@ pub mod perspective;
 pub mod volume;
 pub mod threshold;
 pub mod frequency;
+pub mod chroma_split;
+pub mod mirror;
+pub mod jitter;
+pub mod color_crush;
+pub mod soft_clip;
+pub mod radial_clamp;
+pub mod lissajous;
+pub mod spin;
+pub mod thicken;
@@ -0,0 +1,79 @@
+use osci_core::{EffectApplication, Point};
+use std::f64::consts::TAU;
+
+/// Decay rate, per second, at the top of the Decay parameter.
+const MAX_DECAY_RATE: f64 = 5.0;
+
+/// Amplitude at which a decaying figure starts again from the top.
+const RESTART_AMPLITUDE: f64 = 0.01;
+
+/// Lissajous / harmonograph generator.
+///
+/// Ignores the input position and draws `x = sin(a·θ)`,
+/// `y = sin(b·θ + δ)`, where `θ` advances at the drawing frequency. `a` and
+/// `b` are the X and Y ratios and `δ` is the Phase parameter in cycles, so
+/// 0.25 is a quarter turn. With a Decay above zero the figure shrinks like
+/// a damped pendulum harmonograph and restarts once it has faded out. Time
+/// and `θ` are accumulated per sample, so a change of frequency bends the
+/// figure smoothly instead of jumping, and the output depends only on the
+/// samples drawn so far. The input's `z` and colour pass through.
+#[derive(Debug, Clone)]
+pub struct LissajousEffect {
+    time: f64,
+    phase: f64,
+}
+
+impl LissajousEffect {
+    pub fn new() -> Self {
+        Self { time: 0.0, phase: 0.0 }
+    }
+}
+
+impl EffectApplication for LissajousEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        sample_rate: f32,
+        frequency: f32,
+    ) -> Point {
+        let (a, b) = (values[0] as f64, values[1] as f64);
+        let delta = values[2] as f64 * TAU;
+        let decay = values[3].max(0.0) as f64 * MAX_DECAY_RATE;
+
+        let mut amplitude = (-decay * self.time).exp();
+        if amplitude < RESTART_AMPLITUDE {
+            self.time = 0.0;
+            self.phase = 0.0;
+            amplitude = 1.0;
+        }
+        let theta = self.phase;
+        if sample_rate > 0.0 {
+            self.time += 1.0 / sample_rate as f64;
+            self.phase += TAU * frequency as f64 / sample_rate as f64;
+        }
+
+        Point::with_rgb(
+            (amplitude * (a * theta).sin()) as f32,
+            (amplitude * (b * theta + delta).sin()) as f32,
+            input.z,
+            input.r,
+            input.g,
+            input.b,
+        )
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Lissajous"
+    }
+}
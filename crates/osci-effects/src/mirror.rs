@@ -0,0 +1,67 @@
+use osci_core::{EffectApplication, Point};
+
+/// Mirror effect — folds the input across the X and/or Y axis.
+///
+/// Mode 0 passes the point through, 1 folds X (`x = |x|`), 2 folds Y and
+/// 3 folds both. Unlike Kaleidoscope no geometry is duplicated; the point is
+/// simply reflected into the positive half-plane or quadrant.
+///
+/// A non-zero Phase makes the fold alternate: it is applied on every other
+/// drawing cycle, with Phase shifting where in the cycle the switch happens.
+#[derive(Debug, Clone)]
+pub struct MirrorEffect {
+    cycle_phase: f64,
+}
+
+impl MirrorEffect {
+    pub fn new() -> Self {
+        Self { cycle_phase: 0.0 }
+    }
+}
+
+impl EffectApplication for MirrorEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        sample_rate: f32,
+        frequency: f32,
+    ) -> Point {
+        let mode = values[0].round() as i32;
+        let phase = values[1].clamp(0.0, 1.0) as f64;
+
+        // Two-cycle period: [0, 1) is the first drawing cycle, [1, 2) the second
+        self.cycle_phase += frequency as f64 / sample_rate.max(1.0) as f64;
+        if self.cycle_phase >= 2.0 {
+            self.cycle_phase -= 2.0 * (self.cycle_phase / 2.0).floor();
+        }
+
+        let active = phase <= 0.0 || (self.cycle_phase + phase * 2.0) % 2.0 >= 1.0;
+        if !active {
+            return input;
+        }
+
+        let mut output = input;
+        if mode == 1 || mode == 3 {
+            output.x = output.x.abs();
+        }
+        if mode == 2 || mode == 3 {
+            output.y = output.y.abs();
+        }
+        output
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Mirror"
+    }
+}
@@ -14,6 +14,9 @@ pub struct MultiplexEffect {
     phase: PhaseAccumulator,
     buffer: Vec<Point>,
     head: usize,
+    // Length of the buffer written since the last reset, so a reset only
+    // clears what was used
+    written: usize,
 }
 
 impl MultiplexEffect {
@@ -22,6 +25,7 @@ impl MultiplexEffect {
             phase: PhaseAccumulator::new(),
             buffer: vec![Point::ZERO; MAX_DELAY],
             head: 0,
+            written: 0,
         }
     }
 }
@@ -96,6 +100,7 @@ impl EffectApplication for MultiplexEffect {
             self.head = 0;
         }
         self.buffer[self.head] = input;
+        self.written = self.written.max(self.head + 1);
 
         // Calculate delayed index
         let delay_samples = (delay_position * grid_delay as f64 * sample_rate as f64) as i64;
@@ -135,6 +140,13 @@ impl EffectApplication for MultiplexEffect {
         result
     }
 
+    fn reset(&mut self) {
+        self.phase.reset();
+        self.buffer[..self.written].fill(Point::ZERO);
+        self.head = 0;
+        self.written = 0;
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
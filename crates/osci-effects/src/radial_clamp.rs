@@ -0,0 +1,50 @@
+use osci_core::{EffectApplication, Point};
+
+/// Radial clamp effect — keeps the beam inside a circle.
+///
+/// Points further than `Radius` from the centre are pulled straight in onto
+/// the circle, keeping their angle; points inside pass unchanged. Only `x`
+/// and `y` are affected.
+#[derive(Debug, Clone)]
+pub struct RadialClampEffect;
+
+impl RadialClampEffect {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl EffectApplication for RadialClampEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) -> Point {
+        let radius = values[0].max(0.0);
+        let distance = (input.x * input.x + input.y * input.y).sqrt();
+        if distance <= radius {
+            return input;
+        }
+        let scale = radius / distance;
+        Point::with_rgb(
+            input.x * scale,
+            input.y * scale,
+            input.z,
+            input.r,
+            input.g,
+            input.b,
+        )
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Radial Clamp"
+    }
+}
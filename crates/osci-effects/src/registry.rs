@@ -9,6 +9,21 @@ pub struct EffectEntry {
     pub parameters: fn() -> Vec<EffectParameter>,
 }
 
+impl EffectEntry {
+    /// The effect's current parameters, taking each one's saved state from
+    /// `saved` by id. Parameters added since the save keep their defaults and
+    /// ones that no longer exist are dropped, so older projects still load.
+    pub fn restore_parameters(&self, mut saved: Vec<EffectParameter>) -> Vec<EffectParameter> {
+        (self.parameters)()
+            .into_iter()
+            .map(|default| match saved.iter().position(|p| p.id == default.id) {
+                Some(i) => saved.swap_remove(i),
+                None => default,
+            })
+            .collect()
+    }
+}
+
 /// Build the complete registry of all available effects.
 ///
 /// Each effect is identified by a unique string ID. The registry is used by
@@ -59,6 +74,7 @@ pub fn build_registry() -> Vec<EffectEntry> {
                 EffectParameter::new("Rotate X", "Rotation around the X axis.", "rotateX", 0.0, -1.0, 1.0),
                 EffectParameter::new("Rotate Y", "Rotation around the Y axis.", "rotateY", 0.0, -1.0, 1.0),
                 EffectParameter::new("Rotate Z", "Rotation around the Z axis.", "rotateZ", 0.0, -1.0, 1.0),
+                EffectParameter::new("Snap", "Round each angle to this many degrees, 0 = continuous.", "rotateSnapDegrees", 0.0, 0.0, 180.0),
             ],
         },
         EffectEntry {
@@ -135,6 +151,87 @@ pub fn build_registry() -> Vec<EffectEntry> {
                 EffectParameter::new("Angle Offset", "Angular offset of copies.", "duplicatorAngle", 0.0, -1.0, 1.0),
             ],
         },
+        EffectEntry {
+            id: "chromaSplit",
+            name: "Chroma Split",
+            constructor: || Box::new(crate::chroma_split::ChromaSplitEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Chroma Split", "Strength of the red/blue fringing.", "chromaSplitAmount", 0.0, 0.0, 1.0),
+                EffectParameter::new("Chroma Angle", "Direction of the split, in turns.", "chromaSplitAngle", 0.0, 0.0, 1.0),
+            ],
+        },
+        EffectEntry {
+            id: "mirror",
+            name: "Mirror",
+            constructor: || Box::new(crate::mirror::MirrorEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Mirror Mode", "0 = off, 1 = X, 2 = Y, 3 = quad.", "mirrorMode", 1.0, 0.0, 3.0).with_step(1.0),
+                EffectParameter::new("Mirror Phase", "Alternates the fold every other cycle when non-zero.", "mirrorPhase", 0.0, 0.0, 1.0),
+            ],
+        },
+        EffectEntry {
+            id: "jitter",
+            name: "Jitter",
+            constructor: || Box::new(crate::jitter::JitterEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Jitter Amount", "Size of the random displacement.", "jitterAmount", 0.0, 0.0, 1.0),
+                EffectParameter::new("Jitter Detail", "Noise points per drawing cycle.", "jitterDetail", 0.5, 0.0, 1.0),
+            ],
+        },
+        EffectEntry {
+            id: "colorCrush",
+            name: "Color Crush",
+            constructor: || Box::new(crate::color_crush::ColorCrushEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Color Levels", "Steps per colour channel.", "colorCrushLevels", 8.0, 2.0, 16.0).with_step(1.0),
+            ],
+        },
+        EffectEntry {
+            id: "softClip",
+            name: "Soft Clip",
+            constructor: || Box::new(crate::soft_clip::SoftClipEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Threshold", "Level the beam bends towards.", "softClipThreshold", 1.0, 0.0, 1.0),
+                EffectParameter::new("Knee", "Width of the soft knee below the threshold.", "softClipKnee", 0.5, 0.0, 1.0),
+            ],
+        },
+        EffectEntry {
+            id: "radialClamp",
+            name: "Radial Clamp",
+            constructor: || Box::new(crate::radial_clamp::RadialClampEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Radius", "Distance from the centre the beam can't go past.", "radialClampRadius", 1.0, 0.0, 1.5),
+            ],
+        },
+        EffectEntry {
+            id: "lissajous",
+            name: "Lissajous",
+            constructor: || Box::new(crate::lissajous::LissajousEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("X Ratio", "Multiple of the frequency traced on X.", "lissajousRatioX", 1.0, 0.0, 8.0),
+                EffectParameter::new("Y Ratio", "Multiple of the frequency traced on Y.", "lissajousRatioY", 1.0, 0.0, 8.0),
+                EffectParameter::new("Phase", "Offset of Y against X, in cycles.", "lissajousPhase", 0.25, 0.0, 1.0),
+                EffectParameter::new("Decay", "How quickly the figure shrinks before restarting.", "lissajousDecay", 0.0, 0.0, 1.0),
+            ],
+        },
+        EffectEntry {
+            id: "spin",
+            name: "Spin",
+            constructor: || Box::new(crate::spin::SpinEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Axis", "Axis to spin around: 0 = X, 1 = Y, 2 = Z.", "spinAxis", 2.0, 0.0, 2.0).with_step(1.0),
+                EffectParameter::new("Speed", "Revolutions per second; negative spins the other way.", "spinSpeed", 0.25, -4.0, 4.0),
+            ],
+        },
+        EffectEntry {
+            id: "thicken",
+            name: "Thicken",
+            constructor: || Box::new(crate::thicken::ThickenEffect::new()),
+            parameters: || vec![
+                EffectParameter::new("Width", "Width of the band the line is spread across.", "thickenWidth", 0.01, 0.0, 0.1),
+                EffectParameter::new("Passes", "Traversals, each at its own offset, that fill the band.", "thickenPasses", 3.0, 1.0, 8.0).with_step(1.0),
+            ],
+        },
 
         // ── Premium effects ───────────────────────────────────────
         EffectEntry {
@@ -1,7 +1,8 @@
 use osci_core::{EffectApplication, Point};
 
 /// Rotate effect — rotates the input point around all three axes by amounts
-/// proportional to the parameter values (scaled by PI).
+/// proportional to the parameter values (scaled by PI). A non-zero snap
+/// rounds each angle to the nearest multiple of that many degrees.
 #[derive(Debug, Clone)]
 pub struct Rotate;
 
@@ -11,6 +12,18 @@ impl Rotate {
     }
 }
 
+/// The rotation in radians for parameter `value`, rounded to the nearest
+/// multiple of `snap_degrees` unless that is zero.
+fn snap_angle(value: f32, snap_degrees: f32) -> f32 {
+    let angle = value * std::f32::consts::PI;
+    if snap_degrees > 0.0 {
+        let step = snap_degrees.to_radians();
+        (angle / step).round() * step
+    } else {
+        angle
+    }
+}
+
 impl EffectApplication for Rotate {
     fn apply(
         &mut self,
@@ -21,14 +34,47 @@ impl EffectApplication for Rotate {
         _sample_rate: f32,
         _frequency: f32,
     ) -> Point {
+        let snap = values.get(3).copied().unwrap_or(0.0);
         input.rotate(
-            values[0] * std::f32::consts::PI,
-            values[1] * std::f32::consts::PI,
-            values[2] * std::f32::consts::PI,
+            snap_angle(values[0], snap),
+            snap_angle(values[1], snap),
+            snap_angle(values[2], snap),
         );
         input
     }
 
+    fn apply_block(
+        &mut self,
+        inputs: &[Point],
+        outputs: &mut [Point],
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) {
+        // Same arithmetic as `Point::rotate`, with the trig hoisted out of the loop
+        let snap = values.get(3).copied().unwrap_or(0.0);
+        let (sin_x, cos_x) = snap_angle(values[0], snap).sin_cos();
+        let (sin_y, cos_y) = snap_angle(values[1], snap).sin_cos();
+        let (sin_z, cos_z) = snap_angle(values[2], snap).sin_cos();
+
+        for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+            let y2 = cos_x * input.y - sin_x * input.z;
+            let z2 = sin_x * input.y + cos_x * input.z;
+
+            let x2 = cos_y * input.x + sin_y * z2;
+            let z = -sin_y * input.x + cos_y * z2;
+
+            *output = Point::with_rgb(
+                cos_z * x2 - sin_z * y2,
+                sin_z * x2 + cos_z * y2,
+                z,
+                input.r,
+                input.g,
+                input.b,
+            );
+        }
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
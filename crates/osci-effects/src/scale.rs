@@ -26,6 +26,20 @@ impl EffectApplication for ScaleEffect {
         input * Point::new(values[0], values[1], values[2])
     }
 
+    fn apply_block(
+        &mut self,
+        inputs: &[Point],
+        outputs: &mut [Point],
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) {
+        let factor = Point::new(values[0], values[1], values[2]);
+        for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+            *output = *input * factor;
+        }
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -38,6 +38,10 @@ impl EffectApplication for SmoothEffect {
         self.avg
     }
 
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
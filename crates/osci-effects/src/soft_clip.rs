@@ -0,0 +1,62 @@
+use osci_core::{EffectApplication, Point};
+
+/// Soft clip effect — limits the beam with a soft knee instead of a hard edge.
+///
+/// Coordinates pass through unchanged up to `Threshold * (1 - Knee)`. Past
+/// that point they are bent over with a tanh curve that meets the linear part
+/// with the same slope and approaches, but never reaches, the threshold. A
+/// knee of 0 is a hard clip like the Threshold effect; a knee of 1 is a plain
+/// `Threshold * tanh(x / Threshold)` saturation. Only `x` and `y` are
+/// affected.
+#[derive(Debug, Clone)]
+pub struct SoftClipEffect;
+
+impl SoftClipEffect {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Soft-clip `value` at `threshold` with a knee of width `knee` (in absolute units).
+pub fn soft_clip(value: f32, threshold: f32, knee: f32) -> f32 {
+    let linear = threshold - knee;
+    let magnitude = value.abs();
+    if magnitude <= linear {
+        value
+    } else if knee <= 0.0 {
+        threshold.copysign(value)
+    } else {
+        (linear + knee * ((magnitude - linear) / knee).tanh()).copysign(value)
+    }
+}
+
+impl EffectApplication for SoftClipEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) -> Point {
+        let threshold = values[0].max(0.0);
+        let knee = threshold * values[1].clamp(0.0, 1.0);
+        Point::with_rgb(
+            soft_clip(input.x, threshold, knee),
+            soft_clip(input.y, threshold, knee),
+            input.z,
+            input.r,
+            input.g,
+            input.b,
+        )
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Soft Clip"
+    }
+}
@@ -0,0 +1,55 @@
+use osci_core::effect::wrap_angle;
+use osci_core::{EffectApplication, Point};
+use std::f64::consts::TAU;
+
+/// Spin effect — rotates the input continuously around one axis.
+///
+/// `values[0]` picks the axis (0 = X, 1 = Y, 2 = Z) and `values[1]` the
+/// speed in revolutions per second, negative for the other direction. The
+/// angle builds up one sample at a time, so the shape keeps turning at a
+/// steady rate for as long as it plays, without needing an LFO.
+#[derive(Debug, Clone)]
+pub struct SpinEffect {
+    angle: f64,
+}
+
+impl SpinEffect {
+    pub fn new() -> Self {
+        Self { angle: 0.0 }
+    }
+}
+
+impl EffectApplication for SpinEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        mut input: Point,
+        _external_input: Point,
+        values: &[f32],
+        sample_rate: f32,
+        _frequency: f32,
+    ) -> Point {
+        let angle = self.angle as f32;
+        match values[0].round() as i32 {
+            0 => input.rotate(angle, 0.0, 0.0),
+            1 => input.rotate(0.0, angle, 0.0),
+            _ => input.rotate(0.0, 0.0, angle),
+        }
+        if sample_rate > 0.0 {
+            self.angle = wrap_angle(self.angle + TAU * values[1] as f64 / sample_rate as f64);
+        }
+        input
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Spin"
+    }
+}
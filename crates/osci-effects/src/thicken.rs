@@ -0,0 +1,90 @@
+use osci_core::{EffectApplication, Point};
+
+/// Thicken effect — fattens the line by retracing it at small offsets.
+///
+/// Each traversal of the frame is drawn shifted sideways, perpendicular to
+/// the direction the beam is moving, by one of `values[1]` evenly spaced
+/// offsets spanning `values[0]`. Successive traversals step through the
+/// offsets in turn, so together they fill a band of that width centred on
+/// the original line. Traversals are counted from the drawing frequency one
+/// sample at a time, so the pattern depends only on how many samples have
+/// been drawn. One pass leaves the input unchanged.
+#[derive(Debug, Clone)]
+pub struct ThickenEffect {
+    cycle_phase: f64,
+    pass: usize,
+    previous: Option<(f32, f32)>,
+    // Unit normal of the last segment the beam actually moved along
+    normal: (f32, f32),
+}
+
+impl ThickenEffect {
+    pub fn new() -> Self {
+        Self {
+            cycle_phase: 0.0,
+            pass: 0,
+            previous: None,
+            normal: (0.0, 1.0),
+        }
+    }
+}
+
+impl EffectApplication for ThickenEffect {
+    fn apply(
+        &mut self,
+        _index: usize,
+        input: Point,
+        _external_input: Point,
+        values: &[f32],
+        sample_rate: f32,
+        frequency: f32,
+    ) -> Point {
+        let width = values[0].max(0.0);
+        let passes = (values[1].round() as usize).max(1);
+
+        if let Some((px, py)) = self.previous {
+            let (dx, dy) = (input.x - px, input.y - py);
+            let length = dx.hypot(dy);
+            if length > 1e-6 {
+                self.normal = (-dy / length, dx / length);
+            }
+        }
+        self.previous = Some((input.x, input.y));
+
+        if sample_rate > 0.0 {
+            self.cycle_phase += (frequency / sample_rate).max(0.0) as f64;
+            if self.cycle_phase >= 1.0 {
+                self.cycle_phase = self.cycle_phase.fract();
+                self.pass += 1;
+            }
+        }
+        self.pass %= passes;
+
+        let offset = if passes > 1 {
+            width * (self.pass as f32 / (passes - 1) as f32 - 0.5)
+        } else {
+            0.0
+        };
+
+        Point::with_rgb(
+            input.x + self.normal.0 * offset,
+            input.y + self.normal.1 * offset,
+            input.z,
+            input.r,
+            input.g,
+            input.b,
+        )
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn clone_effect(&self) -> Box<dyn EffectApplication> {
+        Box::new(self.clone())
+    }
+
+    fn name(&self) -> &str {
+        "Thicken"
+    }
+}
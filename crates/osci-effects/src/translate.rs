@@ -25,6 +25,20 @@ impl EffectApplication for Translate {
         input + Point::with_rgb(values[0], values[1], values[2], 0.0, 0.0, 0.0)
     }
 
+    fn apply_block(
+        &mut self,
+        inputs: &[Point],
+        outputs: &mut [Point],
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) {
+        let offset = Point::with_rgb(values[0], values[1], values[2], 0.0, 0.0, 0.0);
+        for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+            *output = *input + offset;
+        }
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -77,6 +77,10 @@ impl EffectApplication for UnfoldEffect {
         )
     }
 
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
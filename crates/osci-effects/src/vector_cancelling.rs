@@ -49,6 +49,10 @@ impl EffectApplication for VectorCancelling {
         }
     }
 
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -34,6 +34,27 @@ impl EffectApplication for VolumeEffect {
         )
     }
 
+    fn apply_block(
+        &mut self,
+        inputs: &[Point],
+        outputs: &mut [Point],
+        values: &[f32],
+        _sample_rate: f32,
+        _frequency: f32,
+    ) {
+        let gain = values[0];
+        for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+            *output = Point::with_rgb(
+                input.x * gain,
+                input.y * gain,
+                input.z * gain,
+                input.r,
+                input.g,
+                input.b,
+            );
+        }
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -35,6 +35,10 @@ impl EffectApplication for WobbleEffect {
         input + delta
     }
 
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
     fn clone_effect(&self) -> Box<dyn EffectApplication> {
         Box::new(self.clone())
     }
@@ -1,5 +1,5 @@
 use osci_core::Point;
-use osci_effects::registry::build_registry;
+use osci_effects::registry::{build_registry, find_effect};
 
 // ── Helpers ──────────────────────────────────────────────────────
 
@@ -28,9 +28,9 @@ fn test_input() -> Point {
 // ── 1. Registry completeness ─────────────────────────────────────
 
 #[test]
-fn registry_has_27_effects() {
+fn registry_has_36_effects() {
     let registry = build_registry();
-    assert_eq!(registry.len(), 27, "expected 27 effects in registry");
+    assert_eq!(registry.len(), 36, "expected 36 effects in registry");
 }
 
 #[test]
@@ -39,7 +39,7 @@ fn registry_ids_are_unique() {
     let mut ids: Vec<&str> = registry.iter().map(|e| e.id).collect();
     ids.sort();
     ids.dedup();
-    assert_eq!(ids.len(), 27, "duplicate effect IDs found");
+    assert_eq!(ids.len(), 36, "duplicate effect IDs found");
 }
 
 #[test]
@@ -87,6 +87,31 @@ fn all_effects_return_valid_point_with_defaults() {
 
 // ── 3. Signal modification ───────────────────────────────────────
 
+#[test]
+fn restoring_older_rotate_fills_in_snap() {
+    // Saved before Rotate gained its Snap parameter
+    let entry = find_effect("rotate").unwrap();
+    let mut saved = (entry.parameters)();
+    saved.truncate(3);
+    saved[2].value = 0.5;
+
+    let params = entry.restore_parameters(saved);
+    assert_eq!(params.len(), 4);
+    assert_eq!(params[2].value, 0.5);
+    assert_eq!(params[3].id, "rotateSnapDegrees");
+    assert_eq!(params[3].value, params[3].default_value);
+
+    // Rotating by Z = 0.5 turns a quarter
+    let values: Vec<f32> = params.iter().map(|p| p.value).collect();
+    let mut effect = (entry.constructor)();
+    let output = effect.apply(0, Point::new(1.0, 0.0, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert!(output.x.abs() < 1e-6 && (output.y - 1.0).abs() < 1e-6, "({}, {})", output.x, output.y);
+
+    // Old values straight from a saved project still apply
+    let output = effect.apply(0, Point::new(1.0, 0.0, 0.0), Point::ZERO, &values[..3], SAMPLE_RATE, FREQUENCY);
+    assert!((output.y - 1.0).abs() < 1e-6);
+}
+
 #[test]
 fn translate_moves_point() {
     let registry = build_registry();
@@ -151,8 +176,8 @@ fn rotate_z_rotates_xy() {
     let registry = build_registry();
     let entry = registry.iter().find(|e| e.id == "rotate").unwrap();
     let mut effect = (entry.constructor)();
-    // values: [rotateX=0, rotateY=0, rotateZ=0.25 (quarter turn)]
-    let values = vec![0.0, 0.0, 0.25];
+    // values: [rotateX=0, rotateY=0, rotateZ=0.25 (quarter turn), snap=0]
+    let values = vec![0.0, 0.0, 0.25, 0.0];
 
     let input = Point::with_rgb(1.0, 0.0, 0.0, 1.0, 1.0, 1.0);
     let output = effect.apply(0, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
@@ -166,6 +191,200 @@ fn rotate_z_rotates_xy() {
     );
 }
 
+#[test]
+fn rotate_snaps_to_degree_increments() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "rotate").unwrap();
+    let mut effect = (entry.constructor)();
+    // rotateZ maps to 50 degrees, snapped to 90 degree steps
+    let values = vec![0.0, 0.0, 50.0 / 180.0, 90.0];
+
+    let input = Point::with_rgb(1.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+    let output = effect.apply(0, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    let degrees = output.y.atan2(output.x).to_degrees();
+    assert!((degrees - 90.0).abs() < 0.01, "snapped rotation was {} degrees", degrees);
+
+    let mut block = [Point::ZERO];
+    effect.apply_block(&[input], &mut block, &values, SAMPLE_RATE, FREQUENCY);
+    assert!(block[0].approx_eq(&output));
+
+    // Without a snap the full 50 degrees comes through
+    let values = vec![0.0, 0.0, 50.0 / 180.0, 0.0];
+    let output = effect.apply(0, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    let degrees = output.y.atan2(output.x).to_degrees();
+    assert!((degrees - 50.0).abs() < 0.01, "unsnapped rotation was {} degrees", degrees);
+}
+
+#[test]
+fn chroma_split_fringes_by_direction() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "chromaSplit").unwrap();
+    let mut effect = (entry.constructor)();
+    // values: [amount, angle] — split along +X
+    let values = vec![0.5, 0.0];
+
+    // Moving right: red boosted, blue suppressed, position untouched
+    effect.apply(0, Point::with_rgb(0.0, 0.0, 0.0, 1.0, 1.0, 1.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    let right = effect.apply(1, Point::with_rgb(0.1, 0.0, 0.0, 1.0, 1.0, 1.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert!((right.x - 0.1).abs() < 1e-6);
+    assert!(right.r > 1.0 && right.b < 1.0, "moving right: r={}, b={}", right.r, right.b);
+
+    // Moving back left: the fringe flips
+    let left = effect.apply(2, Point::with_rgb(0.0, 0.0, 0.0, 1.0, 1.0, 1.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert!(left.r < 1.0 && left.b > 1.0, "moving left: r={}, b={}", left.r, left.b);
+}
+
+#[test]
+fn mirror_x_folds_negative_x() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "mirror").unwrap();
+    let mut effect = (entry.constructor)();
+    // values: [mode=X, phase=0]
+    let values = vec![1.0, 0.0];
+
+    let input = Point::with_rgb(-0.5, 0.3, 0.0, 1.0, 1.0, 1.0);
+    let output = effect.apply(0, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+
+    assert!(
+        (output.x - 0.5).abs() < 1e-6 && (output.y - 0.3).abs() < 1e-6,
+        "mirror X incorrect: ({}, {}) expected (0.5, 0.3)",
+        output.x,
+        output.y
+    );
+}
+
+#[test]
+fn mirror_quad_folds_both_axes() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "mirror").unwrap();
+    let mut effect = (entry.constructor)();
+    let values = vec![3.0, 0.0];
+
+    let input = Point::with_rgb(-0.5, -0.3, 0.0, 1.0, 1.0, 1.0);
+    let output = effect.apply(0, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+
+    assert!((output.x - 0.5).abs() < 1e-6 && (output.y - 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn mirror_keeps_flipping_after_zero_sample_rate() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "mirror").unwrap();
+    let mut effect = (entry.constructor)();
+    // values: [mode=quad, phase=0.5], so the flip follows the cycle phase
+    let values = vec![3.0, 0.5];
+    let input = Point::with_rgb(-0.5, -0.3, 0.0, 1.0, 1.0, 1.0);
+
+    effect.apply(0, input, Point::ZERO, &values, 0.0, FREQUENCY);
+    let samples = (2.0 * SAMPLE_RATE / FREQUENCY) as usize;
+    let folded = (1..=samples)
+        .filter(|&i| effect.apply(i, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY).x > 0.0)
+        .count();
+    assert!(folded > 0, "never flipped after a zero sample rate");
+}
+
+#[test]
+fn color_crush_two_levels_rounds_channels() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "colorCrush").unwrap();
+    let mut effect = (entry.constructor)();
+    let values = vec![2.0];
+
+    let input = Point::with_rgb(0.25, -0.5, 0.1, 0.3, 0.7, 1.0);
+    let output = effect.apply(0, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+
+    assert_eq!((output.x, output.y, output.z), (0.25, -0.5, 0.1));
+    assert_eq!((output.r, output.g, output.b), (0.0, 1.0, 1.0));
+}
+
+#[test]
+fn soft_clip_is_linear_when_small_and_saturates_below_threshold() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "softClip").unwrap();
+    let mut effect = (entry.constructor)();
+    // Threshold 0.8, full knee: 0.8 * tanh(x / 0.8)
+    let values = vec![0.8, 1.0];
+
+    let small = effect.apply(0, Point::new(0.01, -0.02, 0.5), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert!((small.x - 0.01).abs() < 1e-5, "got {}", small.x);
+    assert!((small.y + 0.02).abs() < 1e-4, "got {}", small.y);
+    assert_eq!(small.z, 0.5);
+
+    let mut last = 0.0;
+    for x in [0.5, 1.0, 2.0, 3.0] {
+        let out = effect.apply(0, Point::new(x, -x, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+        assert!(out.x < 0.8 && out.x > last, "x {x} gave {}", out.x);
+        assert_eq!(out.y, -out.x);
+        last = out.x;
+    }
+    assert!(last > 0.79);
+    // Far past the knee tanh rounds to 1, so the threshold is reached but never exceeded
+    let far = effect.apply(0, Point::new(50.0, 0.0, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert!(far.x <= 0.8);
+
+    // Half knee: untouched up to 0.4, then bent towards 0.8
+    let values = vec![0.8, 0.5];
+    let out = effect.apply(0, Point::new(0.3, 0.0, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert_eq!(out.x, 0.3);
+    let out = effect.apply(0, Point::new(0.6, 0.0, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert!(out.x > 0.4 && out.x < 0.6);
+}
+
+#[test]
+fn radial_clamp_projects_outliers_onto_circle() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "radialClamp").unwrap();
+    let mut effect = (entry.constructor)();
+    let values = vec![1.0];
+
+    let outside = effect.apply(0, Point::new(2.0, 0.0, 0.5), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert_eq!((outside.x, outside.y, outside.z), (1.0, 0.0, 0.5));
+
+    let inside = effect.apply(0, Point::new(0.3, 0.0, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert_eq!((inside.x, inside.y), (0.3, 0.0));
+
+    // The angle is kept
+    let diagonal = effect.apply(0, Point::new(3.0, 4.0, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert!((diagonal.x - 0.6).abs() < 1e-6 && (diagonal.y - 0.8).abs() < 1e-6);
+}
+
+#[test]
+fn lissajous_quarter_phase_traces_a_circle() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "lissajous").unwrap();
+    let mut effect = (entry.constructor)();
+    // 1:1 ratio, a quarter cycle apart, no decay
+    let values = vec![1.0, 1.0, 0.25, 0.0];
+
+    let points: Vec<Point> = (0..400)
+        .map(|i| effect.apply(i, test_input(), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY))
+        .collect();
+    // Starts at the top and stays on the unit circle
+    assert!(points[0].x.abs() < 1e-6 && (points[0].y - 1.0).abs() < 1e-6);
+    for p in &points {
+        assert!((p.x.hypot(p.y) - 1.0).abs() < 1e-4, "radius {}", p.x.hypot(p.y));
+        assert_eq!(p.z, test_input().z);
+    }
+    // Goes all the way round in one cycle of the frequency (about 100 samples)
+    assert!(points.iter().any(|p| p.x < -0.99) && points.iter().any(|p| p.y < -0.99));
+
+    // A fresh instance repeats the same figure
+    let mut again = (entry.constructor)();
+    let first = again.apply(0, test_input(), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    assert_eq!((first.x, first.y), (points[0].x, points[0].y));
+
+    // Doubling the frequency mid-figure carries on from the same angle,
+    // moving twice as far per sample
+    let before = effect.apply(0, test_input(), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+    let after = effect.apply(0, test_input(), Point::ZERO, &values, SAMPLE_RATE, 2.0 * FREQUENCY);
+    let next = effect.apply(0, test_input(), Point::ZERO, &values, SAMPLE_RATE, 2.0 * FREQUENCY);
+    let angle = |p: Point| p.x.atan2(p.y);
+    let step = |a: Point, b: Point| (angle(b) - angle(a)).rem_euclid(std::f32::consts::TAU);
+    let base_step = std::f32::consts::TAU * FREQUENCY / SAMPLE_RATE;
+    assert!((step(before, after) - base_step).abs() < 1e-4);
+    assert!((step(after, next) - 2.0 * base_step).abs() < 1e-4);
+}
+
 // ── 4. Stateful effects — state evolves over time ────────────────
 
 #[test]
@@ -248,6 +467,87 @@ fn bounce_state_evolves() {
     assert!(has_variation, "bounce produced no variation over 500 samples");
 }
 
+#[test]
+fn jitter_state_evolves() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "jitter").unwrap();
+    let mut effect = (entry.constructor)();
+    // values: [amount=0.2, detail=1.0]
+    let values = vec![0.2, 1.0];
+
+    let input = Point::with_rgb(0.0, 0.0, 0.0, 1.0, 1.0, 1.0);
+    let outputs: Vec<Point> = (0..500)
+        .map(|i| effect.apply(i, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY))
+        .collect();
+
+    let has_variation = outputs.iter().any(|p| (p.x - outputs[0].x).abs() > 0.001);
+    assert!(has_variation, "jitter produced no variation over 500 samples");
+    assert!(
+        outputs.iter().all(|p| p.x.abs() <= 0.2 + 1e-6 && p.y.abs() <= 0.2 + 1e-6),
+        "jitter exceeded its amount"
+    );
+}
+
+#[test]
+fn spin_angle_advances_linearly_with_samples() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "spin").unwrap();
+    let mut effect = (entry.constructor)();
+    // Around Z at 2 revolutions per second
+    let values = vec![2.0, 2.0];
+    let (pi, tau) = (std::f32::consts::PI, std::f32::consts::TAU);
+    let step = tau * 2.0 / SAMPLE_RATE;
+
+    for n in 0..2000 {
+        let out = effect.apply(n, Point::new(1.0, 0.0, 0.0), Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+        let angle = out.y.atan2(out.x);
+        // Difference from n steps, wrapped to [-pi, pi)
+        let error = (angle - n as f32 * step + pi).rem_euclid(tau) - pi;
+        assert!(error.abs() < 1e-3, "sample {n}: angle {angle}");
+        assert!((out.x.hypot(out.y) - 1.0).abs() < 1e-5);
+    }
+
+    // Around X a point on the X axis stays put
+    let mut effect = (entry.constructor)();
+    for n in 0..100 {
+        let out = effect.apply(n, Point::new(1.0, 0.0, 0.0), Point::ZERO, &[0.0, 2.0], SAMPLE_RATE, FREQUENCY);
+        assert_eq!((out.x, out.y), (1.0, 0.0));
+    }
+}
+
+#[test]
+fn thicken_spreads_line_into_band() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "thicken").unwrap();
+    // 441 Hz at 44.1 kHz: a horizontal line traced left to right every 100 samples
+    let trace = |values: &[f32]| {
+        let mut effect = (entry.constructor)();
+        (0..1000)
+            .map(|n| {
+                let x = (n % 100) as f32 / 100.0 - 0.5;
+                effect.apply(n, Point::new(x, 0.0, 0.0), Point::ZERO, values, SAMPLE_RATE, 441.0)
+            })
+            .collect::<Vec<Point>>()
+    };
+
+    let width = 0.02;
+    let out = trace(&[width, 3.0]);
+    let min = out.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+    let max = out.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+    assert!((max - min - width).abs() < 1e-6, "band {min}..{max}");
+    assert!(out.iter().all(|p| p.y.abs() <= width / 2.0 + 1e-6));
+    // Three distinct offsets, centred on the line
+    let mut offsets: Vec<i32> = out.iter().map(|p| (p.y / width * 2.0).round() as i32).collect();
+    offsets.sort();
+    offsets.dedup();
+    assert_eq!(offsets, [-1, 0, 1]);
+
+    // One pass is the line itself
+    for (n, p) in trace(&[width, 1.0]).iter().enumerate() {
+        assert_eq!((p.x, p.y), ((n % 100) as f32 / 100.0 - 0.5, 0.0));
+    }
+}
+
 // ── 5. Determinism — same inputs produce same outputs ────────────
 
 #[test]
@@ -283,6 +583,104 @@ fn all_effects_are_deterministic() {
     }
 }
 
+#[test]
+fn jitter_fresh_instances_match() {
+    let registry = build_registry();
+    let entry = registry.iter().find(|e| e.id == "jitter").unwrap();
+    let values = vec![0.5, 0.7];
+    let input = test_input();
+
+    let mut effect_a = (entry.constructor)();
+    let mut effect_b = (entry.constructor)();
+    for i in 0..2000 {
+        let a = effect_a.apply(i, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+        let b = effect_b.apply(i, input, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY);
+        assert_eq!((a.x, a.y), (b.x, b.y), "jitter streams diverged at sample {i}");
+    }
+}
+
+#[test]
+fn all_effects_block_path_matches_per_sample() {
+    let registry = build_registry();
+    let inputs: Vec<Point> = (0..256)
+        .map(|i| {
+            let t = i as f32 * 0.05;
+            Point::new(t.sin() * 0.8, t.cos() * 0.6, (t * 0.5).sin() * 0.3)
+        })
+        .collect();
+
+    for entry in &registry {
+        let params = (entry.parameters)();
+        let mut values = padded_defaults(&params);
+        // Nudge every value off its default so identity shortcuts aren't all we test
+        for (v, p) in values.iter_mut().zip(&params) {
+            *v = p.min + (p.max - p.min) * 0.37;
+        }
+
+        let mut per_sample = (entry.constructor)();
+        let expected: Vec<Point> = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, p)| per_sample.apply(i, *p, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY))
+            .collect();
+
+        let mut block = (entry.constructor)();
+        let mut actual = vec![Point::ZERO; inputs.len()];
+        block.apply_block(&inputs, &mut actual, &values, SAMPLE_RATE, FREQUENCY);
+
+        for (i, (a, e)) in actual.iter().zip(&expected).enumerate() {
+            assert!(
+                a.x == e.x && a.y == e.y && a.z == e.z,
+                "effect '{}' block output differs at sample {}: block=({},{},{}), per-sample=({},{},{})",
+                entry.id, i, a.x, a.y, a.z, e.x, e.y, e.z
+            );
+        }
+    }
+}
+
+#[test]
+fn all_effects_reset_to_fresh_state() {
+    let registry = build_registry();
+    let inputs: Vec<Point> = (0..512)
+        .map(|i| {
+            let t = i as f32 * 0.05;
+            Point::new(t.sin() * 0.8, t.cos() * 0.6, (t * 0.5).sin() * 0.3)
+        })
+        .collect();
+
+    for entry in &registry {
+        let params = (entry.parameters)();
+        let mut values = padded_defaults(&params);
+        for (v, p) in values.iter_mut().zip(&params) {
+            *v = p.min + (p.max - p.min) * 0.37;
+        }
+        let run = |effect: &mut Box<dyn osci_core::EffectApplication>| -> Vec<Point> {
+            inputs
+                .iter()
+                .enumerate()
+                .map(|(i, p)| effect.apply(i, *p, Point::ZERO, &values, SAMPLE_RATE, FREQUENCY))
+                .collect()
+        };
+
+        let mut fresh = (entry.constructor)();
+        let expected = run(&mut fresh);
+
+        // Build up state, then reset
+        let mut used = (entry.constructor)();
+        run(&mut used);
+        used.reset();
+        let actual = run(&mut used);
+
+        for (i, (a, e)) in actual.iter().zip(&expected).enumerate() {
+            assert!(
+                a.x == e.x && a.y == e.y && a.z == e.z,
+                "effect '{}' differs from a fresh instance at sample {} after reset: ({},{},{}) vs ({},{},{})",
+                entry.id, i, a.x, a.y, a.z, e.x, e.y, e.z
+            );
+        }
+    }
+}
+
 // ── 6. Parameter construction / validation ───────────────────────
 
 #[test]
@@ -337,6 +735,8 @@ fn find_effect_returns_known_ids() {
         "wobble", "duplicator", "multiplex", "unfold", "bounce", "twist",
         "skew", "polygonizer", "kaleidoscope", "vortex", "godRay",
         "spiralBitcrush", "perspective", "volume", "threshold", "frequency",
+        "chromaSplit", "mirror", "jitter", "colorCrush", "softClip",
+        "radialClamp", "lissajous", "spin", "thicken",
     ];
 
     for id in &known_ids {
@@ -1,5 +1,6 @@
 use crate::state::AudioInfo;
 use nih_plug_egui::egui;
+use osci_core::NormalizeMode;
 
 /// Draw the About dialog window.
 pub fn draw_about_dialog(ctx: &egui::Context, open: &mut bool) {
@@ -22,6 +23,25 @@ pub fn draw_about_dialog(ctx: &egui::Context, open: &mut bool) {
         });
 }
 
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+/// Nearest equal-tempered note to `hz` (A4 = 440 Hz), with scientific pitch
+/// octave, and how far off it is in cents, from -50 to +50.
+///
+/// Returns `None` for frequencies that aren't positive and finite.
+pub fn frequency_to_note(hz: f64) -> Option<(String, f64)> {
+    if !hz.is_finite() || hz <= 0.0 {
+        return None;
+    }
+    let midi = 69.0 + 12.0 * (hz / 440.0).log2();
+    let nearest = midi.round();
+    let cents = (midi - nearest) * 100.0;
+    let nearest = nearest as i64;
+    let name = NOTE_NAMES[nearest.rem_euclid(12) as usize];
+    let octave = nearest.div_euclid(12) - 1;
+    Some((format!("{}{}", name, octave), cents))
+}
+
 /// Draw the Audio Device Info dialog window.
 pub fn draw_audio_info_dialog(ctx: &egui::Context, open: &mut bool, info: &AudioInfo) {
     egui::Window::new("Audio Device Info")
@@ -51,6 +71,21 @@ pub fn draw_audio_info_dialog(ctx: &egui::Context, open: &mut bool, info: &Audio
                         ui.label("N/A");
                     }
                     ui.end_row();
+
+                    ui.label("Drawing Frequency:");
+                    if info.current_frequency > 0.0 {
+                        ui.label(format!("{:.2} Hz", info.current_frequency));
+                    } else {
+                        ui.label("N/A");
+                    }
+                    ui.end_row();
+
+                    ui.label("Nearest Note:");
+                    match frequency_to_note(info.current_frequency as f64) {
+                        Some((note, cents)) => ui.label(format!("{} {:+.0} cents", note, cents)),
+                        None => ui.label("N/A"),
+                    };
+                    ui.end_row();
                 });
             ui.add_space(8.0);
             ui.separator();
@@ -76,6 +111,9 @@ pub fn draw_shortcuts_dialog(ctx: &egui::Context, open: &mut bool) {
                         ("Ctrl+O", "Open Project"),
                         ("Ctrl+S", "Save Project"),
                         ("Ctrl+Shift+S", "Save Project As"),
+                        ("Ctrl+.", "All Notes Off"),
+                        ("Ctrl+K", "Quick Add Effect"),
+                        ("Ctrl+V", "Load Pasted SVG/OBJ"),
                     ];
                     for (key, desc) in shortcuts {
                         ui.label(
@@ -87,3 +125,154 @@ pub fn draw_shortcuts_dialog(ctx: &egui::Context, open: &mut bool) {
                 });
         });
 }
+
+/// Draw the Paste Shapes dialog window. Returns `true` when the user asks to
+/// load the pasted text.
+pub fn draw_paste_shapes_dialog(
+    ctx: &egui::Context,
+    open: &mut bool,
+    text: &mut String,
+    config: &mut osci_parsers::ParseConfig,
+) -> bool {
+    let mut confirmed = false;
+    egui::Window::new("Paste Shapes")
+        .open(open)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("Paste SVG markup or OBJ text to draw it.");
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                ui.add(egui::TextEdit::multiline(text).code_editor().desired_rows(8));
+            });
+            ui.checkbox(&mut config.draw_order.two_opt, "Shorten jumps between edges")
+                .on_hover_text("Reorder OBJ edges to cut long jumps; slower on big meshes");
+            ui.horizontal(|ui| {
+                let mut simplify = config.simplify_tolerance.is_some();
+                let mut tolerance = config.simplify_tolerance.unwrap_or(0.002);
+                ui.checkbox(&mut simplify, "Simplify")
+                    .on_hover_text("Merge runs of nearly straight segments into single lines");
+                ui.add_enabled(
+                    simplify,
+                    egui::DragValue::new(&mut tolerance).speed(0.0005).range(0.0..=0.1).prefix("tolerance "),
+                );
+                config.simplify_tolerance = simplify.then_some(tolerance);
+            });
+            ui.horizontal(|ui| {
+                ui.label("Fit");
+                ui.radio_value(&mut config.normalize, NormalizeMode::Anchored, "Anchored");
+                ui.radio_value(&mut config.normalize, NormalizeMode::Centered, "Centered")
+                    .on_hover_text("Centre the drawing's bounding box on the screen");
+            });
+            ui.add_space(8.0);
+            let valid = !text.trim().is_empty();
+            if ui.add_enabled(valid, egui::Button::new("Load")).clicked() {
+                confirmed = true;
+            }
+        });
+    if confirmed {
+        *open = false;
+    }
+    confirmed
+}
+
+/// Draw a window showing an error or warning `message`, clearing it when closed.
+pub fn draw_error_dialog(ctx: &egui::Context, title: &str, message: &mut Option<String>) {
+    let Some(text) = message.as_deref() else {
+        return;
+    };
+    let mut open = true;
+    let mut dismissed = false;
+    egui::Window::new(title)
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(text);
+            ui.add_space(8.0);
+            dismissed = ui.button("OK").clicked();
+        });
+    if !open || dismissed {
+        *message = None;
+    }
+}
+
+/// Draw the Save Preset dialog window. Returns `true` when the user confirms
+/// a non-empty name.
+pub fn draw_save_preset_dialog(ctx: &egui::Context, open: &mut bool, name: &mut String) -> bool {
+    let mut confirmed = false;
+    egui::Window::new("Save Preset")
+        .open(open)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(name);
+            });
+            ui.add_space(8.0);
+            let valid = !name.trim().is_empty();
+            if ui.add_enabled(valid, egui::Button::new("Save")).clicked() {
+                confirmed = true;
+            }
+        });
+    if confirmed {
+        *open = false;
+    }
+    confirmed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_note(hz: f64, name: &str, cents: f64) {
+        let (note, offset) = frequency_to_note(hz).unwrap();
+        assert_eq!(note, name, "{} Hz", hz);
+        assert!((offset - cents).abs() < 0.05, "{} Hz is {} cents off", hz, offset);
+    }
+
+    #[test]
+    fn test_frequency_to_note_and_cents() {
+        assert_note(440.0, "A4", 0.0);
+        assert_note(466.16, "A#4", 0.0);
+        assert_note(261.63, "C4", 0.0);
+        assert_note(27.5, "A0", 0.0);
+        // Detuned pitches keep the nearest note
+        assert_note(440.0 * 2f64.powf(0.25 / 12.0), "A4", 25.0);
+        assert_note(440.0 * 2f64.powf(-0.3 / 12.0), "A4", -30.0);
+        assert_note(8.0, "C-1", -37.6);
+
+        assert!(frequency_to_note(0.0).is_none());
+        assert!(frequency_to_note(f64::NAN).is_none());
+    }
+}
+
+/// Draw the shape stepper, for finding a bad stroke in an import by drawing
+/// the frame's `count` shapes one at a time. Returns whether `index`
+/// changed.
+pub fn draw_shape_stepper(ctx: &egui::Context, open: &mut bool, index: &mut usize, count: usize) -> bool {
+    let before = *index;
+    egui::Window::new("Step Through Shapes")
+        .open(open)
+        .resizable(false)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            if count == 0 {
+                ui.label("The frame has no shapes.");
+                return;
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Prev").clicked() {
+                    *index = (*index + count - 1) % count;
+                }
+                ui.label(format!("Shape {} of {}", *index + 1, count));
+                if ui.button("Next").clicked() {
+                    *index = (*index + 1) % count;
+                }
+            });
+            ui.label("Close this window to draw the whole frame again.");
+        });
+    *index != before
+}
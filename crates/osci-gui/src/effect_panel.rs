@@ -1,9 +1,11 @@
-use crate::state::{EffectSnapshot, UiCommand};
+use crate::project::{self, EffectStateEntry};
+use crate::state::{EffectSnapshot, MacroDefinition, UiCommand, NUM_MACROS};
 use crossbeam::channel::Sender;
 use nih_plug_egui::egui::{self, Ui};
-use osci_core::LfoType;
+use osci_core::{LfoType, SmoothCurve};
 use osci_effects::registry::build_registry;
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Cached (id, name) pairs for the "Add Effect" dropdown.
 /// Built once from the registry and reused every frame.
@@ -25,14 +27,97 @@ fn cached_registry() -> &'static [RegistryEntry] {
     })
 }
 
+/// Cost at which the effect cost bar is full, in nanoseconds per sample:
+/// a fifth of the time available per sample at 48 kHz.
+const FULL_COST_NS: f32 = 1e9 / 48_000.0 / 5.0;
+
+/// A thin bar showing how long an effect takes per sample, relative to
+/// [`FULL_COST_NS`].
+fn draw_cost_bar(ui: &mut Ui, cost_ns: f32) {
+    let fraction = (cost_ns / FULL_COST_NS).clamp(0.0, 1.0);
+    ui.add(
+        egui::ProgressBar::new(fraction)
+            .desired_height(4.0)
+            .fill(cost_color(fraction)),
+    )
+    .on_hover_text(format!("{:.0} ns per sample", cost_ns));
+}
+
+/// Green for cheap effects, through yellow, to red for ones that fill the bar.
+fn cost_color(fraction: f32) -> egui::Color32 {
+    let red = (fraction * 2.0).min(1.0);
+    let green = (2.0 - fraction * 2.0).min(1.0);
+    egui::Color32::from_rgb((red * 220.0) as u8, (green * 200.0) as u8, 40)
+}
+
+/// Move the effect at `from` to `to`, taking macro targets on it along.
+fn move_effect(tx: &Sender<UiCommand>, macros: &Mutex<[MacroDefinition; NUM_MACROS]>, from: usize, to: usize) {
+    if tx.try_send(UiCommand::MoveEffect { from, to }).is_ok() {
+        if let Ok(mut macros) = macros.lock() {
+            macros.iter_mut().for_each(|m| m.effect_moved(from, to));
+        }
+    }
+}
+
+/// Remove the effect at `idx` along with any macro targets on it.
+fn remove_effect(tx: &Sender<UiCommand>, macros: &Mutex<[MacroDefinition; NUM_MACROS]>, idx: usize) {
+    if tx.try_send(UiCommand::RemoveEffect(idx)).is_ok() {
+        if let Ok(mut macros) = macros.lock() {
+            macros.iter_mut().for_each(|m| m.effect_removed(idx));
+        }
+    }
+}
+
 /// Draw the full effect chain panel: list of effects + add-effect controls.
+///
+/// `bypassed` holds the state of the Bypass All toggle in the panel header.
+/// Macro targets in `macros` follow effects that are moved or removed.
 pub fn draw_effect_chain(
     ui: &mut Ui,
     snapshots: &[EffectSnapshot],
     tx: &Sender<UiCommand>,
     selected_effect_id: &mut String,
+    bypassed: &Mutex<bool>,
+    macros: &Mutex<[MacroDefinition; NUM_MACROS]>,
 ) {
-    ui.heading("Effect Chain");
+    ui.horizontal(|ui| {
+        ui.heading("Effect Chain");
+        if let Ok(mut bypassed) = bypassed.lock() {
+            if ui.toggle_value(&mut bypassed, "Bypass All").changed() {
+                let _ = tx.try_send(UiCommand::BypassAll { bypass: *bypassed });
+            }
+        }
+        if ui
+            .button("Randomize")
+            .on_hover_text("Set every unlocked parameter to a random value")
+            .clicked()
+        {
+            let seed = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or(1);
+            let _ = tx.try_send(UiCommand::RandomizeParams {
+                respect_locks: true,
+                seed,
+            });
+        }
+        if ui
+            .button("Copy Chain")
+            .on_hover_text("Copy the chain as text to share")
+            .clicked()
+        {
+            let entries: Vec<EffectStateEntry> = snapshots
+                .iter()
+                .map(|s| EffectStateEntry {
+                    id: s.id.clone(),
+                    enabled: s.enabled,
+                    parameters: s.parameters.clone(),
+                })
+                .collect();
+            ui.ctx().copy_text(project::to_snippet(&entries));
+        }
+    });
+    draw_paste_chain(ui, tx);
     ui.separator();
 
     if snapshots.is_empty() {
@@ -43,7 +128,12 @@ pub fn draw_effect_chain(
 
     for (idx, snap) in snapshots.iter().enumerate() {
         let header_id = ui.make_persistent_id(format!("effect_{}", idx));
-        egui::CollapsingHeader::new(format!("{} — {}", idx + 1, snap.name))
+        let title = if snap.solo {
+            format!("{} — {} (solo)", idx + 1, snap.name)
+        } else {
+            format!("{} — {}", idx + 1, snap.name)
+        };
+        egui::CollapsingHeader::new(title)
             .id_salt(header_id)
             .show(ui, |ui| {
                 ui.horizontal(|ui| {
@@ -53,25 +143,33 @@ pub fn draw_effect_chain(
                         let _ = tx.try_send(UiCommand::SetEffectEnabled { idx, enabled });
                     }
 
+                    let mut solo = snap.solo;
+                    if ui.toggle_value(&mut solo, "Solo").changed() {
+                        let _ = tx.try_send(UiCommand::SetEffectSolo { idx, solo });
+                    }
+
+                    let mut reset_on_note = snap.reset_on_note;
+                    if ui
+                        .checkbox(&mut reset_on_note, "Reset on Note")
+                        .on_hover_text("Clear the effect's state, like echoes, when a note starts")
+                        .changed()
+                    {
+                        let _ = tx.try_send(UiCommand::SetEffectResetOnNote { idx, reset_on_note });
+                    }
+
                     // Move up
                     if idx > 0 && ui.button("Up").clicked() {
-                        let _ = tx.try_send(UiCommand::MoveEffect {
-                            from: idx,
-                            to: idx - 1,
-                        });
+                        move_effect(tx, macros, idx, idx - 1);
                     }
 
                     // Move down
                     if idx + 1 < num_effects && ui.button("Down").clicked() {
-                        let _ = tx.try_send(UiCommand::MoveEffect {
-                            from: idx,
-                            to: idx + 1,
-                        });
+                        move_effect(tx, macros, idx, idx + 1);
                     }
 
                     // Remove
                     if ui.button("Remove").clicked() {
-                        let _ = tx.try_send(UiCommand::RemoveEffect(idx));
+                        remove_effect(tx, macros, idx);
                     }
                 });
 
@@ -80,6 +178,9 @@ pub fn draw_effect_chain(
                     draw_param_controls(ui, idx, param_idx, param, tx);
                 }
             });
+        if let Some(cost_ns) = snap.cost_ns {
+            draw_cost_bar(ui, cost_ns);
+        }
     }
 
     ui.separator();
@@ -107,6 +208,123 @@ pub fn draw_effect_chain(
     });
 }
 
+/// Rough match quality of `query` against `candidate`, or `None` if the
+/// characters of `query` don't all appear in order. Matching ignores case.
+/// Characters that start a word, including the humps of camelCase ids, and
+/// runs of consecutive characters score higher, so "dl" prefers "Dashed
+/// Line" to "Delay". Matches that begin later in `candidate` score lower.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let chars: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut last_match: Option<usize> = None;
+
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let q = q.to_ascii_lowercase();
+        let found = (pos..chars.len()).find(|&i| chars[i].to_ascii_lowercase() == q)?;
+
+        let prev = found.checked_sub(1).map(|i| chars[i]);
+        let word_start = match prev {
+            None => true,
+            Some(p) => !p.is_alphanumeric() || (p.is_lowercase() && chars[found].is_uppercase()),
+        };
+        score += 1;
+        if word_start {
+            score += 10;
+        }
+        match last_match {
+            Some(last) if last + 1 == found => score += 5,
+            None => score -= found as i32,
+            _ => {}
+        }
+
+        last_match = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+/// Registry entries matching `query` by name or id, best first. Entries that
+/// score the same keep their registry order, so an empty query lists them all.
+fn rank_effects<'a>(query: &str, entries: &'a [RegistryEntry]) -> Vec<&'a RegistryEntry> {
+    let mut ranked: Vec<(i32, &RegistryEntry)> = entries
+        .iter()
+        .filter_map(|e| {
+            let score = fuzzy_score(query, e.name).max(fuzzy_score(query, &e.id))?;
+            Some((score, e))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0));
+    ranked.into_iter().map(|(_, e)| e).collect()
+}
+
+/// Draw the quick-add palette: a filter box over the registry where Enter
+/// adds the best match and clicking adds any listed effect. Escape closes it.
+pub fn draw_quick_add(ctx: &egui::Context, open: &mut bool, query: &mut String, tx: &Sender<UiCommand>) {
+    if !*open {
+        return;
+    }
+    let mut chosen = None;
+    let mut close = ctx.input(|i| i.key_pressed(egui::Key::Escape));
+
+    egui::Window::new("Quick Add Effect")
+        .open(open)
+        .resizable(false)
+        .collapsible(false)
+        .title_bar(false)
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+        .show(ctx, |ui| {
+            let response = ui.add(egui::TextEdit::singleline(query).hint_text("Type an effect name..."));
+            response.request_focus();
+
+            let matches = rank_effects(query, cached_registry());
+            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                chosen = matches.first().map(|e| e.id.clone());
+            }
+
+            ui.separator();
+            if matches.is_empty() {
+                ui.label("No matching effects");
+            }
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                for (i, entry) in matches.iter().enumerate() {
+                    if ui.selectable_label(i == 0, entry.name).clicked() {
+                        chosen = Some(entry.id.clone());
+                    }
+                }
+            });
+        });
+
+    if let Some(id) = chosen {
+        let _ = tx.try_send(UiCommand::AddEffect(id));
+        close = true;
+    }
+    if close {
+        *open = false;
+    }
+}
+
+/// Text box to paste a chain copied with "Copy Chain" into, replacing the
+/// current chain when loaded.
+fn draw_paste_chain(ui: &mut Ui, tx: &Sender<UiCommand>) {
+    egui::CollapsingHeader::new("Paste Chain")
+        .default_open(false)
+        .show(ui, |ui| {
+            let id = ui.make_persistent_id("paste_chain_text");
+            let mut text = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_default();
+            ui.add(
+                egui::TextEdit::multiline(&mut text)
+                    .desired_rows(3)
+                    .hint_text("Paste a copied chain here"),
+            );
+            if ui.button("Load").clicked() && !text.trim().is_empty() {
+                crate::send_effect_chain(tx, project::from_snippet(&text));
+                text.clear();
+            }
+            ui.data_mut(|d| d.insert_temp(id, text));
+        });
+}
+
 /// Draw parameter controls for a single effect parameter.
 fn draw_param_controls(
     ui: &mut Ui,
@@ -116,22 +334,34 @@ fn draw_param_controls(
     tx: &Sender<UiCommand>,
 ) {
     ui.group(|ui| {
-        // Value slider
-        let mut value = param.value;
-        let slider = if param.step > 0.0 {
-            egui::Slider::new(&mut value, param.min..=param.max)
-                .text(&param.name)
-                .step_by(param.step as f64)
-        } else {
-            egui::Slider::new(&mut value, param.min..=param.max).text(&param.name)
-        };
-        if ui.add(slider).changed() {
-            let _ = tx.try_send(UiCommand::SetParamValue {
-                effect_idx,
-                param_idx,
-                value,
-            });
-        }
+        ui.horizontal(|ui| {
+            // Value slider
+            let mut value = param.value;
+            let slider = if param.step > 0.0 {
+                egui::Slider::new(&mut value, param.min..=param.max)
+                    .text(&param.name)
+                    .step_by(param.step as f64)
+            } else {
+                egui::Slider::new(&mut value, param.min..=param.max).text(&param.name)
+            };
+            if ui.add(slider).changed() {
+                let _ = tx.try_send(UiCommand::SetParamValue {
+                    effect_idx,
+                    param_idx,
+                    value,
+                });
+            }
+
+            // Lock against Randomize
+            let mut locked = param.locked;
+            if ui.toggle_value(&mut locked, "Lock").changed() {
+                let _ = tx.try_send(UiCommand::SetParamLocked {
+                    effect_idx,
+                    param_idx,
+                    locked,
+                });
+            }
+        });
 
         // LFO controls (collapsible)
         egui::CollapsingHeader::new(format!("LFO##{}_{}", effect_idx, param_idx))
@@ -142,6 +372,7 @@ fn draw_param_controls(
                 let mut rate = param.lfo_rate;
                 let mut start = param.lfo_start_percent;
                 let mut end = param.lfo_end_percent;
+                let mut bipolar = param.lfo_bipolar;
 
                 let mut changed = false;
 
@@ -177,6 +408,12 @@ fn draw_param_controls(
                 {
                     changed = true;
                 }
+                if ui
+                    .checkbox(&mut bipolar, "Bipolar (swing around value)")
+                    .changed()
+                {
+                    changed = true;
+                }
 
                 if changed {
                     let _ = tx.try_send(UiCommand::SetLfo {
@@ -186,20 +423,33 @@ fn draw_param_controls(
                         rate,
                         start,
                         end,
+                        bipolar,
                     });
                 }
             });
 
-        // Smoothing slider
+        // Smoothing slider and curve
         let mut smooth = param.smooth_value_change;
-        if ui
-            .add(egui::Slider::new(&mut smooth, 0.0..=1.0).text("Smoothing"))
-            .changed()
-        {
+        let mut curve = param.smooth_curve;
+        let mut smoothing_changed = false;
+        ui.horizontal(|ui| {
+            smoothing_changed |= ui
+                .add(egui::Slider::new(&mut smooth, 0.0..=1.0).text("Smoothing"))
+                .changed();
+            egui::ComboBox::from_id_salt(format!("smooth_curve_{}_{}", effect_idx, param_idx))
+                .selected_text(curve.name())
+                .show_ui(ui, |ui| {
+                    for c in SmoothCurve::ALL {
+                        smoothing_changed |= ui.selectable_value(&mut curve, c, c.name()).changed();
+                    }
+                });
+        });
+        if smoothing_changed {
             let _ = tx.try_send(UiCommand::SetSmoothing {
                 effect_idx,
                 param_idx,
                 value: smooth,
+                curve,
             });
         }
 
@@ -224,6 +474,7 @@ const ALL_LFO_TYPES: &[LfoType] = &[
     LfoType::Sawtooth,
     LfoType::ReverseSawtooth,
     LfoType::Noise,
+    LfoType::SmoothNoise,
 ];
 
 fn lfo_type_name(t: LfoType) -> &'static str {
@@ -236,5 +487,52 @@ fn lfo_type_name(t: LfoType) -> &'static str {
         LfoType::Sawtooth => "Sawtooth",
         LfoType::ReverseSawtooth => "Reverse Saw",
         LfoType::Noise => "Noise",
+        LfoType::SmoothNoise => "Smooth Noise",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top_match(query: &str) -> Option<&'static str> {
+        rank_effects(query, cached_registry()).first().map(|e| e.name)
+    }
+
+    #[test]
+    fn quick_add_ranks_best_match_first() {
+        assert_eq!(top_match("kal"), Some("Kaleidoscope"));
+        assert_eq!(top_match("KALEI"), Some("Kaleidoscope"));
+        // Word starts beat letters buried inside a word
+        assert_eq!(top_match("dl"), Some("Dashed Line"));
+        assert_eq!(top_match("bc"), Some("Bit Crush"));
+        // Ids match as well as names
+        assert_eq!(top_match("godray"), Some("God Ray"));
+
+        assert!(rank_effects("qqq", cached_registry()).is_empty());
+        assert_eq!(rank_effects("", cached_registry()).len(), cached_registry().len());
+    }
+
+    #[test]
+    fn macro_targets_follow_moved_and_removed_effects() {
+        use crate::state::MacroTarget;
+
+        let (tx, rx) = crossbeam::channel::unbounded();
+        let macros = Mutex::new(<[MacroDefinition; NUM_MACROS]>::default());
+        macros.lock().unwrap()[0].targets =
+            (0..3).map(|effect_idx| MacroTarget::full_range(effect_idx, 0)).collect();
+        let effects = |macros: &Mutex<[MacroDefinition; NUM_MACROS]>| -> Vec<usize> {
+            macros.lock().unwrap()[0].targets.iter().map(|t| t.effect_idx).collect()
+        };
+
+        // Chain a b c: moving a to the end gives b c a
+        move_effect(&tx, &macros, 0, 2);
+        assert!(matches!(rx.try_recv(), Ok(UiCommand::MoveEffect { from: 0, to: 2 })));
+        assert_eq!(effects(&macros), [2, 0, 1]);
+
+        // Removing c leaves b a, and c's target goes with it
+        remove_effect(&tx, &macros, 1);
+        assert!(matches!(rx.try_recv(), Ok(UiCommand::RemoveEffect(1))));
+        assert_eq!(effects(&macros), [1, 0]);
     }
 }
@@ -0,0 +1,314 @@
+use crate::state::UiCommand;
+use crossbeam::channel::Sender;
+use nih_plug_egui::egui::{self, Ui};
+use osci_core::{Env, EnvCurve, EnvCurveType};
+use std::sync::{Arc, Mutex};
+
+/// Segment shapes offered in the curve picker.
+///
+/// `Exponential` is left out: it is undefined for segments that start or end
+/// at zero, which nearly every envelope has.
+const CURVE_CHOICES: &[(&str, EnvCurveType)] = &[
+    ("Linear", EnvCurveType::Linear),
+    ("Curved", EnvCurveType::Numerical(-4.0)),
+    ("Sine", EnvCurveType::Sine),
+    ("Welch", EnvCurveType::Welch),
+    ("Step", EnvCurveType::Step),
+];
+
+/// Height of the breakpoint canvas, in logical pixels.
+const CANVAS_HEIGHT: f32 = 120.0;
+/// Side of the square drag handle drawn for each breakpoint.
+const NODE_SIZE: f32 = 10.0;
+/// Number of points used to draw the envelope curve.
+const CURVE_RESOLUTION: usize = 128;
+
+/// One breakpoint of a custom envelope.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnvNode {
+    /// Absolute time of the breakpoint in seconds.
+    pub time: f64,
+    pub level: f64,
+    /// Shape of the segment arriving at this node (ignored for the first).
+    pub curve: EnvCurveType,
+}
+
+/// Build an [`Env`] from breakpoints sorted by time.
+///
+/// `release_node` is the index of the node the envelope sustains at until
+/// note off, or -1 for a one-shot envelope.
+pub fn env_from_nodes(nodes: &[EnvNode], release_node: i32) -> Env {
+    let levels = nodes.iter().map(|n| n.level).collect();
+    let times = nodes
+        .windows(2)
+        .map(|pair| (pair[1].time - pair[0].time).max(0.0))
+        .collect();
+    let curves = nodes
+        .iter()
+        .skip(1)
+        .map(|n| EnvCurve { curve_type: n.curve })
+        .collect();
+    let release_node = if release_node >= 0 && (release_node as usize) < nodes.len() {
+        release_node
+    } else {
+        -1
+    };
+    Env::new(levels, times, curves, release_node, -1)
+}
+
+/// Split an [`Env`] into breakpoints with absolute times.
+pub fn nodes_from_env(env: &Env) -> Vec<EnvNode> {
+    let mut time = 0.0;
+    env.levels
+        .iter()
+        .enumerate()
+        .map(|(i, &level)| {
+            let curve = if i == 0 || env.curves.is_empty() {
+                EnvCurveType::Linear
+            } else {
+                time += env.times.get(i - 1).copied().unwrap_or(0.0);
+                env.curves[(i - 1) % env.curves.len()].curve_type
+            };
+            EnvNode { time, level, curve }
+        })
+        .collect()
+}
+
+fn curve_name(curve: EnvCurveType) -> &'static str {
+    CURVE_CHOICES
+        .iter()
+        .find(|(_, c)| *c == curve)
+        .map(|(name, _)| *name)
+        .unwrap_or("Custom")
+}
+
+/// Draw the custom envelope editor.
+///
+/// While enabled, the drawn envelope replaces the ADSR sliders: drag a node to
+/// move it, double-click the canvas to add one and right-click a node to
+/// remove it. Clicking a node selects it so its incoming segment shape and
+/// sustain flag can be edited below the canvas. `fallback` seeds the editor
+/// when it is first switched on. Every edit is sent to the audio thread as
+/// [`UiCommand::SetEnvelope`].
+pub fn draw_envelope_editor(
+    ui: &mut Ui,
+    envelope: &Arc<Mutex<Option<Env>>>,
+    command_tx: &Sender<UiCommand>,
+    fallback: &Env,
+) {
+    let Ok(mut envelope) = envelope.lock() else {
+        return;
+    };
+
+    let mut enabled = envelope.is_some();
+    if ui.checkbox(&mut enabled, "Custom envelope (overrides ADSR)").changed() {
+        if enabled {
+            *envelope = Some(fallback.clone());
+            let _ = command_tx.try_send(UiCommand::SetEnvelope(fallback.clone()));
+        } else {
+            *envelope = None;
+            let _ = command_tx.try_send(UiCommand::ClearEnvelope);
+        }
+    }
+
+    let Some(env) = envelope.as_mut() else {
+        return;
+    };
+
+    let mut nodes = nodes_from_env(env);
+    let mut release_node = env.release_node;
+    let mut changed = false;
+
+    let id = ui.make_persistent_id("envelope_editor");
+    let selected_id = id.with("selected");
+    let span_id = id.with("span");
+    let mut selected: Option<usize> = ui.data(|d| d.get_temp(selected_id));
+
+    let (rect, background) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), CANVAS_HEIGHT),
+        egui::Sense::click(),
+    );
+
+    // Freeze the time axis while a node is dragged so it doesn't run away
+    let dragging = (0..nodes.len()).any(|i| ui.ctx().is_being_dragged(id.with(i)));
+    let duration = nodes.last().map_or(0.0, |n| n.time);
+    let span = match ui.data(|d| d.get_temp::<f64>(span_id)) {
+        Some(span) if dragging => span,
+        _ => (duration * 1.25).max(0.1),
+    };
+    ui.data_mut(|d| d.insert_temp(span_id, span));
+
+    let to_screen = |time: f64, level: f64| {
+        egui::pos2(
+            rect.left() + (time / span) as f32 * rect.width(),
+            rect.bottom() - level.clamp(0.0, 1.0) as f32 * rect.height(),
+        )
+    };
+
+    if background.double_clicked() {
+        if let Some(pos) = background.interact_pointer_pos() {
+            let time = ((pos.x - rect.left()) / rect.width()) as f64 * span;
+            let level = ((rect.bottom() - pos.y) / rect.height()).clamp(0.0, 1.0) as f64;
+            let index = nodes.iter().position(|n| n.time > time).unwrap_or(nodes.len());
+            if index > 0 {
+                nodes.insert(index, EnvNode { time, level, curve: nodes[index - 1].curve });
+                if release_node >= index as i32 {
+                    release_node += 1;
+                }
+                selected = Some(index);
+                changed = true;
+            }
+        }
+    }
+
+    let mut remove = None;
+    for i in 0..nodes.len() {
+        let pos = to_screen(nodes[i].time, nodes[i].level);
+        let handle = egui::Rect::from_center_size(pos, egui::vec2(NODE_SIZE, NODE_SIZE));
+        let response = ui.interact(handle, id.with(i), egui::Sense::click_and_drag());
+
+        if response.dragged() {
+            let delta = response.drag_delta();
+            // The first node always starts the envelope at t = 0
+            if i > 0 {
+                let min = nodes[i - 1].time;
+                let max = nodes.get(i + 1).map_or(f64::MAX, |n| n.time);
+                let time = nodes[i].time + (delta.x / rect.width()) as f64 * span;
+                nodes[i].time = time.clamp(min, max);
+            }
+            let level = nodes[i].level - (delta.y / rect.height()) as f64;
+            nodes[i].level = level.clamp(0.0, 1.0);
+            changed = true;
+        }
+        if response.clicked() || response.drag_started() {
+            selected = Some(i);
+        }
+        if response.secondary_clicked() && i > 0 && nodes.len() > 2 {
+            remove = Some(i);
+        }
+    }
+
+    if let Some(i) = remove {
+        nodes.remove(i);
+        if release_node == i as i32 {
+            release_node = -1;
+        } else if release_node > i as i32 {
+            release_node -= 1;
+        }
+        selected = None;
+        changed = true;
+    }
+
+    if changed {
+        *env = env_from_nodes(&nodes, release_node);
+    }
+
+    // Canvas: background, curve, then handles on top
+    let visuals = ui.visuals();
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+
+    let duration = env.duration();
+    let curve: Vec<egui::Pos2> = (0..=CURVE_RESOLUTION)
+        .map(|k| {
+            let time = duration * k as f64 / CURVE_RESOLUTION as f64;
+            to_screen(time, env.lookup(time as f32) as f64)
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        curve,
+        egui::Stroke::new(1.5, visuals.selection.stroke.color),
+    ));
+
+    for (i, node) in nodes.iter().enumerate() {
+        let pos = to_screen(node.time, node.level);
+        let color = if selected == Some(i) {
+            visuals.selection.bg_fill
+        } else {
+            visuals.widgets.inactive.fg_stroke.color
+        };
+        painter.rect_filled(
+            egui::Rect::from_center_size(pos, egui::vec2(NODE_SIZE, NODE_SIZE)),
+            1.0,
+            color,
+        );
+        if i as i32 == env.release_node {
+            painter.vline(pos.x, rect.y_range(), egui::Stroke::new(1.0, visuals.weak_text_color()));
+        }
+    }
+
+    // Per-node controls for the selection
+    if let Some(i) = selected.filter(|&i| i < nodes.len()) {
+        ui.horizontal(|ui| {
+            ui.label(format!("Node {} at {:.3} s", i + 1, nodes[i].time));
+
+            if i > 0 {
+                let mut curve = nodes[i].curve;
+                egui::ComboBox::from_id_salt("envelope_curve")
+                    .selected_text(curve_name(curve))
+                    .show_ui(ui, |ui| {
+                        for (name, choice) in CURVE_CHOICES {
+                            ui.selectable_value(&mut curve, *choice, *name);
+                        }
+                    });
+                if curve != nodes[i].curve {
+                    nodes[i].curve = curve;
+                    changed = true;
+                }
+            }
+
+            let mut sustain = release_node == i as i32;
+            if ui.checkbox(&mut sustain, "Sustain here").changed() {
+                release_node = if sustain { i as i32 } else { -1 };
+                changed = true;
+            }
+        });
+
+        if changed {
+            *env = env_from_nodes(&nodes, release_node);
+        }
+    }
+
+    ui.data_mut(|d| d.insert_temp(selected_id, selected));
+
+    if changed {
+        let _ = command_tx.try_send(UiCommand::SetEnvelope(env.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_list_serializes_to_valid_env() {
+        let nodes = vec![
+            EnvNode { time: 0.0, level: 0.0, curve: EnvCurveType::Linear },
+            EnvNode { time: 0.1, level: 1.0, curve: EnvCurveType::Sine },
+            EnvNode { time: 0.4, level: 0.6, curve: EnvCurveType::Numerical(-4.0) },
+            EnvNode { time: 1.4, level: 0.0, curve: EnvCurveType::Welch },
+        ];
+        let env = env_from_nodes(&nodes, 2);
+
+        let json = serde_json::to_string(&env).unwrap();
+        let env: Env = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(env.levels.len(), 4);
+        assert_eq!(env.times.len(), 3);
+        assert_eq!(env.curves.len(), 3);
+        assert_eq!(env.release_node, 2);
+        assert!((env.duration() - 1.4).abs() < 1e-9);
+        assert!((env.lookup(0.1) - 1.0).abs() < 1e-4);
+        assert!((env.lookup(0.4) - 0.6).abs() < 1e-4);
+        assert_eq!(nodes_from_env(&env), nodes);
+    }
+
+    #[test]
+    fn out_of_range_release_node_is_dropped() {
+        let nodes = vec![
+            EnvNode { time: 0.0, level: 0.0, curve: EnvCurveType::Linear },
+            EnvNode { time: 0.5, level: 1.0, curve: EnvCurveType::Linear },
+        ];
+        assert_eq!(env_from_nodes(&nodes, 5).release_node, -1);
+    }
+}
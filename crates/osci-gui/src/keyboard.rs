@@ -0,0 +1,208 @@
+use crate::state::UiCommand;
+use crossbeam::channel::Sender;
+use nih_plug_egui::egui;
+
+/// Lowest note of the keyboard at octave offset zero (middle C).
+const BASE_NOTE: u8 = 60;
+
+/// Octaves drawn on screen.
+const OCTAVES: u8 = 2;
+
+/// Height of the on-screen keys, in logical pixels.
+const KEYBOARD_HEIGHT: f32 = 56.0;
+
+/// Velocity for notes played from the computer keyboard.
+const QWERTY_VELOCITY: f32 = 0.8;
+
+/// Computer keys laid out like a piano: the home row plays the white keys
+/// and the row above the black keys, starting from C.
+const QWERTY_KEYS: [(egui::Key, u8); 16] = [
+    (egui::Key::A, 0),
+    (egui::Key::W, 1),
+    (egui::Key::S, 2),
+    (egui::Key::E, 3),
+    (egui::Key::D, 4),
+    (egui::Key::F, 5),
+    (egui::Key::T, 6),
+    (egui::Key::G, 7),
+    (egui::Key::Y, 8),
+    (egui::Key::H, 9),
+    (egui::Key::U, 10),
+    (egui::Key::J, 11),
+    (egui::Key::K, 12),
+    (egui::Key::O, 13),
+    (egui::Key::L, 14),
+    (egui::Key::P, 15),
+];
+
+/// Notes held on the on-screen and computer keyboards, kept in egui memory
+/// between frames.
+#[derive(Clone, Default)]
+struct KeyboardState {
+    /// Octaves above or below [`BASE_NOTE`], changed with Z and X.
+    octave: i8,
+    /// Note held down with the mouse.
+    mouse_note: Option<u8>,
+    /// Computer keys held down and the note each one started.
+    held_keys: Vec<(egui::Key, u8)>,
+}
+
+/// MIDI note played by `key` with the keyboard shifted by `octave`, if the
+/// key is part of the piano layout and the note is in MIDI range.
+pub fn qwerty_note(key: egui::Key, octave: i8) -> Option<u8> {
+    let (_, offset) = QWERTY_KEYS.iter().find(|(k, _)| *k == key)?;
+    let note = BASE_NOTE as i32 + octave as i32 * 12 + *offset as i32;
+    u8::try_from(note).ok().filter(|n| *n <= 127)
+}
+
+fn is_black_key(note: u8) -> bool {
+    matches!(note % 12, 1 | 3 | 6 | 8 | 10)
+}
+
+/// Draw a mini piano keyboard that plays the instrument.
+///
+/// Clicking a key sends [`UiCommand::NoteOn`], louder towards the bottom of
+/// the key, and releasing it sends [`UiCommand::NoteOff`]. While no text
+/// field has focus the computer keyboard plays too: A to K are the white
+/// keys from C, W E T Y U the black keys, and Z / X shift down or up an
+/// octave.
+pub fn draw_keyboard(ui: &mut egui::Ui, command_tx: &Sender<UiCommand>) {
+    let id = ui.make_persistent_id("keyboard");
+    let mut state: KeyboardState = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+
+    let low = (BASE_NOTE as i32 + state.octave as i32 * 12).clamp(0, 127 - 12 * OCTAVES as i32) as u8;
+    let notes: Vec<u8> = (low..=low + 12 * OCTAVES).collect();
+    let white_count = notes.iter().filter(|n| !is_black_key(**n)).count();
+
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), KEYBOARD_HEIGHT),
+        egui::Sense::click_and_drag(),
+    );
+    let white_width = rect.width() / white_count as f32;
+    let black_size = egui::vec2(white_width * 0.6, rect.height() * 0.6);
+
+    // Each key's rect, black keys first so they win hit tests
+    let mut keys: Vec<(u8, egui::Rect)> = Vec::with_capacity(notes.len());
+    let mut white_index = 0;
+    for &note in &notes {
+        if is_black_key(note) {
+            let centre_x = rect.left() + white_index as f32 * white_width;
+            let key = egui::Rect::from_min_size(
+                egui::pos2(centre_x - black_size.x / 2.0, rect.top()),
+                black_size,
+            );
+            keys.insert(0, (note, key));
+        } else {
+            let key = egui::Rect::from_min_size(
+                egui::pos2(rect.left() + white_index as f32 * white_width, rect.top()),
+                egui::vec2(white_width, rect.height()),
+            );
+            keys.push((note, key));
+            white_index += 1;
+        }
+    }
+
+    // Mouse: follow the pointer across keys while the button is held
+    let pointer = response
+        .is_pointer_button_down_on()
+        .then(|| response.interact_pointer_pos())
+        .flatten();
+    let hovered = pointer.and_then(|pos| {
+        keys.iter()
+            .find(|(_, key)| key.contains(pos))
+            .map(|(note, key)| (*note, (pos.y - key.top()) / key.height()))
+    });
+    if hovered.map(|(note, _)| note) != state.mouse_note {
+        if let Some(note) = state.mouse_note.take() {
+            let _ = command_tx.try_send(UiCommand::NoteOff { note });
+        }
+        if let Some((note, depth)) = hovered {
+            let velocity = 0.3 + 0.7 * depth.clamp(0.0, 1.0);
+            let _ = command_tx.try_send(UiCommand::NoteOn { note, velocity });
+            state.mouse_note = Some(note);
+        }
+    }
+
+    // Computer keyboard, unless the user is typing somewhere
+    if !ui.ctx().wants_keyboard_input() {
+        let events = ui.input(|i| i.events.clone());
+        for event in events {
+            let egui::Event::Key { key, pressed, repeat, modifiers, .. } = event else {
+                continue;
+            };
+            if repeat || modifiers.command || modifiers.alt {
+                continue;
+            }
+            match (key, pressed) {
+                (egui::Key::Z, true) => state.octave = (state.octave - 1).max(-5),
+                (egui::Key::X, true) => state.octave = (state.octave + 1).min(4),
+                (key, true) => {
+                    if let Some(note) = qwerty_note(key, state.octave) {
+                        if !state.held_keys.iter().any(|(k, _)| *k == key) {
+                            let _ = command_tx.try_send(UiCommand::NoteOn {
+                                note,
+                                velocity: QWERTY_VELOCITY,
+                            });
+                            state.held_keys.push((key, note));
+                        }
+                    }
+                }
+                (key, false) => {
+                    // Release the note the key started, even if the octave moved since
+                    if let Some(i) = state.held_keys.iter().position(|(k, _)| *k == key) {
+                        let (_, note) = state.held_keys.remove(i);
+                        let _ = command_tx.try_send(UiCommand::NoteOff { note });
+                    }
+                }
+            }
+        }
+    }
+
+    // Paint white keys under black keys
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    let sounding = |note: u8| {
+        state.mouse_note == Some(note) || state.held_keys.iter().any(|(_, n)| *n == note)
+    };
+    for (note, key) in keys.iter().rev() {
+        let fill = if sounding(*note) {
+            visuals.selection.bg_fill
+        } else if is_black_key(*note) {
+            egui::Color32::from_gray(30)
+        } else {
+            egui::Color32::from_gray(220)
+        };
+        painter.rect_filled(key.shrink(0.5), 2.0, fill);
+    }
+    painter.text(
+        rect.left_bottom() + egui::vec2(3.0, -3.0),
+        egui::Align2::LEFT_BOTTOM,
+        format!("C{}", low as i32 / 12 - 1),
+        egui::FontId::proportional(10.0),
+        egui::Color32::from_gray(90),
+    );
+
+    ui.data_mut(|d| d.insert_temp(id, state));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qwerty_layout_follows_piano() {
+        assert_eq!(qwerty_note(egui::Key::A, 0), Some(60));
+        assert_eq!(qwerty_note(egui::Key::W, 0), Some(61));
+        assert_eq!(qwerty_note(egui::Key::K, 0), Some(72));
+        assert_eq!(qwerty_note(egui::Key::P, 0), Some(75));
+
+        // Z / X shift whole octaves
+        assert_eq!(qwerty_note(egui::Key::A, -1), Some(48));
+        assert_eq!(qwerty_note(egui::Key::J, 1), Some(83));
+
+        // Keys outside the layout and notes outside MIDI range play nothing
+        assert_eq!(qwerty_note(egui::Key::Q, 0), None);
+        assert_eq!(qwerty_note(egui::Key::A, -6), None);
+        assert_eq!(qwerty_note(egui::Key::P, 5), None);
+    }
+}
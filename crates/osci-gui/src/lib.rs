@@ -1,21 +1,60 @@
 pub mod dialogs;
 pub mod effect_panel;
+pub mod envelope_editor;
+pub mod keyboard;
+pub mod lua_panel;
+pub mod macro_panel;
 pub mod menu_bar;
+pub mod meter;
+pub mod preset;
 pub mod project;
+pub mod recent;
 pub mod scope;
 pub mod state;
 pub mod theme;
 
 pub use menu_bar::MenuState;
+pub use meter::{measure_level, ChannelLevel};
 pub use scope::GpuScopeState;
-pub use state::{AudioInfo, EditorSharedState, EffectSnapshot, LoadedEffect, UiCommand, VisBuffer};
+pub use state::{
+    AudioInfo, EditorSharedState, EffectSnapshot, LoadedEffect, MacroDefinition, MacroTarget, UiCommand,
+    VisBuffer, NUM_LUA_SLIDERS, NUM_MACROS,
+};
 
 use menu_bar::MenuAction;
 use nih_plug::prelude::*;
 use nih_plug_egui::egui;
+use nih_plug_egui::resizable_window::ResizableWindow;
+use nih_plug_egui::EguiState;
+use osci_parsers::audio::AudioSampleSource;
+use osci_parsers::lua::LuaParser;
+use osci_parsers::sample_source::LuaSource;
+use osci_parsers::SampleSource;
 use state::EditorSharedState as SharedState;
 use std::sync::{Arc, Mutex};
 
+/// Range of the voice count slider; the synth allows up to 64.
+const VOICE_COUNT_RANGE: std::ops::RangeInclusive<usize> = 1..=64;
+
+/// Smallest size the editor window can be dragged down to, in logical pixels.
+pub const MIN_EDITOR_SIZE: egui::Vec2 = egui::vec2(420.0, 480.0);
+
+/// Lay out editor contents inside a user-resizable window.
+///
+/// Dragging the bottom-right corner asks the host for a new size, clamped to
+/// [`MIN_EDITOR_SIZE`]. The size is stored in `egui_state`, which plugins
+/// persist, so it survives closing the editor and reloading the session.
+pub fn show_resizable<R>(
+    egui_ctx: &egui::Context,
+    egui_state: &EguiState,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+    ResizableWindow::new("editor_resize")
+        .min_size(MIN_EDITOR_SIZE)
+        .show(egui_ctx, egui_state, add_contents)
+        .inner
+}
+
 /// References to the nih-plug parameters exposed to the editor.
 pub struct OsciPluginParamRefs<'a> {
     pub volume: &'a FloatParam,
@@ -24,6 +63,7 @@ pub struct OsciPluginParamRefs<'a> {
     pub decay: &'a FloatParam,
     pub sustain: &'a FloatParam,
     pub release: &'a FloatParam,
+    pub macros: [&'a FloatParam; NUM_MACROS],
 }
 
 /// Check for keyboard shortcuts and return the corresponding menu action.
@@ -42,6 +82,12 @@ fn check_shortcuts(ctx: &egui::Context) -> MenuAction {
         if ctx.input(|i| i.key_pressed(egui::Key::N)) {
             return MenuAction::NewProject;
         }
+        if ctx.input(|i| i.key_pressed(egui::Key::Period)) {
+            return MenuAction::AllNotesOff;
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::K)) {
+            return MenuAction::QuickAddEffect;
+        }
     }
     MenuAction::None
 }
@@ -78,27 +124,38 @@ fn apply_synth_params(
 fn build_project_file(
     params: &OsciPluginParamRefs,
     effect_snapshots: &[EffectSnapshot],
+    shared: &SharedState,
     scope_state: &Arc<Mutex<GpuScopeState>>,
 ) -> project::ProjectFile {
     let visualizer = scope_state.lock().ok().map(|state| {
-        let s = &state.settings;
-        project::VisualizerSnapshot {
-            focus: s.focus,
-            intensity: s.intensity,
-            persistence: s.persistence,
-            afterglow: s.afterglow,
-            glow_amount: s.glow_amount,
-            scatter_amount: s.scatter_amount,
-            color: s.color,
-            exposure: s.exposure,
-            overexposure: s.overexposure,
-            saturation: s.saturation,
-            ambient: s.ambient,
-            noise: s.noise,
-            afterglow_color: Some(s.afterglow_color),
-            reflection_mode: Some(s.reflection_mode),
-            goniometer: Some(s.goniometer),
-        }
+        project::VisualizerSnapshot::from_settings(&state.settings)
+    });
+
+    let lua_sliders = shared.lua_sliders.lock().ok().and_then(|values| {
+        let changed: std::collections::BTreeMap<String, f64> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| **v != 0.0)
+            .map(|(i, v)| (state::lua_slider_name(i), *v))
+            .collect();
+        (!changed.is_empty()).then_some(changed)
+    });
+
+    let envelope = shared.envelope.lock().ok().and_then(|env| env.clone());
+
+    // The host parameters hold the macro values
+    let macros = shared.macros.lock().ok().and_then(|macros| {
+        let used = macros.iter().any(|m| !m.targets.is_empty());
+        used.then(|| {
+            macros
+                .iter()
+                .zip(params.macros)
+                .map(|(m, param)| MacroDefinition {
+                    value: param.unmodulated_plain_value(),
+                    targets: m.targets.clone(),
+                })
+                .collect()
+        })
     });
 
     project::ProjectFile {
@@ -113,6 +170,9 @@ fn build_project_file(
             })
             .collect(),
         visualizer,
+        lua_sliders,
+        envelope,
+        macros,
     }
 }
 
@@ -137,6 +197,7 @@ fn pick_open_path() -> Option<std::path::PathBuf> {
     rfd::FileDialog::new()
         .set_title("Open Project")
         .add_filter("osci-project", &["osci-project"])
+        .add_filter("osci-render project", &["osci"])
         .pick_file()
 }
 
@@ -146,12 +207,41 @@ fn pick_open_path() -> Option<std::path::PathBuf> {
     None
 }
 
+#[cfg(feature = "file-dialog")]
+fn pick_audio_path() -> Option<std::path::PathBuf> {
+    rfd::FileDialog::new()
+        .set_title("Load Audio")
+        .add_filter("Audio", &["wav", "aiff", "ogg", "flac", "mp3"])
+        .pick_file()
+}
+
+#[cfg(not(feature = "file-dialog"))]
+fn pick_audio_path() -> Option<std::path::PathBuf> {
+    log::warn!("File dialogs not available (build with 'file-dialog' feature)");
+    None
+}
+
+#[cfg(feature = "file-dialog")]
+fn pick_lua_path() -> Option<std::path::PathBuf> {
+    rfd::FileDialog::new()
+        .set_title("Load Lua Script")
+        .add_filter("Lua", &["lua"])
+        .pick_file()
+}
+
+#[cfg(not(feature = "file-dialog"))]
+fn pick_lua_path() -> Option<std::path::PathBuf> {
+    log::warn!("File dialogs not available (build with 'file-dialog' feature)");
+    None
+}
+
 /// Handle a save action (Save or Save As).
 fn handle_save(
     params: &OsciPluginParamRefs,
     effect_snapshots: &[EffectSnapshot],
     shared: &SharedState,
     scope_state: &Arc<Mutex<GpuScopeState>>,
+    menu_state: &mut MenuState,
     force_dialog: bool,
 ) {
     let existing_path = shared
@@ -167,10 +257,11 @@ fn handle_save(
     };
 
     if let Some(path) = path {
-        let proj = build_project_file(params, effect_snapshots, scope_state);
+        let proj = build_project_file(params, effect_snapshots, shared, scope_state);
         if let Err(e) = project::save_project(&path, &proj) {
             log::error!("Failed to save project: {}", e);
         } else {
+            remember_recent(menu_state, &path);
             if let Ok(mut p) = shared.current_project_path.lock() {
                 *p = Some(path);
             }
@@ -178,74 +269,381 @@ fn handle_save(
     }
 }
 
-/// Handle the open action.
+/// Move `path` to the top of the recent files list and save the list.
+fn remember_recent(menu_state: &mut MenuState, path: &std::path::Path) {
+    update_recent(menu_state, |recent| recent.push(path.to_path_buf()));
+}
+
+fn update_recent(menu_state: &mut MenuState, change: impl FnOnce(&mut recent::RecentFiles)) {
+    let file = recent::default_recent_files_path();
+    let recent = menu_state
+        .recent_files
+        .get_or_insert_with(|| file.as_deref().map(recent::RecentFiles::load).unwrap_or_default());
+    change(recent);
+    if let Some(file) = file {
+        if let Err(e) = recent.save(&file) {
+            log::warn!("Failed to save recent files: {}", e);
+        }
+    }
+}
+
+/// Send a saved effect chain to the audio thread, replacing the current one.
+pub(crate) fn send_effect_chain(
+    command_tx: &crossbeam::channel::Sender<UiCommand>,
+    entries: Vec<project::EffectStateEntry>,
+) {
+    let effects: Vec<LoadedEffect> = entries
+        .into_iter()
+        .map(|e| LoadedEffect {
+            id: e.id,
+            enabled: e.enabled,
+            parameters: e.parameters,
+        })
+        .collect();
+    let _ = command_tx.try_send(UiCommand::LoadProject { effects });
+}
+
+/// Send macro `idx`'s targets to the audio thread and move its host
+/// parameter to the stored value.
+fn send_macro(
+    params: &OsciPluginParamRefs,
+    setter: &ParamSetter,
+    command_tx: &crossbeam::channel::Sender<UiCommand>,
+    idx: usize,
+    definition: &MacroDefinition,
+) {
+    let _ = command_tx.try_send(UiCommand::SetMacroTargets {
+        idx,
+        targets: definition.targets.clone(),
+    });
+    setter.begin_set_parameter(params.macros[idx]);
+    setter.set_parameter(params.macros[idx], definition.value);
+    setter.end_set_parameter(params.macros[idx]);
+}
+
+/// Apply a loaded project (or preset) to the synth, effect chain, Lua
+/// sliders and visualizer.
+fn apply_project(
+    params: &OsciPluginParamRefs,
+    setter: &ParamSetter,
+    shared: &SharedState,
+    scope_state: &Arc<Mutex<GpuScopeState>>,
+    proj: project::ProjectFile,
+) {
+    // Apply synth params on UI thread
+    apply_synth_params(params, setter, &proj.synth);
+
+    // Send effect chain to audio thread
+    send_effect_chain(&shared.command_tx, proj.effects);
+
+    // Restore the custom envelope, or fall back to the ADSR params
+    let command = match &proj.envelope {
+        Some(env) => UiCommand::SetEnvelope(env.clone()),
+        None => UiCommand::ClearEnvelope,
+    };
+    let _ = shared.command_tx.try_send(command);
+    if let Ok(mut env) = shared.envelope.lock() {
+        *env = proj.envelope;
+    }
+
+    // Restore Lua sliders (missing entries fall back to 0)
+    if let Ok(mut values) = shared.lua_sliders.lock() {
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = proj
+                .lua_sliders
+                .as_ref()
+                .and_then(|m| m.get(&state::lua_slider_name(i)).copied())
+                .unwrap_or(0.0);
+        }
+    }
+
+    // Restore macros after the chain they point into
+    let macros = proj.macros.unwrap_or_default();
+    for idx in 0..NUM_MACROS {
+        let definition = macros.get(idx).cloned().unwrap_or_default();
+        send_macro(params, setter, &shared.command_tx, idx, &definition);
+        if let Ok(mut shared_macros) = shared.macros.lock() {
+            shared_macros[idx] = definition;
+        }
+    }
+
+    // Apply visualizer settings
+    if let Some(vis) = &proj.visualizer {
+        if let Ok(mut state) = scope_state.lock() {
+            state.settings.focus = vis.focus;
+            state.settings.intensity = vis.intensity;
+            state.settings.persistence = vis.persistence;
+            state.settings.afterglow = vis.afterglow;
+            state.settings.glow_amount = vis.glow_amount;
+            state.settings.scatter_amount = vis.scatter_amount;
+            state.settings.color = vis.color;
+            state.settings.exposure = vis.exposure;
+            state.settings.overexposure = vis.overexposure;
+            state.settings.saturation = vis.saturation;
+            state.settings.ambient = vis.ambient;
+            state.settings.noise = vis.noise;
+            if let Some(c) = vis.afterglow_color {
+                state.settings.afterglow_color = c;
+            }
+            if let Some(m) = vis.reflection_mode {
+                state.settings.reflection_mode = m;
+            }
+            if let Some(g) = vis.goniometer {
+                state.settings.goniometer = g;
+            }
+            state.settings.rotation = vis.rotation.unwrap_or(0.0);
+            state.settings.zoom = vis.zoom.unwrap_or(1.0);
+            state.settings.offset = vis.offset.unwrap_or([0.0, 0.0]);
+            state.settings.transparent_background = vis.transparent_background.unwrap_or(false);
+            state.settings.persistence_rgb = vis.persistence_rgb;
+            state.settings.afterglow_curve =
+                osci_visualizer::AfterglowCurve::from_index(vis.afterglow_curve.unwrap_or(0));
+            state.settings.auto_focus = vis.auto_focus.unwrap_or(false);
+            state.settings.tone_curve =
+                osci_visualizer::ToneCurve::from_index(vis.tone_curve.unwrap_or(0));
+            state.settings.gamma = vis.gamma.unwrap_or(1.0);
+            [state.settings.flip_x, state.settings.flip_y] = vis.flip.unwrap_or([false, false]);
+            state.settings.blend_mode =
+                osci_visualizer::BlendMode::from_index(vis.blend_mode.unwrap_or(0));
+        }
+    }
+}
+
+/// Handle the open action: pick a project with a dialog, or open `path`
+/// straight away when one comes from the recent files list.
 fn handle_open(
     params: &OsciPluginParamRefs,
     setter: &ParamSetter,
     shared: &SharedState,
     scope_state: &Arc<Mutex<GpuScopeState>>,
+    menu_state: &mut MenuState,
+    path: Option<std::path::PathBuf>,
 ) {
-    let path = pick_open_path();
+    let path = path.or_else(pick_open_path);
 
     if let Some(path) = path {
-        match project::load_project(&path) {
+        match project::load_any_project(&path) {
             Ok(proj) => {
-                // Apply synth params on UI thread
-                apply_synth_params(params, setter, &proj.synth);
-
-                // Send effect chain to audio thread
-                let effects: Vec<LoadedEffect> = proj
-                    .effects
-                    .into_iter()
-                    .map(|e| LoadedEffect {
-                        id: e.id,
-                        enabled: e.enabled,
-                        parameters: e.parameters,
-                    })
-                    .collect();
-                let _ = shared.command_tx.try_send(UiCommand::LoadProject { effects });
-
-                // Apply visualizer settings
-                if let Some(vis) = &proj.visualizer {
-                    if let Ok(mut state) = scope_state.lock() {
-                        state.settings.focus = vis.focus;
-                        state.settings.intensity = vis.intensity;
-                        state.settings.persistence = vis.persistence;
-                        state.settings.afterglow = vis.afterglow;
-                        state.settings.glow_amount = vis.glow_amount;
-                        state.settings.scatter_amount = vis.scatter_amount;
-                        state.settings.color = vis.color;
-                        state.settings.exposure = vis.exposure;
-                        state.settings.overexposure = vis.overexposure;
-                        state.settings.saturation = vis.saturation;
-                        state.settings.ambient = vis.ambient;
-                        state.settings.noise = vis.noise;
-                        if let Some(c) = vis.afterglow_color {
-                            state.settings.afterglow_color = c;
-                        }
-                        if let Some(m) = vis.reflection_mode {
-                            state.settings.reflection_mode = m;
-                        }
-                        if let Some(g) = vis.goniometer {
-                            state.settings.goniometer = g;
-                        }
-                    }
-                }
+                apply_project(params, setter, shared, scope_state, proj);
+                remember_recent(menu_state, &path);
 
+                // Saving over a legacy project would replace it with our format
                 if let Ok(mut p) = shared.current_project_path.lock() {
-                    *p = Some(path);
+                    *p = (!project::is_legacy_project(&path)).then_some(path);
                 }
             }
             Err(e) => {
                 log::error!("Failed to load project: {}", e);
+                update_recent(menu_state, |recent| recent.remove(&path));
+                menu_state.load_error = Some(format!("{}: {}", path.display(), e));
             }
         }
     }
 }
 
+/// Queue `shapes` as the next frame for the audio thread to draw.
+///
+/// Fails instead of waiting when the frame queue is full, which happens
+/// while the audio thread isn't running or frames are streaming in.
+fn send_frame(shared: &SharedState, shapes: Vec<Box<dyn osci_core::Shape>>) -> Result<(), String> {
+    shared
+        .frame_tx
+        .try_send(shapes)
+        .map_err(|_| "The audio engine isn't taking new frames right now. Try again.".to_string())
+}
+
+/// Load `shapes` as the editor's frame. While the shape stepper is open
+/// (`isolated` is the shape it shows) only that shape is drawn.
+fn load_frame(
+    shared: &SharedState,
+    shapes: Vec<Box<dyn osci_core::Shape>>,
+    isolated: Option<usize>,
+) -> Result<(), String> {
+    let frame = match isolated {
+        Some(index) => osci_core::shape::isolate_shape(&shapes, index),
+        None => shapes.iter().map(|s| s.clone_shape()).collect(),
+    };
+    send_frame(shared, frame)?;
+    if let Ok(mut loaded) = shared.loaded_frame.lock() {
+        *loaded = shapes;
+    }
+    Ok(())
+}
+
+/// Draw shape `index` of the editor's frame on its own, or the whole frame
+/// again with `None`.
+fn isolate_loaded_shape(shared: &SharedState, index: Option<usize>) -> Result<(), String> {
+    let frame = match shared.loaded_frame.lock() {
+        Ok(loaded) => match index {
+            Some(index) => osci_core::shape::isolate_shape(&loaded, index),
+            None => loaded.iter().map(|s| s.clone_shape()).collect(),
+        },
+        Err(_) => return Ok(()),
+    };
+    send_frame(shared, frame)
+}
+
+/// Parse pasted SVG or OBJ text and send its shapes to the audio thread.
+/// Returns a warning when the drawing is complex enough to struggle.
+fn load_pasted_shapes(
+    shared: &SharedState,
+    text: &str,
+    config: &osci_parsers::ParseConfig,
+    isolated: Option<usize>,
+) -> Result<Option<String>, String> {
+    let extension = osci_parsers::sniff_text_extension(text)
+        .ok_or_else(|| "The pasted text isn't SVG or OBJ.".to_string())?;
+    match osci_parsers::parse_file_with_stats(text.as_bytes(), extension, config)? {
+        (osci_parsers::ParseResult::Shapes(shapes), stats) if !shapes.is_empty() => {
+            load_frame(shared, shapes, isolated)?;
+            Ok(stats.warning)
+        }
+        _ => Err(format!("The pasted {} has nothing to draw.", extension.to_uppercase())),
+    }
+}
+
+/// Decode the audio file at `path` and have the audio thread trace it.
+fn load_audio(shared: &SharedState, path: &std::path::Path) -> Result<(), String> {
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    let audio = osci_parsers::audio::parse_audio(&data)?;
+    // The audio thread sets the output rate when it takes the source
+    let rate = audio.sample_rate as f64;
+    let source = SampleSource::Audio(AudioSampleSource::new(audio, rate));
+    shared
+        .command_tx
+        .try_send(UiCommand::SetSampleSource(Some(source)))
+        .map_err(|_| "The audio engine isn't taking commands right now. Try again.".to_string())
+}
+
+/// Compile the Lua script at `path` and have the audio thread run it, with
+/// the Lua sliders passed in every block.
+fn load_lua_script(shared: &SharedState, path: &std::path::Path) -> Result<(), String> {
+    let script = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let parser = LuaParser::new(&script)?;
+    if parser.is_using_fallback() {
+        return Err("The script has errors; see the log for details.".to_string());
+    }
+    let source = SampleSource::Lua(Box::new(LuaSource::new(parser)));
+    shared
+        .command_tx
+        .try_send(UiCommand::SetSampleSource(Some(source)))
+        .map_err(|_| "The audio engine isn't taking commands right now. Try again.".to_string())
+}
+
+/// Text pasted with Ctrl+V while no text field has focus.
+fn pasted_text(ctx: &egui::Context) -> Option<String> {
+    if ctx.wants_keyboard_input() {
+        return None;
+    }
+    ctx.input(|i| {
+        i.events.iter().find_map(|event| match event {
+            egui::Event::Paste(text) => Some(text.clone()),
+            _ => None,
+        })
+    })
+}
+
+/// Collect the built-in presets followed by the user's saved presets.
+fn refresh_presets(menu_state: &mut MenuState) {
+    let mut presets: Vec<(String, preset::PresetSource)> = preset::builtin_preset_names()
+        .into_iter()
+        .map(|name| (name, preset::PresetSource::BuiltIn))
+        .collect();
+
+    if let Some(dir) = preset::default_presets_dir() {
+        match preset::list_presets(&dir) {
+            Ok(names) => {
+                presets.extend(names.into_iter().map(|name| (name, preset::PresetSource::User)));
+            }
+            Err(e) => log::warn!("Failed to list presets: {}", e),
+        }
+    }
+
+    menu_state.presets = Some(presets);
+}
+
+/// Handle selecting a preset from the preset combo box.
+fn handle_load_preset(
+    params: &OsciPluginParamRefs,
+    setter: &ParamSetter,
+    shared: &SharedState,
+    scope_state: &Arc<Mutex<GpuScopeState>>,
+    menu_state: &mut MenuState,
+    name: &str,
+    source: &preset::PresetSource,
+) {
+    let loaded = match source {
+        preset::PresetSource::BuiltIn => preset::load_builtin_preset(name),
+        preset::PresetSource::User => match preset::default_presets_dir() {
+            Some(dir) => preset::load_preset(&dir, name),
+            None => return,
+        },
+    };
+
+    match loaded {
+        Ok(p) => {
+            apply_project(params, setter, shared, scope_state, p.project);
+            menu_state.current_preset = Some(p.name);
+        }
+        Err(e) => {
+            log::error!("Failed to load preset '{}': {}", name, e);
+        }
+    }
+}
+
+/// Save the current state as a user preset named `name`.
+fn handle_save_preset(
+    params: &OsciPluginParamRefs,
+    effect_snapshots: &[EffectSnapshot],
+    shared: &SharedState,
+    scope_state: &Arc<Mutex<GpuScopeState>>,
+    menu_state: &mut MenuState,
+    name: &str,
+) {
+    let Some(dir) = preset::default_presets_dir() else {
+        log::error!("Failed to save preset: no presets directory available");
+        return;
+    };
+
+    let p = preset::Preset {
+        name: name.trim().to_string(),
+        project: build_project_file(params, effect_snapshots, shared, scope_state),
+    };
+    if let Err(e) = preset::save_preset(&dir, &p) {
+        log::error!("Failed to save preset: {}", e);
+        menu_state.load_error = Some(format!("Failed to save preset: {e}"));
+    } else {
+        menu_state.current_preset = Some(p.name);
+        menu_state.presets = None;
+    }
+}
+
+/// Apply the patch stored in an A/B compare slot, if there is one.
+fn handle_recall_snapshot(
+    params: &OsciPluginParamRefs,
+    setter: &ParamSetter,
+    shared: &SharedState,
+    scope_state: &Arc<Mutex<GpuScopeState>>,
+    menu_state: &mut MenuState,
+    slot: menu_bar::AbSlot,
+) {
+    if let Some(proj) = menu_state.recall_snapshot(slot) {
+        apply_project(params, setter, shared, scope_state, proj);
+    }
+}
+
 /// Handle the new project action.
 fn handle_new(shared: &SharedState) {
     let _ = shared.command_tx.try_send(UiCommand::ClearProject);
+    if let Ok(mut values) = shared.lua_sliders.lock() {
+        *values = [0.0; NUM_LUA_SLIDERS];
+    }
+    if let Ok(mut env) = shared.envelope.lock() {
+        *env = None;
+    }
+    if let Ok(mut macros) = shared.macros.lock() {
+        *macros = Default::default();
+    }
     if let Ok(mut p) = shared.current_project_path.lock() {
         *p = None;
     }
@@ -253,10 +651,12 @@ fn handle_new(shared: &SharedState) {
 
 /// Draw the complete plugin editor UI.
 ///
-/// Call this from within the `nih_plug_egui::create_egui_editor` update closure.
-/// The `menu_state` must be persisted across frames by the caller.
+/// Call this from within the `nih_plug_egui::create_egui_editor` update closure,
+/// passing the same `EguiState` the editor was created with so the window can
+/// be resized. The `menu_state` must be persisted across frames by the caller.
 pub fn draw_editor(
     egui_ctx: &egui::Context,
+    egui_state: &EguiState,
     params: &OsciPluginParamRefs,
     setter: &ParamSetter,
     shared: &SharedState,
@@ -270,13 +670,163 @@ pub fn draw_editor(
     // Apply Dracula theme + Fira Sans font (guarded by Once)
     theme::apply(egui_ctx);
 
+    // Free sources the audio thread has finished with
+    while shared.retired_sources.try_recv().is_ok() {}
+
     // Check keyboard shortcuts
     let shortcut_action = check_shortcuts(egui_ctx);
+    if let Some(text) = pasted_text(egui_ctx) {
+        let isolated = menu_state.show_shape_stepper.then_some(menu_state.isolated_shape);
+        match load_pasted_shapes(shared, &text, &menu_state.paste_config, isolated) {
+            Ok(warning) => menu_state.load_warning = warning,
+            Err(e) => menu_state.load_error = Some(e),
+        }
+    }
 
-    // Menu bar at top
-    let menu_action = egui::TopBottomPanel::top("menu_bar").show(egui_ctx, |ui| {
-        menu_bar::draw_menu_bar(ui, menu_state)
-    }).inner;
+    if menu_state.presets.is_none() {
+        refresh_presets(menu_state);
+    }
+    if menu_state.recent_files.is_none() {
+        menu_state.recent_files = Some(
+            recent::default_recent_files_path()
+                .map(|file| recent::RecentFiles::load(&file))
+                .unwrap_or_default(),
+        );
+    }
+
+    // Menu bar and main content, inside a resizable window
+    let menu_action = show_resizable(egui_ctx, egui_state, |ui| {
+        let menu_action = egui::TopBottomPanel::top("menu_bar").show_inside(ui, |ui| {
+            menu_bar::draw_menu_bar(ui, menu_state)
+        }).inner;
+
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                // Synth Controls
+                ui.heading("Synth Controls");
+                ui.separator();
+
+                ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.volume, setter));
+                ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.frequency, setter));
+
+                let prev_drone = *drone_active;
+                ui.checkbox(drone_active, "Drone (continuous play, no MIDI required)");
+                if *drone_active != prev_drone {
+                    let _ = shared.command_tx.try_send(UiCommand::SetDroneEnabled(*drone_active));
+                }
+
+                if let Ok(mut voices) = shared.voice_count.lock() {
+                    let slider = egui::Slider::new(&mut *voices, VOICE_COUNT_RANGE).text("Voices");
+                    if ui.add(slider).changed() {
+                        let _ = shared.command_tx.try_send(UiCommand::SetMaxVoices(*voices));
+                    }
+                }
+
+                if let Ok(mut reversed) = shared.frame_reversed.lock() {
+                    if ui.checkbox(&mut reversed, "Reverse drawing direction").changed() {
+                        let _ = shared.command_tx.try_send(UiCommand::SetFrameReversed(*reversed));
+                    }
+                }
+
+                if let Ok(mut seed) = shared.seed.lock() {
+                    ui.horizontal(|ui| {
+                        let mut fixed = seed.is_some();
+                        let mut value = seed.unwrap_or(0);
+                        let mut changed = ui.checkbox(&mut fixed, "Fixed random seed").changed();
+                        changed |= ui.add_enabled(fixed, egui::DragValue::new(&mut value)).changed();
+                        changed |= ui
+                            .add_enabled(fixed, egui::Button::new("Reseed"))
+                            .on_hover_text("Restart every noise source from the seed")
+                            .clicked();
+                        if changed {
+                            *seed = fixed.then_some(value);
+                            let _ = shared.command_tx.try_send(UiCommand::SetSeed(*seed));
+                        }
+                    });
+                }
+
+                ui.add_space(8.0);
+                ui.label("ADSR Envelope");
+                ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.attack, setter));
+                ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.decay, setter));
+                ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.sustain, setter));
+                ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.release, setter));
+
+                let adsr = osci_core::Env::adsr(
+                    params.attack.value() as f64,
+                    params.decay.value() as f64,
+                    params.sustain.value() as f64,
+                    params.release.value() as f64,
+                    1.0,
+                    -4.0,
+                );
+                envelope_editor::draw_envelope_editor(ui, &shared.envelope, &shared.command_tx, &adsr);
+
+                ui.add_space(8.0);
+                ui.label("Keyboard (A-K to play, Z/X to change octave)");
+                keyboard::draw_keyboard(ui, &shared.command_tx);
+
+                ui.add_space(12.0);
+
+                // Effect Chain
+                effect_panel::draw_effect_chain(
+                    ui,
+                    effect_snapshots,
+                    &shared.command_tx,
+                    selected_effect_id,
+                    &shared.effects_bypassed,
+                    &shared.macros,
+                );
+
+                macro_panel::draw_macros(
+                    ui,
+                    effect_snapshots,
+                    &shared.macros,
+                    params.macros,
+                    setter,
+                    &shared.command_tx,
+                );
+
+                ui.add_space(12.0);
+
+                // Lua slider variables
+                lua_panel::draw_lua_sliders(ui, &shared.lua_sliders);
+
+                ui.add_space(12.0);
+
+                // XY Scope (GPU-rendered)
+                ui.heading("XY Scope");
+                ui.separator();
+                let (levels, frequency) = shared
+                    .audio_info
+                    .lock()
+                    .map(|i| (i.output_levels, i.current_frequency))
+                    .unwrap_or_default();
+                meter::draw_output_meters(ui, &levels);
+                if let Ok(mut state) = scope_state.lock() {
+                    ui.checkbox(&mut state.settings.auto_focus, "Auto Focus")
+                        .on_hover_text("Widen the beam for faster drawing so lines keep their weight");
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut state.settings.flip_x, "Flip X");
+                        ui.checkbox(&mut state.settings.flip_y, "Flip Y")
+                            .on_hover_text("Mirror the display for sources drawn Y-down");
+                    });
+                    egui::ComboBox::from_label("Blend")
+                        .selected_text(state.settings.blend_mode.label())
+                        .show_ui(ui, |ui| {
+                            for mode in osci_visualizer::BlendMode::ALL {
+                                ui.selectable_value(&mut state.settings.blend_mode, mode, mode.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text("Max keeps overlapping lines coloured instead of adding up to white");
+                }
+                scope::draw_gpu_scope(ui, vis, frequency, scope_state.clone(), menu_state.show_render_stats);
+            });
+        });
+
+        menu_action
+    });
 
     // Use whichever action was triggered (shortcut takes priority)
     let action = if shortcut_action != MenuAction::None {
@@ -288,9 +838,87 @@ pub fn draw_editor(
     // Process menu action
     match action {
         MenuAction::NewProject => handle_new(shared),
-        MenuAction::OpenProject => handle_open(params, setter, shared, &scope_state),
-        MenuAction::SaveProject => handle_save(params, effect_snapshots, shared, &scope_state, false),
-        MenuAction::SaveProjectAs => handle_save(params, effect_snapshots, shared, &scope_state, true),
+        MenuAction::OpenProject => handle_open(params, setter, shared, &scope_state, menu_state, None),
+        MenuAction::OpenRecent(path) => {
+            handle_open(params, setter, shared, &scope_state, menu_state, Some(path))
+        }
+        MenuAction::SaveProject => {
+            handle_save(params, effect_snapshots, shared, &scope_state, menu_state, false)
+        }
+        MenuAction::SaveProjectAs => {
+            handle_save(params, effect_snapshots, shared, &scope_state, menu_state, true)
+        }
+        MenuAction::LoadPreset { name, source } => handle_load_preset(
+            params,
+            setter,
+            shared,
+            &scope_state,
+            menu_state,
+            &name,
+            &source,
+        ),
+        MenuAction::StoreSnapshot(slot) => {
+            let proj = build_project_file(params, effect_snapshots, shared, &scope_state);
+            menu_state.store_snapshot(slot, proj);
+        }
+        MenuAction::RecallSnapshot(slot) => {
+            handle_recall_snapshot(params, setter, shared, &scope_state, menu_state, slot)
+        }
+        MenuAction::LoadCalibration(pattern) => {
+            let frame = osci_parsers::calibration_frame(pattern);
+            let isolated = menu_state.show_shape_stepper.then_some(menu_state.isolated_shape);
+            if let Err(e) = load_frame(shared, frame.shapes, isolated) {
+                menu_state.load_error = Some(e);
+            }
+        }
+        MenuAction::AllNotesOff => {
+            let _ = shared.command_tx.try_send(UiCommand::AllNotesOff);
+        }
+        MenuAction::QuickAddEffect => {
+            menu_state.quick_add_query.clear();
+            menu_state.show_quick_add = true;
+        }
+        MenuAction::SetProfiling(enabled) => {
+            let _ = shared.command_tx.try_send(UiCommand::SetProfiling(enabled));
+        }
+        MenuAction::StepThroughShapes => {
+            menu_state.isolated_shape = 0;
+            match isolate_loaded_shape(shared, Some(0)) {
+                Ok(()) => menu_state.show_shape_stepper = true,
+                Err(e) => menu_state.load_error = Some(e),
+            }
+        }
+        MenuAction::SetMorphStart => {
+            if let Ok(loaded) = shared.loaded_frame.lock() {
+                menu_state.morph_start = Some(loaded.iter().map(|s| s.clone_shape()).collect());
+            }
+        }
+        MenuAction::MorphToLoadedFrame => {
+            if let (Some(start), Ok(loaded)) = (&menu_state.morph_start, shared.loaded_frame.lock()) {
+                let morph = osci_core::ShapeMorph::new(start, &loaded);
+                let _ = shared.command_tx.try_send(UiCommand::SetMorph(morph));
+            }
+        }
+        MenuAction::StopMorph => {
+            let _ = shared.command_tx.try_send(UiCommand::ClearMorph);
+        }
+        MenuAction::LoadAudio => {
+            if let Some(path) = pick_audio_path() {
+                if let Err(e) = load_audio(shared, &path) {
+                    menu_state.load_error = Some(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+        MenuAction::LoadLuaScript => {
+            if let Some(path) = pick_lua_path() {
+                if let Err(e) = load_lua_script(shared, &path) {
+                    menu_state.load_error = Some(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+        MenuAction::StopSampleSource => {
+            let _ = shared.command_tx.try_send(UiCommand::SetSampleSource(None));
+        }
         MenuAction::None => {}
     }
 
@@ -299,41 +927,50 @@ pub fn draw_editor(
     dialogs::draw_about_dialog(egui_ctx, &mut menu_state.show_about);
     dialogs::draw_audio_info_dialog(egui_ctx, &mut menu_state.show_audio_info, &audio_info);
     dialogs::draw_shortcuts_dialog(egui_ctx, &mut menu_state.show_shortcuts);
-
-    // Main content
-    egui::CentralPanel::default().show(egui_ctx, |ui| {
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            // Synth Controls
-            ui.heading("Synth Controls");
-            ui.separator();
-
-            ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.volume, setter));
-            ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.frequency, setter));
-
-            let prev_drone = *drone_active;
-            ui.checkbox(drone_active, "Drone (continuous play, no MIDI required)");
-            if *drone_active != prev_drone {
-                let _ = shared.command_tx.try_send(UiCommand::SetDroneEnabled(*drone_active));
+    if dialogs::draw_paste_shapes_dialog(
+        egui_ctx,
+        &mut menu_state.show_paste_shapes,
+        &mut menu_state.paste_shapes_text,
+        &mut menu_state.paste_config,
+    ) {
+        let isolated = menu_state.show_shape_stepper.then_some(menu_state.isolated_shape);
+        let text = &menu_state.paste_shapes_text;
+        match load_pasted_shapes(shared, text, &menu_state.paste_config, isolated) {
+            Ok(warning) => {
+                menu_state.paste_shapes_text.clear();
+                menu_state.load_warning = warning;
             }
-
-            ui.add_space(8.0);
-            ui.label("ADSR Envelope");
-            ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.attack, setter));
-            ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.decay, setter));
-            ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.sustain, setter));
-            ui.add(nih_plug_egui::widgets::ParamSlider::for_param(params.release, setter));
-
-            ui.add_space(12.0);
-
-            // Effect Chain
-            effect_panel::draw_effect_chain(ui, effect_snapshots, &shared.command_tx, selected_effect_id);
-
-            ui.add_space(12.0);
-
-            // XY Scope (GPU-rendered)
-            ui.heading("XY Scope");
-            ui.separator();
-            scope::draw_gpu_scope(ui, vis, scope_state);
-        });
-    });
+            Err(e) => menu_state.load_error = Some(e),
+        }
+    }
+    dialogs::draw_error_dialog(egui_ctx, "Load Failed", &mut menu_state.load_error);
+    dialogs::draw_error_dialog(egui_ctx, "Complex Drawing", &mut menu_state.load_warning);
+    if menu_state.show_shape_stepper {
+        let count = shared.loaded_frame.lock().map(|f| f.len()).unwrap_or(0);
+        let index = &mut menu_state.isolated_shape;
+        let mut result = Ok(());
+        if dialogs::draw_shape_stepper(egui_ctx, &mut menu_state.show_shape_stepper, index, count) {
+            result = isolate_loaded_shape(shared, Some(*index));
+        }
+        if !menu_state.show_shape_stepper {
+            result = isolate_loaded_shape(shared, None);
+        }
+        if let Err(e) = result {
+            menu_state.load_error = Some(e);
+        }
+    }
+    effect_panel::draw_quick_add(
+        egui_ctx,
+        &mut menu_state.show_quick_add,
+        &mut menu_state.quick_add_query,
+        &shared.command_tx,
+    );
+    if dialogs::draw_save_preset_dialog(
+        egui_ctx,
+        &mut menu_state.show_save_preset,
+        &mut menu_state.preset_name,
+    ) {
+        let name = menu_state.preset_name.clone();
+        handle_save_preset(params, effect_snapshots, shared, &scope_state, menu_state, &name);
+    }
 }
@@ -0,0 +1,34 @@
+use crate::state::{lua_slider_name, NUM_LUA_SLIDERS};
+use nih_plug_egui::egui::{self, Ui};
+use std::sync::{Arc, Mutex};
+
+/// Draw the Lua slider grid (`slider_a` through `slider_z`).
+///
+/// Values are written straight into the shared array so a loaded script
+/// picks them up on the next audio block.
+pub fn draw_lua_sliders(ui: &mut Ui, sliders: &Arc<Mutex<[f64; NUM_LUA_SLIDERS]>>) {
+    egui::CollapsingHeader::new("Lua Sliders")
+        .id_salt("lua_sliders")
+        .show(ui, |ui| {
+            let Ok(mut values) = sliders.lock() else {
+                return;
+            };
+
+            egui::Grid::new("lua_slider_grid")
+                .num_columns(4)
+                .spacing([8.0, 2.0])
+                .show(ui, |ui| {
+                    for (idx, value) in values.iter_mut().enumerate() {
+                        ui.label(lua_slider_name(idx));
+                        ui.add(egui::Slider::new(value, 0.0..=1.0));
+                        if idx % 2 == 1 {
+                            ui.end_row();
+                        }
+                    }
+                });
+
+            if ui.button("Reset Sliders").clicked() {
+                *values = [0.0; NUM_LUA_SLIDERS];
+            }
+        });
+}
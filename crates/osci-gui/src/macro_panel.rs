@@ -0,0 +1,98 @@
+use crate::state::{EffectSnapshot, MacroDefinition, MacroTarget, UiCommand, NUM_MACROS};
+use crossbeam::channel::Sender;
+use nih_plug::prelude::{FloatParam, ParamSetter};
+use nih_plug_egui::egui::{self, Ui};
+use nih_plug_egui::widgets::ParamSlider;
+use std::sync::Mutex;
+
+/// Label for the parameter at `effect_idx`/`param_idx`, e.g. "2 Rotate: Rotate Y".
+fn param_label(snapshots: &[EffectSnapshot], effect_idx: usize, param_idx: usize) -> String {
+    snapshots
+        .get(effect_idx)
+        .and_then(|e| {
+            let param = e.parameters.get(param_idx)?;
+            Some(format!("{} {}: {}", effect_idx + 1, e.name, param.name))
+        })
+        .unwrap_or_else(|| "Missing".to_string())
+}
+
+/// Draw the four macro knobs. Each has a slider for its value and a list of
+/// the parameters it drives, each with the part of that parameter's range
+/// the macro sweeps. The sliders move the host's Macro 1-4 parameters, which
+/// hold the macro values.
+pub fn draw_macros(
+    ui: &mut Ui,
+    snapshots: &[EffectSnapshot],
+    macros: &Mutex<[MacroDefinition; NUM_MACROS]>,
+    params: [&FloatParam; NUM_MACROS],
+    setter: &ParamSetter,
+    tx: &Sender<UiCommand>,
+) {
+    egui::CollapsingHeader::new("Macros")
+        .id_salt("macros")
+        .show(ui, |ui| {
+            let Ok(mut macros) = macros.lock() else {
+                return;
+            };
+
+            for (idx, definition) in macros.iter_mut().enumerate() {
+                ui.push_id(idx, |ui| draw_macro(ui, idx, definition, snapshots, params[idx], setter, tx));
+                ui.add_space(4.0);
+            }
+        });
+}
+
+fn draw_macro(
+    ui: &mut Ui,
+    idx: usize,
+    definition: &mut MacroDefinition,
+    snapshots: &[EffectSnapshot],
+    param: &FloatParam,
+    setter: &ParamSetter,
+    tx: &Sender<UiCommand>,
+) {
+    ui.add(ParamSlider::for_param(param, setter));
+
+    let mut targets_changed = false;
+    let mut remove = None;
+    for (i, target) in definition.targets.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(param_label(snapshots, target.effect_idx, target.param_idx));
+            let min = egui::DragValue::new(&mut target.min).range(0.0..=1.0).speed(0.01);
+            let max = egui::DragValue::new(&mut target.max).range(0.0..=1.0).speed(0.01);
+            targets_changed |= ui.add(min).on_hover_text("Position at macro 0").changed();
+            targets_changed |= ui.add(max).on_hover_text("Position at macro 1").changed();
+            if ui.small_button("x").clicked() {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        definition.targets.remove(i);
+        targets_changed = true;
+    }
+
+    let mut added = None;
+    egui::ComboBox::from_id_salt("add_target")
+        .selected_text("Add target...")
+        .show_ui(ui, |ui| {
+            for (effect_idx, effect) in snapshots.iter().enumerate() {
+                for param_idx in 0..effect.parameters.len() {
+                    if ui.selectable_label(false, param_label(snapshots, effect_idx, param_idx)).clicked() {
+                        added = Some(MacroTarget::full_range(effect_idx, param_idx));
+                    }
+                }
+            }
+        });
+    if let Some(target) = added {
+        definition.targets.push(target);
+        targets_changed = true;
+    }
+
+    if targets_changed {
+        let _ = tx.try_send(UiCommand::SetMacroTargets {
+            idx,
+            targets: definition.targets.clone(),
+        });
+    }
+}
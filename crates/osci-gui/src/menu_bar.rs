@@ -1,3 +1,7 @@
+use crate::preset::PresetSource;
+use crate::project::ProjectFile;
+use crate::recent::RecentFiles;
+use osci_parsers::CalPattern;
 use nih_plug_egui::egui;
 
 /// Tracks which dialogs are currently open.
@@ -6,6 +10,82 @@ pub struct MenuState {
     pub show_about: bool,
     pub show_audio_info: bool,
     pub show_shortcuts: bool,
+    pub show_save_preset: bool,
+    /// Show the dialog for pasting SVG or OBJ text to load.
+    pub show_paste_shapes: bool,
+    /// Text typed or pasted into the Paste Shapes dialog.
+    pub paste_shapes_text: String,
+    /// How pasted SVG or OBJ text is parsed.
+    pub paste_config: osci_parsers::ParseConfig,
+    /// Error from the last failed load, shown until dismissed.
+    pub load_error: Option<String>,
+    /// Warning from the last load, e.g. that the drawing is very complex.
+    pub load_warning: Option<String>,
+    /// Show the quick-add palette for adding an effect by typing its name.
+    pub show_quick_add: bool,
+    /// Filter typed into the quick-add palette.
+    pub quick_add_query: String,
+    /// Overlay FPS and segment count on the scope.
+    pub show_render_stats: bool,
+    /// Time each effect and show its cost in the effect chain.
+    pub show_effect_cost: bool,
+    /// Show the shape stepper, which draws one shape of the frame at a time.
+    pub show_shape_stepper: bool,
+    /// Index of the shape the stepper is drawing.
+    pub isolated_shape: usize,
+    /// Name typed into the Save Preset dialog.
+    pub preset_name: String,
+    /// Presets offered in the preset combo box; refreshed when `None`.
+    pub presets: Option<Vec<(String, PresetSource)>>,
+    /// Recently opened or saved projects; loaded from disk when `None`.
+    pub recent_files: Option<RecentFiles>,
+    /// Name of the most recently applied preset, shown in the combo box.
+    pub current_preset: Option<String>,
+    /// Patches stored in the A/B compare slots, indexed by [`AbSlot::index`].
+    pub ab_slots: [Option<ProjectFile>; 2],
+    /// Slot most recently stored or recalled, highlighted in the menu bar.
+    pub ab_active: Option<AbSlot>,
+    /// Frame the next morph starts from, picked with View > Morph.
+    pub morph_start: Option<Vec<Box<dyn osci_core::Shape>>>,
+}
+
+/// One of the two A/B compare slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbSlot {
+    A,
+    B,
+}
+
+impl AbSlot {
+    pub fn index(self) -> usize {
+        match self {
+            AbSlot::A => 0,
+            AbSlot::B => 1,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AbSlot::A => "A",
+            AbSlot::B => "B",
+        }
+    }
+}
+
+impl MenuState {
+    /// Keep `project` in `slot`, replacing whatever was stored there.
+    pub fn store_snapshot(&mut self, slot: AbSlot, project: ProjectFile) {
+        self.ab_slots[slot.index()] = Some(project);
+        self.ab_active = Some(slot);
+    }
+
+    /// Mark `slot` active and return a copy of its patch to apply, or `None`
+    /// if nothing has been stored there yet.
+    pub fn recall_snapshot(&mut self, slot: AbSlot) -> Option<ProjectFile> {
+        let project = self.ab_slots[slot.index()].clone()?;
+        self.ab_active = Some(slot);
+        Some(project)
+    }
 }
 
 /// Actions returned from the menu bar that require processing by the caller.
@@ -14,8 +94,37 @@ pub enum MenuAction {
     None,
     NewProject,
     OpenProject,
+    /// Open a project from the recent files list.
+    OpenRecent(std::path::PathBuf),
     SaveProject,
     SaveProjectAs,
+    LoadPreset { name: String, source: PresetSource },
+    /// Copy the current patch into an A/B slot.
+    StoreSnapshot(AbSlot),
+    /// Apply the patch stored in an A/B slot.
+    RecallSnapshot(AbSlot),
+    /// Replace the loaded frame with a calibration pattern.
+    LoadCalibration(CalPattern),
+    /// Stop every sounding note.
+    AllNotesOff,
+    /// Open the quick-add effect palette.
+    QuickAddEffect,
+    /// Turn effect profiling on or off.
+    SetProfiling(bool),
+    /// Start drawing the frame one shape at a time.
+    StepThroughShapes,
+    /// Remember the loaded frame as the one to morph from.
+    SetMorphStart,
+    /// Morph from the remembered frame to the loaded one.
+    MorphToLoadedFrame,
+    /// Go back to drawing the loaded frame.
+    StopMorph,
+    /// Pick an audio file to trace as XY.
+    LoadAudio,
+    /// Pick a Lua script to run once per sample.
+    LoadLuaScript,
+    /// Go back to the synth after loading audio or a script.
+    StopSampleSource,
 }
 
 /// Draw the menu bar inside a `TopBottomPanel`. Returns a `MenuAction` if a file
@@ -40,6 +149,18 @@ pub fn draw_menu_bar(ui: &mut egui::Ui, state: &mut MenuState) -> MenuAction {
                 action = MenuAction::OpenProject;
                 ui.close_menu();
             }
+            let recent = state.recent_files.as_ref().map(RecentFiles::paths).unwrap_or_default();
+            ui.add_enabled_ui(!recent.is_empty(), |ui| {
+                ui.menu_button("Open Recent", |ui| {
+                    for path in recent {
+                        let name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+                        if ui.button(name).on_hover_text(path.display().to_string()).clicked() {
+                            action = MenuAction::OpenRecent(path.clone());
+                            ui.close_menu();
+                        }
+                    }
+                });
+            });
             if ui
                 .add(egui::Button::new("Save Project").shortcut_text("Ctrl+S"))
                 .clicked()
@@ -54,6 +175,31 @@ pub fn draw_menu_bar(ui: &mut egui::Ui, state: &mut MenuState) -> MenuAction {
                 action = MenuAction::SaveProjectAs;
                 ui.close_menu();
             }
+            ui.separator();
+            if ui.button("Save Preset...").clicked() {
+                state.show_save_preset = true;
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui
+                .add(egui::Button::new("Paste Shapes...").shortcut_text("Ctrl+V"))
+                .clicked()
+            {
+                state.show_paste_shapes = true;
+                ui.close_menu();
+            }
+            if ui.button("Load Audio...").clicked() {
+                action = MenuAction::LoadAudio;
+                ui.close_menu();
+            }
+            if ui.button("Load Lua Script...").clicked() {
+                action = MenuAction::LoadLuaScript;
+                ui.close_menu();
+            }
+            if ui.button("Back to Shapes").clicked() {
+                action = MenuAction::StopSampleSource;
+                ui.close_menu();
+            }
         });
 
         ui.menu_button("Audio", |ui| {
@@ -61,6 +207,68 @@ pub fn draw_menu_bar(ui: &mut egui::Ui, state: &mut MenuState) -> MenuAction {
                 state.show_audio_info = true;
                 ui.close_menu();
             }
+            ui.separator();
+            if ui
+                .add(egui::Button::new("All Notes Off").shortcut_text("Ctrl+."))
+                .clicked()
+            {
+                action = MenuAction::AllNotesOff;
+                ui.close_menu();
+            }
+        });
+
+        ui.menu_button("View", |ui| {
+            if ui.checkbox(&mut state.show_render_stats, "Render Stats").clicked() {
+                ui.close_menu();
+            }
+            if ui
+                .checkbox(&mut state.show_effect_cost, "Effect Cost")
+                .on_hover_text("Time each effect and show how much of the audio thread it takes")
+                .clicked()
+            {
+                action = MenuAction::SetProfiling(state.show_effect_cost);
+                ui.close_menu();
+            }
+            if ui.button("Step Through Shapes...").clicked() {
+                action = MenuAction::StepThroughShapes;
+                ui.close_menu();
+            }
+            ui.separator();
+            ui.menu_button("Calibration Pattern", |ui| {
+                for pattern in CalPattern::ALL {
+                    if ui.button(pattern.label()).clicked() {
+                        action = MenuAction::LoadCalibration(pattern);
+                        ui.close_menu();
+                    }
+                }
+            });
+            ui.menu_button("Morph", |ui| {
+                if ui.button("Start From Loaded Frame").clicked() {
+                    action = MenuAction::SetMorphStart;
+                    ui.close_menu();
+                }
+                let morph_to = ui
+                    .add_enabled(state.morph_start.is_some(), egui::Button::new("Morph To Loaded Frame"))
+                    .on_hover_text("Blend between the two frames with the Morph parameter")
+                    .on_disabled_hover_text("Pick the frame to start from first");
+                if morph_to.clicked() {
+                    action = MenuAction::MorphToLoadedFrame;
+                    ui.close_menu();
+                }
+                if ui.button("Stop Morphing").clicked() {
+                    action = MenuAction::StopMorph;
+                    ui.close_menu();
+                }
+            });
+        });
+
+        ui.menu_button("Compare", |ui| {
+            for slot in [AbSlot::A, AbSlot::B] {
+                if ui.button(format!("Store in {}", slot.label())).clicked() {
+                    action = MenuAction::StoreSnapshot(slot);
+                    ui.close_menu();
+                }
+            }
         });
 
         ui.menu_button("Help", |ui| {
@@ -73,7 +281,100 @@ pub fn draw_menu_bar(ui: &mut egui::Ui, state: &mut MenuState) -> MenuAction {
                 ui.close_menu();
             }
         });
+
+        ui.separator();
+        let selected = state.current_preset.as_deref().unwrap_or("Presets");
+        egui::ComboBox::from_id_salt("preset_combo")
+            .selected_text(selected)
+            .width(160.0)
+            .show_ui(ui, |ui| {
+                let presets = state.presets.as_deref().unwrap_or_default();
+                let mut last_source = None;
+                for (name, source) in presets {
+                    if last_source.is_some_and(|s| s != source) {
+                        ui.separator();
+                    }
+                    last_source = Some(source);
+                    let is_current = state.current_preset.as_deref() == Some(name.as_str());
+                    if ui.selectable_label(is_current, name).clicked() {
+                        action = MenuAction::LoadPreset {
+                            name: name.clone(),
+                            source: source.clone(),
+                        };
+                    }
+                }
+            });
+
+        // A/B toggle: clicking a stored slot applies it
+        ui.separator();
+        for slot in [AbSlot::A, AbSlot::B] {
+            let stored = state.ab_slots[slot.index()].is_some();
+            let active = state.ab_active == Some(slot);
+            let response = ui
+                .add_enabled(stored, egui::SelectableLabel::new(active, slot.label()))
+                .on_disabled_hover_text("Empty slot: use Compare > Store");
+            if response.clicked() {
+                action = MenuAction::RecallSnapshot(slot);
+            }
+        }
     });
 
     action
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{EffectStateEntry, SynthParamSnapshot};
+    use crate::state::UiCommand;
+
+    fn patch(ids: &[&str]) -> ProjectFile {
+        ProjectFile {
+            version: 1,
+            synth: SynthParamSnapshot {
+                volume: 1.0,
+                frequency: 440.0,
+                attack: 0.005,
+                decay: 0.1,
+                sustain: 0.8,
+                release: 0.3,
+            },
+            effects: ids
+                .iter()
+                .map(|id| EffectStateEntry {
+                    id: id.to_string(),
+                    enabled: true,
+                    parameters: Vec::new(),
+                })
+                .collect(),
+            visualizer: None,
+            lua_sliders: None,
+            envelope: None,
+            macros: None,
+        }
+    }
+
+    #[test]
+    fn recalling_a_restores_its_effect_chain() {
+        let mut state = MenuState::default();
+        assert!(state.recall_snapshot(AbSlot::A).is_none());
+
+        state.store_snapshot(AbSlot::A, patch(&["bitcrush", "jitter"]));
+        // Edit the patch, then keep the result in B
+        state.store_snapshot(AbSlot::B, patch(&["rotate"]));
+        assert_eq!(state.ab_active, Some(AbSlot::B));
+
+        let (tx, rx) = crossbeam::channel::unbounded();
+        let recalled = state.recall_snapshot(AbSlot::A).unwrap();
+        crate::send_effect_chain(&tx, recalled.effects);
+        assert_eq!(state.ab_active, Some(AbSlot::A));
+
+        match rx.try_recv().unwrap() {
+            UiCommand::LoadProject { effects } => {
+                let ids: Vec<&str> = effects.iter().map(|e| e.id.as_str()).collect();
+                assert_eq!(ids, ["bitcrush", "jitter"]);
+            }
+            _ => panic!("expected a LoadProject command"),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use nih_plug_egui::egui;
+
+/// Bottom of the meter scale, in dBFS.
+const METER_FLOOR_DB: f32 = -60.0;
+
+/// Top of the meter scale, in dBFS; the headroom above 0 dB shows how hot
+/// effects are driving the output.
+const METER_CEILING_DB: f32 = 6.0;
+
+/// Size of each meter bar, in logical pixels.
+const METER_SIZE: egui::Vec2 = egui::vec2(10.0, 64.0);
+
+/// How long the peak marker stays put before falling, in seconds.
+const PEAK_HOLD_SECONDS: f32 = 1.5;
+
+/// How fast the peak marker falls once the hold runs out, in dB per second.
+const PEAK_FALL_DB_PER_SECOND: f32 = 20.0;
+
+/// RMS and peak level of one output channel over a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChannelLevel {
+    pub rms: f32,
+    pub peak: f32,
+}
+
+/// Measure the RMS and absolute peak of `samples`.
+pub fn measure_level(samples: &[f32]) -> ChannelLevel {
+    if samples.is_empty() {
+        return ChannelLevel::default();
+    }
+    let (sum_squares, peak) = samples
+        .iter()
+        .fold((0.0f32, 0.0f32), |(sum, peak), s| (sum + s * s, peak.max(s.abs())));
+    ChannelLevel {
+        rms: (sum_squares / samples.len() as f32).sqrt(),
+        peak,
+    }
+}
+
+/// A peak marker that jumps up to new peaks, holds, then falls.
+#[derive(Debug, Clone, Copy)]
+pub struct PeakHold {
+    /// Held level in dBFS.
+    level_db: f32,
+    held_for: f32,
+}
+
+impl Default for PeakHold {
+    fn default() -> Self {
+        Self {
+            level_db: METER_FLOOR_DB,
+            held_for: 0.0,
+        }
+    }
+}
+
+impl PeakHold {
+    /// Feed the latest peak (linear) after `dt` seconds and return the
+    /// held level in dBFS.
+    pub fn update(&mut self, peak: f32, dt: f32) -> f32 {
+        let peak_db = to_db(peak);
+        if peak_db >= self.level_db {
+            self.level_db = peak_db;
+            self.held_for = 0.0;
+        } else {
+            self.held_for += dt;
+            if self.held_for > PEAK_HOLD_SECONDS {
+                self.level_db = (self.level_db - PEAK_FALL_DB_PER_SECOND * dt).max(peak_db);
+            }
+        }
+        self.level_db
+    }
+}
+
+fn to_db(level: f32) -> f32 {
+    (20.0 * level.max(1e-6).log10()).max(METER_FLOOR_DB)
+}
+
+/// Position of `db` up the meter, from 0 at the floor to 1 at the ceiling.
+fn meter_fraction(db: f32) -> f32 {
+    ((db - METER_FLOOR_DB) / (METER_CEILING_DB - METER_FLOOR_DB)).clamp(0.0, 1.0)
+}
+
+/// Draw compact vertical meters for the X and Y outputs.
+///
+/// Each bar shows the RMS level, with a line at the held peak that turns
+/// red once the output goes over 0 dBFS.
+pub fn draw_output_meters(ui: &mut egui::Ui, levels: &[ChannelLevel; 2]) {
+    let id = ui.make_persistent_id("output_meters");
+    let mut holds: [PeakHold; 2] = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+    let dt = ui.input(|i| i.stable_dt);
+
+    ui.horizontal(|ui| {
+        for ((label, level), hold) in ["X", "Y"].iter().zip(levels).zip(&mut holds) {
+            let held_db = hold.update(level.peak, dt);
+            ui.vertical(|ui| {
+                let (rect, _) = ui.allocate_exact_size(METER_SIZE, egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 1.0, egui::Color32::from_gray(30));
+
+                let rms_top = rect.bottom() - rect.height() * meter_fraction(to_db(level.rms));
+                painter.rect_filled(
+                    egui::Rect::from_min_max(egui::pos2(rect.left(), rms_top), rect.right_bottom()),
+                    1.0,
+                    ui.visuals().selection.bg_fill,
+                );
+
+                // 0 dBFS tick
+                let zero = rect.bottom() - rect.height() * meter_fraction(0.0);
+                painter.hline(rect.x_range(), zero, (1.0, egui::Color32::from_gray(90)));
+
+                let peak_y = rect.bottom() - rect.height() * meter_fraction(held_db);
+                let peak_colour = if held_db > 0.0 {
+                    egui::Color32::RED
+                } else {
+                    egui::Color32::from_gray(220)
+                };
+                painter.hline(rect.x_range(), peak_y, (2.0, peak_colour));
+
+                ui.label(egui::RichText::new(*label).small());
+            });
+        }
+    });
+
+    ui.data_mut(|d| d.insert_temp(id, holds));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_level_of_known_buffer() {
+        let level = measure_level(&[0.5, 0.0, -0.5, 0.0]);
+        assert!((level.rms - 0.125f32.sqrt()).abs() < 1e-6, "rms {}", level.rms);
+        assert_eq!(level.peak, 0.5);
+
+        let square = measure_level(&[1.0, -1.0, 1.0, -1.0]);
+        assert_eq!((square.rms, square.peak), (1.0, 1.0));
+
+        assert_eq!(measure_level(&[]), ChannelLevel::default());
+    }
+
+    #[test]
+    fn test_peak_hold_holds_then_falls() {
+        let mut hold = PeakHold::default();
+        assert_eq!(hold.update(0.0, 0.1), METER_FLOOR_DB);
+        assert_eq!(hold.update(1.0, 0.1), 0.0);
+
+        // Held through quieter blocks, then falling at the set rate
+        assert_eq!(hold.update(0.1, 1.0), 0.0);
+        assert_eq!(hold.update(0.1, 0.4), 0.0);
+        let fallen = hold.update(0.1, 0.5);
+        assert!((fallen + PEAK_FALL_DB_PER_SECOND * 0.5).abs() < 1e-4, "{fallen}");
+
+        // Never below the current peak
+        assert!((hold.update(0.1, 10.0) + 20.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::project::{self, ProjectFile};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File extension used for preset files in the presets directory.
+pub const PRESET_EXTENSION: &str = "osci-preset";
+
+/// Presets compiled into the binary, as `(name, json)` pairs.
+const BUILTIN_PRESETS: &[(&str, &str)] = &[
+    ("Init", include_str!("../presets/init.osci-preset")),
+    ("Spinning Square", include_str!("../presets/spinning_square.osci-preset")),
+    ("Glitch Scope", include_str!("../presets/glitch_scope.osci-preset")),
+    ("Kaleido Bloom", include_str!("../presets/kaleido_bloom.osci-preset")),
+];
+
+/// A named, shareable patch: synth params, effect chain and visualizer settings.
+///
+/// The payload is an ordinary [`ProjectFile`], so a preset is applied through
+/// exactly the same path as opening a project.
+pub struct Preset {
+    pub name: String,
+    pub project: ProjectFile,
+}
+
+/// Where a preset shown in the menu comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PresetSource {
+    /// Embedded in the binary.
+    BuiltIn,
+    /// A file in the user's presets directory.
+    User,
+}
+
+/// Names of the built-in presets, in menu order.
+pub fn builtin_preset_names() -> Vec<String> {
+    BUILTIN_PRESETS.iter().map(|(name, _)| name.to_string()).collect()
+}
+
+/// Parse a built-in preset by name.
+pub fn load_builtin_preset(name: &str) -> io::Result<Preset> {
+    let (name, json) = BUILTIN_PRESETS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no built-in preset '{name}'")))?;
+    let project = serde_json::from_str(json)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(Preset {
+        name: name.to_string(),
+        project,
+    })
+}
+
+/// The default user presets directory.
+///
+/// `$XDG_CONFIG_HOME/rusci-render/presets`, falling back to
+/// `~/.config/rusci-render/presets`, or `%APPDATA%\rusci-render\presets` on
+/// Windows. Returns `None` if no home directory can be determined.
+pub fn default_presets_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("presets"))
+}
+
+/// The rusci-render config directory: `$XDG_CONFIG_HOME/rusci-render`,
+/// falling back to `~/.config/rusci-render`, or `%APPDATA%\rusci-render` on
+/// Windows. Returns `None` if no home directory can be determined.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+    };
+    base.map(|b| b.join("rusci-render"))
+}
+
+/// List the names of the presets in `dir`, sorted alphabetically.
+///
+/// A missing directory is treated as empty.
+pub fn list_presets(dir: &Path) -> io::Result<Vec<String>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == PRESET_EXTENSION))
+        .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Load the preset called `name` from `dir`.
+pub fn load_preset(dir: &Path, name: &str) -> io::Result<Preset> {
+    let project = project::load_project(&preset_path(dir, name)?)?;
+    Ok(Preset {
+        name: name.to_string(),
+        project,
+    })
+}
+
+/// Save `preset` into `dir`, creating the directory if needed.
+pub fn save_preset(dir: &Path, preset: &Preset) -> io::Result<()> {
+    let path = preset_path(dir, &preset.name)?;
+    std::fs::create_dir_all(dir)?;
+    project::save_project(&path, &preset.project)
+}
+
+/// Characters Windows doesn't allow in file names, besides the separators.
+const RESERVED_CHARACTERS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+/// The file for the preset called `name` in `dir`.
+///
+/// Names come from the user, so anything that could leave `dir` or isn't a
+/// valid file name on every platform is rejected: path separators, `.` and
+/// `..`, reserved and control characters.
+fn preset_path(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let invalid = name.trim().is_empty()
+        || name == "."
+        || name == ".."
+        || name.chars().any(|c| c == '/' || c == '\\' || c.is_control() || RESERVED_CHARACTERS.contains(&c));
+    if invalid {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{name}' can't be used as a preset name"),
+        ));
+    }
+    Ok(dir.join(format!("{name}.{PRESET_EXTENSION}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::UiCommand;
+
+    #[test]
+    fn builtin_presets_parse() {
+        for name in builtin_preset_names() {
+            let preset = load_builtin_preset(&name).unwrap();
+            for effect in &preset.project.effects {
+                assert!(
+                    osci_effects::registry::find_effect(&effect.id).is_some(),
+                    "preset '{name}' uses unknown effect '{}'",
+                    effect.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn applying_builtin_preset_loads_its_effects() {
+        let preset = load_builtin_preset("Glitch Scope").unwrap();
+        let (tx, rx) = crossbeam::channel::unbounded();
+
+        crate::send_effect_chain(&tx, preset.project.effects);
+
+        match rx.try_recv().unwrap() {
+            UiCommand::LoadProject { effects } => {
+                let ids: Vec<&str> = effects.iter().map(|e| e.id.as_str()).collect();
+                assert_eq!(ids, ["bitcrush", "jitter", "chromaSplit"]);
+            }
+            _ => panic!("expected a LoadProject command"),
+        }
+    }
+
+    #[test]
+    fn preset_names_stay_inside_the_directory() {
+        let dir = Path::new("presets");
+        assert_eq!(preset_path(dir, "My Patch").unwrap(), dir.join("My Patch.osci-preset"));
+        assert_eq!(preset_path(dir, "v1.2").unwrap(), dir.join("v1.2.osci-preset"));
+        for name in ["", "  ", ".", "..", "../escape", "a/b", "a\\b", "what?", "a:b", "tab\there"] {
+            let error = preset_path(dir, name).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput, "{name:?}");
+        }
+    }
+}
@@ -1,20 +1,32 @@
-use osci_core::EffectParameter;
+use crate::state::MacroDefinition;
+use osci_core::{EffectParameter, Env, LfoType};
+use osci_visualizer::VisualiserSettings;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::io;
 use std::path::Path;
 
 /// On-disk project file format.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ProjectFile {
     pub version: u32,
     pub synth: SynthParamSnapshot,
     pub effects: Vec<EffectStateEntry>,
     #[serde(default)]
     pub visualizer: Option<VisualizerSnapshot>,
+    /// Non-default Lua slider values, keyed by variable name (e.g. `slider_a`).
+    #[serde(default)]
+    pub lua_sliders: Option<BTreeMap<String, f64>>,
+    /// Custom envelope from the envelope editor, if one replaces the ADSR.
+    #[serde(default)]
+    pub envelope: Option<Env>,
+    /// Macro values and targets, in macro order; absent if no macro drives anything.
+    #[serde(default)]
+    pub macros: Option<Vec<MacroDefinition>>,
 }
 
 /// Snapshot of synthesizer parameters.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SynthParamSnapshot {
     pub volume: f32,
     pub frequency: f32,
@@ -24,8 +36,22 @@ pub struct SynthParamSnapshot {
     pub release: f32,
 }
 
+impl Default for SynthParamSnapshot {
+    /// The plugin's parameter defaults.
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            frequency: 440.0,
+            attack: 0.01,
+            decay: 0.3,
+            sustain: 0.5,
+            release: 1.0,
+        }
+    }
+}
+
 /// One effect in the saved chain.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct EffectStateEntry {
     pub id: String,
     pub enabled: bool,
@@ -33,7 +59,7 @@ pub struct EffectStateEntry {
 }
 
 /// Snapshot of visualizer settings.
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct VisualizerSnapshot {
     pub focus: f32,
     pub intensity: f32,
@@ -53,6 +79,70 @@ pub struct VisualizerSnapshot {
     pub reflection_mode: Option<u32>,
     #[serde(default)]
     pub goniometer: Option<bool>,
+    #[serde(default)]
+    pub rotation: Option<f32>,
+    #[serde(default)]
+    pub zoom: Option<f32>,
+    #[serde(default)]
+    pub offset: Option<[f32; 2]>,
+    #[serde(default)]
+    pub transparent_background: Option<bool>,
+    /// Per-channel persistence; absent in older projects, which used
+    /// `persistence` for every channel.
+    #[serde(default)]
+    pub persistence_rgb: Option<[f32; 3]>,
+    /// [`osci_visualizer::AfterglowCurve::index`] of the persistence decay curve.
+    #[serde(default)]
+    pub afterglow_curve: Option<u32>,
+    /// [`osci_visualizer::VisualiserSettings::auto_focus`]; absent in older projects.
+    #[serde(default)]
+    pub auto_focus: Option<bool>,
+    /// [`osci_visualizer::ToneCurve::index`] of the compositor's tone curve.
+    #[serde(default)]
+    pub tone_curve: Option<u32>,
+    /// Output gamma; absent in older projects, which drew at gamma 1.
+    #[serde(default)]
+    pub gamma: Option<f32>,
+    /// [`osci_visualizer::VisualiserSettings::flip_x`] and `flip_y`, as
+    /// `[x, y]`; absent in older projects.
+    #[serde(default)]
+    pub flip: Option<[bool; 2]>,
+    /// [`osci_visualizer::BlendMode::index`] of how overlapping lines combine.
+    #[serde(default)]
+    pub blend_mode: Option<u32>,
+}
+
+impl VisualizerSnapshot {
+    pub fn from_settings(s: &VisualiserSettings) -> Self {
+        Self {
+            focus: s.focus,
+            intensity: s.intensity,
+            persistence: s.persistence,
+            afterglow: s.afterglow,
+            glow_amount: s.glow_amount,
+            scatter_amount: s.scatter_amount,
+            color: s.color,
+            exposure: s.exposure,
+            overexposure: s.overexposure,
+            saturation: s.saturation,
+            ambient: s.ambient,
+            noise: s.noise,
+            afterglow_color: Some(s.afterglow_color),
+            reflection_mode: Some(s.reflection_mode),
+            goniometer: Some(s.goniometer),
+            rotation: Some(s.rotation),
+            zoom: Some(s.zoom),
+            offset: Some(s.offset),
+            transparent_background: Some(s.transparent_background),
+            persistence_rgb: s.persistence_rgb,
+            afterglow_curve: Some(s.afterglow_curve.index()),
+            auto_focus: Some(s.auto_focus),
+            tone_curve: Some(s.tone_curve.index()),
+            gamma: Some(s.gamma),
+            flip: Some([s.flip_x, s.flip_y]),
+            blend_mode: Some(s.blend_mode.index()),
+        }
+    }
 }
 
 /// Save a project file to disk as JSON.
@@ -67,3 +157,508 @@ pub fn load_project(path: &Path) -> io::Result<ProjectFile> {
     let json = std::fs::read_to_string(path)?;
     serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
+
+/// Extension of projects saved by the C++ osci-render.
+const LEGACY_EXTENSION: &str = "osci";
+
+/// Whether `path` is a C++ osci-render project, going by its extension.
+pub fn is_legacy_project(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(LEGACY_EXTENSION))
+}
+
+/// Load a project from disk, importing it with [`import_legacy_project`]
+/// if it comes from the C++ osci-render.
+pub fn load_any_project(path: &Path) -> io::Result<ProjectFile> {
+    if is_legacy_project(path) {
+        import_legacy_project(&std::fs::read(path)?)
+    } else {
+        load_project(path)
+    }
+}
+
+/// Magic number JUCE puts in front of XML saved as binary plugin state.
+const JUCE_XML_MAGIC: &[u8] = b"VC2!";
+
+/// C++ osci-render effect ids, which are the ids of their first parameter,
+/// with the registry effect each one maps to and any parameters that were
+/// renamed. Other parameters keep their ids.
+const LEGACY_EFFECTS: &[(&str, &str, &[(&str, &str)])] = &[
+    ("bitCrush", "bitcrush", &[("bitCrush", "bitCrushDepth")]),
+    ("bulge", "bulge", &[]),
+    ("vectorCancelling", "vectorCancelling", &[]),
+    ("rippleDepth", "ripple", &[("rippleDepth", "rippleAmplitude"), ("rippleAmount", "rippleFrequency")]),
+    ("rotateX", "rotate", &[]),
+    ("translateX", "translate", &[]),
+    ("scaleX", "scale", &[]),
+    ("swirl", "swirl", &[]),
+    ("smoothing", "smooth", &[("smoothing", "smooth")]),
+    ("delayDecay", "delay", &[]),
+    ("dashLength", "dashedLine", &[("dashLength", "dashCoverage")]),
+    ("wobble", "wobble", &[("wobble", "wobbleAmplitude")]),
+    ("twist", "twist", &[]),
+    ("skewX", "skew", &[]),
+    ("kaleidoscopeSegments", "kaleidoscope", &[]),
+    ("multiplexGridX", "multiplex", &[]),
+    ("unfoldSegments", "unfold", &[]),
+    ("bounceSize", "bounce", &[]),
+    ("vortex", "vortex", &[]),
+    ("perspective", "perspective", &[("perspective", "perspectiveStrength")]),
+    ("perspectiveStrength", "perspective", &[]),
+    ("threshold", "threshold", &[]),
+];
+
+/// Legacy parameters that are synth settings here rather than effects.
+const LEGACY_SYNTH: &[(&str, fn(&mut SynthParamSnapshot) -> &mut f32)] = &[
+    ("volume", |s| &mut s.volume),
+    ("frequency", |s| &mut s.frequency),
+    ("attackTime", |s| &mut s.attack),
+    ("decayTime", |s| &mut s.decay),
+    ("sustainLevel", |s| &mut s.sustain),
+    ("releaseTime", |s| &mut s.release),
+];
+
+/// Legacy visualiser parameters, with the range of the matching setting
+/// here that their values are clamped to.
+const LEGACY_VISUALIZER: &[(&str, (f32, f32), fn(&mut VisualizerSnapshot) -> &mut f32)] = &[
+    ("focus", (0.001, 0.02), |v| &mut v.focus),
+    ("intensity", (0.1, 5.0), |v| &mut v.intensity),
+    ("persistence", (0.0, 1.0), |v| &mut v.persistence),
+    ("afterglow", (0.0, 1.0), |v| &mut v.afterglow),
+    ("glow", (0.0, 2.0), |v| &mut v.glow_amount),
+    ("scatter", (0.0, 2.0), |v| &mut v.scatter_amount),
+    ("exposure", (0.5, 5.0), |v| &mut v.exposure),
+    ("overexposure", (0.0, 1.0), |v| &mut v.overexposure),
+    ("saturation", (0.0, 2.0), |v| &mut v.saturation),
+    ("ambient", (0.0, 0.1), |v| &mut v.ambient),
+    ("noise", (0.0, 0.05), |v| &mut v.noise),
+];
+
+/// Import a project saved by the C++ osci-render.
+///
+/// Those are JUCE plugin state: XML, optionally behind JUCE's binary
+/// header. Effects switched on are mapped to registry effects through
+/// [`LEGACY_EFFECTS`], or by id when the registry has the same one, and
+/// keep their values, LFOs and sidechain settings. osci-render saves every
+/// effect it knows whether it's on or not, so effects switched off are left
+/// out. Effects that don't map are skipped with a warning. Volume,
+/// frequency, the envelope and the visualiser settings carry over where
+/// they have a counterpart here; files, fonts and Lua code don't.
+pub fn import_legacy_project(data: &[u8]) -> io::Result<ProjectFile> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+
+    let xml = match data.strip_prefix(JUCE_XML_MAGIC) {
+        Some(rest) if rest.len() >= 4 => {
+            let len = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+            &rest[4..(4 + len).min(rest.len())]
+        }
+        _ => data,
+    };
+    let xml = std::str::from_utf8(xml)
+        .map_err(|e| invalid(e.to_string()))?
+        .trim_end_matches('\0');
+    let doc = roxmltree::Document::parse(xml).map_err(|e| invalid(e.to_string()))?;
+    let root = doc.root_element();
+    if !root.has_tag_name("project") {
+        return Err(invalid("not an osci-render project".to_string()));
+    }
+
+    // Settings can be saved as effects or as plain parameters
+    let settings: HashMap<&str, f32> = root
+        .descendants()
+        .filter(|n| n.has_tag_name("parameter"))
+        .filter_map(|n| Some((n.attribute("id")?, parse_legacy_number(n.attribute("value")?)?)))
+        .filter(|(id, _)| is_legacy_setting(id))
+        .collect();
+
+    let mut synth = SynthParamSnapshot::default();
+    for (id, field) in LEGACY_SYNTH {
+        if let Some(&value) = settings.get(id) {
+            *field(&mut synth) = value;
+        }
+    }
+
+    let mut visualizer = None;
+    for (id, (min, max), field) in LEGACY_VISUALIZER {
+        if let Some(&value) = settings.get(id) {
+            let snapshot = visualizer
+                .get_or_insert_with(|| VisualizerSnapshot::from_settings(&VisualiserSettings::default()));
+            *field(snapshot) = value.clamp(*min, *max);
+        }
+    }
+
+    let mut effects: Vec<(f32, EffectStateEntry)> = Vec::new();
+    let legacy_effects = root
+        .children()
+        .filter(|n| n.has_tag_name("effects"))
+        .flat_map(|n| n.children())
+        .filter(|n| n.has_tag_name("effect"));
+    for node in legacy_effects {
+        let Some(id) = node.attribute("id") else { continue };
+        if is_legacy_setting(id) || !legacy_enabled(node) {
+            continue;
+        }
+        let Some(effect) = import_legacy_effect(id, node) else {
+            log::warn!("Skipping osci-render effect '{id}' with no equivalent here");
+            continue;
+        };
+        let precedence = node.attribute("precedence").and_then(parse_legacy_number).unwrap_or(0.0);
+        effects.push((precedence, effect));
+    }
+    // osci-render applies effects in order of precedence
+    effects.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    Ok(ProjectFile {
+        version: 1,
+        synth,
+        effects: effects.into_iter().map(|(_, effect)| effect).collect(),
+        visualizer,
+        lua_sliders: None,
+        envelope: None,
+        macros: None,
+    })
+}
+
+fn is_legacy_setting(id: &str) -> bool {
+    LEGACY_SYNTH.iter().any(|(s, _)| *s == id) || LEGACY_VISUALIZER.iter().any(|(s, _, _)| *s == id)
+}
+
+/// Map one legacy `<effect>` element to a registry effect.
+fn import_legacy_effect(legacy_id: &str, node: roxmltree::Node) -> Option<EffectStateEntry> {
+    let (id, renames): (&str, &[(&str, &str)]) = match LEGACY_EFFECTS.iter().find(|(l, _, _)| *l == legacy_id) {
+        Some((_, id, renames)) => (*id, *renames),
+        None => (legacy_id, &[]),
+    };
+    let entry = osci_effects::registry::find_effect(id)?;
+
+    let mut parameters = (entry.parameters)();
+    for param_node in node.children().filter(|n| n.has_tag_name("parameter")) {
+        let Some(legacy_param) = param_node.attribute("id") else { continue };
+        let param_id = renames
+            .iter()
+            .find(|(from, _)| *from == legacy_param)
+            .map_or(legacy_param, |(_, to)| *to);
+        let Some(param) = parameters.iter_mut().find(|p| p.id == param_id) else {
+            log::warn!("Skipping osci-render parameter '{legacy_param}' of effect '{legacy_id}'");
+            continue;
+        };
+        apply_legacy_param(param, param_node);
+    }
+
+    Some(EffectStateEntry {
+        id: id.to_string(),
+        enabled: true,
+        parameters,
+    })
+}
+
+/// Copy a legacy parameter's value, LFO and sidechain setting.
+///
+/// Different osci-render versions name the LFO attributes differently, so
+/// each is looked up under every name it has had.
+fn apply_legacy_param(param: &mut EffectParameter, node: roxmltree::Node) {
+    if let Some(value) = node.attribute("value").and_then(parse_legacy_number) {
+        param.value = value.clamp(param.min.min(param.max), param.max.max(param.min));
+    }
+
+    if let Some(lfo) = node.children().find(|n| n.has_tag_name("lfo")) {
+        let attr = |names: &[&str]| names.iter().find_map(|name| lfo.attribute(*name));
+        if let Some(lfo_type) = attr(&["lfo", "type"]).and_then(parse_legacy_lfo) {
+            param.lfo_type = lfo_type;
+        }
+        if let Some(rate) = attr(&["lfoRate", "rate"]).and_then(parse_legacy_number) {
+            param.lfo_rate = rate;
+        }
+        if let Some(start) = attr(&["lfoStartPercent", "start"]).and_then(parse_legacy_number) {
+            param.lfo_start_percent = start;
+        }
+        if let Some(end) = attr(&["lfoEndPercent", "end"]).and_then(parse_legacy_number) {
+            param.lfo_end_percent = end;
+        }
+    }
+
+    if let Some(sidechain) = node.children().find(|n| n.has_tag_name("sidechain")) {
+        param.sidechain_enabled = sidechain.attribute("value").is_some_and(parse_legacy_bool);
+    }
+}
+
+/// Whether a legacy effect is switched on, from its `enabled` attribute or
+/// child element. Effects without either are always on.
+fn legacy_enabled(node: roxmltree::Node) -> bool {
+    let value = node.attribute("enabled").or_else(|| {
+        node.children()
+            .find(|n| n.has_tag_name("enabled"))
+            .and_then(|n| n.attribute("value"))
+    });
+    value.is_none_or(parse_legacy_bool)
+}
+
+fn parse_legacy_number(text: &str) -> Option<f32> {
+    text.trim().parse().ok().filter(|v: &f32| v.is_finite())
+}
+
+fn parse_legacy_bool(text: &str) -> bool {
+    matches!(text.trim(), "1" | "true" | "True")
+}
+
+/// An LFO type saved either as its number or its name, e.g. "Reverse Sawtooth".
+fn parse_legacy_lfo(text: &str) -> Option<LfoType> {
+    if let Ok(n) = text.trim().parse::<i32>() {
+        return Some(LfoType::from_i32(n));
+    }
+    let wanted = text.replace(' ', "").to_lowercase();
+    (1..=9)
+        .map(LfoType::from_i32)
+        .find(|t| t.name().replace(' ', "").to_lowercase() == wanted)
+}
+
+/// First line of every exported chain snippet.
+const SNIPPET_HEADER: &str = "# osci-render chain";
+
+/// Write an effect chain as a short text snippet for sharing.
+///
+/// Each effect takes one line: its id, prefixed with `-` when disabled,
+/// followed by the parameters that differ from their defaults:
+///
+/// ```text
+/// rotate rotateY=0.25~sine,2,0,100
+/// -bitcrush bitCrushDepth=0.3
+/// scale scaleX=-1.5+sidechain
+/// ```
+///
+/// A parameter is `id=value`, then `~type,rate,start,end` with an optional
+/// `,bipolar` when it has an LFO, then `+sidechain` when sidechained. Lines
+/// starting with `#` are comments. Read it back with [`from_snippet`].
+pub fn to_snippet(effects: &[EffectStateEntry]) -> String {
+    let mut out = String::from(SNIPPET_HEADER);
+    out.push('\n');
+    for effect in effects {
+        if !effect.enabled {
+            out.push('-');
+        }
+        out.push_str(&effect.id);
+        for param in &effect.parameters {
+            let modulated = param.lfo_type != LfoType::Static;
+            if param.value == param.default_value && !modulated && !param.sidechain_enabled {
+                continue;
+            }
+            out.push_str(&format!(" {}={}", param.id, param.value));
+            if modulated {
+                out.push_str(&format!(
+                    "~{},{},{},{}",
+                    lfo_slug(param.lfo_type),
+                    param.lfo_rate,
+                    param.lfo_start_percent,
+                    param.lfo_end_percent
+                ));
+                if param.lfo_bipolar {
+                    out.push_str(",bipolar");
+                }
+            }
+            if param.sidechain_enabled {
+                out.push_str("+sidechain");
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Rebuild an effect chain from a [`to_snippet`] snippet.
+///
+/// Parameters left out of the snippet keep their defaults. Unknown effects,
+/// unknown parameters and malformed values are skipped with a warning, so a
+/// snippet from a newer version still loads what it can.
+pub fn from_snippet(text: &str) -> Vec<EffectStateEntry> {
+    let mut effects = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        let Some(first) = tokens.next() else { continue };
+        let (id, enabled) = match first.strip_prefix('-') {
+            Some(id) => (id, false),
+            None => (first, true),
+        };
+        let Some(entry) = osci_effects::registry::find_effect(id) else {
+            log::warn!("Skipping unknown effect '{id}' in chain snippet");
+            continue;
+        };
+
+        let mut parameters = (entry.parameters)();
+        for token in tokens {
+            if let Err(e) = apply_snippet_param(&mut parameters, token) {
+                log::warn!("Skipping '{token}' for effect '{id}' in chain snippet: {e}");
+            }
+        }
+        effects.push(EffectStateEntry {
+            id: id.to_string(),
+            enabled,
+            parameters,
+        });
+    }
+    effects
+}
+
+/// Apply one `id=value~lfo+sidechain` token to the matching parameter.
+fn apply_snippet_param(parameters: &mut [EffectParameter], token: &str) -> Result<(), String> {
+    let (param_id, rest) = token.split_once('=').ok_or("expected id=value")?;
+    let param = parameters
+        .iter_mut()
+        .find(|p| p.id == param_id)
+        .ok_or("unknown parameter")?;
+
+    let (rest, sidechain) = match rest.strip_suffix("+sidechain") {
+        Some(rest) => (rest, true),
+        None => (rest, false),
+    };
+    let (value, lfo) = match rest.split_once('~') {
+        Some((value, lfo)) => (value, Some(lfo)),
+        None => (rest, None),
+    };
+    let value: f32 = value.parse().map_err(|_| format!("bad value '{value}'"))?;
+
+    let mut lfo_settings = None;
+    if let Some(lfo) = lfo {
+        let fields: Vec<&str> = lfo.split(',').collect();
+        let bipolar = match fields.len() {
+            4 => false,
+            5 if fields[4] == "bipolar" => true,
+            _ => return Err(format!("bad LFO '{lfo}'")),
+        };
+        let lfo_type = lfo_from_slug(fields[0]).ok_or_else(|| format!("unknown LFO '{}'", fields[0]))?;
+        let mut numbers = [0.0f32; 3];
+        for (number, field) in numbers.iter_mut().zip(&fields[1..4]) {
+            *number = field.parse().map_err(|_| format!("bad LFO setting '{field}'"))?;
+        }
+        lfo_settings = Some((lfo_type, numbers, bipolar));
+    }
+
+    param.value = value.clamp(param.min.min(param.max), param.min.max(param.max));
+    if let Some((lfo_type, [rate, start, end], bipolar)) = lfo_settings {
+        param.lfo_type = lfo_type;
+        param.lfo_rate = rate;
+        param.lfo_start_percent = start;
+        param.lfo_end_percent = end;
+        param.lfo_bipolar = bipolar;
+    }
+    param.sidechain_enabled = sidechain;
+    Ok(())
+}
+
+/// Name of an LFO type in snippets, e.g. `smooth-noise`.
+fn lfo_slug(lfo_type: LfoType) -> String {
+    lfo_type.name().to_lowercase().replace(' ', "-")
+}
+
+fn lfo_from_slug(slug: &str) -> Option<LfoType> {
+    (1..=9).map(LfoType::from_i32).find(|&t| lfo_slug(t) == slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, enabled: bool, edit: impl FnOnce(&mut [EffectParameter])) -> EffectStateEntry {
+        let mut parameters = (osci_effects::registry::find_effect(id).unwrap().parameters)();
+        edit(&mut parameters);
+        EffectStateEntry {
+            id: id.to_string(),
+            enabled,
+            parameters,
+        }
+    }
+
+    #[test]
+    fn snippet_round_trips_modulated_chain() {
+        let chain = vec![
+            entry("rotate", true, |p| {
+                p[1].value = 0.25;
+                p[1].lfo_type = LfoType::Sine;
+                p[1].lfo_rate = 2.0;
+                p[1].lfo_start_percent = 10.0;
+                p[1].lfo_end_percent = 90.0;
+                p[1].lfo_bipolar = true;
+            }),
+            entry("bitcrush", false, |p| p[1].value = 0.3),
+            entry("scale", true, |p| {
+                p[0].value = -1.5;
+                p[0].sidechain_enabled = true;
+                p[2].lfo_type = LfoType::SmoothNoise;
+                p[2].lfo_rate = 0.5;
+            }),
+        ];
+
+        let snippet = to_snippet(&chain);
+        assert_eq!(snippet.lines().count(), 4, "{snippet}");
+        assert!(snippet.contains("-bitcrush bitCrushDepth=0.3\n"), "{snippet}");
+
+        let loaded = from_snippet(&snippet);
+        assert_eq!(loaded.len(), chain.len());
+        for (a, b) in chain.iter().zip(&loaded) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.enabled, b.enabled);
+            assert_eq!(a.parameters.len(), b.parameters.len());
+            for (pa, pb) in a.parameters.iter().zip(&b.parameters) {
+                assert_eq!(pa.value, pb.value, "{}", pa.id);
+                assert_eq!(pa.lfo_type, pb.lfo_type, "{}", pa.id);
+                assert_eq!(pa.lfo_rate, pb.lfo_rate, "{}", pa.id);
+                assert_eq!(pa.lfo_start_percent, pb.lfo_start_percent, "{}", pa.id);
+                assert_eq!(pa.lfo_end_percent, pb.lfo_end_percent, "{}", pa.id);
+                assert_eq!(pa.lfo_bipolar, pb.lfo_bipolar, "{}", pa.id);
+                assert_eq!(pa.sidechain_enabled, pb.sidechain_enabled, "{}", pa.id);
+            }
+        }
+    }
+
+    #[test]
+    fn snippet_skips_what_it_does_not_know() {
+        let loaded = from_snippet("notAnEffect foo=1\nrotate rotateX=0.5 nope=1 rotateY=abc\n");
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, "rotate");
+        assert_eq!(loaded[0].parameters[0].value, 0.5);
+        assert_eq!(loaded[0].parameters[1].value, 0.0);
+    }
+
+    #[test]
+    fn imports_legacy_project() {
+        let xml = include_bytes!("../tests/fixtures/legacy_project.osci");
+        let project = import_legacy_project(xml).unwrap();
+
+        // Ordered by precedence; the disabled ripple and the unknown effect are left out
+        let ids: Vec<&str> = project.effects.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["bitcrush", "rotate", "wobble"]);
+
+        let value = |effect: usize, id: &str| {
+            project.effects[effect].parameters.iter().find(|p| p.id == id).unwrap().clone()
+        };
+        assert_eq!(value(0, "bitCrushDepth").value, 0.3);
+        let rotate_y = value(1, "rotateY");
+        assert_eq!(rotate_y.value, 0.5);
+        assert_eq!(rotate_y.lfo_type, LfoType::Sine);
+        assert_eq!(rotate_y.lfo_rate, 2.0);
+        let wobble = value(2, "wobbleAmplitude");
+        assert_eq!(wobble.value, 0.4);
+        assert!(wobble.sidechain_enabled);
+        assert_eq!(value(2, "wobblePhase").value, -0.25);
+
+        assert_eq!(project.synth.volume, 0.8);
+        assert_eq!(project.synth.frequency, 220.0);
+        assert_eq!(project.synth.attack, 0.02);
+        assert_eq!(project.synth.decay, SynthParamSnapshot::default().decay);
+        let visualizer = project.visualizer.unwrap();
+        assert_eq!(visualizer.focus, 0.008);
+        assert_eq!(visualizer.persistence, 0.7);
+
+        // The same state behind JUCE's binary header
+        let mut binary = JUCE_XML_MAGIC.to_vec();
+        binary.extend_from_slice(&(xml.len() as u32 + 1).to_le_bytes());
+        binary.extend_from_slice(xml);
+        binary.push(0);
+        assert_eq!(import_legacy_project(&binary).unwrap().effects.len(), 3);
+
+        assert!(import_legacy_project(b"{\"version\": 1}").is_err());
+    }
+}
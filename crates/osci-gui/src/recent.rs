@@ -0,0 +1,99 @@
+//! Recently opened and saved project files, kept across sessions.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Longest the recent files list gets; older entries drop off the end.
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// Recently used files, most recent first, without duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecentFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl RecentFiles {
+    /// Move `path` to the front, adding it if it's new and dropping the
+    /// oldest entry past [`MAX_RECENT_FILES`].
+    pub fn push(&mut self, path: PathBuf) {
+        self.paths.retain(|p| *p != path);
+        self.paths.insert(0, path);
+        self.paths.truncate(MAX_RECENT_FILES);
+    }
+
+    /// Forget `path`, e.g. after it failed to open.
+    pub fn remove(&mut self, path: &Path) {
+        self.paths.retain(|p| p != path);
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Read the list saved at `path`. A missing or unreadable file gives an
+    /// empty list.
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the list to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Where the recent files list is kept: `recent.json` in the config
+/// directory, next to the presets folder. Returns `None` if no home
+/// directory can be determined.
+pub fn default_recent_files_path() -> Option<PathBuf> {
+    crate::preset::config_dir().map(|dir| dir.join("recent.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_recent_first_without_duplicates() {
+        let mut recent = RecentFiles::default();
+        recent.push("a.json".into());
+        recent.push("b.json".into());
+        recent.push("a.json".into());
+        assert_eq!(recent.paths(), [PathBuf::from("a.json"), PathBuf::from("b.json")]);
+
+        for i in 0..MAX_RECENT_FILES + 3 {
+            recent.push(format!("{i}.json").into());
+        }
+        assert_eq!(recent.paths().len(), MAX_RECENT_FILES);
+        assert_eq!(recent.paths()[0], PathBuf::from(format!("{}.json", MAX_RECENT_FILES + 2)));
+        assert!(!recent.paths().contains(&PathBuf::from("a.json")));
+
+        recent.remove(Path::new("5.json"));
+        assert_eq!(recent.paths().len(), MAX_RECENT_FILES - 1);
+        assert!(!recent.paths().contains(&PathBuf::from("5.json")));
+    }
+
+    #[test]
+    fn round_trips_through_file() {
+        let path = std::env::temp_dir()
+            .join(format!("rusci-recent-{}", std::process::id()))
+            .join("recent.json");
+        let mut recent = RecentFiles::default();
+        recent.push("/music/square.json".into());
+        recent.push("/music/spiral.json".into());
+        recent.save(&path).unwrap();
+
+        assert_eq!(RecentFiles::load(&path), recent);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(RecentFiles::load(&path), RecentFiles::default());
+    }
+}
@@ -1,13 +1,21 @@
 use crate::state::VisBuffer;
 use nih_plug_egui::egui::{self, Vec2};
-use osci_visualizer::{OsciRenderer, VisualiserSettings};
+use osci_visualizer::{OsciRenderer, RenderStats, VisualiserSettings};
 use std::sync::{Arc, Mutex};
 
+/// Receives the composited scope each frame as `(gl, texture, width, height)`.
+pub type FrameOutput = Box<dyn FnMut(&glow::Context, glow::Texture, u32, u32) + Send>;
+
 /// Shared state for the GPU oscilloscope scope, accessed from both
 /// the egui layout code and the glow paint callback.
 pub struct GpuScopeState {
     pub renderer: Option<OsciRenderer>,
     pub settings: VisualiserSettings,
+    /// Stats from the last frame the paint callback rendered.
+    pub stats: RenderStats,
+    /// Called after each render while `settings.shared_output` is on, e.g.
+    /// to publish the scope as a shared texture.
+    pub frame_output: Option<FrameOutput>,
 }
 
 impl Default for GpuScopeState {
@@ -15,19 +23,112 @@ impl Default for GpuScopeState {
         Self {
             renderer: None,
             settings: VisualiserSettings::default(),
+            stats: RenderStats::default(),
+            frame_output: None,
         }
     }
 }
 
+/// Smallest side length of the square scope, in logical pixels.
+const MIN_SCOPE_SIZE: f32 = 200.0;
+
+/// Fraction of the window height the scope may take up, so some controls
+/// stay visible above it when the editor is tall and narrow.
+const MAX_SCOPE_HEIGHT_FRACTION: f32 = 0.8;
+
+/// Fraction of the view the trace spans after Fit to View, leaving a small
+/// border so the beam's glow isn't clipped at the edges.
+const FIT_MARGIN: f32 = 0.9;
+
+/// The zoom and offset that frame `x`/`y` in the view, keeping the current
+/// rotation and goniometer mode. The larger side of the trace's bounding
+/// box spans [`FIT_MARGIN`] of the view, centred. Zoom and offset are
+/// limited to the ranges of their sliders. Returns `None` when there is
+/// nothing finite to frame.
+pub fn fit_view(x: &[f32], y: &[f32], settings: &VisualiserSettings) -> Option<(f32, [f32; 2])> {
+    let unscaled = VisualiserSettings {
+        zoom: 1.0,
+        offset: [0.0, 0.0],
+        ..settings.clone()
+    };
+    let mut min = [f32::INFINITY; 2];
+    let mut max = [f32::NEG_INFINITY; 2];
+    for (&x, &y) in x.iter().zip(y) {
+        let (vx, vy) = unscaled.view_transform(x, y);
+        if !vx.is_finite() || !vy.is_finite() {
+            continue;
+        }
+        min = [min[0].min(vx), min[1].min(vy)];
+        max = [max[0].max(vx), max[1].max(vy)];
+    }
+    if min[0] > max[0] {
+        return None;
+    }
+
+    let half_extent = (max[0] - min[0]).max(max[1] - min[1]) / 2.0;
+    let zoom = if half_extent > 0.0 {
+        (FIT_MARGIN / half_extent).clamp(0.25, 4.0)
+    } else {
+        4.0
+    };
+    let centre = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+    let offset = [
+        (-centre[0] * zoom).clamp(-1.0, 1.0),
+        (-centre[1] * zoom).clamp(-1.0, 1.0),
+    ];
+    Some((zoom, offset))
+}
+
+/// Put rotation, zoom and offset back to their defaults.
+pub fn reset_view(settings: &mut VisualiserSettings) {
+    let defaults = VisualiserSettings::default();
+    settings.rotation = defaults.rotation;
+    settings.zoom = defaults.zoom;
+    settings.offset = defaults.offset;
+}
+
 /// Draw the GPU-accelerated oscilloscope scope using `egui::PaintCallback`.
-pub fn draw_gpu_scope(ui: &mut egui::Ui, vis: &VisBuffer, scope_state: Arc<Mutex<GpuScopeState>>) {
-    let desired_size = Vec2::splat(ui.available_width().min(300.0));
-    let (rect, _response) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
+///
+/// The scope is a square filling the available width, so it grows with the
+/// editor window. The GL viewport comes from the callback's own rect each
+/// frame, so it always tracks where the widget actually landed.
+///
+/// `frequency` is the rate the frame is being drawn at, for auto-focus; 0
+/// when unknown. With `show_stats` set, the previous frame's [`RenderStats`] are drawn in
+/// the top-left corner of the scope. Right-clicking the scope offers Fit to
+/// View, which frames what `vis` currently holds, and Reset View.
+pub fn draw_gpu_scope(
+    ui: &mut egui::Ui,
+    vis: &VisBuffer,
+    frequency: f32,
+    scope_state: Arc<Mutex<GpuScopeState>>,
+    show_stats: bool,
+) {
+    let max_height = ui.ctx().screen_rect().height() * MAX_SCOPE_HEIGHT_FRACTION;
+    let side = ui.available_width().min(max_height).max(MIN_SCOPE_SIZE);
+    let desired_size = Vec2::splat(side);
+    let (rect, response) = ui.allocate_exact_size(desired_size, egui::Sense::click());
+    response.context_menu(|ui| {
+        if ui.button("Fit to View").clicked() {
+            let mut state = scope_state.lock().unwrap();
+            if let Some((zoom, offset)) = fit_view(&vis.x, &vis.y, &state.settings) {
+                state.settings.zoom = zoom;
+                state.settings.offset = offset;
+            }
+            ui.close_menu();
+        }
+        if ui.button("Reset View").clicked() {
+            reset_view(&mut scope_state.lock().unwrap().settings);
+            ui.close_menu();
+        }
+    });
 
     // Clone sample data for the callback closure
     let x_samples = vis.x.clone();
     let y_samples = vis.y.clone();
 
+    let stats = scope_state.lock().unwrap().stats;
+
     let cb = egui_glow::CallbackFn::new(move |info, painter| {
         let gl = painter.gl();
 
@@ -39,7 +140,8 @@ pub fn draw_gpu_scope(ui: &mut egui::Ui, vis: &VisBuffer, scope_state: Arc<Mutex
             vp.height_px,
         ];
 
-        let mut state = scope_state.lock().unwrap();
+        let mut guard = scope_state.lock().unwrap();
+        let state = &mut *guard;
 
         // Lazy-initialize the renderer on first use
         if state.renderer.is_none() {
@@ -50,7 +152,13 @@ pub fn draw_gpu_scope(ui: &mut egui::Ui, vis: &VisBuffer, scope_state: Arc<Mutex
         // Clone settings before taking mutable borrow on renderer
         let settings = state.settings.clone();
         if let Some(renderer) = &mut state.renderer {
-            renderer.render(gl, &x_samples, &y_samples, &settings, viewport);
+            state.stats = renderer.render(gl, &x_samples, &y_samples, frequency, &settings, viewport);
+
+            if let (Some(output), Some((texture, width, height))) =
+                (state.frame_output.as_mut(), renderer.shared_output())
+            {
+                output(gl, texture, width, height);
+            }
         }
     });
 
@@ -58,4 +166,51 @@ pub fn draw_gpu_scope(ui: &mut egui::Ui, vis: &VisBuffer, scope_state: Arc<Mutex
         rect,
         callback: Arc::new(cb),
     });
+
+    if show_stats {
+        ui.painter().text(
+            rect.left_top() + Vec2::splat(6.0),
+            egui::Align2::LEFT_TOP,
+            format!(
+                "{:.0} fps\n{} segments\n{:.2} ms",
+                stats.fps, stats.segments, stats.frame_ms
+            ),
+            egui::FontId::monospace(11.0),
+            egui::Color32::from_gray(200),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_view_frames_the_bounding_box() {
+        let x = [-0.5, 0.1, -0.2];
+        let y = [0.2, 0.4, 0.3];
+        // The current zoom and offset don't affect the fit
+        let mut settings = VisualiserSettings {
+            zoom: 2.0,
+            offset: [0.5, 0.5],
+            ..Default::default()
+        };
+
+        let (zoom, offset) = fit_view(&x, &y, &settings).unwrap();
+        assert!((zoom - 3.0).abs() < 1e-5, "zoom {zoom}");
+        assert!((offset[0] - 0.6).abs() < 1e-5 && (offset[1] + 0.9).abs() < 1e-5, "{offset:?}");
+
+        // The wider side spans the margin, centred
+        settings.zoom = zoom;
+        settings.offset = offset;
+        let (left, bottom) = settings.view_transform(-0.5, 0.2);
+        let (right, top) = settings.view_transform(0.1, 0.4);
+        assert!((left + FIT_MARGIN).abs() < 1e-5 && (right - FIT_MARGIN).abs() < 1e-5);
+        assert!((bottom + top).abs() < 1e-5);
+
+        reset_view(&mut settings);
+        assert!(!settings.has_view_transform());
+        assert_eq!(fit_view(&[], &[], &settings), None);
+        assert_eq!(fit_view(&[f32::NAN], &[0.0], &settings), None);
+    }
 }
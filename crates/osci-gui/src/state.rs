@@ -1,8 +1,85 @@
-use crossbeam::channel::Sender;
-use osci_core::{EffectParameter, LfoType};
+use crate::meter::ChannelLevel;
+use crossbeam::channel::{Receiver, Sender};
+use osci_core::{EffectParameter, Env, LfoType, Shape, ShapeMorph, SmoothCurve};
+use osci_parsers::SampleSource;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+/// Number of generic Lua slider variables (`slider_a` through `slider_z`).
+pub const NUM_LUA_SLIDERS: usize = 26;
+
+/// Lua variable name for the slider at `idx` (0 → `slider_a`).
+pub fn lua_slider_name(idx: usize) -> String {
+    format!("slider_{}", (b'a' + idx as u8) as char)
+}
+
+/// Number of macro controls, each also a host-automatable parameter.
+pub const NUM_MACROS: usize = 4;
+
+/// An effect parameter a macro drives, by position in the chain.
+///
+/// The macro's 0..1 value is mapped onto `min..max`, which are normalized
+/// positions in the parameter's own range, so a target can cover part of
+/// its range or run backwards.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MacroTarget {
+    pub effect_idx: usize,
+    pub param_idx: usize,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl MacroTarget {
+    /// A target sweeping the parameter's whole range.
+    pub fn full_range(effect_idx: usize, param_idx: usize) -> Self {
+        Self {
+            effect_idx,
+            param_idx,
+            min: 0.0,
+            max: 1.0,
+        }
+    }
+
+    /// Normalized parameter value for macro value `value`.
+    pub fn normalized(&self, value: f32) -> f32 {
+        self.min + value.clamp(0.0, 1.0) * (self.max - self.min)
+    }
+}
+
+/// One macro control: its value and the parameters it drives.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MacroDefinition {
+    pub value: f32,
+    pub targets: Vec<MacroTarget>,
+}
+
+impl MacroDefinition {
+    /// Follow the chain when the effect at `idx` is removed: targets on it
+    /// are dropped and targets on later effects shift down.
+    pub fn effect_removed(&mut self, idx: usize) {
+        self.targets.retain(|t| t.effect_idx != idx);
+        for target in &mut self.targets {
+            if target.effect_idx > idx {
+                target.effect_idx -= 1;
+            }
+        }
+    }
+
+    /// Follow the chain when the effect at `from` moves to `to`.
+    pub fn effect_moved(&mut self, from: usize, to: usize) {
+        for target in &mut self.targets {
+            let idx = target.effect_idx;
+            target.effect_idx = if idx == from {
+                to
+            } else {
+                let shifted = if idx > from { idx - 1 } else { idx };
+                if shifted >= to { shifted + 1 } else { shifted }
+            };
+        }
+    }
+}
+
 /// A serializable snapshot of one effect for project load.
 #[derive(Clone, Debug)]
 pub struct LoadedEffect {
@@ -21,6 +98,12 @@ pub enum UiCommand {
     MoveEffect { from: usize, to: usize },
     /// Enable or disable an effect at the given index.
     SetEffectEnabled { idx: usize, enabled: bool },
+    /// Solo an effect: while any effect is soloed, only soloed effects apply.
+    SetEffectSolo { idx: usize, solo: bool },
+    /// Choose whether an effect clears its state, e.g. echoes, on each new note.
+    SetEffectResetOnNote { idx: usize, reset_on_note: bool },
+    /// Skip the whole effect chain without changing it.
+    BypassAll { bypass: bool },
     /// Set a parameter value on an effect.
     SetParamValue {
         effect_idx: usize,
@@ -35,12 +118,15 @@ pub enum UiCommand {
         rate: f32,
         start: f32,
         end: f32,
+        /// Swing around the current value rather than between start and end.
+        bipolar: bool,
     },
-    /// Set the smoothing amount for a parameter.
+    /// Set the smoothing amount and curve for a parameter.
     SetSmoothing {
         effect_idx: usize,
         param_idx: usize,
         value: f32,
+        curve: SmoothCurve,
     },
     /// Enable or disable sidechain modulation for a parameter.
     SetSidechain {
@@ -48,6 +134,16 @@ pub enum UiCommand {
         param_idx: usize,
         enabled: bool,
     },
+    /// Lock or unlock a parameter against [`UiCommand::RandomizeParams`].
+    SetParamLocked {
+        effect_idx: usize,
+        param_idx: usize,
+        locked: bool,
+    },
+    /// Set every effect parameter to a random value in its range, keeping
+    /// locked ones when `respect_locks` is set. The same `seed` gives the
+    /// same values.
+    RandomizeParams { respect_locks: bool, seed: u32 },
     /// Load a project: replace the entire effect chain with the given effects.
     LoadProject {
         effects: Vec<LoadedEffect>,
@@ -65,6 +161,37 @@ pub enum UiCommand {
     StopRecording,
     /// Enable or disable drone mode (continuous playback without MIDI).
     SetDroneEnabled(bool),
+    /// Use a custom envelope for new notes instead of the ADSR parameters.
+    SetEnvelope(Env),
+    /// Go back to the envelope built from the ADSR parameters.
+    ClearEnvelope,
+    /// Draw the loaded frame backwards: shapes in reverse order, each from end to start.
+    SetFrameReversed(bool),
+    /// Seed every noise source from this value, restarting their streams,
+    /// or with `None` let them run on unseeded.
+    SetSeed(Option<u64>),
+    /// Start a note from the on-screen or computer keyboard.
+    NoteOn { note: u8, velocity: f32 },
+    /// Release a note started with [`UiCommand::NoteOn`].
+    NoteOff { note: u8 },
+    /// Change the number of voices available for polyphony.
+    SetMaxVoices(usize),
+    /// Stop every voice immediately, without a release tail.
+    AllNotesOff,
+    /// Replace the parameters macro `idx` drives.
+    SetMacroTargets { idx: usize, targets: Vec<MacroTarget> },
+    /// Time each effect so the panel can show what it costs.
+    SetProfiling(bool),
+    /// Draw a blend of two frames in place of the loaded frame, picked by
+    /// the host-automatable Morph parameter. The morph is built here so the
+    /// audio thread doesn't resample the frames.
+    SetMorph(ShapeMorph),
+    /// Go back to playing the loaded frame after [`UiCommand::SetMorph`].
+    ClearMorph,
+    /// Trace a per-sample source straight to the output in place of the
+    /// synth, or go back to the synth with `None`. The source it replaces
+    /// comes back through [`EditorSharedState::retired_sources`].
+    SetSampleSource(Option<SampleSource>),
 }
 
 /// A lightweight, UI-readable mirror of one effect in the chain.
@@ -73,7 +200,12 @@ pub struct EffectSnapshot {
     pub id: String,
     pub name: String,
     pub enabled: bool,
+    pub solo: bool,
+    pub reset_on_note: bool,
     pub parameters: Vec<EffectParameter>,
+    /// Average processing time in nanoseconds per sample, summed over the
+    /// sounding voices; `None` unless profiling is on.
+    pub cost_ns: Option<f32>,
 }
 
 /// Audio device information for display in the UI.
@@ -81,6 +213,13 @@ pub struct EffectSnapshot {
 pub struct AudioInfo {
     pub sample_rate: f32,
     pub buffer_size: u32,
+    /// Frequency the beam is drawing the frame at, in Hz.
+    pub current_frequency: f32,
+    /// Levels of the X and Y outputs over the last block.
+    pub output_levels: [ChannelLevel; 2],
+    /// Shapes in the loaded frame, counting the whole frame while one
+    /// shape is isolated.
+    pub shape_count: usize,
 }
 
 /// Downsampled XY output buffer for the oscilloscope widget.
@@ -107,8 +246,32 @@ impl Default for VisBuffer {
 /// All shared data passed from the plugin to the editor.
 pub struct EditorSharedState {
     pub command_tx: Sender<UiCommand>,
+    /// Frames for the audio thread to draw, sent straight from the UI so a
+    /// full queue never holds up `process()`.
+    pub frame_tx: Sender<Vec<Box<dyn Shape>>>,
+    /// Sample sources the audio thread has replaced, to be freed here
+    /// rather than in `process()`.
+    pub retired_sources: Receiver<SampleSource>,
+    /// The last whole frame sent from the editor, which the shape stepper
+    /// trims down to one shape.
+    pub loaded_frame: Arc<Mutex<Vec<Box<dyn Shape>>>>,
     pub effect_snapshots: Arc<Mutex<Vec<EffectSnapshot>>>,
     pub vis_buffer: Arc<Mutex<VisBuffer>>,
     pub current_project_path: Arc<Mutex<Option<PathBuf>>>,
     pub audio_info: Arc<Mutex<AudioInfo>>,
+    /// Lua slider values, written by the UI and passed to a loaded script each block.
+    pub lua_sliders: Arc<Mutex<[f64; NUM_LUA_SLIDERS]>>,
+    /// Custom envelope drawn in the envelope editor; `None` uses the ADSR parameters.
+    pub envelope: Arc<Mutex<Option<Env>>>,
+    /// Whether the loaded frame is drawn backwards.
+    pub frame_reversed: Arc<Mutex<bool>>,
+    /// Seed for the noise sources, so a patch renders the same every time.
+    pub seed: Arc<Mutex<Option<u64>>>,
+    /// Whether the effect chain is bypassed.
+    pub effects_bypassed: Arc<Mutex<bool>>,
+    /// Number of voices the synth plays with.
+    pub voice_count: Arc<Mutex<usize>>,
+    /// Macro values and targets. The UI edits them and the audio thread
+    /// writes back values the host automates.
+    pub macros: Arc<Mutex<[MacroDefinition; NUM_MACROS]>>,
 }
@@ -35,7 +35,7 @@ pub async fn start_blender_server(
         match accept {
             Ok((stream, peer)) => {
                 log::info!("Blender client connected: {}", peer);
-                let sink_clone = FrameSink::new(sink.sender());
+                let sink_clone = sink.clone();
                 let shutdown_clone = shutdown.clone();
                 tokio::spawn(async move {
                     if let Err(e) = handle_blender_connection(stream, sink_clone, shutdown_clone).await {
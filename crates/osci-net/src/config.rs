@@ -4,6 +4,15 @@ pub struct NetConfig {
     pub blender_port: u16,
     /// WebSocket port for JSON shape streaming.
     pub ws_port: u16,
+    /// TCP port for JSON-Lines point streaming (plain or HTTP POST).
+    pub jsonl_port: u16,
+    /// Number of streamed JSON-Lines points batched into each frame.
+    pub jsonl_points_per_frame: usize,
+    /// Rescale incoming frames that stray outside [-1, 1] to fit, centred.
+    pub normalize_frames: bool,
+    /// Publish the scope as a shared texture under this name; `None` disables
+    /// it. Defaults to on only when built with the `shared-texture` feature.
+    pub shared_texture_name: Option<String>,
     /// Bind address.
     pub bind_addr: String,
 }
@@ -13,6 +22,10 @@ impl Default for NetConfig {
         Self {
             blender_port: 51677,
             ws_port: 51678,
+            jsonl_port: 51679,
+            jsonl_points_per_frame: 256,
+            normalize_frames: false,
+            shared_texture_name: cfg!(feature = "shared-texture").then(|| "rusci-render".to_string()),
             bind_addr: "127.0.0.1".to_string(),
         }
     }
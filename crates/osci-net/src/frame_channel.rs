@@ -1,24 +1,176 @@
-use crossbeam::channel::Sender;
-use osci_core::shape::Shape;
+use crossbeam::channel::{Receiver, Sender};
+use osci_core::shape::{normalize_shapes_centered, shapes_bounds, Shape};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Minimum time between warnings about dropped shapes.
+const WARN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Points along each shape checked for non-finite coordinates.
+const CHECK_POINTS: [f32; 3] = [0.0, 0.5, 1.0];
 
 /// Wraps a crossbeam sender so network servers can push shape frames
 /// to the audio thread without blocking.
+///
+/// Optionally holds the receiving end of the audio thread's recycling
+/// channel (`ShapeSound::recycler`), so frames can be built in vectors the
+/// audio thread has finished with rather than freshly allocated ones.
+///
+/// Every frame is checked on the way through: shapes with NaN or infinite
+/// coordinates are dropped so they can't reach the synth, with a warning
+/// logged at most once every few seconds.
+#[derive(Clone)]
 pub struct FrameSink {
     tx: Sender<Vec<Box<dyn Shape>>>,
+    recycled: Option<Receiver<Vec<Box<dyn Shape>>>>,
+    normalize: bool,
+    warnings: Arc<DropWarnings>,
+}
+
+/// Shapes dropped since the last warning, shared between clones of a sink.
+#[derive(Default)]
+struct DropWarnings {
+    dropped: AtomicUsize,
+    last_warning: Mutex<Option<Instant>>,
+}
+
+impl DropWarnings {
+    fn record(&self, dropped: usize) {
+        self.dropped.fetch_add(dropped, Ordering::Relaxed);
+        let Ok(mut last) = self.last_warning.lock() else {
+            return;
+        };
+        if last.is_some_and(|t| t.elapsed() < WARN_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+        let total = self.dropped.swap(0, Ordering::Relaxed);
+        log::warn!("Dropped {} incoming shapes with non-finite coordinates", total);
+    }
 }
 
 impl FrameSink {
     pub fn new(tx: Sender<Vec<Box<dyn Shape>>>) -> Self {
-        Self { tx }
+        Self {
+            tx,
+            recycled: None,
+            normalize: false,
+            warnings: Arc::default(),
+        }
+    }
+
+    /// Create a sink that reuses frames returned on `recycled`.
+    pub fn with_recycling(
+        tx: Sender<Vec<Box<dyn Shape>>>,
+        recycled: Receiver<Vec<Box<dyn Shape>>>,
+    ) -> Self {
+        Self {
+            recycled: Some(recycled),
+            ..Self::new(tx)
+        }
+    }
+
+    /// Scale frames that stray outside [-1, 1] back into it, centred.
+    /// Frames already in range are left alone.
+    pub fn with_normalization(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Get an empty frame to fill, reusing a recycled vector's capacity
+    /// when one is available. Only the vector is reused: the shapes pushed
+    /// into it are still new boxes. Dropping the old shapes here keeps
+    /// their deallocation off the audio thread.
+    pub fn take_buffer(&self) -> Vec<Box<dyn Shape>> {
+        match self.recycled.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            Some(mut frame) => {
+                frame.clear();
+                frame
+            }
+            None => Vec::new(),
+        }
     }
 
     /// Non-blocking send. Returns `true` if the frame was accepted.
-    pub fn send(&self, frame: Vec<Box<dyn Shape>>) -> bool {
+    ///
+    /// The frame is sanitized first; a frame left with no shapes is still
+    /// sent, so the scope goes blank rather than freezing on the last one.
+    pub fn send(&self, mut frame: Vec<Box<dyn Shape>>) -> bool {
+        self.sanitize(&mut frame);
         self.tx.try_send(frame).is_ok()
     }
 
+    /// Drop shapes with non-finite coordinates and, if enabled, normalize
+    /// out-of-range frames.
+    fn sanitize(&self, frame: &mut Vec<Box<dyn Shape>>) {
+        let before = frame.len();
+        frame.retain(|shape| is_finite_shape(shape.as_ref()));
+        if frame.len() < before {
+            self.warnings.record(before - frame.len());
+        }
+
+        if self.normalize {
+            let out_of_range = shapes_bounds(frame).is_some_and(|(min, max)| {
+                min.x < -1.0 || min.y < -1.0 || max.x > 1.0 || max.y > 1.0
+            });
+            if out_of_range {
+                normalize_shapes_centered(frame);
+            }
+        }
+    }
+
     /// Clone the underlying sender for use in spawned tasks.
     pub fn sender(&self) -> Sender<Vec<Box<dyn Shape>>> {
         self.tx.clone()
     }
 }
+
+
+fn is_finite_shape(shape: &dyn Shape) -> bool {
+    shape.length().is_finite()
+        && CHECK_POINTS.iter().all(|&t| {
+            let p = shape.next_vector(t);
+            p.x.is_finite() && p.y.is_finite() && p.z.is_finite()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam::channel::unbounded;
+    use osci_core::Line;
+
+    #[test]
+    fn test_send_drops_non_finite_shapes() {
+        let (tx, rx) = unbounded();
+        let sink = FrameSink::new(tx);
+        let frame: Vec<Box<dyn Shape>> = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 0.5, 0.5)),
+            Box::new(Line::new_2d(f32::NAN, 0.0, 1.0, 1.0)),
+            Box::new(Line::new_2d(0.0, f32::INFINITY, 1.0, 1.0)),
+        ];
+        assert!(sink.send(frame));
+
+        let received = rx.try_recv().unwrap();
+        assert_eq!(received.len(), 1);
+        let end = received[0].next_vector(1.0);
+        assert_eq!((end.x, end.y), (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_normalization_fits_out_of_range_frames() {
+        let (tx, rx) = unbounded();
+        let sink = FrameSink::new(tx).with_normalization(true);
+
+        sink.send(vec![Box::new(Line::new_2d(0.0, 0.0, 100.0, 0.0))]);
+        let wide = rx.try_recv().unwrap();
+        let (start, end) = (wide[0].next_vector(0.0), wide[0].next_vector(1.0));
+        assert_eq!((start.x, end.x), (-1.0, 1.0));
+
+        // Frames already in range pass through untouched
+        sink.send(vec![Box::new(Line::new_2d(0.0, 0.0, 0.5, 0.0))]);
+        let small = rx.try_recv().unwrap();
+        assert_eq!(small[0].next_vector(1.0).x, 0.5);
+    }
+}
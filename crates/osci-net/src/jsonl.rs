@@ -0,0 +1,287 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use osci_core::shape::{Line, PointShape, Shape};
+use osci_core::Point;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+use crate::config::NetConfig;
+use crate::frame_channel::FrameSink;
+
+const MAX_BODY_SIZE: usize = 64 * 1024 * 1024; // 64 MB
+
+/// Longest line read from a stream or HTTP header, so a client that never
+/// sends a newline can't make the server buffer without limit.
+const MAX_LINE_LENGTH: usize = 64 * 1024;
+
+/// One point of the JSON-Lines protocol: `{"x":0.1,"y":-0.5,"z":0.0}`.
+#[derive(serde::Deserialize)]
+struct JsonlPoint {
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    z: f32,
+}
+
+/// Batches streamed points into frames.
+///
+/// Each frame is the polyline through `points_per_frame` consecutive points,
+/// so the beam follows the points in the order they arrived.
+pub struct JsonlFramer {
+    points_per_frame: usize,
+    pending: Vec<Point>,
+}
+
+impl JsonlFramer {
+    pub fn new(points_per_frame: usize) -> Self {
+        let points_per_frame = points_per_frame.max(1);
+        Self {
+            points_per_frame,
+            pending: Vec::with_capacity(points_per_frame),
+        }
+    }
+
+    /// Parse one line, returning a frame once a full batch has arrived.
+    ///
+    /// Blank lines are skipped. Malformed lines and non-finite coordinates
+    /// are logged and dropped without affecting the batch.
+    pub fn push_line(&mut self, line: &str) -> Option<Vec<Box<dyn Shape>>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        let point: JsonlPoint = match serde_json::from_str(line) {
+            Ok(p) => p,
+            Err(e) => {
+                log::warn!("JSONL parse error: {}", e);
+                return None;
+            }
+        };
+        if ![point.x, point.y, point.z].iter().all(|v| v.is_finite()) {
+            log::warn!("JSONL point has non-finite coordinates: {}", line);
+            return None;
+        }
+
+        self.pending.push(Point::new(point.x, point.y, point.z));
+        if self.pending.len() >= self.points_per_frame {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    /// Turn whatever points are pending into a frame, if there are any.
+    pub fn flush(&mut self) -> Option<Vec<Box<dyn Shape>>> {
+        let frame: Vec<Box<dyn Shape>> = match self.pending.as_slice() {
+            [] => return None,
+            [p] => vec![Box::new(PointShape::new(*p))],
+            points => points
+                .windows(2)
+                .map(|w| {
+                    Box::new(Line::new_3d(w[0].x, w[0].y, w[0].z, w[1].x, w[1].y, w[1].z))
+                        as Box<dyn Shape>
+                })
+                .collect(),
+        };
+        self.pending.clear();
+        Some(frame)
+    }
+}
+
+/// Start the JSON-Lines point server. Blocks until shutdown is signalled.
+///
+/// Clients either stream newline-delimited points over a plain TCP
+/// connection, or POST them as the body of an HTTP request to any path.
+pub async fn start_jsonl_server(
+    config: &NetConfig,
+    sink: FrameSink,
+    shutdown: Arc<AtomicBool>,
+) -> Result<(), String> {
+    let addr = format!("{}:{}", config.bind_addr, config.jsonl_port);
+    let listener = TcpListener::bind(&addr)
+        .await
+        .map_err(|e| format!("JSONL bind failed on {}: {}", addr, e))?;
+
+    log::info!("JSONL point server listening on {}", addr);
+
+    loop {
+        if shutdown.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let accept = tokio::select! {
+            result = listener.accept() => result,
+            _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => continue,
+        };
+
+        match accept {
+            Ok((stream, peer)) => {
+                log::info!("JSONL client connected: {}", peer);
+                let sink_clone = sink.clone();
+                let shutdown_clone = shutdown.clone();
+                let points_per_frame = config.jsonl_points_per_frame;
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        handle_jsonl_connection(stream, sink_clone, shutdown_clone, points_per_frame).await
+                    {
+                        log::warn!("JSONL connection error: {}", e);
+                    }
+                    log::info!("JSONL client disconnected: {}", peer);
+                });
+            }
+            Err(e) => {
+                log::warn!("JSONL accept error: {}", e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_jsonl_connection(
+    stream: tokio::net::TcpStream,
+    sink: FrameSink,
+    shutdown: Arc<AtomicBool>,
+    points_per_frame: usize,
+) -> Result<(), String> {
+    let mut reader = BufReader::new(stream);
+    let mut framer = JsonlFramer::new(points_per_frame);
+    let mut line = String::new();
+
+    let read = |e: std::io::Error| format!("Read error: {}", e);
+
+    if read_line(&mut reader, &mut line).await? == 0 {
+        return Ok(());
+    }
+
+    if line.starts_with("POST ") {
+        // Skip the headers, noting the body length
+        let mut content_length = None;
+        loop {
+            line.clear();
+            if read_line(&mut reader, &mut line).await? == 0 {
+                return Err("Connection closed inside HTTP headers".to_string());
+            }
+            let header = line.trim();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse::<usize>().ok();
+                }
+            }
+        }
+
+        let length = content_length.ok_or("HTTP request without Content-Length")?;
+        if length > MAX_BODY_SIZE {
+            return Err(format!("Body too large: {} bytes", length));
+        }
+        let mut body = vec![0u8; length];
+        reader.read_exact(&mut body).await.map_err(read)?;
+
+        for line in String::from_utf8_lossy(&body).lines() {
+            if let Some(frame) = framer.push_line(line) {
+                sink.send(frame);
+            }
+        }
+        if let Some(frame) = framer.flush() {
+            sink.send(frame);
+        }
+
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await
+            .map_err(|e| format!("Write error: {}", e))?;
+        return Ok(());
+    }
+
+    loop {
+        if let Some(frame) = framer.push_line(&line) {
+            sink.send(frame);
+        }
+
+        if shutdown.load(Ordering::Relaxed) {
+            break;
+        }
+
+        line.clear();
+        if read_line(&mut reader, &mut line).await? == 0 {
+            break;
+        }
+    }
+
+    // Send the tail of the stream as a short final frame
+    if let Some(frame) = framer.flush() {
+        sink.send(frame);
+    }
+
+    Ok(())
+}
+
+/// Read one line of at most [`MAX_LINE_LENGTH`] bytes into `line`,
+/// returning how many bytes were read: 0 at the end of the stream.
+async fn read_line(reader: &mut (impl AsyncBufRead + Unpin), line: &mut String) -> Result<usize, String> {
+    let read = reader
+        .take(MAX_LINE_LENGTH as u64)
+        .read_line(line)
+        .await
+        .map_err(|e| format!("Read error: {}", e))?;
+    if read == MAX_LINE_LENGTH && !line.ends_with('\n') {
+        return Err(format!("Line longer than {} bytes", MAX_LINE_LENGTH));
+    }
+    Ok(read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batches_points_into_frames() {
+        let mut framer = JsonlFramer::new(4);
+        let input = [
+            r#"{"x":0.0,"y":0.0,"z":0.0}"#,
+            r#"{"x":0.5,"y":0.0}"#,
+            "not json",
+            r#"{"x":0.5,"y":"high"}"#,
+            "",
+            r#"{"x":0.5,"y":0.5,"z":0.25}"#,
+            r#"{"x":0.0,"y":0.5}"#,
+            r#"{"x":-0.5,"y":-0.5}"#,
+        ];
+
+        let frames: Vec<_> = input.iter().filter_map(|line| framer.push_line(line)).collect();
+        assert_eq!(frames.len(), 1);
+
+        // Four points make a three-segment polyline
+        let frame = &frames[0];
+        assert_eq!(frame.len(), 3);
+        let end = frame[1].next_vector(1.0);
+        assert_eq!((end.x, end.y, end.z), (0.5, 0.5, 0.25));
+
+        // The fifth point is left over until the stream ends
+        let tail = framer.flush().unwrap();
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0].shape_type(), "Point");
+        assert!(framer.flush().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_overlong_line_is_rejected() {
+        let mut input = vec![b'1'; MAX_LINE_LENGTH + 10];
+        input.push(b'\n');
+        let mut reader = BufReader::new(&input[..]);
+        let mut line = String::new();
+        assert!(read_line(&mut reader, &mut line).await.is_err());
+        assert!(line.len() <= MAX_LINE_LENGTH);
+
+        let mut reader = BufReader::new(&b"{\"x\":0.5,\"y\":0.0}\n"[..]);
+        line.clear();
+        assert_eq!(read_line(&mut reader, &mut line).await, Ok(18));
+        assert_eq!(read_line(&mut reader, &mut line).await, Ok(0));
+    }
+}
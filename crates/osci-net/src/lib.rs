@@ -1,6 +1,7 @@
 pub mod blender;
 pub mod config;
 pub mod frame_channel;
+pub mod jsonl;
 pub mod server;
 pub mod shared_texture;
 pub mod websocket;
@@ -8,4 +9,4 @@ pub mod websocket;
 pub use config::NetConfig;
 pub use frame_channel::FrameSink;
 pub use server::NetServer;
-pub use shared_texture::{SharedTexture, create_shared_texture};
+pub use shared_texture::{SharedFrame, SharedTexture, create_shared_texture};
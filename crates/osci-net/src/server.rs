@@ -1,3 +1,4 @@
+use std::future::Future;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
@@ -5,17 +6,19 @@ use std::thread::{self, JoinHandle};
 use crate::blender::start_blender_server;
 use crate::config::NetConfig;
 use crate::frame_channel::FrameSink;
+use crate::jsonl::start_jsonl_server;
 use crate::websocket::start_ws_server;
 
-/// Orchestrates Blender TCP and WebSocket servers on a background thread.
+/// Orchestrates Blender TCP, WebSocket and JSON-Lines servers on a background thread.
 pub struct NetServer {
     shutdown: Arc<AtomicBool>,
     thread: Option<JoinHandle<()>>,
 }
 
 impl NetServer {
-    /// Start both network servers on a background thread with a dedicated tokio runtime.
+    /// Start all network servers on a background thread with a dedicated tokio runtime.
     pub fn start(config: NetConfig, sink: FrameSink) -> Self {
+        let sink = sink.with_normalization(config.normalize_frames);
         let shutdown = Arc::new(AtomicBool::new(false));
         let shutdown_clone = shutdown.clone();
 
@@ -35,22 +38,23 @@ impl NetServer {
                 };
 
                 rt.block_on(async {
-                    let blender_sink = FrameSink::new(sink.sender());
-                    let ws_sink = FrameSink::new(sink.sender());
-                    let shutdown_b = shutdown_clone.clone();
-                    let shutdown_w = shutdown_clone.clone();
-
-                    tokio::select! {
-                        result = start_blender_server(&config, blender_sink, shutdown_b) => {
-                            if let Err(e) = result {
-                                log::error!("Blender server error: {}", e);
-                            }
-                        }
-                        result = start_ws_server(&config, ws_sink, shutdown_w) => {
-                            if let Err(e) = result {
-                                log::error!("WebSocket server error: {}", e);
-                            }
-                        }
+                    let config = Arc::new(config);
+                    let servers = [
+                        spawn_server("Blender", {
+                            let (config, sink, shutdown) = (config.clone(), sink.clone(), shutdown_clone.clone());
+                            async move { start_blender_server(&config, sink, shutdown).await }
+                        }),
+                        spawn_server("WebSocket", {
+                            let (config, sink, shutdown) = (config.clone(), sink.clone(), shutdown_clone.clone());
+                            async move { start_ws_server(&config, sink, shutdown).await }
+                        }),
+                        spawn_server("JSONL", {
+                            let (config, sink, shutdown) = (config.clone(), sink.clone(), shutdown_clone.clone());
+                            async move { start_jsonl_server(&config, sink, shutdown).await }
+                        }),
+                    ];
+                    for server in servers {
+                        let _ = server.await;
                     }
                 });
             })
@@ -71,6 +75,20 @@ impl NetServer {
     }
 }
 
+/// Run one server on its own task, logging the error it stops with, so a
+/// server that fails (say, because its port is taken) leaves the others
+/// running.
+fn spawn_server(
+    name: &'static str,
+    server: impl Future<Output = Result<(), String>> + Send + 'static,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            log::error!("{} server error: {}", name, e);
+        }
+    })
+}
+
 impl Drop for NetServer {
     fn drop(&mut self) {
         self.stop();
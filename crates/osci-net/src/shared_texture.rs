@@ -1,3 +1,31 @@
+//! Shared texture output, so other apps can composite the live scope.
+//!
+//! Platform support:
+//!
+//! - **Readback** (any platform, `shared-texture` feature): each frame is
+//!   read back from the GPU into a [`SharedFrame`] that in-process consumers
+//!   (NDI senders, recorders, tests) fetch with
+//!   [`SharedTexture::latest_frame`]. This costs a GPU→CPU copy per frame,
+//!   made asynchronously so frames arrive a couple of frames late. Nothing
+//!   outside this process can see them.
+//! - **Spout** (Windows) and **Syphon** (macOS): not implemented yet; these
+//!   would hand the texture to other processes without the copy.
+//!
+//! Without the feature, [`create_shared_texture`] returns [`NoOpSharedTexture`].
+
+#[cfg(feature = "shared-texture")]
+use glow::HasContext;
+#[cfg(feature = "shared-texture")]
+use std::sync::Mutex;
+
+/// One frame of RGBA8 pixels, rows ordered bottom to top as OpenGL reads them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedFrame {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
 /// Trait for shared texture output (Spout on Windows, Syphon on macOS).
 pub trait SharedTexture {
     fn init(&mut self, gl: &glow::Context, name: &str) -> Result<(), String>;
@@ -12,6 +40,11 @@ pub trait SharedTexture {
     fn is_available() -> bool
     where
         Self: Sized;
+
+    /// The most recently sent frame, for implementations that keep a CPU copy.
+    fn latest_frame(&self) -> Option<SharedFrame> {
+        None
+    }
 }
 
 /// No-op stub for unsupported platforms.
@@ -39,7 +72,221 @@ impl SharedTexture for NoOpSharedTexture {
     }
 }
 
+/// Frames of readback in flight at once. Each frame's pixels are picked up
+/// this many frames after they're requested, by which time the GPU has
+/// normally finished copying them.
+#[cfg(feature = "shared-texture")]
+const READBACK_BUFFERS: usize = 2;
+
+/// Portable shared texture that reads each frame back into CPU memory.
+///
+/// Pixels are copied into pixel buffer objects and fetched a couple of
+/// frames later, so sending a frame never waits for the GPU. If a copy
+/// still isn't finished when its buffer comes round again, that frame is
+/// skipped rather than stalling.
+#[cfg(feature = "shared-texture")]
+pub struct ReadbackSharedTexture {
+    fbo: Option<glow::Framebuffer>,
+    readbacks: Vec<Readback>,
+    next: usize,
+    frame: Mutex<Option<SharedFrame>>,
+}
+
+/// A pixel buffer and the copy into it the GPU may still be working on.
+#[cfg(feature = "shared-texture")]
+struct Readback {
+    buffer: glow::Buffer,
+    width: u32,
+    height: u32,
+    fence: Option<glow::Fence>,
+}
+
+// SAFETY: the fence is a GL sync object handle. It's only ever passed back
+// to the GL context that created it, from whichever thread has that context
+// current, so moving it between threads along with the texture is sound.
+#[cfg(feature = "shared-texture")]
+unsafe impl Send for Readback {}
+
+#[cfg(feature = "shared-texture")]
+impl ReadbackSharedTexture {
+    pub fn new() -> Self {
+        Self {
+            fbo: None,
+            readbacks: Vec::new(),
+            next: 0,
+            frame: Mutex::new(None),
+        }
+    }
+
+    /// Store `pixels` (RGBA8, `width * height * 4` bytes) as the latest frame.
+    pub fn publish(&self, width: u32, height: u32, pixels: Vec<u8>) -> Result<(), String> {
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected {
+            return Err(format!(
+                "Shared frame is {} bytes, expected {} for {}x{}",
+                pixels.len(),
+                expected,
+                width,
+                height
+            ));
+        }
+        if let Ok(mut frame) = self.frame.lock() {
+            *frame = Some(SharedFrame { width, height, pixels });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "shared-texture")]
+impl Readback {
+    /// Move the copied pixels into `frame` if the GPU has finished the
+    /// copy. Returns `false` if it's still in progress.
+    fn collect(&mut self, gl: &glow::Context, frame: &Mutex<Option<SharedFrame>>) -> bool {
+        let Some(fence) = self.fence else {
+            return true;
+        };
+        let status = unsafe { gl.client_wait_sync(fence, 0, 0) };
+        if status != glow::ALREADY_SIGNALED && status != glow::CONDITION_SATISFIED {
+            return false;
+        }
+        unsafe { gl.delete_sync(fence) };
+        self.fence = None;
+
+        let Ok(mut frame) = frame.lock() else {
+            return true;
+        };
+        // Reuse the previous frame's storage when it's the same size
+        let size = self.width as usize * self.height as usize * 4;
+        let mut pixels = match frame.take() {
+            Some(previous) if previous.pixels.len() == size => previous.pixels,
+            _ => vec![0u8; size],
+        };
+        unsafe {
+            gl.bind_buffer(glow::PIXEL_PACK_BUFFER, Some(self.buffer));
+            gl.get_buffer_sub_data(glow::PIXEL_PACK_BUFFER, 0, &mut pixels);
+            gl.bind_buffer(glow::PIXEL_PACK_BUFFER, None);
+        }
+        *frame = Some(SharedFrame {
+            width: self.width,
+            height: self.height,
+            pixels,
+        });
+        true
+    }
+}
+
+#[cfg(feature = "shared-texture")]
+impl Default for ReadbackSharedTexture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "shared-texture")]
+impl SharedTexture for ReadbackSharedTexture {
+    fn init(&mut self, gl: &glow::Context, name: &str) -> Result<(), String> {
+        if self.fbo.is_none() {
+            self.fbo = Some(unsafe { gl.create_framebuffer()? });
+        }
+        while self.readbacks.len() < READBACK_BUFFERS {
+            let buffer = unsafe { gl.create_buffer()? };
+            self.readbacks.push(Readback {
+                buffer,
+                width: 0,
+                height: 0,
+                fence: None,
+            });
+        }
+        log::info!("Shared texture '{}' publishing by readback", name);
+        Ok(())
+    }
+
+    fn send_texture(
+        &mut self,
+        gl: &glow::Context,
+        texture: glow::Texture,
+        width: u32,
+        height: u32,
+    ) -> Result<(), String> {
+        let fbo = self.fbo.ok_or("Shared texture used before init")?;
+        let readback = self
+            .readbacks
+            .get_mut(self.next)
+            .ok_or("Shared texture used before init")?;
+
+        // The oldest copy comes first; skip this frame if it isn't done yet
+        if !readback.collect(gl, &self.frame) {
+            return Ok(());
+        }
+
+        unsafe {
+            gl.bind_buffer(glow::PIXEL_PACK_BUFFER, Some(readback.buffer));
+            if (readback.width, readback.height) != (width, height) {
+                let size = width as usize * height as usize * 4;
+                gl.buffer_data_size(glow::PIXEL_PACK_BUFFER, size as i32, glow::STREAM_READ);
+                readback.width = width;
+                readback.height = height;
+            }
+
+            let previous = gl.get_parameter_framebuffer(glow::READ_FRAMEBUFFER_BINDING);
+            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(fbo));
+            gl.framebuffer_texture_2d(
+                glow::READ_FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+            gl.read_pixels(
+                0,
+                0,
+                width as i32,
+                height as i32,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelPackData::BufferOffset(0),
+            );
+            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, previous);
+            gl.bind_buffer(glow::PIXEL_PACK_BUFFER, None);
+            readback.fence = Some(gl.fence_sync(glow::SYNC_GPU_COMMANDS_COMPLETE, 0)?);
+        }
+
+        self.next = (self.next + 1) % self.readbacks.len();
+        Ok(())
+    }
+
+    fn shutdown(&mut self, gl: &glow::Context) {
+        for readback in self.readbacks.drain(..) {
+            unsafe {
+                if let Some(fence) = readback.fence {
+                    gl.delete_sync(fence);
+                }
+                gl.delete_buffer(readback.buffer);
+            }
+        }
+        self.next = 0;
+        if let Some(fbo) = self.fbo.take() {
+            unsafe { gl.delete_framebuffer(fbo) };
+        }
+    }
+
+    fn is_available() -> bool {
+        true
+    }
+
+    fn latest_frame(&self) -> Option<SharedFrame> {
+        self.frame.lock().ok().and_then(|f| f.clone())
+    }
+}
+
+/// Create the appropriate shared texture implementation for the current platform.
+#[cfg(feature = "shared-texture")]
+pub fn create_shared_texture() -> Box<dyn SharedTexture + Send> {
+    Box::new(ReadbackSharedTexture::new())
+}
+
 /// Create the appropriate shared texture implementation for the current platform.
+#[cfg(not(feature = "shared-texture"))]
 pub fn create_shared_texture() -> Box<dyn SharedTexture + Send> {
     Box::new(NoOpSharedTexture)
 }
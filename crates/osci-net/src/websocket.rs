@@ -34,7 +34,7 @@ pub async fn start_ws_server(
         match accept {
             Ok((stream, peer)) => {
                 log::info!("WebSocket client connected: {}", peer);
-                let sink_clone = FrameSink::new(sink.sender());
+                let sink_clone = sink.clone();
                 let shutdown_clone = shutdown.clone();
                 tokio::spawn(async move {
                     if let Err(e) = handle_ws_connection(stream, sink_clone, shutdown_clone).await {
@@ -128,14 +128,11 @@ fn handle_json_message(text: &str, sink: &FrameSink) {
 
     match msg.msg_type.as_str() {
         "shapes" => {
-            let shapes: Vec<Box<dyn osci_core::shape::Shape>> = msg
-                .lines
-                .iter()
-                .map(|l| {
-                    Box::new(osci_core::shape::Line::new_2d(l.x0, l.y0, l.x1, l.y1))
-                        as Box<dyn osci_core::shape::Shape>
-                })
-                .collect();
+            let mut shapes = sink.take_buffer();
+            shapes.extend(msg.lines.iter().map(|l| {
+                Box::new(osci_core::shape::Line::new_2d(l.x0, l.y0, l.x1, l.y1))
+                    as Box<dyn osci_core::shape::Shape>
+            }));
             sink.send(shapes);
         }
         "ping" => {
@@ -0,0 +1,45 @@
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::bounded;
+use osci_net::{FrameSink, NetConfig, NetServer};
+
+/// A port nothing is listening on right now.
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port()
+}
+
+#[test]
+fn servers_keep_running_when_one_port_is_taken() {
+    // Hold the Blender port so that server fails to bind
+    let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = NetConfig {
+        blender_port: taken.local_addr().unwrap().port(),
+        ws_port: free_port(),
+        jsonl_port: free_port(),
+        jsonl_points_per_frame: 2,
+        shared_texture_name: None,
+        ..NetConfig::default()
+    };
+    let jsonl_port = config.jsonl_port;
+    let (tx, rx) = bounded(4);
+    let mut server = NetServer::start(config, FrameSink::new(tx));
+
+    // The JSONL server still comes up and accepts points
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut stream = loop {
+        match TcpStream::connect(("127.0.0.1", jsonl_port)) {
+            Ok(stream) => break stream,
+            Err(_) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(20)),
+            Err(e) => panic!("JSONL server never came up: {e}"),
+        }
+    };
+    stream.write_all(b"{\"x\":0.0,\"y\":0.0}\n{\"x\":0.5,\"y\":0.5}\n").unwrap();
+
+    let frame = rx.recv_timeout(Duration::from_secs(5)).expect("no frame received");
+    assert_eq!(frame.len(), 1);
+
+    drop(stream);
+    server.stop();
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "shared-texture")]
+
+use glow::HasContext;
+use osci_core::shape::{Line, Shape};
+use osci_net::shared_texture::ReadbackSharedTexture;
+use osci_net::{create_shared_texture, SharedTexture};
+use osci_visualizer::offline::{sample_frame, HeadlessContext, OFFLINE_SAMPLES_PER_FRAME};
+use osci_visualizer::{OsciRenderer, VisualiserSettings};
+
+#[test]
+fn created_texture_starts_empty() {
+    let texture = create_shared_texture();
+    assert!(texture.latest_frame().is_none());
+    assert!(ReadbackSharedTexture::is_available());
+}
+
+#[test]
+fn published_frame_reads_back_unchanged() {
+    // 2x2 frame: red, green, blue, white
+    let pixels = vec![
+        255, 0, 0, 255, 0, 255, 0, 255, //
+        0, 0, 255, 255, 255, 255, 255, 255,
+    ];
+    let texture = ReadbackSharedTexture::new();
+    texture.publish(2, 2, pixels.clone()).unwrap();
+
+    let frame = texture.latest_frame().unwrap();
+    assert_eq!((frame.width, frame.height), (2, 2));
+    assert_eq!(frame.pixels, pixels);
+}
+
+#[test]
+fn publish_rejects_wrong_size() {
+    let texture = ReadbackSharedTexture::new();
+    assert!(texture.publish(2, 2, vec![0; 12]).is_err());
+    assert!(texture.latest_frame().is_none());
+}
+
+#[test]
+fn scope_reads_back_as_shown_on_screen() {
+    const SIZE: u32 = 32;
+    let context = HeadlessContext::new(SIZE, SIZE).expect("headless GL context");
+    let gl = context.gl();
+
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.5, -0.5, 0.5, 0.5))];
+    let (x, y) = sample_frame(&osci_core::Frame::new(shapes), OFFLINE_SAMPLES_PER_FRAME);
+    let settings = VisualiserSettings {
+        shared_output: true,
+        noise: 0.0,
+        ..VisualiserSettings::default()
+    };
+    let mut renderer = OsciRenderer::new(gl);
+    renderer.set_frame_interval(Some(1.0 / 60.0));
+
+    let mut texture = ReadbackSharedTexture::new();
+    texture.init(gl, "test").unwrap();
+
+    // Frames arrive a few sends late, once the GPU has finished copying them
+    let mut screen = Vec::new();
+    for _ in 0..8 {
+        unsafe {
+            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+            gl.clear(glow::COLOR_BUFFER_BIT);
+        }
+        renderer.render(gl, &x, &y, 0.0, &settings, [0, 0, SIZE as i32, SIZE as i32]);
+        let (shared, width, height) = renderer.shared_output().expect("shared output");
+        assert_eq!((width, height), (SIZE, SIZE));
+        texture.send_texture(gl, shared, width, height).unwrap();
+        screen.push(renderer.capture_frame(gl, SIZE, SIZE));
+        unsafe { gl.finish() };
+    }
+    let frame = texture.latest_frame().expect("no frame read back");
+    assert_eq!((frame.width, frame.height), (SIZE, SIZE));
+
+    // It's one of the frames shown on screen, rows bottom to top
+    let row = SIZE as usize * 4;
+    let flipped: Vec<u8> = frame.pixels.chunks(row).rev().flatten().copied().collect();
+    assert!(flipped.iter().any(|&p| p > 0), "shared frame is blank");
+    let matches = |shown: &Vec<u8>| shown.iter().zip(&flipped).all(|(a, b)| a.abs_diff(*b) <= 1);
+    assert!(screen.iter().any(matches));
+
+    texture.shutdown(gl);
+    renderer.destroy(gl);
+}
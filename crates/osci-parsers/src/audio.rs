@@ -4,6 +4,8 @@ use symphonia::core::formats::FormatReader;
 use symphonia::core::io::MediaSourceStream;
 use symphonia::core::probe::Hint;
 
+use osci_core::Point;
+
 /// Decoded audio data with per-channel sample buffers.
 pub struct AudioData {
     /// Sample data indexed by channel: `samples[channel][sample_index]`.
@@ -16,6 +18,130 @@ pub struct AudioData {
     pub num_samples: usize,
 }
 
+impl AudioData {
+    /// The XY point at `index`: left channel → X, right channel → Y.
+    ///
+    /// Mono files drive both axes from the single channel. Out-of-range
+    /// indices return the origin.
+    pub fn point_at(&self, index: usize) -> Point {
+        let left = self
+            .samples
+            .first()
+            .and_then(|c| c.get(index))
+            .copied()
+            .unwrap_or(0.0);
+        let right = self
+            .samples
+            .get(1)
+            .and_then(|c| c.get(index))
+            .copied()
+            .unwrap_or(left);
+        Point::xy(left, right)
+    }
+}
+
+/// Plays decoded audio back as a looping per-sample XY point stream.
+///
+/// This is the classic oscilloscope-music path: a stereo file traced
+/// directly with left → X and right → Y. Playback is resampled to the
+/// output rate with linear interpolation.
+///
+/// With a loop crossfade of `n` samples the loop is `n` samples shorter than
+/// the file: its first `n` samples fade in over the `n` that follow the loop
+/// end, so the trace carries straight on across the seam instead of jumping.
+pub struct AudioSampleSource {
+    data: AudioData,
+    position: f64,
+    step: f64,
+    loop_crossfade_samples: usize,
+}
+
+impl AudioSampleSource {
+    /// Create a source that plays `data` at `output_sample_rate`.
+    pub fn new(data: AudioData, output_sample_rate: f64) -> Self {
+        let mut source = Self {
+            data,
+            position: 0.0,
+            step: 1.0,
+            loop_crossfade_samples: 0,
+        };
+        source.set_sample_rate(output_sample_rate);
+        source
+    }
+
+    /// Update the output sample rate (e.g. when the host changes it).
+    pub fn set_sample_rate(&mut self, output_sample_rate: f64) {
+        self.step = if output_sample_rate > 0.0 {
+            self.data.sample_rate as f64 / output_sample_rate
+        } else {
+            1.0
+        };
+    }
+
+    /// Crossfade the loop seam over `samples` file samples; 0 loops with a
+    /// hard cut. At most half the file is used.
+    pub fn set_loop_crossfade_samples(&mut self, samples: usize) {
+        self.loop_crossfade_samples = samples;
+        self.position %= self.loop_len().max(1) as f64;
+    }
+
+    /// Crossfade length as set, before limiting to half the file.
+    pub fn loop_crossfade_samples(&self) -> usize {
+        self.loop_crossfade_samples
+    }
+
+    fn crossfade_len(&self) -> usize {
+        self.loop_crossfade_samples.min(self.data.num_samples / 2)
+    }
+
+    fn loop_len(&self) -> usize {
+        self.data.num_samples - self.crossfade_len()
+    }
+
+    /// The point at `index` within the loop, blended with the samples past
+    /// the loop end during the crossfade.
+    fn loop_point(&self, index: usize) -> Point {
+        let fade = self.crossfade_len();
+        let head = self.data.point_at(index);
+        if index >= fade {
+            return head;
+        }
+        let tail = self.data.point_at(self.loop_len() + index);
+        let t = index as f32 / fade as f32;
+        Point::xy(tail.x + (head.x - tail.x) * t, tail.y + (head.y - tail.y) * t)
+    }
+
+    /// Produce the next output point, wrapping to the start at the end of the loop.
+    pub fn next_point(&mut self) -> Point {
+        let len = self.loop_len();
+        if len == 0 {
+            return Point::ZERO;
+        }
+
+        let index = self.position.floor() as usize;
+        let frac = (self.position - index as f64) as f32;
+        let a = self.loop_point(index);
+        let b = self.loop_point((index + 1) % len);
+        let point = Point::xy(a.x + (b.x - a.x) * frac, a.y + (b.y - a.y) * frac);
+
+        self.position += self.step;
+        if self.position >= len as f64 {
+            self.position %= len as f64;
+        }
+        point
+    }
+
+    /// Rewind playback to the start of the file.
+    pub fn reset(&mut self) {
+        self.position = 0.0;
+    }
+
+    /// The decoded audio being played.
+    pub fn data(&self) -> &AudioData {
+        &self.data
+    }
+}
+
 /// Decode audio file bytes into interleaved sample buffers.
 ///
 /// Supports any format that symphonia can probe (MP3, FLAC, WAV, OGG, etc.).
@@ -116,4 +242,81 @@ mod tests {
         let result = parse_audio(&[]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_stereo_maps_left_to_x_right_to_y() {
+        let audio = AudioData {
+            samples: vec![vec![0.25, -0.5], vec![0.75, 0.1]],
+            sample_rate: 44100,
+            num_channels: 2,
+            num_samples: 2,
+        };
+        let mut source = AudioSampleSource::new(audio, 44100.0);
+
+        let p = source.next_point();
+        assert!((p.x - 0.25).abs() < 1e-6);
+        assert!((p.y - 0.75).abs() < 1e-6);
+
+        let p = source.next_point();
+        assert!((p.x + 0.5).abs() < 1e-6);
+        assert!((p.y - 0.1).abs() < 1e-6);
+
+        // Loops back to the first sample
+        let p = source.next_point();
+        assert!((p.x - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mono_duplicates_to_both_axes() {
+        let audio = AudioData {
+            samples: vec![vec![0.4]],
+            sample_rate: 44100,
+            num_channels: 1,
+            num_samples: 1,
+        };
+        let p = audio.point_at(0);
+        assert_eq!(p.x, 0.4);
+        assert_eq!(p.y, 0.4);
+    }
+
+    #[test]
+    fn test_loop_crossfade_smooths_seam() {
+        // A ramp jumps from 1 back to -1 at the loop point
+        let ramp: Vec<f32> = (0..1000).map(|i| -1.0 + 2.0 * i as f32 / 999.0).collect();
+        let largest_step = |crossfade: usize| {
+            let audio = AudioData {
+                samples: vec![ramp.clone(), ramp.clone()],
+                sample_rate: 44100,
+                num_channels: 2,
+                num_samples: ramp.len(),
+            };
+            let mut source = AudioSampleSource::new(audio, 44100.0);
+            source.set_loop_crossfade_samples(crossfade);
+            let points: Vec<Point> = (0..3000).map(|_| source.next_point()).collect();
+            points
+                .windows(2)
+                .map(|w| (w[1].x - w[0].x).abs().max((w[1].y - w[0].y).abs()))
+                .fold(0.0f32, f32::max)
+        };
+
+        assert!(largest_step(0) > 1.9);
+        let smoothed = largest_step(200);
+        assert!(smoothed < 0.05, "largest step {smoothed}");
+    }
+
+    #[test]
+    fn test_resamples_to_output_rate() {
+        // File at half the output rate: every other output sample is interpolated
+        let audio = AudioData {
+            samples: vec![vec![0.0, 1.0], vec![0.0, -1.0]],
+            sample_rate: 22050,
+            num_channels: 2,
+            num_samples: 2,
+        };
+        let mut source = AudioSampleSource::new(audio, 44100.0);
+        source.next_point();
+        let p = source.next_point();
+        assert!((p.x - 0.5).abs() < 1e-6);
+        assert!((p.y + 0.5).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,106 @@
+//! Built-in calibration frames for lining up a real oscilloscope.
+//!
+//! Each pattern fills the [-1, 1] square exactly, so the scope's gain and
+//! position controls can be trimmed until it touches the graticule edges.
+
+use osci_core::shape::{CircleArc, Line, Shape};
+use osci_core::Frame;
+
+/// Half-sizes of the squares drawn by [`CalPattern::ConcentricSquares`].
+const SQUARE_SIZES: [f32; 4] = [0.25, 0.5, 0.75, 1.0];
+
+/// A calibration pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalPattern {
+    /// Horizontal and vertical lines through the centre, edge to edge.
+    Crosshair,
+    /// The outline of the full screen.
+    Box,
+    /// A circle touching all four edges.
+    Circle,
+    /// Squares at a quarter, half, three quarters and all of the screen.
+    ConcentricSquares,
+}
+
+impl CalPattern {
+    pub const ALL: [CalPattern; 4] = [
+        CalPattern::Crosshair,
+        CalPattern::Box,
+        CalPattern::Circle,
+        CalPattern::ConcentricSquares,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CalPattern::Crosshair => "Crosshair",
+            CalPattern::Box => "Full-Screen Box",
+            CalPattern::Circle => "Circle",
+            CalPattern::ConcentricSquares => "Concentric Squares",
+        }
+    }
+}
+
+/// Generate the frame for a calibration pattern.
+pub fn calibration_frame(pattern: CalPattern) -> Frame {
+    let shapes: Vec<Box<dyn Shape>> = match pattern {
+        CalPattern::Crosshair => vec![
+            Box::new(Line::new_2d(-1.0, 0.0, 1.0, 0.0)),
+            Box::new(Line::new_2d(0.0, -1.0, 0.0, 1.0)),
+        ],
+        CalPattern::Box => square(1.0),
+        CalPattern::Circle => vec![Box::new(CircleArc::new(
+            0.0,
+            0.0,
+            1.0,
+            1.0,
+            0.0,
+            std::f32::consts::TAU,
+        ))],
+        CalPattern::ConcentricSquares => SQUARE_SIZES.iter().flat_map(|half| square(*half)).collect(),
+    };
+    Frame::new(shapes)
+}
+
+/// A square of half-side `half` centred on the origin, drawn anticlockwise
+/// from the bottom-left corner.
+fn square(half: f32) -> Vec<Box<dyn Shape>> {
+    let corners = [(-half, -half), (half, -half), (half, half), (-half, half)];
+    (0..4)
+        .map(|i| {
+            let (a, b) = (corners[i], corners[(i + 1) % 4]);
+            Box::new(Line::new_2d(a.0, a.1, b.0, b.1)) as Box<dyn Shape>
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use osci_core::shape::shapes_bounds;
+    use osci_core::Point;
+
+    #[test]
+    fn test_pattern_shape_counts() {
+        let count = |pattern| calibration_frame(pattern).shapes.len();
+        assert_eq!(count(CalPattern::Crosshair), 2);
+        assert_eq!(count(CalPattern::Box), 4);
+        assert_eq!(count(CalPattern::Circle), 1);
+        assert_eq!(count(CalPattern::ConcentricSquares), 16);
+    }
+
+    #[test]
+    fn test_patterns_fill_the_screen() {
+        for pattern in CalPattern::ALL {
+            let frame = calibration_frame(pattern);
+            let (min, max) = shapes_bounds(&frame.shapes).unwrap();
+            assert!(min.approx_eq(&Point::xy(-1.0, -1.0)), "{pattern:?} min {min:?}");
+            assert!(max.approx_eq(&Point::xy(1.0, 1.0)), "{pattern:?} max {max:?}");
+        }
+
+        // The innermost square sits a quarter of the way out
+        let squares = calibration_frame(CalPattern::ConcentricSquares);
+        let (min, max) = shapes_bounds(&squares.shapes[..4]).unwrap();
+        assert!(min.approx_eq(&Point::xy(-0.25, -0.25)));
+        assert!(max.approx_eq(&Point::xy(0.25, 0.25)));
+    }
+}
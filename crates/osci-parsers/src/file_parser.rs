@@ -1,8 +1,12 @@
 //! File parser dispatch — routes files to the appropriate parser by extension.
 
-use osci_core::shape::Shape;
+use osci_core::shape::{
+    fit_bounds_centered, normalize_shapes_centered, shapes_bounds, simplify_shapes, total_length, NormalizeMode,
+    Shape,
+};
 use osci_core::Point;
 
+use crate::draw_order::DrawOrderConfig;
 use crate::image::ImageConfig;
 
 /// Supported file types.
@@ -62,6 +66,102 @@ pub enum ParseResult {
     LuaScript(String),
 }
 
+/// Post-processing applied to the shapes a file parses into.
+#[derive(Debug, Clone, Default)]
+pub struct ParseConfig {
+    /// Collapse near-collinear runs of lines whose vertices stray less than
+    /// this from a straight path; see [`simplify_shapes`]. `None` keeps
+    /// every segment.
+    pub simplify_tolerance: Option<f32>,
+    /// How vector formats are fitted to the screen. With
+    /// [`NormalizeMode::Centered`] the parsed shapes are re-fitted around
+    /// their bounding box centre; every frame of an animation shares one fit
+    /// so it doesn't jump around.
+    pub normalize: NormalizeMode,
+    /// How OBJ edges and GPLA strokes are ordered for drawing.
+    pub draw_order: DrawOrderConfig,
+    /// Sizes past which [`parse_file_with_stats`] warns that the drawing
+    /// may be too much for the beam to trace smoothly.
+    pub limits: ComplexityLimits,
+}
+
+/// How big a frame can get before a load warns about it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComplexityLimits {
+    /// Most shapes in one frame.
+    pub max_shapes: usize,
+    /// Longest total path in one frame, in the same units as
+    /// [`Shape::length`]; the outline of the full screen is 8.
+    pub max_path_length: f32,
+}
+
+impl Default for ComplexityLimits {
+    fn default() -> Self {
+        Self {
+            max_shapes: 5_000,
+            max_path_length: 500.0,
+        }
+    }
+}
+
+/// Size of what a file parsed into. For animations these are the figures
+/// of the largest frame, as that is what the beam has to keep up with.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParseStats {
+    pub shape_count: usize,
+    pub path_length: f32,
+    /// Suggestion to simplify the drawing, set when it passes the
+    /// [`ComplexityLimits`].
+    pub warning: Option<String>,
+}
+
+impl ParseStats {
+    /// Measure `result` against `limits`. Audio and scripts count as empty.
+    pub fn of(result: &ParseResult, limits: &ComplexityLimits) -> Self {
+        let frames: &[Vec<Box<dyn Shape>>] = match result {
+            ParseResult::Shapes(shapes) => std::slice::from_ref(shapes),
+            ParseResult::AnimatedShapes { frames, .. } => frames,
+            ParseResult::Audio(_) | ParseResult::LuaScript(_) => &[],
+        };
+        let shape_count = frames.iter().map(Vec::len).max().unwrap_or(0);
+        let path_length = frames.iter().map(|f| total_length(f)).fold(0.0, f32::max);
+
+        let warning = (shape_count > limits.max_shapes || path_length > limits.max_path_length).then(|| {
+            format!(
+                "This drawing has {} shapes with a total length of {:.0}, which may be too much \
+                 to draw smoothly. Try simplifying it before importing.",
+                shape_count, path_length
+            )
+        });
+        Self {
+            shape_count,
+            path_length,
+            warning,
+        }
+    }
+}
+
+/// Guess the extension of pasted text: `"svg"` for SVG markup, `"obj"` for
+/// a Wavefront OBJ with vertices and faces or lines, or `None` for anything
+/// else. Only the content is looked at, so this works without a file name.
+pub fn sniff_text_extension(text: &str) -> Option<&'static str> {
+    let start = text.trim_start();
+    if start.starts_with("<svg") || (start.starts_with("<?xml") && start.contains("<svg")) {
+        return Some("svg");
+    }
+
+    let mut vertices = false;
+    let mut elements = false;
+    for line in start.lines().map(str::trim_start) {
+        vertices |= line.starts_with("v ");
+        elements |= line.starts_with("f ") || line.starts_with("l ");
+        if vertices && elements {
+            return Some("obj");
+        }
+    }
+    None
+}
+
 /// Parse a file given its raw data and file extension.
 ///
 /// The extension should not include the leading dot.
@@ -70,15 +170,85 @@ pub fn parse_file(data: &[u8], extension: &str) -> Result<ParseResult, String> {
     parse_file_typed(data, file_type)
 }
 
+/// Parse a file given its raw data and file extension, then post-process
+/// the shapes according to `config`.
+pub fn parse_file_with_config(
+    data: &[u8],
+    extension: &str,
+    config: &ParseConfig,
+) -> Result<ParseResult, String> {
+    let file_type = FileType::from_extension(extension);
+    let result = parse_file_ordered(data, file_type, &config.draw_order)?;
+    let centered = config.normalize == NormalizeMode::Centered;
+    let tolerance = config.simplify_tolerance.unwrap_or(0.0);
+    Ok(match result {
+        ParseResult::Shapes(mut shapes) => {
+            if centered {
+                normalize_shapes_centered(&mut shapes);
+            }
+            ParseResult::Shapes(simplify_shapes(shapes, tolerance))
+        }
+        ParseResult::AnimatedShapes { mut frames, frame_rate } => {
+            if centered {
+                let all_bounds = frames.iter().filter_map(|frame| shapes_bounds(frame));
+                if let Some((min, max)) = all_bounds.reduce(|(min_a, max_a), (min_b, max_b)| {
+                    (
+                        Point::xy(min_a.x.min(min_b.x), min_a.y.min(min_b.y)),
+                        Point::xy(max_a.x.max(max_b.x), max_a.y.max(max_b.y)),
+                    )
+                }) {
+                    for frame in &mut frames {
+                        fit_bounds_centered(frame, min, max);
+                    }
+                }
+            }
+            ParseResult::AnimatedShapes {
+                frames: frames
+                    .into_iter()
+                    .map(|frame| simplify_shapes(frame, tolerance))
+                    .collect(),
+                frame_rate,
+            }
+        }
+        other => other,
+    })
+}
+
+/// Like [`parse_file_with_config`], also measuring the result against
+/// `config.limits`. Drawings past them still load; check
+/// [`ParseStats::warning`].
+pub fn parse_file_with_stats(
+    data: &[u8],
+    extension: &str,
+    config: &ParseConfig,
+) -> Result<(ParseResult, ParseStats), String> {
+    let result = parse_file_with_config(data, extension, config)?;
+    let stats = ParseStats::of(&result, &config.limits);
+    if let Some(warning) = &stats.warning {
+        log::warn!("{}", warning);
+    }
+    Ok((result, stats))
+}
+
 /// Parse a file given its raw data and known file type.
 pub fn parse_file_typed(data: &[u8], file_type: FileType) -> Result<ParseResult, String> {
+    parse_file_ordered(data, file_type, &DrawOrderConfig::default())
+}
+
+/// Parse a file of a known type, ordering OBJ and GPLA line work by
+/// `draw_order`.
+fn parse_file_ordered(
+    data: &[u8],
+    file_type: FileType,
+    draw_order: &DrawOrderConfig,
+) -> Result<ParseResult, String> {
     match file_type {
         FileType::Svg => {
             let shapes = crate::svg::parse_svg(data)?;
             Ok(ParseResult::Shapes(shapes))
         }
         FileType::Obj => {
-            let shapes = crate::obj::parse_obj(data)?;
+            let shapes = crate::obj::parse_obj_with_config(data, draw_order)?;
             Ok(ParseResult::Shapes(shapes))
         }
         FileType::Text => {
@@ -94,7 +264,7 @@ pub fn parse_file_typed(data: &[u8], file_type: FileType) -> Result<ParseResult,
             Ok(ParseResult::LuaScript(script.to_string()))
         }
         FileType::Gpla => {
-            let gpla = crate::gpla::parse_gpla(data)?;
+            let gpla = crate::gpla::parse_gpla_with_config(data, draw_order)?;
             Ok(ParseResult::AnimatedShapes {
                 frames: gpla.frames,
                 frame_rate: gpla.frame_rate as f64,
@@ -154,6 +324,23 @@ mod tests {
         assert_eq!(FileType::from_extension("xyz"), FileType::Unknown);
     }
 
+    #[test]
+    fn test_sniff_text_extension() {
+        assert_eq!(sniff_text_extension("<svg xmlns=\"http://www.w3.org/2000/svg\"/>"), Some("svg"));
+        let xml = "  <?xml version=\"1.0\"?>\n<!-- drawn by hand -->\n<svg></svg>";
+        assert_eq!(sniff_text_extension(xml), Some("svg"));
+
+        let obj = "# cube\nv 0 0 0\nv 1 0 0\nv 1 1 0\nf 1 2 3\n";
+        assert_eq!(sniff_text_extension(obj), Some("obj"));
+        assert_eq!(sniff_text_extension("v 0 0 0\nv 1 1 1\nl 1 2"), Some("obj"));
+
+        // Vertices alone, other XML and plain prose aren't recognised
+        assert_eq!(sniff_text_extension("v 0 0 0\nv 1 0 0"), None);
+        assert_eq!(sniff_text_extension("<?xml version=\"1.0\"?><html/>"), None);
+        assert_eq!(sniff_text_extension("for a very fine day"), None);
+        assert_eq!(sniff_text_extension(""), None);
+    }
+
     #[test]
     fn test_sample_source() {
         assert!(FileType::Lua.is_sample_source());
@@ -175,6 +362,40 @@ mod tests {
         assert_eq!(shapes.len(), 4);
     }
 
+    #[test]
+    fn test_complexity_warning() {
+        let limits = ComplexityLimits::default();
+        let small = ParseStats::of(&ParseResult::Shapes(default_shapes()), &limits);
+        assert_eq!(small.shape_count, 4);
+        assert!((small.path_length - 4.0).abs() < 1e-4);
+        assert_eq!(small.warning, None);
+
+        // A dense mesh of short edges
+        let edges = |count: usize| -> Vec<Box<dyn Shape>> {
+            (0..count)
+                .map(|i| {
+                    let x = (i % 100) as f32 * 0.02 - 1.0;
+                    Box::new(osci_core::shape::Line::new_2d(x, 0.0, x + 0.01, 0.01)) as Box<dyn Shape>
+                })
+                .collect()
+        };
+        let large = ParseStats::of(&ParseResult::Shapes(edges(50_000)), &limits);
+        assert_eq!(large.shape_count, 50_000);
+        assert!(large.warning.is_some());
+
+        // The largest frame of an animation counts
+        let animation = ParseResult::AnimatedShapes {
+            frames: vec![default_shapes(), edges(6_000)],
+            frame_rate: 24.0,
+        };
+        assert_eq!(ParseStats::of(&animation, &limits).shape_count, 6_000);
+        let relaxed = ComplexityLimits {
+            max_shapes: 10_000,
+            ..limits
+        };
+        assert_eq!(ParseStats::of(&animation, &relaxed).warning, None);
+    }
+
     #[test]
     fn test_unknown_file_type_error() {
         let result = parse_file(b"data", "xyz");
@@ -2,6 +2,26 @@ use osci_core::shape::{Shape, normalize_shapes_to};
 
 use super::image::ImageConfig;
 
+/// GIFs whose header reports a larger width or height are rejected outright.
+pub const MAX_GIF_DIMENSION: u32 = 8192;
+
+/// Limits applied while decoding animated GIFs to keep memory bounded.
+pub struct GifConfig {
+    /// Maximum number of frames to keep. Longer animations are sampled evenly.
+    pub max_frames: usize,
+    /// Frames larger than this (in either axis) are downscaled before scanning.
+    pub max_dimension: u32,
+}
+
+impl Default for GifConfig {
+    fn default() -> Self {
+        Self {
+            max_frames: 256,
+            max_dimension: 512,
+        }
+    }
+}
+
 /// A collection of parsed GIF frames, each containing oscilloscope shapes.
 pub struct GifFrames {
     /// One entry per frame, each containing the shapes for that frame.
@@ -16,7 +36,23 @@ pub struct GifFrames {
 /// and then threshold-scanned to produce horizontal line segments (using the
 /// same algorithm as the image parser). Frame delays are averaged to compute
 /// a playback frame rate.
+///
+/// Uses the default [`GifConfig`] limits; see [`parse_gif_with_limits`].
 pub fn parse_gif(data: &[u8], config: &ImageConfig) -> Result<GifFrames, String> {
+    parse_gif_with_limits(data, config, &GifConfig::default())
+}
+
+/// Parse an animated GIF, capping the frame count and frame size.
+///
+/// If the animation has more than `limits.max_frames` frames, that many are
+/// sampled evenly across it and the frame rate is scaled so playback still
+/// spans the original duration. Frames larger than `limits.max_dimension` are
+/// downscaled (preserving aspect) before threshold scanning.
+pub fn parse_gif_with_limits(
+    data: &[u8],
+    config: &ImageConfig,
+    limits: &GifConfig,
+) -> Result<GifFrames, String> {
     let mut decoder = gif::DecodeOptions::new();
     decoder.set_color_output(gif::ColorOutput::RGBA);
     let mut decoder = decoder
@@ -26,6 +62,12 @@ pub fn parse_gif(data: &[u8], config: &ImageConfig) -> Result<GifFrames, String>
     let global_width = decoder.width() as u32;
     let global_height = decoder.height() as u32;
 
+    if global_width > MAX_GIF_DIMENSION || global_height > MAX_GIF_DIMENSION {
+        return Err(format!(
+            "GIF dimensions {global_width}x{global_height} exceed the {MAX_GIF_DIMENSION} pixel limit"
+        ));
+    }
+
     if global_width == 0 || global_height == 0 {
         return Ok(GifFrames {
             frames: Vec::new(),
@@ -33,9 +75,16 @@ pub fn parse_gif(data: &[u8], config: &ImageConfig) -> Result<GifFrames, String>
         });
     }
 
-    let mut frames: Vec<Vec<Box<dyn Shape>>> = Vec::new();
+    let total_frames = count_frames(data)?;
+    let keep = frames_to_keep(total_frames, limits.max_frames);
+
+    let (scan_width, scan_height) =
+        scaled_dimensions(global_width, global_height, limits.max_dimension);
+
+    let mut frames: Vec<Vec<Box<dyn Shape>>> = Vec::with_capacity(keep.len());
     let mut total_delay: u64 = 0;
     let mut frame_count: u64 = 0;
+    let mut next_keep = 0;
 
     // Persistent canvas for frame compositing (RGBA)
     let canvas_size = (global_width * global_height * 4) as usize;
@@ -66,6 +115,17 @@ pub fn parse_gif(data: &[u8], config: &ImageConfig) -> Result<GifFrames, String>
             }
         }
 
+        // Accumulate delay (delay is in 1/100ths of a second)
+        total_delay += frame.delay as u64;
+        let frame_idx = frame_count as usize;
+        frame_count += 1;
+
+        // Only the sampled frames are scanned; the rest just update the canvas
+        if keep.get(next_keep) != Some(&frame_idx) {
+            continue;
+        }
+        next_keep += 1;
+
         // Convert canvas to grayscale
         let num_pixels = (global_width * global_height) as usize;
         let mut gray_pixels = vec![0u8; num_pixels];
@@ -79,23 +139,28 @@ pub fn parse_gif(data: &[u8], config: &ImageConfig) -> Result<GifFrames, String>
             }
         }
 
-        let gray_image = image::GrayImage::from_raw(global_width, global_height, gray_pixels)
+        let mut gray_image = image::GrayImage::from_raw(global_width, global_height, gray_pixels)
             .ok_or_else(|| "failed to create grayscale image from GIF frame".to_string())?;
 
+        if (scan_width, scan_height) != (global_width, global_height) {
+            gray_image = image::imageops::resize(
+                &gray_image,
+                scan_width,
+                scan_height,
+                image::imageops::FilterType::Triangle,
+            );
+        }
+
         // Threshold scan to produce shapes
         let mut shapes = crate::image::threshold_scan(
             &gray_image,
-            global_width,
-            global_height,
+            scan_width,
+            scan_height,
             config,
         );
 
-        normalize_shapes_to(&mut shapes, global_width as f32, global_height as f32);
+        normalize_shapes_to(&mut shapes, scan_width as f32, scan_height as f32, config.keep_aspect);
         frames.push(shapes);
-
-        // Accumulate delay (delay is in 1/100ths of a second)
-        total_delay += frame.delay as u64;
-        frame_count += 1;
     }
 
     // Calculate average frame rate from delays
@@ -107,9 +172,58 @@ pub fn parse_gif(data: &[u8], config: &ImageConfig) -> Result<GifFrames, String>
         10.0 // default GIF frame rate
     };
 
+    // Dropped frames stretch the remaining ones over the original duration
+    let frame_rate = if frame_count > 0 && !frames.is_empty() {
+        frame_rate * frames.len() as f64 / frame_count as f64
+    } else {
+        frame_rate
+    };
+
     Ok(GifFrames { frames, frame_rate })
 }
 
+/// Count the frames in a GIF without decoding pixel data.
+fn count_frames(data: &[u8]) -> Result<usize, String> {
+    let mut options = gif::DecodeOptions::new();
+    options.skip_frame_decoding(true);
+    let mut decoder = options
+        .read_info(std::io::Cursor::new(data))
+        .map_err(|e| format!("failed to decode GIF: {e}"))?;
+
+    let mut count = 0;
+    while decoder
+        .read_next_frame()
+        .map_err(|e| format!("GIF frame error: {e}"))?
+        .is_some()
+    {
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Indices of the frames to keep, evenly spaced and in ascending order.
+fn frames_to_keep(total: usize, max_frames: usize) -> Vec<usize> {
+    let max_frames = max_frames.max(1);
+    if total <= max_frames {
+        return (0..total).collect();
+    }
+    (0..max_frames).map(|k| k * total / max_frames).collect()
+}
+
+/// Scale `width`x`height` down so neither side exceeds `max_dimension`.
+fn scaled_dimensions(width: u32, height: u32, max_dimension: u32) -> (u32, u32) {
+    let max_dimension = max_dimension.max(1);
+    let largest = width.max(height);
+    if largest <= max_dimension {
+        return (width, height);
+    }
+    let scale = max_dimension as f64 / largest as f64;
+    (
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +249,67 @@ mod tests {
         assert_eq!(gif_frames.frames.len(), 2);
         assert!((gif_frames.frame_rate - 10.0).abs() < 0.001);
     }
+
+    /// Encode a `frames`-frame GIF of `size`x`size` pixels, alternating a
+    /// white and black frame, each 10/100 s long.
+    fn synthetic_gif(frames: usize, size: u16) -> Vec<u8> {
+        let palette = [0u8, 0, 0, 255, 255, 255];
+        let mut out = Vec::new();
+        {
+            let mut encoder = gif::Encoder::new(&mut out, size, size, &palette).unwrap();
+            for i in 0..frames {
+                let color = (i % 2) as u8;
+                let pixels = vec![color; size as usize * size as usize];
+                let mut frame = gif::Frame::from_indexed_pixels(size, size, pixels, None);
+                frame.delay = 10;
+                encoder.write_frame(&frame).unwrap();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_max_frames_samples_evenly() {
+        let data = synthetic_gif(100, 4);
+        let limits = GifConfig {
+            max_frames: 10,
+            max_dimension: 512,
+        };
+        let result = parse_gif_with_limits(&data, &ImageConfig::default(), &limits).unwrap();
+        assert_eq!(result.frames.len(), 10);
+        // 100 frames at 10 fps last 10 s; 10 sampled frames must span the same time
+        assert!((result.frame_rate - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_under_limit_keeps_all_frames() {
+        let data = synthetic_gif(6, 4);
+        let result = parse_gif(&data, &ImageConfig::default()).unwrap();
+        assert_eq!(result.frames.len(), 6);
+        assert!((result.frame_rate - 10.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_oversized_frames_are_downscaled() {
+        let data = synthetic_gif(2, 64);
+        let config = ImageConfig {
+            stride: 1,
+            ..ImageConfig::default()
+        };
+        let limits = GifConfig {
+            max_frames: 10,
+            max_dimension: 16,
+        };
+        let result = parse_gif_with_limits(&data, &config, &limits).unwrap();
+        // Frame 1 is solid white: one scanline segment per downscaled row
+        assert_eq!(result.frames[1].len(), 16);
+    }
+
+    #[test]
+    fn test_frames_to_keep() {
+        assert_eq!(frames_to_keep(5, 10), vec![0, 1, 2, 3, 4]);
+        assert_eq!(frames_to_keep(100, 4), vec![0, 25, 50, 75]);
+        assert_eq!(scaled_dimensions(1000, 500, 100), (100, 50));
+        assert_eq!(scaled_dimensions(50, 20, 100), (50, 20));
+    }
 }
@@ -1,6 +1,8 @@
 use osci_core::shape::{normalize_shapes, Line, Shape};
 use serde::Deserialize;
 
+use crate::draw_order::{sort_by_depth, two_opt, DrawOrderConfig};
+
 /// Parsed GPLA animation data: a sequence of frames, each containing drawable shapes.
 pub struct GplaFrames {
     pub frames: Vec<Vec<Box<dyn Shape>>>,
@@ -62,13 +64,20 @@ struct GplaObject {
 // ---------------------------------------------------------------------------
 
 /// Parse GPLA data, auto-detecting binary vs JSON format.
+///
+/// Uses the default [`DrawOrderConfig`]; see [`parse_gpla_with_config`].
 pub fn parse_gpla(data: &[u8]) -> Result<GplaFrames, String> {
+    parse_gpla_with_config(data, &DrawOrderConfig::default())
+}
+
+/// Parse GPLA data, ordering each object's strokes according to `draw_order`.
+pub fn parse_gpla_with_config(data: &[u8], draw_order: &DrawOrderConfig) -> Result<GplaFrames, String> {
     // Trim leading whitespace to detect JSON
     let trimmed = data.iter().position(|&b| !b.is_ascii_whitespace());
     if let Some(pos) = trimmed {
         let first = data[pos];
         if first == b'{' || first == b'[' {
-            return parse_json_gpla(data);
+            return parse_json_gpla(data, draw_order);
         }
     }
 
@@ -76,7 +85,7 @@ pub fn parse_gpla(data: &[u8]) -> Result<GplaFrames, String> {
     if data.len() >= 8 {
         let tag = read_tag(&data[..8]);
         if tag == "GPLA    " {
-            return parse_binary_gpla(data);
+            return parse_binary_gpla(data, draw_order);
         }
     }
 
@@ -87,7 +96,7 @@ pub fn parse_gpla(data: &[u8]) -> Result<GplaFrames, String> {
 // JSON parser
 // ---------------------------------------------------------------------------
 
-fn parse_json_gpla(data: &[u8]) -> Result<GplaFrames, String> {
+fn parse_json_gpla(data: &[u8], draw_order: &DrawOrderConfig) -> Result<GplaFrames, String> {
     let gpla: GplaJson =
         serde_json::from_slice(data).map_err(|e| format!("Failed to parse GPLA JSON: {e}"))?;
 
@@ -125,7 +134,7 @@ fn parse_json_gpla(data: &[u8]) -> Result<GplaFrames, String> {
             })
             .collect();
 
-        let mut shapes = assemble_frame(&objects, focal_length);
+        let mut shapes = assemble_frame(&objects, focal_length, draw_order);
         if !shapes.is_empty() {
             normalize_shapes(&mut shapes);
         }
@@ -143,7 +152,7 @@ fn parse_json_gpla(data: &[u8]) -> Result<GplaFrames, String> {
 // Binary parser
 // ---------------------------------------------------------------------------
 
-fn parse_binary_gpla(data: &[u8]) -> Result<GplaFrames, String> {
+fn parse_binary_gpla(data: &[u8], draw_order: &DrawOrderConfig) -> Result<GplaFrames, String> {
     let mut pos: usize = 0;
 
     let read_i64 = |pos: &mut usize| -> Result<i64, String> {
@@ -281,7 +290,7 @@ fn parse_binary_gpla(data: &[u8]) -> Result<GplaFrames, String> {
             objects.push(GplaObject { strokes, matrix });
         }
 
-        let mut shapes = assemble_frame(&objects, focal_length);
+        let mut shapes = assemble_frame(&objects, focal_length, draw_order);
         if !shapes.is_empty() {
             normalize_shapes(&mut shapes);
         }
@@ -296,12 +305,19 @@ fn parse_binary_gpla(data: &[u8]) -> Result<GplaFrames, String> {
 // ---------------------------------------------------------------------------
 
 /// Assemble a single frame: apply transforms, project, and create line shapes.
-fn assemble_frame(objects: &[GplaObject], focal_length: f64) -> Vec<Box<dyn Shape>> {
-    let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+///
+/// With `draw_order.depth_sort` set, segments are drawn farthest first by
+/// their average depth in camera space.
+fn assemble_frame(
+    objects: &[GplaObject],
+    focal_length: f64,
+    draw_order: &DrawOrderConfig,
+) -> Vec<Box<dyn Shape>> {
+    // Each projected line with its distance from the camera
+    let mut lines: Vec<(f64, Line)> = Vec::new();
 
     for obj in objects {
-        // Reorder strokes using nearest-neighbor greedy algorithm
-        let reordered = reorder_strokes(&obj.strokes);
+        let reordered = order_strokes(&obj.strokes, draw_order);
 
         for stroke in &reordered {
             if stroke.len() < 2 {
@@ -334,12 +350,35 @@ fn assemble_frame(objects: &[GplaObject], focal_length: f64) -> Vec<Box<dyn Shap
                 let px1 = (rx1 * focal_length / rz1) as f32;
                 let py1 = (ry1 * focal_length / rz1) as f32;
 
-                shapes.push(Box::new(Line::new_2d(px0, py0, px1, py1)));
+                // The camera looks down -Z
+                let distance = -(rz0 + rz1) / 2.0;
+                lines.push((distance, Line::new_2d(px0, py0, px1, py1)));
             }
         }
     }
 
-    shapes
+    if draw_order.depth_sort {
+        sort_by_depth(&mut lines, |(distance, _)| *distance);
+    }
+    lines
+        .into_iter()
+        .map(|(_, line)| Box::new(line) as Box<dyn Shape>)
+        .collect()
+}
+
+/// Order strokes greedily, then refine with 2-opt if enabled.
+fn order_strokes(strokes: &[Stroke], draw_order: &DrawOrderConfig) -> Vec<Stroke> {
+    let mut order = reorder_strokes(strokes);
+    if draw_order.two_opt {
+        two_opt(&mut order, stroke_endpoints, |s| s.reverse(), draw_order.max_passes);
+    }
+    order
+}
+
+/// First and last vertex of a stroke.
+fn stroke_endpoints(stroke: &Stroke) -> ([f64; 3], [f64; 3]) {
+    let point = |v: Option<&Vertex3>| v.map_or([0.0; 3], |v| [v.x, v.y, v.z]);
+    (point(stroke.first()), point(stroke.last()))
 }
 
 /// Reorder strokes using nearest-neighbor (greedy) to minimise jumps between
@@ -411,6 +450,7 @@ fn read_tag(bytes: &[u8]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::draw_order::total_jump_distance;
 
     #[test]
     fn test_parse_json_single_frame() {
@@ -559,6 +599,44 @@ mod tests {
         assert!((second_start.x - 1.1).abs() < 0.001);
     }
 
+    #[test]
+    fn test_two_opt_shortens_greedy_stroke_order() {
+        // Two rows of short strokes, all drawn left to right. Greedy never
+        // reverses a stroke, so it zig-zags back along the top row; 2-opt
+        // flips those strokes to walk the row right to left instead.
+        let stroke = |x: f64, y: f64| {
+            vec![
+                Vertex3 { x, y, z: 0.0 },
+                Vertex3 { x: x + 0.1, y, z: 0.0 },
+            ]
+        };
+        let strokes = vec![
+            stroke(0.0, 0.0),
+            stroke(1.0, 0.0),
+            stroke(2.0, 0.0),
+            stroke(0.0, 1.0),
+            stroke(1.0, 1.0),
+            stroke(2.0, 1.0),
+        ];
+
+        let greedy = order_strokes(&strokes, &DrawOrderConfig::default());
+        let refined = order_strokes(
+            &strokes,
+            &DrawOrderConfig {
+                two_opt: true,
+                ..DrawOrderConfig::default()
+            },
+        );
+
+        let greedy_jump = total_jump_distance(&greedy, stroke_endpoints);
+        let refined_jump = total_jump_distance(&refined, stroke_endpoints);
+        assert_eq!(refined.len(), 6);
+        assert!(
+            refined_jump < greedy_jump - 0.2,
+            "greedy {greedy_jump}, 2-opt {refined_jump}"
+        );
+    }
+
     #[test]
     fn test_auto_detect_json() {
         let json = b"  { \"frames\": [] }";
@@ -8,6 +8,12 @@ pub struct ImageConfig {
     pub stride: u32,
     /// If true, invert brightness before thresholding (dark pixels become "on").
     pub invert: bool,
+    /// If true, run a Sobel edge detector first and threshold the gradient
+    /// magnitude instead of brightness, producing outlines rather than fill.
+    pub edge_detect: bool,
+    /// If true, keep the image's aspect ratio and centre it on screen;
+    /// otherwise stretch it to fill the square.
+    pub keep_aspect: bool,
 }
 
 impl Default for ImageConfig {
@@ -16,6 +22,8 @@ impl Default for ImageConfig {
             threshold: 128,
             stride: 2,
             invert: false,
+            edge_detect: false,
+            keep_aspect: true,
         }
     }
 }
@@ -25,7 +33,11 @@ impl Default for ImageConfig {
 /// The algorithm converts the image to grayscale, then scans each row (stepping
 /// by `config.stride`) to find continuous horizontal runs of pixels that exceed
 /// the brightness threshold. Each run becomes a `Line` shape. The resulting
-/// coordinates are normalized to the [-1, 1] range.
+/// coordinates are normalized to the [-1, 1] range, letterboxed or stretched
+/// according to `config.keep_aspect`.
+///
+/// With `config.edge_detect` set, the scan runs over the Sobel gradient
+/// magnitude so only contours are traced.
 pub fn parse_image(data: &[u8], config: &ImageConfig) -> Result<Vec<Box<dyn Shape>>, String> {
     let img = image::load_from_memory(data)
         .map_err(|e| format!("failed to load image: {e}"))?;
@@ -40,21 +52,65 @@ pub fn parse_image(data: &[u8], config: &ImageConfig) -> Result<Vec<Box<dyn Shap
     let shapes = threshold_scan(&gray, width, height, config);
 
     let mut shapes = shapes;
-    normalize_shapes_to(&mut shapes, width as f32, height as f32);
+    normalize_shapes_to(&mut shapes, width as f32, height as f32, config.keep_aspect);
 
     Ok(shapes)
 }
 
 /// Scan a grayscale image row-by-row with the given config, producing horizontal
 /// line segments for each continuous run of "on" pixels.
+///
+/// In edge-detect mode the image is first replaced by its Sobel gradient
+/// magnitude, and `invert` is ignored.
 pub(crate) fn threshold_scan(
     gray: &image::GrayImage,
     width: u32,
     height: u32,
     config: &ImageConfig,
+) -> Vec<Box<dyn Shape>> {
+    if config.edge_detect {
+        let edges = sobel_magnitude(gray, width, height);
+        scan_runs(&edges, width, height, config.threshold, config.stride, false)
+    } else {
+        scan_runs(gray, width, height, config.threshold, config.stride, config.invert)
+    }
+}
+
+/// Compute the Sobel gradient magnitude of a grayscale image.
+///
+/// The magnitude is scaled so that a hard black/white edge maps to 255.
+/// Pixels outside the image are clamped to the nearest edge pixel.
+fn sobel_magnitude(gray: &image::GrayImage, width: u32, height: u32) -> image::GrayImage {
+    let px = |x: i64, y: i64| -> f32 {
+        let cx = x.clamp(0, width as i64 - 1) as u32;
+        let cy = y.clamp(0, height as i64 - 1) as u32;
+        gray.get_pixel(cx, cy).0[0] as f32
+    };
+
+    let mut out = image::GrayImage::new(width, height);
+    for y in 0..height as i64 {
+        for x in 0..width as i64 {
+            let gx = (px(x + 1, y - 1) + 2.0 * px(x + 1, y) + px(x + 1, y + 1))
+                - (px(x - 1, y - 1) + 2.0 * px(x - 1, y) + px(x - 1, y + 1));
+            let gy = (px(x - 1, y + 1) + 2.0 * px(x, y + 1) + px(x + 1, y + 1))
+                - (px(x - 1, y - 1) + 2.0 * px(x, y - 1) + px(x + 1, y - 1));
+            let magnitude = (gx * gx + gy * gy).sqrt() / 4.0;
+            out.put_pixel(x as u32, y as u32, image::Luma([magnitude.min(255.0) as u8]));
+        }
+    }
+    out
+}
+
+fn scan_runs(
+    gray: &image::GrayImage,
+    width: u32,
+    height: u32,
+    threshold: u8,
+    stride: u32,
+    invert: bool,
 ) -> Vec<Box<dyn Shape>> {
     let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
-    let stride = config.stride.max(1);
+    let stride = stride.max(1);
 
     let mut y = 0u32;
     while y < height {
@@ -63,10 +119,10 @@ pub(crate) fn threshold_scan(
 
         for x in 0..width {
             let pixel = gray.get_pixel(x, y).0[0];
-            let is_on = if config.invert {
-                pixel < config.threshold
+            let is_on = if invert {
+                pixel < threshold
             } else {
-                pixel > config.threshold
+                pixel > threshold
             };
 
             if is_on {
@@ -112,6 +168,7 @@ mod tests {
         assert_eq!(config.threshold, 128);
         assert_eq!(config.stride, 2);
         assert!(!config.invert);
+        assert!(!config.edge_detect);
     }
 
     #[test]
@@ -135,6 +192,8 @@ mod tests {
             threshold: 128,
             stride: 1,
             invert: false,
+            edge_detect: false,
+            keep_aspect: true,
         };
         let shapes = threshold_scan(&gray, 4, 2, &config);
 
@@ -168,6 +227,8 @@ mod tests {
             threshold: 128,
             stride: 2,
             invert: false,
+            edge_detect: false,
+            keep_aspect: true,
         };
         let shapes = threshold_scan(&gray, 4, 4, &config);
 
@@ -185,6 +246,8 @@ mod tests {
             threshold: 128,
             stride: 1,
             invert: true,
+            edge_detect: false,
+            keep_aspect: true,
         };
         let shapes = threshold_scan(&gray, 4, 1, &config);
 
@@ -194,4 +257,56 @@ mod tests {
         assert!((start.x - 0.0).abs() < 0.01);
         assert!((end.x - 2.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_edge_detect_traces_square_outline() {
+        // 16x16 black image with a white square covering x,y in 4..12
+        let mut gray = image::GrayImage::new(16, 16);
+        for y in 4..12 {
+            for x in 4..12 {
+                gray.put_pixel(x, y, image::Luma([255]));
+            }
+        }
+
+        let fill_config = ImageConfig {
+            threshold: 128,
+            stride: 1,
+            invert: false,
+            edge_detect: false,
+            keep_aspect: true,
+        };
+        let edge_config = ImageConfig {
+            edge_detect: true,
+            ..fill_config
+        };
+
+        let fill = threshold_scan(&gray, 16, 16, &fill_config);
+        let edges = threshold_scan(&gray, 16, 16, &edge_config);
+        assert!(!edges.is_empty());
+
+        // Every edge segment must touch the square's border region
+        let mut interior_len = 0.0;
+        for shape in &edges {
+            let start = shape.next_vector(0.0);
+            let end = shape.next_vector(1.0);
+            let y = start.y;
+            if y > 5.0 && y < 10.0 {
+                // Rows through the middle of the square: only the left/right sides
+                assert!(start.x < 6.0 || start.x > 9.0);
+                assert!(end.x < 7.0 || end.x > 10.0);
+                interior_len += end.x - start.x;
+            }
+        }
+
+        // Middle rows carry far less ink than the filled scan
+        let fill_interior: f32 = fill
+            .iter()
+            .filter(|s| {
+                let y = s.next_vector(0.0).y;
+                y > 5.0 && y < 10.0
+            })
+            .map(|s| s.next_vector(1.0).x - s.next_vector(0.0).x)
+            .sum();
+        assert!(interior_len < fill_interior * 0.75);
+    }
 }
@@ -4,8 +4,16 @@ pub mod text;
 pub mod image;
 pub mod gif;
 pub mod gpla;
+pub mod draw_order;
 pub mod audio;
 pub mod lua;
 pub mod file_parser;
+pub mod calibration;
+pub mod sample_source;
 
-pub use file_parser::{FileType, ParseResult, parse_file, parse_file_typed, default_shapes};
+pub use file_parser::{
+    ComplexityLimits, FileType, ParseConfig, ParseResult, ParseStats, parse_file, parse_file_with_config,
+    parse_file_with_stats, parse_file_typed, default_shapes, sniff_text_extension,
+};
+pub use calibration::{calibration_frame, CalPattern};
+pub use sample_source::SampleSource;
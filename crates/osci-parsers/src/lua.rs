@@ -4,12 +4,16 @@
 //! The runtime exposes global variables: `step`, `phase`, `sampleRate`,
 //! `frequency`, `cycle`, and 26 slider variables (`slider_a` through `slider_z`).
 
+/// Number of slider variables exposed to scripts.
+pub const NUM_SLIDERS: usize = 26;
+
 #[cfg(feature = "lua")]
 mod inner {
     use mlua::{Lua, Function, Result as LuaResult, Value, MultiValue};
     use osci_core::Point;
 
-    const NUM_SLIDERS: usize = 26;
+    use super::NUM_SLIDERS;
+
     const MAX_INSTRUCTIONS: u32 = 5_000_000;
 
     const SLIDER_NAMES: [&str; NUM_SLIDERS] = [
@@ -57,6 +61,23 @@ mod inner {
     }
 
     impl LuaVariables {
+        /// Copy slider values in from the UI. Extra values are ignored.
+        pub fn set_sliders(&mut self, values: &[f64]) {
+            for (slider, value) in self.sliders.iter_mut().zip(values) {
+                *slider = *value;
+            }
+        }
+
+        /// Set the output sample rate `phase` advances at.
+        pub fn set_sample_rate(&mut self, sample_rate: f64) {
+            self.sample_rate = sample_rate;
+        }
+
+        /// Set the frequency `phase` goes round at.
+        pub fn set_frequency(&mut self, frequency: f64) {
+            self.frequency = frequency;
+        }
+
         /// Advance state after one sample.
         pub fn increment(&mut self) {
             self.step += 1.0;
@@ -207,6 +228,21 @@ mod inner {
             self.using_fallback
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn slider_a_reaches_script() {
+            let parser = LuaParser::new("return { slider_a, 0 }").unwrap();
+            let mut vars = LuaVariables::default();
+            vars.set_sliders(&[0.75]);
+            let p = parser.run(&mut vars);
+            assert!((p.x - 0.75).abs() < 1e-6);
+            assert_eq!(p.y, 0.0);
+        }
+    }
 }
 
 #[cfg(feature = "lua")]
@@ -217,13 +253,29 @@ pub use inner::*;
 pub mod stub {
     use osci_core::Point;
 
-    pub struct LuaVariables;
+    use super::NUM_SLIDERS;
+
+    pub struct LuaVariables {
+        pub sliders: [f64; NUM_SLIDERS],
+    }
 
     impl Default for LuaVariables {
-        fn default() -> Self { Self }
+        fn default() -> Self {
+            Self { sliders: [0.0; NUM_SLIDERS] }
+        }
     }
 
     impl LuaVariables {
+        pub fn set_sliders(&mut self, values: &[f64]) {
+            for (slider, value) in self.sliders.iter_mut().zip(values) {
+                *slider = *value;
+            }
+        }
+
+        pub fn set_sample_rate(&mut self, _sample_rate: f64) {}
+
+        pub fn set_frequency(&mut self, _frequency: f64) {}
+
         pub fn increment(&mut self) {}
     }
 
@@ -237,6 +289,10 @@ pub mod stub {
         pub fn run(&self, _vars: &mut LuaVariables) -> Point {
             Point::ZERO
         }
+
+        pub fn is_using_fallback(&self) -> bool {
+            false
+        }
     }
 }
 
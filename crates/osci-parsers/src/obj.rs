@@ -2,13 +2,30 @@ use osci_core::shape::{Line, Shape};
 use std::collections::HashSet;
 use std::io::Cursor;
 
+use crate::draw_order::{sort_by_depth, two_opt, DrawOrderConfig};
+
 /// Parse OBJ (Wavefront) mesh data into a vector of drawable line shapes.
 ///
 /// The mesh vertices are normalized by centering on the centroid and scaling
 /// to fit within a reasonable range. Unique edges are extracted from all faces,
 /// then reordered using a greedy nearest-neighbor heuristic to minimize jump
 /// distances between consecutive edges (simplified Chinese Postman optimization).
+///
+/// Uses the default [`DrawOrderConfig`]; see [`parse_obj_with_config`].
 pub fn parse_obj(data: &[u8]) -> Result<Vec<Box<dyn Shape>>, String> {
+    parse_obj_with_config(data, &DrawOrderConfig::default())
+}
+
+/// Parse OBJ mesh data, ordering the edges according to `draw_order`.
+///
+/// With `draw_order.two_opt` set, the greedy edge order is refined with
+/// 2-opt passes to remove long jumps, which costs extra load time on large
+/// meshes. With `draw_order.depth_sort` set, edges are then drawn farthest
+/// first, as seen through the Perspective effect's camera on the -Z side.
+pub fn parse_obj_with_config(
+    data: &[u8],
+    draw_order: &DrawOrderConfig,
+) -> Result<Vec<Box<dyn Shape>>, String> {
     let mut cursor = Cursor::new(data);
 
     let (models, _materials) = tobj::load_obj_buf(
@@ -121,7 +138,7 @@ pub fn parse_obj(data: &[u8]) -> Result<Vec<Box<dyn Shape>>, String> {
 
     // Reorder edges using greedy nearest-neighbor to minimize jump distances
     if edges.len() > 1 {
-        edges = reorder_edges_nearest_neighbor(&edges, &all_vertices);
+        edges = order_edges(&edges, &all_vertices, draw_order);
     }
 
     // Generate Line shapes
@@ -137,6 +154,33 @@ pub fn parse_obj(data: &[u8]) -> Result<Vec<Box<dyn Shape>>, String> {
     Ok(shapes)
 }
 
+/// Order edges greedily, then refine with 2-opt and sort by depth if enabled.
+fn order_edges(
+    edges: &[(u32, u32)],
+    vertices: &[[f32; 3]],
+    draw_order: &DrawOrderConfig,
+) -> Vec<(u32, u32)> {
+    let mut order = reorder_edges_nearest_neighbor(edges, vertices);
+    if draw_order.two_opt {
+        let endpoints = |e: &(u32, u32)| edge_endpoints(e, vertices);
+        two_opt(&mut order, endpoints, |e| *e = (e.1, e.0), draw_order.max_passes);
+    }
+    if draw_order.depth_sort {
+        // The camera looks along +Z, so a larger average Z is farther away
+        let average_z = |e: &(u32, u32)| {
+            (vertices[e.0 as usize][2] as f64 + vertices[e.1 as usize][2] as f64) / 2.0
+        };
+        sort_by_depth(&mut order, average_z);
+    }
+    order
+}
+
+/// Start and end position of an edge in traversal order.
+fn edge_endpoints(edge: &(u32, u32), vertices: &[[f32; 3]]) -> ([f64; 3], [f64; 3]) {
+    let point = |i: u32| vertices[i as usize].map(|c| c as f64);
+    (point(edge.0), point(edge.1))
+}
+
 /// Reorder edges using a greedy nearest-neighbor heuristic.
 ///
 /// Starting from the first edge, repeatedly select the unvisited edge whose
@@ -240,4 +284,64 @@ f 4 1 5 8
         // A cube has 12 unique edges
         assert_eq!(shapes.len(), 12);
     }
+
+    #[test]
+    fn test_two_opt_shortens_greedy_edge_order() {
+        // Dashes along the X axis. Starting from the dash at the origin,
+        // greedy heads right first and then has to jump all the way back
+        // for the dash on the left.
+        let vertices = [
+            [0.0, 0.0, 0.0],
+            [0.1, 0.0, 0.0],
+            [0.5, 0.0, 0.0],
+            [0.6, 0.0, 0.0],
+            [1.2, 0.0, 0.0],
+            [1.3, 0.0, 0.0],
+            [-0.6, 0.0, 0.0],
+            [-0.5, 0.0, 0.0],
+        ];
+        let edges = [(0, 1), (2, 3), (4, 5), (6, 7)];
+        let endpoints = |e: &(u32, u32)| edge_endpoints(e, &vertices);
+
+        let greedy = order_edges(&edges, &vertices, &DrawOrderConfig::default());
+        let refined = order_edges(
+            &edges,
+            &vertices,
+            &DrawOrderConfig {
+                two_opt: true,
+                ..DrawOrderConfig::default()
+            },
+        );
+
+        let greedy_jump = crate::draw_order::total_jump_distance(&greedy, endpoints);
+        let refined_jump = crate::draw_order::total_jump_distance(&refined, endpoints);
+        assert!((greedy_jump - 2.8).abs() < 1e-5);
+        assert!(refined_jump < 1.6, "2-opt left {refined_jump}");
+        assert_eq!(refined.len(), 4);
+    }
+
+    #[test]
+    fn test_depth_sort_draws_nearer_edge_last() {
+        // The near edge is first in the greedy order
+        let vertices = [
+            [0.0, 0.0, -0.5],
+            [0.5, 0.0, -0.5],
+            [0.5, 0.5, 0.5],
+            [0.0, 0.5, 0.5],
+        ];
+        let edges = [(0, 1), (2, 3)];
+
+        let greedy = order_edges(&edges, &vertices, &DrawOrderConfig::default());
+        assert_eq!(greedy, [(0, 1), (2, 3)]);
+
+        let sorted = order_edges(
+            &edges,
+            &vertices,
+            &DrawOrderConfig {
+                depth_sort: true,
+                ..DrawOrderConfig::default()
+            },
+        );
+        assert_eq!(sorted, [(2, 3), (0, 1)]);
+    }
 }
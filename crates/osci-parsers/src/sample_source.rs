@@ -0,0 +1,112 @@
+//! Sources traced straight to the output one point per sample, in place of
+//! the shape synth.
+
+use osci_core::Point;
+
+use crate::audio::AudioSampleSource;
+use crate::lua::{LuaParser, LuaVariables};
+
+/// A per-sample point source: something a file loads into that isn't a
+/// frame of shapes (see [`FileType::is_sample_source`](crate::FileType::is_sample_source)).
+pub enum SampleSource {
+    /// A decoded audio file, left channel as X and right as Y.
+    Audio(AudioSampleSource),
+    /// A Lua script run once per sample.
+    Lua(Box<LuaSource>),
+}
+
+/// A compiled Lua script with the variables it reads.
+pub struct LuaSource {
+    parser: LuaParser,
+    vars: LuaVariables,
+}
+
+impl LuaSource {
+    pub fn new(parser: LuaParser) -> Self {
+        Self {
+            parser,
+            vars: LuaVariables::default(),
+        }
+    }
+}
+
+impl SampleSource {
+    /// Update the output sample rate.
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        match self {
+            SampleSource::Audio(source) => source.set_sample_rate(sample_rate),
+            SampleSource::Lua(source) => source.vars.set_sample_rate(sample_rate),
+        }
+    }
+
+    /// Pass the UI's slider values to a script as `slider_a` onwards.
+    /// Audio ignores them.
+    pub fn set_sliders(&mut self, values: &[f64]) {
+        if let SampleSource::Lua(source) = self {
+            source.vars.set_sliders(values);
+        }
+    }
+
+    /// Set the frequency a script's `phase` goes round at. Audio plays at
+    /// its own rate.
+    pub fn set_frequency(&mut self, frequency: f64) {
+        if let SampleSource::Lua(source) = self {
+            source.vars.set_frequency(frequency);
+        }
+    }
+
+    /// Produce the next output point.
+    pub fn next_point(&mut self) -> Point {
+        match self {
+            SampleSource::Audio(source) => source.next_point(),
+            SampleSource::Lua(source) => source.parser.run(&mut source.vars),
+        }
+    }
+
+    /// Fill the axes with consecutive points.
+    pub fn fill(&mut self, x: &mut [f32], y: &mut [f32], z: &mut [f32]) {
+        for ((x, y), z) in x.iter_mut().zip(y.iter_mut()).zip(z.iter_mut()) {
+            let point = self.next_point();
+            (*x, *y, *z) = (point.x, point.y, point.z);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AudioData;
+
+    #[test]
+    fn test_audio_fills_left_as_x_and_right_as_y() {
+        let audio = AudioData {
+            samples: vec![vec![0.5, -0.25], vec![-0.5, 0.75]],
+            sample_rate: 44100,
+            num_channels: 2,
+            num_samples: 2,
+        };
+        let mut source = SampleSource::Audio(AudioSampleSource::new(audio, 44100.0));
+
+        let (mut x, mut y, mut z) = ([0.0; 3], [0.0; 3], [9.0; 3]);
+        source.fill(&mut x, &mut y, &mut z);
+        assert_eq!(x, [0.5, -0.25, 0.5]);
+        assert_eq!(y, [-0.5, 0.75, -0.5]);
+        assert_eq!(z, [0.0; 3]);
+    }
+
+    #[cfg(feature = "lua")]
+    #[test]
+    fn test_lua_reads_sliders_each_block() {
+        let parser = LuaParser::new("return { slider_a, slider_b }").unwrap();
+        let mut source = SampleSource::Lua(Box::new(LuaSource::new(parser)));
+
+        let (mut x, mut y, mut z) = ([0.0; 2], [0.0; 2], [0.0; 2]);
+        source.set_sliders(&[0.75, -0.5]);
+        source.fill(&mut x, &mut y, &mut z);
+        assert_eq!((x, y), ([0.75; 2], [-0.5; 2]));
+
+        source.set_sliders(&[0.25]);
+        source.fill(&mut x, &mut y, &mut z);
+        assert_eq!((x, y), ([0.25; 2], [-0.5; 2]));
+    }
+}
@@ -1,4 +1,4 @@
-use osci_core::shape::{normalize_shapes, CubicBezierCurve, Line, QuadraticBezierCurve, Shape};
+use osci_core::shape::{normalize_shapes, Coloured, CubicBezierCurve, Line, QuadraticBezierCurve, Shape};
 
 /// Parse SVG data into a vector of drawable shapes.
 ///
@@ -6,6 +6,10 @@ use osci_core::shape::{normalize_shapes, CubicBezierCurve, Line, QuadraticBezier
 /// osci-core shape primitives. Y coordinates are negated to flip the SVG
 /// coordinate system (Y-down) into the oscilloscope coordinate system (Y-up).
 /// The resulting shapes are normalized to fit within [-1, 1].
+///
+/// Paths with a solid `stroke` colour are drawn in that colour, dimmed by
+/// `stroke-opacity`. Unstroked paths and gradient strokes keep the default
+/// colour.
 pub fn parse_svg(data: &[u8]) -> Result<Vec<Box<dyn Shape>>, String> {
     let tree = usvg::Tree::from_data(data, &usvg::Options::default())
         .map_err(|e| format!("Failed to parse SVG: {e}"))?;
@@ -46,8 +50,36 @@ fn transform_point(transform: &usvg::Transform, x: f32, y: f32) -> (f64, f64) {
     (pt.x as f64, pt.y as f64)
 }
 
+/// The path's solid stroke colour as RGB in [0, 1], scaled by its opacity.
+fn stroke_colour(path: &usvg::Path) -> Option<[f32; 3]> {
+    let stroke = path.stroke()?;
+    let usvg::Paint::Color(colour) = stroke.paint() else {
+        return None;
+    };
+    let opacity = stroke.opacity().get();
+    Some([colour.red, colour.green, colour.blue].map(|c| c as f32 / 255.0 * opacity))
+}
+
+/// A line in `colour`, if there is one.
+fn line(x1: f32, y1: f32, x2: f32, y2: f32, colour: Option<[f32; 3]>) -> Box<dyn Shape> {
+    let line = Line::new_2d(x1, y1, x2, y2);
+    match colour {
+        Some([r, g, b]) => Box::new(line.with_colour(r, g, b)),
+        None => Box::new(line),
+    }
+}
+
+/// A curve in `colour`, if there is one.
+fn curve(shape: Box<dyn Shape>, colour: Option<[f32; 3]>) -> Box<dyn Shape> {
+    match colour {
+        Some([r, g, b]) => Box::new(Coloured::new(shape, r, g, b)),
+        None => shape,
+    }
+}
+
 fn collect_shapes_from_path(path: &usvg::Path, shapes: &mut Vec<Box<dyn Shape>>) {
     let transform = path.abs_transform();
+    let colour = stroke_colour(path);
 
     let mut cur_x: f64 = 0.0;
     let mut cur_y: f64 = 0.0;
@@ -66,26 +98,22 @@ fn collect_shapes_from_path(path: &usvg::Path, shapes: &mut Vec<Box<dyn Shape>>)
             }
             PathSegment::LineTo(pt) => {
                 let (tx, ty) = transform_point(&transform, pt.x, pt.y);
-                shapes.push(Box::new(Line::new_2d(
-                    cur_x as f32,
-                    -cur_y as f32,
-                    tx as f32,
-                    -ty as f32,
-                )));
+                shapes.push(line(cur_x as f32, -cur_y as f32, tx as f32, -ty as f32, colour));
                 cur_x = tx;
                 cur_y = ty;
             }
             PathSegment::QuadTo(pt1, pt2) => {
                 let (tx1, ty1) = transform_point(&transform, pt1.x, pt1.y);
                 let (tx2, ty2) = transform_point(&transform, pt2.x, pt2.y);
-                shapes.push(Box::new(QuadraticBezierCurve::new(
+                let quad = QuadraticBezierCurve::new(
                     cur_x as f32,
                     -cur_y as f32,
                     tx1 as f32,
                     -ty1 as f32,
                     tx2 as f32,
                     -ty2 as f32,
-                )));
+                );
+                shapes.push(curve(Box::new(quad), colour));
                 cur_x = tx2;
                 cur_y = ty2;
             }
@@ -93,7 +121,7 @@ fn collect_shapes_from_path(path: &usvg::Path, shapes: &mut Vec<Box<dyn Shape>>)
                 let (tx1, ty1) = transform_point(&transform, pt1.x, pt1.y);
                 let (tx2, ty2) = transform_point(&transform, pt2.x, pt2.y);
                 let (tx3, ty3) = transform_point(&transform, pt3.x, pt3.y);
-                shapes.push(Box::new(CubicBezierCurve::new(
+                let cubic = CubicBezierCurve::new(
                     cur_x as f32,
                     -cur_y as f32,
                     tx1 as f32,
@@ -102,7 +130,8 @@ fn collect_shapes_from_path(path: &usvg::Path, shapes: &mut Vec<Box<dyn Shape>>)
                     -ty2 as f32,
                     tx3 as f32,
                     -ty3 as f32,
-                )));
+                );
+                shapes.push(curve(Box::new(cubic), colour));
                 cur_x = tx3;
                 cur_y = ty3;
             }
@@ -111,12 +140,13 @@ fn collect_shapes_from_path(path: &usvg::Path, shapes: &mut Vec<Box<dyn Shape>>)
                 let dx = cur_x - subpath_start_x;
                 let dy = cur_y - subpath_start_y;
                 if (dx * dx + dy * dy).sqrt() > 1e-6 {
-                    shapes.push(Box::new(Line::new_2d(
+                    shapes.push(line(
                         cur_x as f32,
                         -cur_y as f32,
                         subpath_start_x as f32,
                         -subpath_start_y as f32,
-                    )));
+                        colour,
+                    ));
                 }
                 cur_x = subpath_start_x;
                 cur_y = subpath_start_y;
@@ -146,4 +176,33 @@ mod tests {
         let shapes = parse_svg(svg).unwrap();
         assert!(shapes.len() >= 4); // rect = 4 lines
     }
+
+    #[test]
+    fn test_stroke_colour_reaches_points() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect x="10" y="10" width="80" height="80" stroke="red" fill="none"/>
+            <circle cx="50" cy="50" r="20" stroke="blue" stroke-opacity="0.5" fill="none"/>
+        </svg>"#;
+        let shapes = parse_svg(svg).unwrap();
+        let rect: Vec<_> = shapes.iter().filter(|s| s.shape_type() == "Line").collect();
+        assert!(rect.len() >= 4);
+        for shape in rect {
+            let p = shape.next_vector(0.5);
+            assert!((p.r - 1.0).abs() < 1e-6 && p.g == 0.0 && p.b == 0.0, "{p:?}");
+        }
+
+        // Curves take the stroke too, dimmed by its opacity
+        let arc = shapes.iter().find(|s| s.shape_type() != "Line").unwrap();
+        let p = arc.next_vector(0.5);
+        assert!(p.r == 0.0 && (p.b - 0.5).abs() < 0.01, "{p:?}");
+    }
+
+    #[test]
+    fn test_unstroked_paths_keep_default_colour() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect x="10" y="10" width="80" height="80"/>
+        </svg>"#;
+        let p = parse_svg(svg).unwrap()[0].next_vector(0.5);
+        assert_eq!((p.r, p.g, p.b), (p.z, p.z, p.z));
+    }
 }
@@ -1,23 +1,167 @@
-use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, SwashCache, SwashContent};
-use osci_core::shape::{normalize_shapes, Line, Shape};
+use cosmic_text::{
+    fontdb, Align, Attrs, Buffer, Command, Family, FontSystem, Metrics, Shaping, SwashCache,
+    SwashContent, Wrap,
+};
+use osci_core::shape::{normalize_shapes, CubicBezierCurve, Line, QuadraticBezierCurve, Shape};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Horizontal alignment of the lines in a block of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl From<TextAlign> for Align {
+    fn from(align: TextAlign) -> Self {
+        match align {
+            TextAlign::Left => Align::Left,
+            TextAlign::Center => Align::Center,
+            TextAlign::Right => Align::Right,
+        }
+    }
+}
 
 /// Configuration for text-to-shape conversion.
 pub struct TextConfig {
     /// Font size in pixels. Default: 24.0
     pub font_size: f32,
+    /// Distance between baselines as a multiple of the font size. Default: 1.2
+    pub line_height: f32,
+    /// Horizontal alignment of the lines. Default: Left
+    pub align: TextAlign,
+    /// Wrap lines longer than this many pixels. Alignment is relative to
+    /// this width, or to the widest line when unset. Default: None
+    pub wrap_width: Option<f32>,
+    /// Font family to render with. Default: None (sans-serif)
+    pub font_family: Option<String>,
+    /// Font file to load before looking up the family. If `font_family` is
+    /// `None`, the file's own family is used. Default: None
+    pub font_path: Option<PathBuf>,
+    /// Trace glyph outlines with lines and Bezier curves instead of filling
+    /// them with horizontal scanlines. Default: false
+    pub outline: bool,
 }
 
 impl Default for TextConfig {
     fn default() -> Self {
-        Self { font_size: 24.0 }
+        Self {
+            font_size: 24.0,
+            line_height: 1.2,
+            align: TextAlign::Left,
+            wrap_width: None,
+            font_family: None,
+            font_path: None,
+            outline: false,
+        }
+    }
+}
+
+/// Resolve the font family requested by `config`, loading its font file first.
+///
+/// Returns `None` (sans-serif) if the file can't be read or the family isn't
+/// installed.
+fn select_family(font_system: &mut FontSystem, config: &TextConfig) -> Option<String> {
+    let mut family = config.font_family.clone();
+
+    if let Some(path) = &config.font_path {
+        match std::fs::read(path) {
+            Ok(data) => {
+                let ids = font_system
+                    .db_mut()
+                    .load_font_source(fontdb::Source::Binary(Arc::new(data)));
+                if family.is_none() {
+                    family = ids
+                        .first()
+                        .and_then(|&id| font_system.db().face(id))
+                        .and_then(|face| face.families.first())
+                        .map(|(name, _)| name.clone());
+                }
+            }
+            Err(e) => log::warn!("Failed to load font {}: {e}", path.display()),
+        }
+    }
+
+    let family = family?;
+    let installed = font_system
+        .db()
+        .faces()
+        .any(|face| face.families.iter().any(|(name, _)| name.eq_ignore_ascii_case(&family)));
+    if installed {
+        Some(family)
+    } else {
+        log::warn!("Font family '{family}' not found, falling back to sans-serif");
+        None
+    }
+}
+
+/// Append the contours described by `commands` to `shapes`.
+///
+/// Outline coordinates are Y-up relative to the glyph origin, which sits at
+/// (`x`, `y`) in raster (Y-down) pixel space; the shapes are emitted in raster
+/// space like the scanline mode's. Open subpaths are closed with a
+/// line back to their start, so every contour is a loop.
+fn push_outline(commands: &[Command], x: f32, y: f32, shapes: &mut Vec<Box<dyn Shape>>) {
+    let map = |px: f32, py: f32| (x + px, y - py);
+
+    let mut start = (0.0, 0.0);
+    let mut current = (0.0, 0.0);
+
+    for command in commands {
+        match *command {
+            Command::MoveTo(p) => {
+                close_contour(current, start, shapes);
+                start = map(p.x, p.y);
+                current = start;
+            }
+            Command::LineTo(p) => {
+                let to = map(p.x, p.y);
+                shapes.push(Box::new(Line::new_2d(current.0, current.1, to.0, to.1)));
+                current = to;
+            }
+            Command::QuadTo(c, p) => {
+                let c = map(c.x, c.y);
+                let to = map(p.x, p.y);
+                shapes.push(Box::new(QuadraticBezierCurve::new(
+                    current.0, current.1, c.0, c.1, to.0, to.1,
+                )));
+                current = to;
+            }
+            Command::CurveTo(c1, c2, p) => {
+                let c1 = map(c1.x, c1.y);
+                let c2 = map(c2.x, c2.y);
+                let to = map(p.x, p.y);
+                shapes.push(Box::new(CubicBezierCurve::new(
+                    current.0, current.1, c1.0, c1.1, c2.0, c2.1, to.0, to.1,
+                )));
+                current = to;
+            }
+            Command::Close => {
+                close_contour(current, start, shapes);
+                current = start;
+            }
+        }
+    }
+    close_contour(current, start, shapes);
+}
+
+/// Join `current` back to the contour's `start` if they differ.
+fn close_contour(current: (f32, f32), start: (f32, f32), shapes: &mut Vec<Box<dyn Shape>>) {
+    if current != start {
+        shapes.push(Box::new(Line::new_2d(current.0, current.1, start.0, start.1)));
     }
 }
 
 /// Parse a text string into vector line shapes suitable for oscilloscope rendering.
 ///
 /// Each character is rasterised using `cosmic-text` and then converted into
-/// horizontal line segments by scanning alpha rows. The resulting shapes are
-/// normalized to fit within the [-1, 1] coordinate range.
+/// horizontal line segments by scanning alpha rows, or, with
+/// [`TextConfig::outline`] set, traced along its outline. The resulting shapes are
+/// normalized to fit within the [-1, 1] coordinate range. Text is set in
+/// sans-serif unless `config` names another font family or font file.
 pub fn parse_text(text: &str, config: &TextConfig) -> Result<Vec<Box<dyn Shape>>, String> {
     if text.is_empty() {
         return Ok(Vec::new());
@@ -27,17 +171,34 @@ pub fn parse_text(text: &str, config: &TextConfig) -> Result<Vec<Box<dyn Shape>>
     let mut font_system = FontSystem::new();
 
     // 2. Create a Buffer for text layout
-    let line_height = config.font_size * 1.2;
+    let line_height = config.font_size * config.line_height;
     let metrics = Metrics::new(config.font_size, line_height);
     let mut buffer = Buffer::new(&mut font_system, metrics);
+    buffer.set_size(&mut font_system, config.wrap_width, None);
 
-    // 3. Set text content with sans-serif font family
-    let attrs = Attrs::new().family(Family::SansSerif);
+    // 3. Set text content in the configured family, or sans-serif
+    let family = select_family(&mut font_system, config);
+    let attrs = match &family {
+        Some(name) => Attrs::new().family(Family::Name(name)),
+        None => Attrs::new().family(Family::SansSerif),
+    };
     buffer.set_text(&mut font_system, text, attrs, Shaping::Advanced);
+    for line in buffer.lines.iter_mut() {
+        line.set_align(Some(config.align.into()));
+    }
 
     // 4. Perform layout
     buffer.shape_until_scroll(&mut font_system, false);
 
+    // Without a wrap width each line is aligned within its own width, so lay
+    // out again against the widest line to align the lines with each other
+    if config.wrap_width.is_none() && config.align != TextAlign::Left {
+        let widest = buffer.layout_runs().map(|run| run.line_w).fold(0.0, f32::max);
+        buffer.set_wrap(&mut font_system, Wrap::None);
+        buffer.set_size(&mut font_system, Some(widest), None);
+        buffer.shape_until_scroll(&mut font_system, false);
+    }
+
     // 5. Create a SwashCache for glyph rasterization
     let mut cache = SwashCache::new();
 
@@ -46,7 +207,15 @@ pub fn parse_text(text: &str, config: &TextConfig) -> Result<Vec<Box<dyn Shape>>
 
     for run in buffer.layout_runs() {
         for glyph in run.glyphs.iter() {
-            let physical = glyph.physical((0.0, 0.0), 1.0);
+            // Glyph positions are relative to their line, so offset by its baseline
+            let physical = glyph.physical((0.0, run.line_y), 1.0);
+
+            if config.outline {
+                if let Some(commands) = cache.get_outline_commands(&mut font_system, physical.cache_key) {
+                    push_outline(commands, physical.x as f32, physical.y as f32, &mut shapes);
+                }
+                continue;
+            }
 
             if let Some(image) = cache.get_image(&mut font_system, physical.cache_key) {
                 let w = image.placement.width as usize;
@@ -100,15 +269,15 @@ pub fn parse_text(text: &str, config: &TextConfig) -> Result<Vec<Box<dyn Shape>>
                             }
                         } else if let Some(start) = line_start.take() {
                             let end = (gx + col as i32) as f32;
-                            // Negate y to flip from raster (Y-down) to scope (Y-up)
-                            shapes.push(Box::new(Line::new_2d(start, -y, end, -y)));
+                            // Still Y-down, like SVG: normalize_shapes flips it upright
+                            shapes.push(Box::new(Line::new_2d(start, y, end, y)));
                         }
                     }
 
                     // Close any run that extends to the right edge of the glyph
                     if let Some(start) = line_start {
                         let end = (gx + w as i32) as f32;
-                        shapes.push(Box::new(Line::new_2d(start, -y, end, -y)));
+                        shapes.push(Box::new(Line::new_2d(start, y, end, y)));
                     }
                 }
             }
@@ -147,7 +316,10 @@ mod tests {
 
     #[test]
     fn test_custom_font_size() {
-        let config = TextConfig { font_size: 48.0 };
+        let config = TextConfig {
+            font_size: 48.0,
+            ..Default::default()
+        };
         let shapes = parse_text("A", &config).unwrap();
         assert!(
             !shapes.is_empty(),
@@ -183,6 +355,165 @@ mod tests {
         }
     }
 
+    fn endpoints(shapes: &[Box<dyn Shape>]) -> Vec<(f32, f32, f32, f32)> {
+        shapes
+            .iter()
+            .map(|s| {
+                let (a, b) = (s.next_vector(0.0), s.next_vector(1.0));
+                (a.x, a.y, b.x, b.y)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_custom_font_file() {
+        let font_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/fonts/FiraSans-Regular.ttf");
+        let config = TextConfig {
+            font_path: Some(PathBuf::from(font_path)),
+            ..Default::default()
+        };
+        let custom = parse_text("g", &config).unwrap();
+        assert!(!custom.is_empty(), "Expected shapes for 'g' in Fira Sans");
+
+        // Naming the family explicitly selects the same face
+        let named = TextConfig {
+            font_family: Some("Fira Sans".to_string()),
+            font_path: Some(PathBuf::from(font_path)),
+            ..Default::default()
+        };
+        assert_eq!(endpoints(&parse_text("g", &named).unwrap()), endpoints(&custom));
+
+        let default = parse_text("g", &TextConfig::default()).unwrap();
+        if !default.is_empty() {
+            assert_ne!(endpoints(&custom), endpoints(&default));
+        }
+    }
+
+    #[test]
+    fn test_unknown_family_falls_back_to_sans_serif() {
+        let config = TextConfig {
+            font_family: Some("No Such Font Family".to_string()),
+            font_path: Some(PathBuf::from("/nonexistent/font.ttf")),
+            ..Default::default()
+        };
+        let fallback = parse_text("Test", &config).unwrap();
+        let default = parse_text("Test", &TextConfig::default()).unwrap();
+        assert_eq!(endpoints(&fallback), endpoints(&default));
+    }
+
+    #[test]
+    fn test_outline_o_is_closed_contours() {
+        let config = TextConfig {
+            font_path: Some(PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../../assets/fonts/FiraSans-Regular.ttf"
+            ))),
+            outline: true,
+            ..Default::default()
+        };
+        let shapes = parse_text("O", &config).unwrap();
+        assert!(!shapes.is_empty());
+
+        // Split into contours wherever a shape doesn't start at the last one's end
+        let close = |a: osci_core::Point, b: osci_core::Point| {
+            (a.x - b.x).abs() < 1e-4 && (a.y - b.y).abs() < 1e-4
+        };
+        let mut contours: Vec<(osci_core::Point, osci_core::Point)> = Vec::new();
+        for shape in &shapes {
+            let (start, end) = (shape.next_vector(0.0), shape.next_vector(1.0));
+            match contours.last_mut() {
+                Some(contour) if close(contour.1, start) => contour.1 = end,
+                _ => contours.push((start, end)),
+            }
+        }
+
+        // Outer and inner ring, each ending where it began
+        assert_eq!(contours.len(), 2);
+        assert!(contours.iter().all(|&(start, end)| close(start, end)));
+        // Curves rather than rows of horizontal fill
+        assert!(shapes.iter().any(|s| s.shape_type() != "Line"));
+        let scanlines = parse_text("O", &TextConfig { outline: false, ..config }).unwrap();
+        assert!(shapes.len() < scanlines.len());
+    }
+
+    /// Horizontal centre of the top and bottom line of two-line `shapes`.
+    fn line_centres(shapes: &[Box<dyn Shape>]) -> (f32, f32) {
+        let points: Vec<osci_core::Point> = shapes
+            .iter()
+            .flat_map(|s| [s.next_vector(0.0), s.next_vector(1.0)])
+            .collect();
+        let (min_y, max_y) = points
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), p| (lo.min(p.y), hi.max(p.y)));
+        let mid_y = (min_y + max_y) / 2.0;
+
+        let centre = |top: bool| {
+            let (lo, hi) = points
+                .iter()
+                .filter(|p| (p.y > mid_y) == top)
+                .fold((f32::MAX, f32::MIN), |(lo, hi), p| (lo.min(p.x), hi.max(p.x)));
+            (lo + hi) / 2.0
+        };
+        (centre(true), centre(false))
+    }
+
+    #[test]
+    fn test_text_is_upright() {
+        // A "T" has its crossbar, the widest row, at the top
+        let shapes = parse_text("T", &TextConfig::default()).unwrap();
+        if shapes.is_empty() {
+            return; // skip if no system fonts available
+        }
+        let widest = shapes
+            .iter()
+            .max_by(|a, b| a.length().total_cmp(&b.length()))
+            .unwrap();
+        let max_y = shapes.iter().map(|s| s.next_vector(0.0).y).fold(f32::MIN, f32::max);
+        assert!(max_y - widest.next_vector(0.0).y < 0.2);
+    }
+
+    #[test]
+    fn test_centered_lines_share_a_centre() {
+        // Capitals only, so no descender reaches into the other line
+        let text = "HI\nWIDE LINE";
+        let config = TextConfig {
+            align: TextAlign::Center,
+            line_height: 1.5,
+            ..Default::default()
+        };
+        let shapes = parse_text(text, &config).unwrap();
+        if shapes.is_empty() {
+            return; // skip if no system fonts available
+        }
+
+        let (top, bottom) = line_centres(&shapes);
+        assert!((top - bottom).abs() < 0.05, "top {top}, bottom {bottom}");
+
+        // Left-aligned, the short line sits well to the left
+        let (top, bottom) = line_centres(&parse_text(text, &TextConfig::default()).unwrap());
+        assert!(top < bottom - 0.2, "top {top}, bottom {bottom}");
+    }
+
+    #[test]
+    fn test_wrap_width_breaks_lines() {
+        let config = TextConfig {
+            wrap_width: Some(60.0),
+            ..Default::default()
+        };
+        let shapes = parse_text("ONE TWO THREE", &config).unwrap();
+        if shapes.is_empty() {
+            return; // skip if no system fonts available
+        }
+
+        // Wrapped into a column, the text is taller than it is wide
+        let points: Vec<osci_core::Point> = shapes.iter().map(|s| s.next_vector(0.0)).collect();
+        let width = points.iter().map(|p| p.x).fold(f32::MIN, f32::max)
+            - points.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+        let height = points.iter().map(|p| p.y).fold(f32::MIN, f32::max)
+            - points.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        assert!(height > width, "width {width}, height {height}");
+    }
+
     #[test]
     fn test_whitespace_only() {
         let config = TextConfig::default();
@@ -1,4 +1,5 @@
-use osci_parsers::{parse_file, default_shapes, FileType, ParseResult};
+use osci_parsers::draw_order::DrawOrderConfig;
+use osci_parsers::{parse_file, parse_file_with_config, default_shapes, FileType, ParseConfig, ParseResult};
 
 // ── Helpers ──────────────────────────────────────────────────────
 
@@ -183,6 +184,106 @@ fn dispatch_unknown_returns_error() {
     assert!(result.is_err(), "unknown extension should return error");
 }
 
+#[test]
+fn dispatch_with_simplify_tolerance_collapses_traced_line() {
+    // A straight stroke traced as 80 short segments
+    let points: Vec<String> = (10..=90).map(|x| format!("{x},50")).collect();
+    let svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+  <polyline points="{}" fill="none" stroke="black"/>
+</svg>"#,
+        points.join(" ")
+    );
+    let count = |config: &ParseConfig| match parse_file_with_config(svg.as_bytes(), "svg", config) {
+        Ok(ParseResult::Shapes(shapes)) => shapes.len(),
+        _ => panic!("expected ParseResult::Shapes from SVG"),
+    };
+
+    assert!(count(&ParseConfig::default()) >= 80);
+    let simplified = ParseConfig {
+        simplify_tolerance: Some(0.001),
+        ..ParseConfig::default()
+    };
+    assert_eq!(count(&simplified), 1);
+}
+
+#[test]
+fn dispatch_with_centered_normalization_centres_circle() {
+    let config = ParseConfig {
+        normalize: osci_core::NormalizeMode::Centered,
+        ..ParseConfig::default()
+    };
+    let Ok(ParseResult::Shapes(shapes)) = parse_file_with_config(SVG_CIRCLE, "svg", &config) else {
+        panic!("expected ParseResult::Shapes from SVG");
+    };
+    let (min, max) = osci_core::shape::shapes_bounds(&shapes).unwrap();
+    assert!(((min.x + max.x) / 2.0).abs() < 1e-3, "x centre {}", (min.x + max.x) / 2.0);
+    assert!(((min.y + max.y) / 2.0).abs() < 1e-3, "y centre {}", (min.y + max.y) / 2.0);
+    assert!((max.x - min.x - 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn dispatch_with_depth_sort_draws_far_gpla_stroke_first() {
+    // The near stroke (z = -1) comes first in the file, the far one (z = -3) second
+    let gpla = r#"{"frames": [{"objects": [{
+        "vertices": [
+            [{"x": 0.0, "y": 0.0, "z": -1.0}, {"x": 1.0, "y": 0.0, "z": -1.0}],
+            [{"x": 1.0, "y": 1.0, "z": -3.0}, {"x": 3.0, "y": 1.0, "z": -3.0}]
+        ],
+        "matrix": [1,0,0,0, 0,1,0,0, 0,0,1,0, 0,0,0,1]
+    }], "focalLength": 1.0}]}"#;
+    let starts = |config: &ParseConfig| match parse_file_with_config(gpla.as_bytes(), "gpla", config) {
+        Ok(ParseResult::AnimatedShapes { frames, .. }) => {
+            frames[0].iter().map(|s| s.next_vector(0.0)).collect::<Vec<_>>()
+        }
+        _ => panic!("expected ParseResult::AnimatedShapes from GPLA"),
+    };
+
+    let file_order = starts(&ParseConfig::default());
+    let sorted = starts(&ParseConfig {
+        draw_order: DrawOrderConfig {
+            depth_sort: true,
+            ..DrawOrderConfig::default()
+        },
+        ..ParseConfig::default()
+    });
+    assert_eq!(file_order.len(), 2);
+    assert_eq!(sorted, [file_order[1], file_order[0]]);
+}
+
+#[test]
+fn dispatch_with_two_opt_shortens_gpla_jumps() {
+    // Two rows of dashes, all drawn left to right; greedy zig-zags back along the second row
+    let dash = |x: f32, y: f32| {
+        format!(r#"[{{"x": {x}, "y": {y}, "z": -1.0}}, {{"x": {}, "y": {y}, "z": -1.0}}]"#, x + 0.1)
+    };
+    let strokes: Vec<String> = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (0.0, 1.0), (1.0, 1.0), (2.0, 1.0)]
+        .iter()
+        .map(|&(x, y)| dash(x, y))
+        .collect();
+    let gpla = format!(
+        r#"{{"frames": [{{"objects": [{{"vertices": [{}], "matrix": [1,0,0,0, 0,1,0,0, 0,0,1,0, 0,0,0,1]}}], "focalLength": 1.0}}]}}"#,
+        strokes.join(", ")
+    );
+    let jumps = |config: &ParseConfig| match parse_file_with_config(gpla.as_bytes(), "gpla", config) {
+        Ok(ParseResult::AnimatedShapes { frames, .. }) => frames[0]
+            .windows(2)
+            .map(|pair| (pair[1].next_vector(0.0) - pair[0].next_vector(1.0)).magnitude())
+            .sum::<f32>(),
+        _ => panic!("expected ParseResult::AnimatedShapes from GPLA"),
+    };
+
+    let greedy = jumps(&ParseConfig::default());
+    let refined = jumps(&ParseConfig {
+        draw_order: DrawOrderConfig {
+            two_opt: true,
+            ..DrawOrderConfig::default()
+        },
+        ..ParseConfig::default()
+    });
+    assert!(refined < greedy - 0.2, "greedy {greedy}, 2-opt {refined}");
+}
+
 // ── 5. Default shapes ────────────────────────────────────────────
 
 #[test]
@@ -16,14 +16,24 @@ impl Default for EditorUiState {
     }
 }
 use osci_effects::registry::find_effect;
-use osci_gui::{AudioInfo, EditorSharedState, EffectSnapshot, GpuScopeState, MenuState, OsciPluginParamRefs, UiCommand, VisBuffer};
+use osci_gui::{
+    measure_level, AudioInfo, EditorSharedState, EffectSnapshot, GpuScopeState, MacroDefinition, MenuState,
+    OsciPluginParamRefs, UiCommand, VisBuffer, NUM_LUA_SLIDERS, NUM_MACROS,
+};
+use osci_parsers::SampleSource;
 use osci_parsers::default_shapes;
-use osci_synth::{MidiEvent, ShapeSound, Synthesizer, VoiceEffect};
+use osci_core::{randomize_parameters, EnvelopeFollower};
+use osci_synth::sidechain::sidechain_follower;
+use osci_synth::sound::Frame;
+use osci_synth::{MidiEvent, ShapeSound, Synthesizer, VoiceEffect, DEFAULT_MAX_VOICES};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 const VIS_BUFFER_SIZE: usize = 512;
 
+/// MIDI controller that silences every note (channel mode message).
+const ALL_NOTES_OFF_CC: u8 = 123;
+
 pub struct OsciPlugin {
     params: Arc<OsciParams>,
     synth: Synthesizer,
@@ -36,19 +46,55 @@ pub struct OsciPlugin {
     // Drone mode: fire a continuous NoteOn when no MIDI voices are active
     drone_active: bool,
 
+    // Envelope of the aux input, used as the sidechain source when enabled
+    sidechain: EnvelopeFollower,
+
+    // Custom envelope from the envelope editor; replaces the ADSR params when set
+    custom_envelope: Option<osci_core::Env>,
+
     // Effect chain template — synced to all voices on change
     effect_template: Vec<VoiceEffect>,
 
+    // Source traced in place of the synth while one is loaded
+    sample_source: Option<SampleSource>,
+
+    // Macro values and targets, the host automation value each macro last
+    // saw, and which macros need writing to their targets this block
+    macros: [MacroDefinition; NUM_MACROS],
+    macro_host_values: [Option<f32>; NUM_MACROS],
+    macros_dirty: [bool; NUM_MACROS],
+
     // Networking
     net_server: Option<osci_net::NetServer>,
 
     // UI ↔ Audio communication
     command_rx: crossbeam::channel::Receiver<UiCommand>,
     command_tx: crossbeam::channel::Sender<UiCommand>,
+    retired_sources_rx: crossbeam::channel::Receiver<SampleSource>,
+    retired_sources_tx: crossbeam::channel::Sender<SampleSource>,
     effect_snapshots: Arc<Mutex<Vec<EffectSnapshot>>>,
     vis_buffer: Arc<Mutex<VisBuffer>>,
     current_project_path: Arc<Mutex<Option<PathBuf>>>,
     audio_info: Arc<Mutex<AudioInfo>>,
+    lua_sliders: Arc<Mutex<[f64; NUM_LUA_SLIDERS]>>,
+    envelope: Arc<Mutex<Option<osci_core::Env>>>,
+    frame_reversed: Arc<Mutex<bool>>,
+    seed: Arc<Mutex<Option<u64>>>,
+    effects_bypassed: Arc<Mutex<bool>>,
+    voice_count: Arc<Mutex<usize>>,
+    macros_ui: Arc<Mutex<[MacroDefinition; NUM_MACROS]>>,
+    loaded_frame: Arc<Mutex<Frame>>,
+}
+
+/// Where the Z channel goes when the host gives the plugin a third output.
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+enum ZOutput {
+    /// Leave the third output silent.
+    #[name = "Off"]
+    Off,
+    /// Send Z (depth/brightness) to the third output.
+    #[name = "Third Channel"]
+    ThirdChannel,
 }
 
 #[derive(Params)]
@@ -60,6 +106,9 @@ struct OsciParams {
     volume: FloatParam,
     #[id = "frequency"]
     frequency: FloatParam,
+    // Scan rate limit: caps the drawing frequency of notes and the default
+    #[id = "max_frequency"]
+    max_frequency: FloatParam,
 
     // ADSR envelope
     #[id = "attack"]
@@ -70,6 +119,45 @@ struct OsciParams {
     sustain: FloatParam,
     #[id = "release"]
     release: FloatParam,
+
+    // Drive sidechained parameters from the aux input instead of the voice envelope
+    #[id = "sidechain"]
+    external_sidechain: BoolParam,
+
+    // High-pass X and Y to remove the offset of off-centre shapes
+    #[id = "dc_block"]
+    dc_block: BoolParam,
+
+    // Blend between the two frames of a morph, from all A to all B
+    #[id = "morph_position"]
+    morph_position: FloatParam,
+
+    // Routing of Z in the three-channel output layout
+    #[id = "z_output"]
+    z_output: EnumParam<ZOutput>,
+
+    // Host-automatable macro knobs, driving the effect parameters picked in the editor
+    #[id = "macro1"]
+    macro1: FloatParam,
+    #[id = "macro2"]
+    macro2: FloatParam,
+    #[id = "macro3"]
+    macro3: FloatParam,
+    #[id = "macro4"]
+    macro4: FloatParam,
+}
+
+impl OsciParams {
+    fn macros(&self) -> [&FloatParam; NUM_MACROS] {
+        [&self.macro1, &self.macro2, &self.macro3, &self.macro4]
+    }
+}
+
+fn macro_param(name: &str) -> FloatParam {
+    FloatParam::new(name, 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+        .with_value_to_string(formatters::v2s_f32_percentage(0))
+        .with_string_to_value(formatters::s2v_f32_percentage())
+        .with_unit("%")
 }
 
 impl Default for OsciParams {
@@ -88,6 +176,16 @@ impl Default for OsciParams {
                 },
             )
             .with_unit(" Hz"),
+            max_frequency: FloatParam::new(
+                "Max Frequency",
+                20000.0,
+                FloatRange::Skewed {
+                    min: 100.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz"),
 
             attack: FloatParam::new(
                 "Attack",
@@ -124,6 +222,20 @@ impl Default for OsciParams {
                 },
             )
             .with_unit(" s"),
+
+            external_sidechain: BoolParam::new("Sidechain Input", false),
+            dc_block: BoolParam::new("DC Block", false),
+            morph_position: FloatParam::new("Morph", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_smoother(SmoothingStyle::Linear(20.0))
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage())
+                .with_unit("%"),
+            z_output: EnumParam::new("Z Output", ZOutput::ThirdChannel),
+
+            macro1: macro_param("Macro 1"),
+            macro2: macro_param("Macro 2"),
+            macro3: macro_param("Macro 3"),
+            macro4: macro_param("Macro 4"),
         }
     }
 }
@@ -131,6 +243,7 @@ impl Default for OsciParams {
 impl Default for OsciPlugin {
     fn default() -> Self {
         let (tx, rx) = crossbeam::channel::bounded(256);
+        let (retired_tx, retired_rx) = crossbeam::channel::bounded(4);
         Self {
             params: Arc::new(OsciParams::default()),
             synth: Synthesizer::with_defaults(44100.0),
@@ -140,14 +253,57 @@ impl Default for OsciPlugin {
             y_buf: Vec::new(),
             z_buf: Vec::new(),
             drone_active: false,
+            sidechain: sidechain_follower(44100.0),
+            custom_envelope: None,
             effect_template: Vec::new(),
+            sample_source: None,
+            macros: Default::default(),
+            macro_host_values: [None; NUM_MACROS],
+            macros_dirty: [false; NUM_MACROS],
             net_server: None,
             command_rx: rx,
             command_tx: tx,
+            retired_sources_rx: retired_rx,
+            retired_sources_tx: retired_tx,
             effect_snapshots: Arc::new(Mutex::new(Vec::new())),
             vis_buffer: Arc::new(Mutex::new(VisBuffer::default())),
             current_project_path: Arc::new(Mutex::new(None)),
             audio_info: Arc::new(Mutex::new(AudioInfo::default())),
+            lua_sliders: Arc::new(Mutex::new([0.0; NUM_LUA_SLIDERS])),
+            envelope: Arc::new(Mutex::new(None)),
+            frame_reversed: Arc::new(Mutex::new(false)),
+            seed: Arc::new(Mutex::new(None)),
+            effects_bypassed: Arc::new(Mutex::new(false)),
+            voice_count: Arc::new(Mutex::new(DEFAULT_MAX_VOICES)),
+            macros_ui: Arc::new(Mutex::new(Default::default())),
+            loaded_frame: Arc::new(Mutex::new(default_shapes())),
+        }
+    }
+}
+
+impl OsciPlugin {
+    /// Apply the editor's synth settings to a freshly built synth.
+    fn restore_synth_settings(&mut self) {
+        self.synth.set_effects_bypassed(self.effects_bypassed.lock().is_ok_and(|b| *b));
+        self.synth.set_reversed(self.frame_reversed.lock().is_ok_and(|r| *r));
+        if let Ok(voices) = self.voice_count.lock() {
+            self.synth.set_max_voices(*voices);
+        }
+        if let Some(seed) = self.seed.lock().ok().and_then(|s| *s) {
+            self.synth.set_seed(seed);
+        }
+    }
+
+    /// Trace `source` in place of the synth, or go back to the synth with
+    /// `None`. The old source goes back to the editor to be freed, as its
+    /// decoded data can be large; only if the editor has stopped taking
+    /// them is it dropped here.
+    fn replace_sample_source(&mut self, mut source: Option<SampleSource>) {
+        if let Some(source) = &mut source {
+            source.set_sample_rate(self.sample_rate);
+        }
+        if let Some(old) = std::mem::replace(&mut self.sample_source, source) {
+            let _ = self.retired_sources_tx.try_send(old);
         }
     }
 }
@@ -162,14 +318,35 @@ impl Plugin for OsciPlugin {
     type SysExMessage = ();
     type BackgroundTask = ();
 
-    const MIDI_INPUT: MidiConfig = MidiConfig::Basic;
+    // CCs are needed for All Notes Off
+    const MIDI_INPUT: MidiConfig = MidiConfig::MidiCCs;
     const SAMPLE_ACCURATE_AUTOMATION: bool = true;
 
-    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[AudioIOLayout {
-        main_input_channels: None,
-        main_output_channels: NonZeroU32::new(2),
-        ..AudioIOLayout::const_default()
-    }];
+    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[
+        AudioIOLayout {
+            main_input_channels: None,
+            main_output_channels: NonZeroU32::new(2),
+            aux_input_ports: &[new_nonzero_u32(2)],
+            names: PortNames {
+                layout: Some("Stereo"),
+                aux_inputs: &["Sidechain"],
+                ..PortNames::const_default()
+            },
+            ..AudioIOLayout::const_default()
+        },
+        // X, Y and Z for three-axis rigs and Z-as-brightness pipelines
+        AudioIOLayout {
+            main_input_channels: None,
+            main_output_channels: NonZeroU32::new(3),
+            aux_input_ports: &[new_nonzero_u32(2)],
+            names: PortNames {
+                layout: Some("X/Y/Z"),
+                aux_inputs: &["Sidechain"],
+                ..PortNames::const_default()
+            },
+            ..AudioIOLayout::const_default()
+        },
+    ];
 
     fn params(&self) -> Arc<dyn Params> {
         self.params.clone()
@@ -179,13 +356,27 @@ impl Plugin for OsciPlugin {
         let params = self.params.clone();
         let shared = EditorSharedState {
             command_tx: self.command_tx.clone(),
+            frame_tx: self.sound.sender(),
+            retired_sources: self.retired_sources_rx.clone(),
+            loaded_frame: self.loaded_frame.clone(),
             effect_snapshots: self.effect_snapshots.clone(),
             vis_buffer: self.vis_buffer.clone(),
             current_project_path: self.current_project_path.clone(),
             audio_info: self.audio_info.clone(),
+            lua_sliders: self.lua_sliders.clone(),
+            envelope: self.envelope.clone(),
+            frame_reversed: self.frame_reversed.clone(),
+            seed: self.seed.clone(),
+            effects_bypassed: self.effects_bypassed.clone(),
+            voice_count: self.voice_count.clone(),
+            macros: self.macros_ui.clone(),
         };
         let scope_state = Arc::new(Mutex::new(GpuScopeState::default()));
+        if let Some(name) = osci_net::NetConfig::default().shared_texture_name {
+            attach_shared_texture(&scope_state, name);
+        }
         let menu_state = Mutex::new(MenuState::default());
+        let editor_state = self.params.editor_state.clone();
 
         create_egui_editor(
             self.params.editor_state.clone(),
@@ -214,11 +405,13 @@ impl Plugin for OsciPlugin {
                     decay: &params.decay,
                     sustain: &params.sustain,
                     release: &params.release,
+                    macros: params.macros(),
                 };
 
                 let scope = scope_state.clone();
                 osci_gui::draw_editor(
                     egui_ctx,
+                    &editor_state,
                     &param_refs,
                     setter,
                     &shared,
@@ -241,6 +434,12 @@ impl Plugin for OsciPlugin {
     ) -> bool {
         self.sample_rate = buffer_config.sample_rate as f64;
         self.synth = Synthesizer::with_defaults(self.sample_rate);
+        self.restore_synth_settings();
+        if let Some(source) = &mut self.sample_source {
+            source.set_sample_rate(self.sample_rate);
+        }
+        self.sidechain.set_sample_rate(buffer_config.sample_rate);
+        self.sidechain.reset();
 
         // Publish audio info for the UI
         if let Ok(mut info) = self.audio_info.lock() {
@@ -248,11 +447,13 @@ impl Plugin for OsciPlugin {
             info.buffer_size = buffer_config.max_buffer_size;
         }
 
-        // Load default shapes (unit square)
-        self.sound = ShapeSound::new(4);
-        let tx = self.sound.sender();
-        let _ = tx.send(default_shapes());
+        // Load default shapes (unit square). The sound itself is kept, since
+        // the editor and the network servers hold senders into its queue.
+        let _ = self.sound.sender().try_send(default_shapes());
         self.sound.update_frame();
+        if let Ok(mut loaded) = self.loaded_frame.lock() {
+            *loaded = default_shapes();
+        }
 
         // Allocate scratch buffers
         let max_size = buffer_config.max_buffer_size as usize;
@@ -273,7 +474,7 @@ impl Plugin for OsciPlugin {
 
         // Start network servers
         let frame_tx = self.sound.sender();
-        let sink = osci_net::FrameSink::new(frame_tx);
+        let sink = osci_net::FrameSink::with_recycling(frame_tx, self.sound.recycler());
         self.net_server = Some(osci_net::NetServer::start(osci_net::NetConfig::default(), sink));
 
         true
@@ -281,12 +482,14 @@ impl Plugin for OsciPlugin {
 
     fn reset(&mut self) {
         self.synth = Synthesizer::with_defaults(self.sample_rate);
+        self.restore_synth_settings();
+        self.sidechain.reset();
     }
 
     fn process(
         &mut self,
         buffer: &mut Buffer,
-        _aux: &mut AuxiliaryBuffers,
+        aux: &mut AuxiliaryBuffers,
         context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
         let num_samples = buffer.samples();
@@ -295,17 +498,24 @@ impl Plugin for OsciPlugin {
         let volume = self.params.volume.smoothed.next();
         let frequency = self.params.frequency.smoothed.next();
         self.synth.set_default_frequency(frequency as f64);
+        self.synth.set_max_frequency(self.params.max_frequency.value() as f64);
+        self.synth.set_dc_block(self.params.dc_block.value());
+        self.synth.set_morph_position(self.params.morph_position.smoothed.next());
 
         // Build ADSR from param values
         let attack = self.params.attack.smoothed.next() as f64;
         let decay = self.params.decay.smoothed.next() as f64;
         let sustain = self.params.sustain.smoothed.next() as f64;
         let release = self.params.release.smoothed.next() as f64;
-        let adsr = osci_core::Env::adsr(attack, decay, sustain, release, 1.0, -4.0);
+        let adsr = match &self.custom_envelope {
+            Some(env) => env.clone(),
+            None => osci_core::Env::adsr(attack, decay, sustain, release, 1.0, -4.0),
+        };
         self.synth.set_adsr(adsr);
 
         // Drain UI commands
         let mut effects_changed = false;
+        let mut snapshots_changed = false;
         while let Ok(cmd) = self.command_rx.try_recv() {
             match cmd {
                 UiCommand::AddEffect(id) => {
@@ -322,6 +532,7 @@ impl Plugin for OsciPlugin {
                 UiCommand::RemoveEffect(idx) => {
                     if idx < self.effect_template.len() {
                         self.effect_template.remove(idx);
+                        self.macros.iter_mut().for_each(|m| m.effect_removed(idx));
                         effects_changed = true;
                     }
                 }
@@ -330,6 +541,7 @@ impl Plugin for OsciPlugin {
                     if from < len && to < len && from != to {
                         let effect = self.effect_template.remove(from);
                         self.effect_template.insert(to, effect);
+                        self.macros.iter_mut().for_each(|m| m.effect_moved(from, to));
                         effects_changed = true;
                     }
                 }
@@ -339,6 +551,24 @@ impl Plugin for OsciPlugin {
                         effects_changed = true;
                     }
                 }
+                UiCommand::SetEffectSolo { idx, solo } => {
+                    if let Some(e) = self.effect_template.get_mut(idx) {
+                        // Flip the flag in place so every effect keeps its state
+                        e.solo = solo;
+                        self.synth.set_effect_solo(idx, solo);
+                        snapshots_changed = true;
+                    }
+                }
+                UiCommand::SetEffectResetOnNote { idx, reset_on_note } => {
+                    if let Some(e) = self.effect_template.get_mut(idx) {
+                        e.reset_on_note = reset_on_note;
+                        self.synth.set_effect_reset_on_note(idx, reset_on_note);
+                        snapshots_changed = true;
+                    }
+                }
+                UiCommand::BypassAll { bypass } => {
+                    self.synth.set_effects_bypassed(bypass);
+                }
                 UiCommand::SetParamValue {
                     effect_idx,
                     param_idx,
@@ -358,6 +588,7 @@ impl Plugin for OsciPlugin {
                     rate,
                     start,
                     end,
+                    bipolar,
                 } => {
                     if let Some(e) = self.effect_template.get_mut(effect_idx) {
                         if let Some(p) = e.parameters.get_mut(param_idx) {
@@ -365,6 +596,7 @@ impl Plugin for OsciPlugin {
                             p.lfo_rate = rate;
                             p.lfo_start_percent = start;
                             p.lfo_end_percent = end;
+                            p.lfo_bipolar = bipolar;
                             p.lfo_enabled = !matches!(lfo_type, osci_core::LfoType::Static);
                             effects_changed = true;
                         }
@@ -374,10 +606,12 @@ impl Plugin for OsciPlugin {
                     effect_idx,
                     param_idx,
                     value,
+                    curve,
                 } => {
                     if let Some(e) = self.effect_template.get_mut(effect_idx) {
                         if let Some(p) = e.parameters.get_mut(param_idx) {
                             p.smooth_value_change = value;
+                            p.smooth_curve = curve;
                             effects_changed = true;
                         }
                     }
@@ -394,6 +628,26 @@ impl Plugin for OsciPlugin {
                         }
                     }
                 }
+                UiCommand::SetParamLocked {
+                    effect_idx,
+                    param_idx,
+                    locked,
+                } => {
+                    if let Some(e) = self.effect_template.get_mut(effect_idx) {
+                        if let Some(p) = e.parameters.get_mut(param_idx) {
+                            p.locked = locked;
+                            snapshots_changed = true;
+                        }
+                    }
+                }
+                UiCommand::RandomizeParams { respect_locks, seed } => {
+                    randomize_parameters(
+                        self.effect_template.iter_mut().flat_map(|e| e.parameters.iter_mut()),
+                        seed,
+                        respect_locks,
+                    );
+                    effects_changed = true;
+                }
                 UiCommand::LoadProject { effects } => {
                     self.effect_template.clear();
                     for loaded in effects {
@@ -401,7 +655,7 @@ impl Plugin for OsciPlugin {
                             let mut effect = VoiceEffect::new(
                                 entry.id,
                                 (entry.constructor)(),
-                                loaded.parameters,
+                                entry.restore_parameters(loaded.parameters),
                             );
                             effect.enabled = loaded.enabled;
                             self.effect_template.push(effect);
@@ -411,8 +665,19 @@ impl Plugin for OsciPlugin {
                 }
                 UiCommand::ClearProject => {
                     self.effect_template.clear();
+                    self.custom_envelope = None;
+                    self.replace_sample_source(None);
+                    self.macros = Default::default();
+                    // Pick the values back up from the host parameters
+                    self.macro_host_values = [None; NUM_MACROS];
                     effects_changed = true;
                 }
+                UiCommand::SetEnvelope(env) => {
+                    self.custom_envelope = Some(env);
+                }
+                UiCommand::ClearEnvelope => {
+                    self.custom_envelope = None;
+                }
                 UiCommand::StartRecording { .. } | UiCommand::StopRecording => {
                     // Recording commands are handled on the UI/render thread
                 }
@@ -427,14 +692,79 @@ impl Plugin for OsciPlugin {
                         );
                     }
                 }
+                UiCommand::SetFrameReversed(reversed) => {
+                    self.synth.set_reversed(reversed);
+                }
+                UiCommand::SetSeed(seed) => match seed {
+                    Some(seed) => self.synth.set_seed(seed),
+                    None => self.synth.clear_seed(),
+                },
+                UiCommand::NoteOn { .. } | UiCommand::NoteOff { .. } => {
+                    play_note_command(&mut self.synth, &mut self.sound, &cmd);
+                }
+                UiCommand::SetMaxVoices(count) => {
+                    self.synth.set_max_voices(count);
+                }
+                UiCommand::AllNotesOff => {
+                    self.synth.all_notes_off();
+                }
+                UiCommand::SetMorph(morph) => {
+                    self.synth.set_morph(morph);
+                }
+                UiCommand::ClearMorph => {
+                    self.synth.clear_morph();
+                }
+                UiCommand::SetSampleSource(source) => {
+                    self.replace_sample_source(source);
+                }
+                UiCommand::SetMacroTargets { idx, targets } => {
+                    if let Some(m) = self.macros.get_mut(idx) {
+                        m.targets = targets;
+                        self.macros_dirty[idx] = true;
+                    }
+                }
+                UiCommand::SetProfiling(enabled) => {
+                    self.synth.set_profiling(enabled);
+                    snapshots_changed = true;
+                }
             }
         }
 
+        // A rebuilt chain starts from the stored values, so write every macro again
+        if effects_changed {
+            self.macros_dirty = [true; NUM_MACROS];
+        }
+
         // Sync effect template to all voices if anything changed
         if effects_changed {
             self.synth.set_effect_template(&self.effect_template);
+        }
 
-            // Publish updated snapshots for the UI
+        // The host parameters hold the macro values; the editor's sliders
+        // move them too. With sample-accurate automation nih-plug splits the
+        // block wherever a parameter changes, so reading them once per block
+        // follows the automation exactly.
+        for (i, param) in self.params.macros().into_iter().enumerate() {
+            let value = param.value();
+            if self.macro_host_values[i] != Some(value) {
+                self.macro_host_values[i] = Some(value);
+                self.macros[i].value = value;
+                self.macros_dirty[i] = true;
+                if let Ok(mut macros) = self.macros_ui.try_lock() {
+                    macros[i].value = value;
+                }
+            }
+        }
+        for i in 0..NUM_MACROS {
+            if std::mem::take(&mut self.macros_dirty[i])
+                && apply_macro(&mut self.effect_template, &mut self.synth, &self.macros[i])
+            {
+                snapshots_changed = true;
+            }
+        }
+
+        // Publish updated snapshots for the UI
+        if effects_changed || snapshots_changed {
             let snapshots: Vec<EffectSnapshot> = self
                 .effect_template
                 .iter()
@@ -444,7 +774,10 @@ impl Plugin for OsciPlugin {
                         .map(|entry| entry.name.to_string())
                         .unwrap_or_else(|| e.id.clone()),
                     enabled: e.enabled,
+                    solo: e.solo,
+                    reset_on_note: e.reset_on_note,
                     parameters: e.parameters.clone(),
+                    cost_ns: None,
                 })
                 .collect();
             if let Ok(mut snaps) = self.effect_snapshots.lock() {
@@ -467,6 +800,9 @@ impl Plugin for OsciPlugin {
                         &mut self.sound,
                     );
                 }
+                NoteEvent::MidiCC { cc: ALL_NOTES_OFF_CC, .. } => {
+                    self.synth.all_notes_off();
+                }
                 _ => {}
             }
         }
@@ -479,22 +815,54 @@ impl Plugin for OsciPlugin {
             );
         }
 
-        // Render audio into scratch buffers
-        self.synth.render_next_block(
-            &mut self.x_buf[..num_samples],
-            &mut self.y_buf[..num_samples],
-            &mut self.z_buf[..num_samples],
-            num_samples,
-            &mut self.sound,
-        );
+        // Follow the aux input so it can stand in for the voice envelope
+        let sidechain_level = match aux.inputs.first() {
+            Some(input) if self.params.external_sidechain.value() => {
+                let channels = input.as_slice_immutable();
+                for i in 0..input.samples() {
+                    let peak = channels.iter().fold(0.0f32, |acc, ch| acc.max(ch[i].abs()));
+                    self.sidechain.next(peak);
+                }
+                Some(self.sidechain.level().min(1.0))
+            }
+            _ => None,
+        };
+        self.synth.set_sidechain_level(sidechain_level);
+
+        // Pass the Lua sliders and drawing frequency to a loaded script
+        if let Some(source) = &mut self.sample_source {
+            if let Ok(sliders) = self.lua_sliders.try_lock() {
+                source.set_sliders(&sliders[..]);
+            }
+            source.set_frequency(frequency as f64);
+        }
 
-        // Copy to output: X -> Left, Y -> Right, apply volume
-        let output = buffer.as_slice();
-        for i in 0..num_samples {
-            output[0][i] = self.x_buf[i] * volume;
-            output[1][i] = self.y_buf[i] * volume;
+        // Render audio into scratch buffers
+        match &mut self.sample_source {
+            Some(source) => source.fill(
+                &mut self.x_buf[..num_samples],
+                &mut self.y_buf[..num_samples],
+                &mut self.z_buf[..num_samples],
+            ),
+            None => self.synth.render_next_block(
+                &mut self.x_buf[..num_samples],
+                &mut self.y_buf[..num_samples],
+                &mut self.z_buf[..num_samples],
+                num_samples,
+                &mut self.sound,
+            ),
         }
 
+        // Copy to output: X -> Left, Y -> Right, Z -> third channel if any
+        write_output(
+            buffer.as_slice(),
+            &self.x_buf[..num_samples],
+            &self.y_buf[..num_samples],
+            &self.z_buf[..num_samples],
+            volume,
+            self.params.z_output.value(),
+        );
+
         // Update vis buffer with the last VIS_BUFFER_SIZE samples
         if let Ok(mut vis) = self.vis_buffer.lock() {
             let copy_len = num_samples.min(VIS_BUFFER_SIZE);
@@ -506,10 +874,112 @@ impl Plugin for OsciPlugin {
             vis.y.extend_from_slice(&self.y_buf[src_start..src_start + copy_len]);
         }
 
+        // Publish effect costs in place, so profiling doesn't rebuild the snapshots
+        if self.synth.profiling() {
+            if let Ok(mut snaps) = self.effect_snapshots.try_lock() {
+                for (i, snap) in snaps.iter_mut().enumerate() {
+                    snap.cost_ns = self.synth.effect_cost(i).map(|ns| ns as f32);
+                }
+            }
+        }
+
+        // Publish the drawing frequency for the tuning readout and the output meters
+        if let Ok(mut info) = self.audio_info.try_lock() {
+            info.current_frequency = self.synth.current_frequency() as f32;
+            info.shape_count = self.sound.shape_count();
+            let channels = buffer.as_slice_immutable();
+            for (level, channel) in info.output_levels.iter_mut().zip(channels.iter()) {
+                *level = measure_level(channel);
+            }
+        }
+
         ProcessStatus::Normal
     }
 }
 
+/// Set every target of `definition` from the macro's value, in the template
+/// and every voice. Targets that no longer exist, e.g. after their effect
+/// was removed, are skipped. Returns whether any parameter was set.
+fn apply_macro(template: &mut [VoiceEffect], synth: &mut Synthesizer, definition: &MacroDefinition) -> bool {
+    let mut applied = false;
+    for target in &definition.targets {
+        let Some(param) = template
+            .get_mut(target.effect_idx)
+            .and_then(|e| e.parameters.get_mut(target.param_idx))
+        else {
+            continue;
+        };
+        param.set_from_normalized(target.normalized(definition.value));
+        synth.set_effect_param_value(target.effect_idx, target.param_idx, param.value);
+        applied = true;
+    }
+    applied
+}
+
+/// Copy the rendered axes to the output channels, scaled by `volume`.
+///
+/// X goes to the first channel and Y to the second. Z goes to the third
+/// channel in [`ZOutput::ThirdChannel`] mode; any other channels are silenced.
+fn write_output(
+    output: &mut [&mut [f32]],
+    x: &[f32],
+    y: &[f32],
+    z: &[f32],
+    volume: f32,
+    z_output: ZOutput,
+) {
+    for (index, channel) in output.iter_mut().enumerate() {
+        let source = match index {
+            0 => Some(x),
+            1 => Some(y),
+            2 if z_output == ZOutput::ThirdChannel => Some(z),
+            _ => None,
+        };
+        match source {
+            Some(samples) => {
+                for (out, sample) in channel.iter_mut().zip(samples) {
+                    *out = sample * volume;
+                }
+            }
+            None => channel.fill(0.0),
+        }
+    }
+}
+
+/// Publish the scope as a shared texture named `name`. The texture is set up
+/// on the first frame, once the editor's GL context exists.
+fn attach_shared_texture(scope_state: &Mutex<GpuScopeState>, name: String) {
+    let mut texture = osci_net::create_shared_texture();
+    let mut available = None;
+    if let Ok(mut state) = scope_state.lock() {
+        state.settings.shared_output = true;
+        state.frame_output = Some(Box::new(move |gl, tex, width, height| {
+            let ready = *available.get_or_insert_with(|| match texture.init(gl, &name) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::error!("Shared texture init failed: {}", e);
+                    false
+                }
+            });
+            if ready {
+                if let Err(e) = texture.send_texture(gl, tex, width, height) {
+                    log::warn!("Shared texture send failed: {}", e);
+                }
+            }
+        }));
+    }
+}
+
+/// Play a note sent by the editor's keyboard as if it came in over MIDI.
+fn play_note_command(synth: &mut Synthesizer, sound: &mut ShapeSound, command: &UiCommand) {
+    let event = match *command {
+        UiCommand::NoteOn { note, velocity } => MidiEvent::NoteOn { note, velocity },
+        UiCommand::NoteOff { note } => MidiEvent::NoteOff { note, velocity: 0.0 },
+        _ => return,
+    };
+    synth.handle_midi_event(event, sound);
+}
+
 impl ClapPlugin for OsciPlugin {
     const CLAP_ID: &'static str = "com.rusci.rusci-render";
     const CLAP_DESCRIPTION: Option<&'static str> = Some("Oscilloscope music synthesizer");
@@ -527,3 +997,114 @@ impl Vst3Plugin for OsciPlugin {
 
 nih_export_clap!(OsciPlugin);
 nih_export_vst3!(OsciPlugin);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use osci_gui::MacroTarget;
+
+    #[test]
+    fn z_reaches_third_output_in_three_channel_mode() {
+        let (x, y, z) = ([0.1, 0.2], [0.3, 0.4], [0.5, 1.0]);
+
+        let (mut left, mut right, mut third) = ([0.0; 2], [0.0; 2], [9.0; 2]);
+        let mut output: [&mut [f32]; 3] = [&mut left, &mut right, &mut third];
+        write_output(&mut output, &x, &y, &z, 2.0, ZOutput::ThirdChannel);
+        assert_eq!(left, [0.2, 0.4]);
+        assert_eq!(right, [0.6, 0.8]);
+        assert_eq!(third, [1.0, 2.0]);
+
+        let mut output: [&mut [f32]; 3] = [&mut left, &mut right, &mut third];
+        write_output(&mut output, &x, &y, &z, 2.0, ZOutput::Off);
+        assert_eq!(third, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn keyboard_note_commands_play_voices() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        // No release tail, so note-off frees the voice on the next block
+        synth.set_adsr(osci_core::Env::adsr(0.0, 0.0, 1.0, 0.0, 1.0, -4.0));
+        let mut sound = ShapeSound::new(4);
+        let line = osci_core::Line::new_2d(-1.0, 0.0, 1.0, 0.0);
+        sound.sender().send(vec![Box::new(line)]).unwrap();
+        sound.update_frame();
+
+        play_note_command(&mut synth, &mut sound, &UiCommand::NoteOn { note: 60, velocity: 0.8 });
+        assert_eq!(synth.active_voice_count(), 1);
+
+        // Commands that aren't notes are ignored
+        play_note_command(&mut synth, &mut sound, &UiCommand::ClearProject);
+        assert_eq!(synth.active_voice_count(), 1);
+
+        play_note_command(&mut synth, &mut sound, &UiCommand::NoteOff { note: 60 });
+        let mut buf = vec![0.0f32; 64];
+        let (mut y, mut z) = (buf.clone(), buf.clone());
+        synth.render_next_block(&mut buf, &mut y, &mut z, 64, &mut sound);
+        assert_eq!(synth.active_voice_count(), 0);
+    }
+
+    fn effect(id: &str) -> VoiceEffect {
+        let entry = find_effect(id).unwrap();
+        VoiceEffect::new(entry.id, (entry.constructor)(), (entry.parameters)())
+    }
+
+    #[test]
+    fn macro_automation_reaches_target_parameter() {
+        let mut synth = Synthesizer::new(2, 44100.0);
+        let mut template = vec![effect("scale")];
+        synth.set_effect_template(&template);
+        let mut definition = MacroDefinition {
+            value: 0.75,
+            targets: vec![MacroTarget::full_range(0, 1)],
+        };
+
+        assert!(apply_macro(&mut template, &mut synth, &definition));
+        let param = &template[0].parameters[1];
+        let expected = param.min + 0.75 * (param.max - param.min);
+        assert!((param.value - expected).abs() < 1e-6);
+        // Every voice's chain follows the template
+        for i in 0..synth.num_voices() {
+            let voice_param = &synth.voice_mut(i).unwrap().effects[0].parameters[1];
+            assert_eq!(voice_param.value, param.value);
+        }
+        // The other parameters are left alone
+        assert_eq!(template[0].parameters[0].value, template[0].parameters[0].default_value);
+
+        definition.targets = vec![MacroTarget::full_range(3, 0)];
+        assert!(!apply_macro(&mut template, &mut synth, &definition));
+    }
+
+    #[test]
+    fn macro_sweeps_each_target_over_its_range() {
+        let mut synth = Synthesizer::new(2, 44100.0);
+        let mut template = vec![effect("vortex"), effect("swirl")];
+        synth.set_effect_template(&template);
+        // Vortex over its whole range, Swirl over the middle part, backwards
+        let mut definition = MacroDefinition {
+            value: 0.0,
+            targets: vec![
+                MacroTarget::full_range(0, 0),
+                MacroTarget { effect_idx: 1, param_idx: 0, min: 0.75, max: 0.25 },
+            ],
+        };
+
+        // Both parameters run from -1 to 1
+        for (value, vortex, swirl) in [(0.0, -1.0, 0.5), (0.5, 0.0, 0.0), (1.0, 1.0, -0.5)] {
+            definition.value = value;
+            assert!(apply_macro(&mut template, &mut synth, &definition));
+            assert!((template[0].parameters[0].value - vortex).abs() < 1e-6);
+            assert!((template[1].parameters[0].value - swirl).abs() < 1e-6);
+            let voice = synth.voice_mut(1).unwrap();
+            assert_eq!(voice.effects[1].parameters[0].value, template[1].parameters[0].value);
+        }
+    }
+
+    #[test]
+    fn stereo_output_ignores_z() {
+        let (mut left, mut right) = ([0.0; 2], [0.0; 2]);
+        let mut output: [&mut [f32]; 2] = [&mut left, &mut right];
+        write_output(&mut output, &[1.0, -1.0], &[0.5, -0.5], &[0.25, 0.25], 1.0, ZOutput::ThirdChannel);
+        assert_eq!(left, [1.0, -1.0]);
+        assert_eq!(right, [0.5, -0.5]);
+    }
+}
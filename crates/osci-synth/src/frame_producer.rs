@@ -4,13 +4,25 @@
 //! Mirrors the C++ `FrameProducer` class. A parser produces frames on a
 //! background thread, the audio thread consumes them from a `ShapeSound`
 //! queue without blocking.
+//!
+//! With [`FrameProducer::start_recycling`] the frames the audio thread has
+//! finished with come back through a second channel and are refilled in
+//! place. Sources copy their shapes into the boxes already in the frame
+//! where they can, so in steady state neither the frame vectors nor the
+//! shapes are reallocated, and whatever is dropped is dropped on the
+//! producer thread rather than the audio thread.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::{Receiver, Sender};
+use osci_core::shape::{clone_shapes_into, Shape};
 
-use crossbeam::channel::Sender;
-use osci_core::shape::Shape;
+use crate::transport::{BeatSync, TransportClock};
+
+pub use osci_core::morph::ShapeMorph;
 
 /// A frame is a vector of boxed shapes.
 pub type Frame = Vec<Box<dyn Shape>>;
@@ -23,6 +35,23 @@ pub type Frame = Vec<Box<dyn Shape>>;
 pub trait FrameSource: Send + 'static {
     /// Generate the next frame. Returns `None` to signal shutdown.
     fn next_frame(&mut self) -> Option<Frame>;
+
+    /// Refill `frame` in place with the next frame. Returns `false` to
+    /// signal shutdown.
+    ///
+    /// `frame` may be a recycled buffer still holding an earlier frame's
+    /// shapes. The default replaces it with [`FrameSource::next_frame`];
+    /// sources override this to reuse the vector's capacity and, with
+    /// [`clone_shapes_into`], the boxes it holds.
+    fn fill_frame(&mut self, frame: &mut Frame) -> bool {
+        match self.next_frame() {
+            Some(next) => {
+                *frame = next;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 /// A simple frame source that repeatedly clones a static set of shapes.
@@ -38,46 +67,205 @@ impl StaticFrameSource {
 
 impl FrameSource for StaticFrameSource {
     fn next_frame(&mut self) -> Option<Frame> {
-        let cloned: Frame = self.shapes.iter().map(|s| s.clone_shape()).collect();
-        Some(cloned)
+        let mut frame = Vec::with_capacity(self.shapes.len());
+        self.fill_frame(&mut frame);
+        Some(frame)
+    }
+
+    fn fill_frame(&mut self, frame: &mut Frame) -> bool {
+        clone_shapes_into(&self.shapes, frame);
+        true
+    }
+}
+
+/// Slowest playback rate of an [`AnimatedFrameSource`], in frames per second.
+pub const MIN_FRAME_RATE: f64 = 1.0;
+
+/// Fastest playback rate of an [`AnimatedFrameSource`], in frames per second.
+pub const MAX_FRAME_RATE: f64 = 120.0;
+
+/// Rate used when an animation doesn't give a usable one, as GPLA files do.
+pub const DEFAULT_FRAME_RATE: f64 = 24.0;
+
+/// `rate` limited to [`MIN_FRAME_RATE`]..=[`MAX_FRAME_RATE`], or
+/// [`DEFAULT_FRAME_RATE`] if it isn't a number.
+pub fn clamp_frame_rate(rate: f64) -> f64 {
+    if rate.is_nan() {
+        DEFAULT_FRAME_RATE
+    } else {
+        rate.clamp(MIN_FRAME_RATE, MAX_FRAME_RATE)
     }
 }
 
 /// A frame source that cycles through pre-parsed animation frames.
+///
+/// Frames are produced no faster than the frame rate: each call waits until
+/// one frame period after the previous one. By default each call moves on
+/// to the next frame. After [`AnimatedFrameSource::sync_to_transport`] the
+/// frame follows the host's beat position instead and holds while the
+/// transport is stopped.
 pub struct AnimatedFrameSource {
     frames: Vec<Frame>,
     current_frame: usize,
     frame_rate: f64,
+    next_deadline: Option<Instant>,
+    beat_sync: Option<(Arc<TransportClock>, BeatSync)>,
 }
 
 impl AnimatedFrameSource {
+    /// Play `frames` at `frame_rate` frames per second, clamped by
+    /// [`clamp_frame_rate`].
     pub fn new(frames: Vec<Frame>, frame_rate: f64) -> Self {
         Self {
             frames,
             current_frame: 0,
-            frame_rate,
+            frame_rate: clamp_frame_rate(frame_rate),
+            next_deadline: None,
+            beat_sync: None,
         }
     }
 
+    /// The playback rate in frames per second, after clamping.
+    pub fn frame_rate(&self) -> f64 {
+        self.frame_rate
+    }
+
+    /// Change the playback rate, clamped by [`clamp_frame_rate`]. Takes
+    /// effect from the frame after the next one.
+    pub fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = clamp_frame_rate(frame_rate);
+    }
+
+    /// Advance one frame every `beats_per_frame` beats of `clock`.
+    pub fn sync_to_transport(&mut self, clock: Arc<TransportClock>, beats_per_frame: f64) {
+        self.beat_sync = Some((clock, BeatSync::new(beats_per_frame)));
+    }
+
+    /// Go back to advancing one frame per call.
+    pub fn unsync(&mut self) {
+        self.beat_sync = None;
+    }
+
     pub fn set_frame(&mut self, index: usize) {
         if !self.frames.is_empty() {
             self.current_frame = index % self.frames.len();
         }
     }
+
+    /// Sleep until the next frame is due. A frame that comes late pushes
+    /// the following ones back rather than bunching them up to catch up.
+    fn wait_for_next_frame(&mut self) {
+        let now = Instant::now();
+        let due = self.next_deadline.unwrap_or(now);
+        if due > now {
+            thread::sleep(due - now);
+        }
+        let period = Duration::from_secs_f64(1.0 / self.frame_rate);
+        self.next_deadline = Some(due.max(now) + period);
+    }
 }
 
 impl FrameSource for AnimatedFrameSource {
     fn next_frame(&mut self) -> Option<Frame> {
+        let mut frame = Vec::new();
+        if self.fill_frame(&mut frame) {
+            Some(frame)
+        } else {
+            None
+        }
+    }
+
+    fn fill_frame(&mut self, frame: &mut Frame) -> bool {
         if self.frames.is_empty() {
-            return None;
+            return false;
         }
-        let frame: Frame = self.frames[self.current_frame]
-            .iter()
-            .map(|s| s.clone_shape())
-            .collect();
-        self.current_frame = (self.current_frame + 1) % self.frames.len();
+        self.wait_for_next_frame();
+        if let Some((clock, sync)) = &self.beat_sync {
+            if let Some(index) = sync.frame_index(&clock.get(), self.frames.len()) {
+                self.current_frame = index;
+            }
+        }
+
+        clone_shapes_into(&self.frames[self.current_frame], frame);
+        if self.beat_sync.is_none() {
+            self.current_frame = (self.current_frame + 1) % self.frames.len();
+        }
+        true
+    }
+}
+
+/// What a [`MorphFrameSource`] does once it reaches the second frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MorphMode {
+    /// Stay on the second frame.
+    #[default]
+    Hold,
+    /// Morph back to the first frame, then forward again, forever.
+    PingPong,
+}
+
+/// A frame source that crossfades vertex positions from one frame to
+/// another over `duration` seconds, pairing shapes as [`ShapeMorph`] does.
+pub struct MorphFrameSource {
+    morph: ShapeMorph,
+    position: f64,
+    step: f64,
+    forward: bool,
+    mode: MorphMode,
+}
+
+impl MorphFrameSource {
+    /// Morph from `from` to `to` over `duration` seconds, at `frame_rate`
+    /// frames per second.
+    pub fn new(from: &Frame, to: &Frame, duration: f64, frame_rate: f64) -> Self {
+        let frames = duration * frame_rate;
+        Self {
+            morph: ShapeMorph::new(from, to),
+            position: 0.0,
+            step: if frames > 0.0 { 1.0 / frames } else { 1.0 },
+            forward: true,
+            mode: MorphMode::Hold,
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: MorphMode) {
+        self.mode = mode;
+    }
+
+    /// The morph `t` of the way from the first frame (0) to the second (1).
+    pub fn frame_at(&self, t: f32) -> Frame {
+        self.morph.frame_at(t)
+    }
+
+    fn advance(&mut self) {
+        if self.forward {
+            self.position += self.step;
+            if self.position >= 1.0 {
+                self.position = 1.0;
+                self.forward = self.mode == MorphMode::Hold;
+            }
+        } else {
+            self.position -= self.step;
+            if self.position <= 0.0 {
+                self.position = 0.0;
+                self.forward = true;
+            }
+        }
+    }
+}
+
+impl FrameSource for MorphFrameSource {
+    fn next_frame(&mut self) -> Option<Frame> {
+        let mut frame = Vec::new();
+        self.fill_frame(&mut frame);
         Some(frame)
     }
+
+    fn fill_frame(&mut self, frame: &mut Frame) -> bool {
+        self.morph.fill_at(self.position as f32, frame);
+        self.advance();
+        true
+    }
 }
 
 /// Background frame producer thread.
@@ -94,7 +282,29 @@ impl FrameProducer {
     ///
     /// Frames are sent to `frame_tx`. The producer runs until `stop()` is
     /// called or the channel is disconnected.
-    pub fn start(mut source: impl FrameSource, frame_tx: Sender<Frame>) -> Self {
+    pub fn start(source: impl FrameSource, frame_tx: Sender<Frame>) -> Self {
+        Self::spawn(source, frame_tx, None)
+    }
+
+    /// Start producing frames, refilling buffers returned on `recycled`.
+    ///
+    /// Typically `recycled` is [`ShapeSound::recycler`](crate::ShapeSound::recycler).
+    /// Each frame is built in a recycled buffer when one is available and a
+    /// new one otherwise, so once the pipeline is primed the same few
+    /// buffers circulate indefinitely.
+    pub fn start_recycling(
+        source: impl FrameSource,
+        frame_tx: Sender<Frame>,
+        recycled: Receiver<Frame>,
+    ) -> Self {
+        Self::spawn(source, frame_tx, Some(recycled))
+    }
+
+    fn spawn(
+        mut source: impl FrameSource,
+        frame_tx: Sender<Frame>,
+        recycled: Option<Receiver<Frame>>,
+    ) -> Self {
         let running = Arc::new(AtomicBool::new(true));
         let running_clone = running.clone();
 
@@ -102,19 +312,21 @@ impl FrameProducer {
             .name("frame-producer".to_string())
             .spawn(move || {
                 while running_clone.load(Ordering::Relaxed) {
-                    match source.next_frame() {
-                        Some(frame) => {
-                            // Send will block if the queue is full, providing
-                            // natural backpressure
-                            if frame_tx.send(frame).is_err() {
-                                // Channel disconnected, stop producing
-                                break;
-                            }
-                        }
-                        None => {
-                            // Source exhausted, stop
-                            break;
-                        }
+                    let mut frame = recycled
+                        .as_ref()
+                        .and_then(|rx| rx.try_recv().ok())
+                        .unwrap_or_default();
+
+                    if !source.fill_frame(&mut frame) {
+                        // Source exhausted, stop
+                        break;
+                    }
+
+                    // Send will block if the queue is full, providing
+                    // natural backpressure
+                    if frame_tx.send(frame).is_err() {
+                        // Channel disconnected, stop producing
+                        break;
                     }
                 }
             })
@@ -151,6 +363,7 @@ mod tests {
     use super::*;
     use crossbeam::channel::bounded;
     use osci_core::shape::Line;
+    use osci_core::Point;
 
     #[test]
     fn test_static_frame_source() {
@@ -178,6 +391,114 @@ mod tests {
         assert!((p1.x - p3.x).abs() < 0.001);
     }
 
+    #[test]
+    fn test_animated_frame_rate_is_clamped() {
+        let frames = || vec![vec![Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0)) as Box<dyn Shape>]];
+
+        let mut source = AnimatedFrameSource::new(frames(), 24.0);
+        assert_eq!(source.frame_rate(), 24.0);
+        source.set_frame_rate(60.0);
+        assert_eq!(source.frame_rate(), 60.0);
+
+        source.set_frame_rate(0.0);
+        assert_eq!(source.frame_rate(), MIN_FRAME_RATE);
+        source.set_frame_rate(1000.0);
+        assert_eq!(source.frame_rate(), MAX_FRAME_RATE);
+        source.set_frame_rate(f64::NAN);
+        assert_eq!(source.frame_rate(), DEFAULT_FRAME_RATE);
+        assert_eq!(AnimatedFrameSource::new(frames(), -5.0).frame_rate(), MIN_FRAME_RATE);
+    }
+
+    #[test]
+    fn test_animated_frame_source_paces_on_frame_rate() {
+        let frames = vec![vec![Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0)) as Box<dyn Shape>]];
+        let mut source = AnimatedFrameSource::new(frames, 50.0);
+
+        // The first frame is immediate, the next four are 20ms apart
+        let start = Instant::now();
+        for _ in 0..5 {
+            source.next_frame().unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(80), "{:?}", start.elapsed());
+    }
+
+    #[test]
+    fn test_animated_frame_source_follows_transport() {
+        use crate::transport::TransportInfo;
+
+        let frames: Vec<Frame> = (0..4)
+            .map(|i| vec![Box::new(Line::new_2d(0.0, 0.0, i as f32, 0.0)) as Box<dyn Shape>])
+            .collect();
+        let clock = Arc::new(TransportClock::new());
+        let mut source = AnimatedFrameSource::new(frames, 30.0);
+        source.sync_to_transport(clock.clone(), 1.0);
+
+        let shown = |source: &mut AnimatedFrameSource| source.next_frame().unwrap()[0].next_vector(1.0).x;
+
+        clock.update(TransportInfo { playing: true, pos_beats: Some(2.5) });
+        assert_eq!(shown(&mut source), 2.0);
+        // Repeated calls within the beat keep the same frame
+        assert_eq!(shown(&mut source), 2.0);
+
+        clock.update(TransportInfo { playing: false, pos_beats: Some(0.0) });
+        assert_eq!(shown(&mut source), 2.0);
+
+        clock.update(TransportInfo { playing: true, pos_beats: Some(3.0) });
+        assert_eq!(shown(&mut source), 3.0);
+    }
+
+    #[test]
+    fn test_morph_frame_source_interpolates_vertices() {
+        let from: Frame = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0)),
+            Box::new(Line::new_2d(1.0, 0.0, 1.0, 1.0)),
+        ];
+        let to: Frame = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 0.0, 1.0)),
+            Box::new(Line::new_2d(0.0, 1.0, -1.0, -1.0)),
+        ];
+        let source = MorphFrameSource::new(&from, &to, 1.0, 30.0);
+
+        let ends = |frame: &Frame| -> Vec<(Point, Point)> {
+            frame.iter().map(|s| (s.next_vector(0.0), s.next_vector(1.0))).collect()
+        };
+        assert_eq!(ends(&source.frame_at(0.0)), ends(&from));
+        assert_eq!(ends(&source.frame_at(1.0)), ends(&to));
+
+        let mid = ends(&source.frame_at(0.5));
+        for (i, (start, end)) in mid.iter().enumerate() {
+            let (a, b) = (&ends(&from)[i], &ends(&to)[i]);
+            assert!(start.approx_eq(&((a.0 + b.0) * 0.5)));
+            assert!(end.approx_eq(&((a.1 + b.1) * 0.5)));
+        }
+    }
+
+    #[test]
+    fn test_morph_frame_source_holds_or_ping_pongs() {
+        let from: Frame = vec![Box::new(Line::new_2d(0.0, 0.0, 0.0, 0.0))];
+        let to: Frame = vec![Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0))];
+        // Two frames from start to end
+        let end_x = |source: &mut MorphFrameSource| source.next_frame().unwrap()[0].next_vector(1.0).x;
+
+        let mut hold = MorphFrameSource::new(&from, &to, 2.0, 1.0);
+        let xs: Vec<f32> = (0..5).map(|_| end_x(&mut hold)).collect();
+        assert_eq!(xs, [0.0, 0.5, 1.0, 1.0, 1.0]);
+
+        let mut ping_pong = MorphFrameSource::new(&from, &to, 2.0, 1.0);
+        ping_pong.set_mode(MorphMode::PingPong);
+        let xs: Vec<f32> = (0..6).map(|_| end_x(&mut ping_pong)).collect();
+        assert_eq!(xs, [0.0, 0.5, 1.0, 0.5, 0.0, 0.5]);
+
+        // Different structures are resampled onto one path
+        let square: Frame = (0..4)
+            .map(|i| Box::new(Line::new_2d(i as f32, 0.0, i as f32 + 1.0, 0.0)) as Box<dyn Shape>)
+            .collect();
+        let morph = MorphFrameSource::new(&to, &square, 1.0, 30.0);
+        assert_eq!(morph.frame_at(0.0).len(), osci_core::morph::MORPH_RESAMPLE_POINTS - 1);
+        let last = morph.frame_at(1.0).last().unwrap().next_vector(1.0);
+        assert!(last.approx_eq(&Point::xy(4.0, 0.0)));
+    }
+
     #[test]
     fn test_frame_producer_lifecycle() {
         let shapes: Frame = vec![
@@ -196,4 +517,32 @@ mod tests {
         producer.stop();
         assert!(!producer.is_running());
     }
+
+    #[test]
+    fn test_frame_producer_recycles_buffers() {
+        let shapes: Frame = vec![
+            Box::new(Line::new_2d(-1.0, -1.0, 1.0, 1.0)),
+            Box::new(Line::new_2d(1.0, -1.0, -1.0, 1.0)),
+        ];
+        let source = StaticFrameSource::new(shapes);
+        let (tx, rx) = bounded(2);
+        let (recycle_tx, recycle_rx) = bounded(3);
+
+        let mut producer = FrameProducer::start_recycling(source, tx, recycle_rx);
+
+        // Consume frames and hand each one straight back, as ShapeSound does
+        let mut buffers = std::collections::HashSet::new();
+        for _ in 0..200 {
+            let frame = rx.recv().unwrap();
+            assert_eq!(frame.len(), 2);
+            buffers.insert(frame.as_ptr() as usize);
+            recycle_tx.send(frame).unwrap();
+        }
+
+        // Only the frame vectors needed to prime the pipeline were ever allocated
+        assert!(buffers.len() <= 5, "{} distinct buffers", buffers.len());
+
+        drop(rx);
+        producer.stop();
+    }
 }
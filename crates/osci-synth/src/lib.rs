@@ -3,9 +3,17 @@ pub mod sound;
 pub mod voice;
 pub mod synthesizer;
 pub mod frame_producer;
+pub mod sidechain;
+pub mod transport;
+pub mod tuning;
 
 pub use renderer::ShapeRenderer;
-pub use sound::ShapeSound;
-pub use voice::{ShapeVoice, VoiceEffect};
-pub use synthesizer::{Synthesizer, MidiEvent};
-pub use frame_producer::{FrameProducer, FrameSource, StaticFrameSource, AnimatedFrameSource};
+pub use sound::{isolate_shape, ShapeSound};
+pub use voice::{EffectCost, MorphBlock, ShapeVoice, VoiceBlock, VoiceEffect};
+pub use synthesizer::{Synthesizer, MidiEvent, DEFAULT_MAX_VOICES, MAX_VOICES};
+pub use frame_producer::{
+    FrameProducer, FrameSource, StaticFrameSource, AnimatedFrameSource, MorphFrameSource, MorphMode, ShapeMorph,
+    clamp_frame_rate, MIN_FRAME_RATE, MAX_FRAME_RATE, DEFAULT_FRAME_RATE,
+};
+pub use transport::{BeatSync, TransportClock, TransportInfo};
+pub use tuning::Tuning;
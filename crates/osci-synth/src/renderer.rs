@@ -1,5 +1,16 @@
 use osci_core::shape::Shape;
-use osci_core::Point;
+use osci_core::{Env, Point};
+
+/// Gap below which one shape's end and the next one's start count as joined,
+/// so no blanking is inserted between them.
+const CONTIGUOUS_EPSILON: f32 = 1e-4;
+
+/// Slowest relative speed a speed curve can slow the beam to, so a curve
+/// touching zero can't stall it.
+const MIN_SPEED: f64 = 0.05;
+
+/// Steps used to average a speed curve over the frame.
+const SPEED_CURVE_STEPS: usize = 256;
 
 /// Shape vector renderer — walks through a list of shapes, sampling points
 /// along each shape at a rate determined by the drawing frequency.
@@ -7,6 +18,28 @@ use osci_core::Point;
 /// Ported from C++ `ShapeVectorRenderer`. Given a frequency and sample rate,
 /// the renderer computes how far along the total frame to advance each sample,
 /// then interpolates the appropriate shape at the appropriate progress.
+///
+/// With [`ShapeRenderer::set_blanking_samples`] set, each jump to a shape
+/// that doesn't start where the previous one ended is preceded by that many
+/// samples at the new start with brightness and colour forced to zero, so the
+/// beam's transit isn't drawn. Z then carries brightness on every path: 1 on
+/// lit samples and 0 while blanked. Without blanking,
+/// [`ShapeRenderer::next_vector_with_increment`] passes the shapes' own Z
+/// through for 3D effects.
+///
+/// With [`ShapeRenderer::set_draw_rate_hz`] set, the frame is traced at the
+/// draw rate while the frequency only sets how fast each shape is retraced.
+///
+/// With [`ShapeRenderer::set_speed_curve`] set, the beam speeds up and slows
+/// down along the frame, dwelling (and so glowing brighter) where it's slow.
+///
+/// With [`ShapeRenderer::set_ping_pong`] set, the beam traces the frame
+/// forward, then backward from its end, and so on, instead of jumping back
+/// to the first shape.
+///
+/// With [`ShapeRenderer::set_reversed`] set, every frame is traced from its
+/// last shape to its first, each from end to start, without touching the
+/// shapes themselves.
 pub struct ShapeRenderer {
     sample_rate: f64,
     frequency: f64,
@@ -16,6 +49,52 @@ pub struct ShapeRenderer {
     current_shape: usize,
     shape_drawn: f64,
     frame_drawn: f64,
+
+    draw_rate_hz: Option<f64>,
+    /// Progress through the current shape while a draw rate is set.
+    shape_phase: f64,
+
+    blanking_samples: usize,
+    blanking_remaining: usize,
+    blanking_point: Point,
+
+    speed_curve: Option<SpeedCurve>,
+
+    ping_pong: bool,
+    /// Whether the current pass runs against the drawing direction.
+    backward: bool,
+    /// Whether the drawing direction is from the last shape to the first.
+    reversed: bool,
+}
+
+/// A speed curve with the factor that keeps the frame period unchanged.
+#[derive(Clone)]
+struct SpeedCurve {
+    env: Env,
+    normalization: f64,
+}
+
+impl SpeedCurve {
+    fn new(env: Env) -> Self {
+        let mut curve = Self {
+            env,
+            normalization: 1.0,
+        };
+        // Time to trace the frame is the mean of 1 / speed over it; scale
+        // the speed by that so one frame still takes one period.
+        let mean_slowness = (0..SPEED_CURVE_STEPS)
+            .map(|i| 1.0 / curve.speed((i as f64 + 0.5) / SPEED_CURVE_STEPS as f64))
+            .sum::<f64>()
+            / SPEED_CURVE_STEPS as f64;
+        curve.normalization = mean_slowness;
+        curve
+    }
+
+    /// Relative speed at `position` in [0, 1] through the frame.
+    fn speed(&self, position: f64) -> f64 {
+        let time = position * self.env.duration();
+        (self.env.lookup(time as f32) as f64).max(MIN_SPEED) * self.normalization
+    }
 }
 
 impl ShapeRenderer {
@@ -28,16 +107,77 @@ impl ShapeRenderer {
             current_shape: 0,
             shape_drawn: 0.0,
             frame_drawn: 0.0,
+            draw_rate_hz: None,
+            shape_phase: 0.0,
+            blanking_samples: 0,
+            blanking_remaining: 0,
+            blanking_point: Point::ZERO,
+            speed_curve: None,
+            ping_pong: false,
+            backward: false,
+            reversed: false,
+        }
+    }
+
+    /// A renderer with the same settings but no shapes and nothing drawn.
+    pub fn idle_copy(&self) -> Self {
+        Self {
+            draw_rate_hz: self.draw_rate_hz,
+            blanking_samples: self.blanking_samples,
+            speed_curve: self.speed_curve.clone(),
+            ping_pong: self.ping_pong,
+            reversed: self.reversed,
+            ..Self::new(self.sample_rate, self.frequency)
         }
     }
 
     /// Replace the current shapes with new ones and reset drawing state.
-    pub fn set_shapes(&mut self, shapes: Vec<Box<dyn Shape>>) {
+    /// In ping-pong mode the pass keeps its direction.
+    ///
+    /// Returns the previous shapes so their storage can be reused.
+    pub fn set_shapes(&mut self, shapes: Vec<Box<dyn Shape>>) -> Vec<Box<dyn Shape>> {
         self.shapes_length = osci_core::shape::total_length(&shapes) as f64;
-        self.shapes = shapes;
-        self.current_shape = 0;
+        let previous = std::mem::replace(&mut self.shapes, shapes);
+        self.current_shape = self.first_shape();
         self.shape_drawn = 0.0;
         self.frame_drawn = 0.0;
+
+        // A jump still pending from the old frame now lands on the new one
+        if self.blanking_remaining > 0 {
+            match self.shapes.get(self.current_shape) {
+                Some(shape) => self.blanking_point = blank(shape.next_vector(self.directed(0.0) as f32)),
+                None => self.blanking_remaining = 0,
+            }
+        }
+        previous
+    }
+
+    /// Jump to `fraction` of the way through the frame, wrapped into
+    /// `[0, 1)`, on a forward pass. The jump isn't blanked, so call it
+    /// before drawing starts.
+    pub fn seek(&mut self, fraction: f64) {
+        self.backward = false;
+        self.current_shape = self.first_shape();
+        self.shape_drawn = 0.0;
+        self.frame_drawn = 0.0;
+        self.shape_phase = 0.0;
+        if self.shapes_length <= 0.0 {
+            return;
+        }
+
+        let mut remaining = fraction.rem_euclid(1.0) * self.shapes_length;
+        self.frame_drawn = remaining;
+        let count = self.shapes.len();
+        for step in 0..count {
+            let i = if self.reversed { count - 1 - step } else { step };
+            let length = self.shapes[i].length() as f64;
+            if remaining <= length || step + 1 == count {
+                self.current_shape = i;
+                self.shape_drawn = remaining.min(length);
+                break;
+            }
+            remaining -= length;
+        }
     }
 
     /// Set the sample rate.
@@ -45,11 +185,92 @@ impl ShapeRenderer {
         self.sample_rate = sample_rate;
     }
 
+    /// Set how many blanked samples to emit before each disconnected shape.
+    ///
+    /// Zero (the default) disables blanking.
+    pub fn set_blanking_samples(&mut self, samples: usize) {
+        self.blanking_samples = samples;
+        self.blanking_remaining = self.blanking_remaining.min(samples);
+    }
+
+    /// Number of blanked samples emitted before each disconnected shape.
+    pub fn blanking_samples(&self) -> usize {
+        self.blanking_samples
+    }
+
     /// Set the drawing frequency.
     pub fn set_frequency(&mut self, frequency: f64) {
         self.frequency = frequency;
     }
 
+    /// Trace the whole frame `hz` times a second, independent of the
+    /// frequency. Zero or less restores the default of one trace per period.
+    ///
+    /// While a draw rate is set, the frequency is the pitch: the current
+    /// shape is retraced once per period until the draw position moves on
+    /// to the next one, so the output keeps its pitch while the frame
+    /// refreshes at the draw rate.
+    pub fn set_draw_rate_hz(&mut self, hz: f64) {
+        self.draw_rate_hz = (hz > 0.0).then_some(hz);
+    }
+
+    /// The draw rate, or `None` when the frame is traced once per period.
+    pub fn draw_rate_hz(&self) -> Option<f64> {
+        self.draw_rate_hz
+    }
+
+    /// Warp the speed the beam moves along the frame.
+    ///
+    /// The curve's time axis is stretched over one frame and its level is
+    /// the relative speed at that point, so an envelope that's low at both
+    /// ends and high in the middle eases in and out of every frame, dwelling
+    /// on its start and end. Speeds are rescaled so a frame still takes the
+    /// same time to trace, and floored at a small minimum. `None` restores
+    /// constant speed.
+    pub fn set_speed_curve(&mut self, curve: Option<Env>) {
+        self.speed_curve = curve.map(SpeedCurve::new);
+    }
+
+    /// Trace the frame alternately forward and backward, turning around at
+    /// each end instead of jumping back to the first shape. Turning it off
+    /// carries on forward from the current position.
+    pub fn set_ping_pong(&mut self, ping_pong: bool) {
+        self.ping_pong = ping_pong;
+        if !ping_pong && self.backward {
+            // Keep drawing the same point of the same shape, now forward
+            if let Some(shape) = self.shapes.get(self.current_shape) {
+                self.shape_drawn = (shape.length() as f64 - self.shape_drawn).max(0.0);
+            }
+            self.shape_phase = 1.0 - self.shape_phase;
+            self.backward = false;
+        }
+    }
+
+    /// Whether the frame is traced alternately forward and backward.
+    pub fn ping_pong(&self) -> bool {
+        self.ping_pong
+    }
+
+    /// Trace frames from the last shape to the first, each from its end to
+    /// its start. The beam carries on from the point it's drawing, now in
+    /// the other direction.
+    pub fn set_reversed(&mut self, reversed: bool) {
+        if reversed == self.reversed {
+            return;
+        }
+        self.reversed = reversed;
+        if let Some(shape) = self.shapes.get(self.current_shape) {
+            self.shape_drawn = (shape.length() as f64 - self.shape_drawn).max(0.0);
+        }
+        self.shape_phase = 1.0 - self.shape_phase;
+        self.frame_drawn = (self.shapes_length - self.frame_drawn).max(0.0);
+    }
+
+    /// Whether frames are traced from the last shape to the first.
+    pub fn reversed(&self) -> bool {
+        self.reversed
+    }
+
     /// Get the total frame length.
     pub fn frame_length(&self) -> f64 {
         self.shapes_length
@@ -69,22 +290,22 @@ impl ShapeRenderer {
             return Point::new(0.0, 0.0, 1.0);
         }
 
-        let point = if self.current_shape < self.shapes.len() {
-            let shape = &self.shapes[self.current_shape];
-            let length = shape.length() as f64;
-            let progress = if length == 0.0 { 1.0 } else { self.shape_drawn / length };
-            let mut p = shape.next_vector(progress as f32);
-            p.z = 1.0;
-            p
-        } else {
-            Point::new(0.0, 0.0, 1.0)
-        };
+        if let Some(point) = self.next_blanked() {
+            return point;
+        }
+
+        let mut point = self.current_point();
+        point.z = 1.0;
 
         self.increment_shape_drawing();
 
         if self.frame_drawn >= self.shapes_length {
             self.frame_drawn -= self.shapes_length;
-            self.current_shape = 0;
+            let previous_shape = self.current_shape;
+            self.start_pass();
+            if self.blanking_samples > 0 && previous_shape != self.current_shape {
+                self.start_blanking(previous_shape);
+            }
         }
 
         point
@@ -99,16 +320,16 @@ impl ShapeRenderer {
             return Point::new(0.0, 0.0, 1.0);
         }
 
-        let point = if self.current_shape < self.shapes.len() {
-            let shape = &self.shapes[self.current_shape];
-            let length = shape.length() as f64;
-            let progress = if length == 0.0 { 1.0 } else { self.shape_drawn / length };
-            shape.next_vector(progress as f32)
-        } else {
-            Point::new(0.0, 0.0, 1.0)
-        };
+        if let Some(point) = self.next_blanked() {
+            return point;
+        }
 
-        self.increment_with(length_increment);
+        let mut point = self.current_point();
+        if self.blanking_samples > 0 {
+            point.z = 1.0;
+        }
+
+        self.advance(length_increment);
 
         point
     }
@@ -119,12 +340,96 @@ impl ShapeRenderer {
         self.frame_drawn >= self.shapes_length && self.shapes_length > 0.0
     }
 
-    /// Reset the frame-drawn counter after updating shapes.
+    /// Move on to `shapes` once the current frame is complete, carrying
+    /// over any distance drawn past its end so the next frame completes one
+    /// period later. Returns the previous shapes, as
+    /// [`ShapeRenderer::set_shapes`] does.
+    pub fn continue_with_shapes(&mut self, shapes: Vec<Box<dyn Shape>>) -> Vec<Box<dyn Shape>> {
+        let overshoot = (self.frame_drawn - self.shapes_length).max(0.0);
+        let previous = self.set_shapes(shapes);
+        self.start_pass();
+        self.frame_drawn = overshoot;
+        self.shape_drawn = overshoot;
+        previous
+    }
+
+    /// Reset the frame-drawn counter after updating shapes, and start the
+    /// next pass.
     pub fn reset_frame_drawn(&mut self) {
         if self.shapes_length > 0.0 {
             self.frame_drawn -= self.shapes_length;
         }
-        self.current_shape = 0;
+        self.start_pass();
+    }
+
+    /// Whether the current pass runs from the last shape to the first.
+    fn tracing_backward(&self) -> bool {
+        self.backward != self.reversed
+    }
+
+    /// Index of the shape a pass starts on: the last one on a backward pass.
+    fn first_shape(&self) -> usize {
+        if self.tracing_backward() {
+            self.shapes.len().saturating_sub(1)
+        } else {
+            0
+        }
+    }
+
+    /// The shape after the current one in the direction of the pass, or
+    /// `None` at the end of a ping-pong pass, where the beam waits for
+    /// [`ShapeRenderer::start_pass`] to turn it around.
+    fn next_shape(&self) -> Option<usize> {
+        let next = if self.tracing_backward() {
+            self.current_shape.checked_sub(1)
+        } else {
+            Some(self.current_shape + 1).filter(|&i| i < self.shapes.len())
+        };
+        match next {
+            None if !self.ping_pong => Some(self.first_shape()),
+            next => next,
+        }
+    }
+
+    /// Go back to the first shape, or in ping-pong mode turn around on the
+    /// shape the last pass ended on, carrying over any distance drawn past
+    /// its end.
+    fn start_pass(&mut self) {
+        if self.ping_pong {
+            if let Some(shape) = self.shapes.get(self.current_shape) {
+                self.shape_drawn = (self.shape_drawn - shape.length() as f64).max(0.0);
+            }
+            self.backward = !self.backward;
+        }
+        self.current_shape = self.first_shape();
+    }
+
+    /// `progress` through a shape in the direction of the pass.
+    fn directed(&self, progress: f64) -> f64 {
+        if self.tracing_backward() { 1.0 - progress } else { progress }
+    }
+
+    /// Sample the current shape at the current progress.
+    fn current_point(&self) -> Point {
+        let Some(shape) = self.shapes.get(self.current_shape) else {
+            return Point::new(0.0, 0.0, 1.0);
+        };
+        let progress = if self.draw_rate_hz.is_some() {
+            self.shape_phase
+        } else {
+            let length = shape.length() as f64;
+            if length == 0.0 { 1.0 } else { (self.shape_drawn / length).min(1.0) }
+        };
+        shape.next_vector(self.directed(progress) as f32)
+    }
+
+    /// Emit one pending blanked sample without advancing the drawing position.
+    fn next_blanked(&mut self) -> Option<Point> {
+        if self.blanking_remaining == 0 {
+            return None;
+        }
+        self.blanking_remaining -= 1;
+        Some(self.blanking_point)
     }
 
     fn increment_shape_drawing(&mut self) {
@@ -138,7 +443,32 @@ impl ShapeRenderer {
             0.0
         };
 
-        self.increment_with(length_increment);
+        self.advance(length_increment);
+    }
+
+    /// Move on by `length_increment`, the distance one sample covers at the
+    /// frequency. With a draw rate set, that only advances the phase within
+    /// the current shape and the draw rate moves the frame along.
+    fn advance(&mut self, length_increment: f64) {
+        let Some(hz) = self.draw_rate_hz else {
+            self.increment_with(length_increment * self.speed());
+            return;
+        };
+        if self.shapes_length <= 0.0 || self.sample_rate <= 0.0 {
+            return;
+        }
+        self.shape_phase = (self.shape_phase + length_increment / self.shapes_length).fract();
+        self.increment_with(self.shapes_length * hz / self.sample_rate * self.speed());
+    }
+
+    /// Relative speed at the current position in the frame.
+    fn speed(&self) -> f64 {
+        match &self.speed_curve {
+            Some(curve) if self.shapes_length > 0.0 => {
+                curve.speed((self.frame_drawn / self.shapes_length).clamp(0.0, 1.0))
+            }
+            _ => 1.0,
+        }
     }
 
     fn increment_with(&mut self, length_increment: f64) {
@@ -146,6 +476,7 @@ impl ShapeRenderer {
             return;
         }
 
+        let previous_shape = self.current_shape;
         let mut length = if self.current_shape < self.shapes.len() {
             self.shapes[self.current_shape].length() as f64
         } else {
@@ -156,17 +487,45 @@ impl ShapeRenderer {
         self.shape_drawn += length_increment;
 
         // Skip over shapes that the increment draws past
-        while self.shape_drawn > length && !self.shapes.is_empty() {
+        while self.shape_drawn > length {
+            let Some(next) = self.next_shape() else {
+                break;
+            };
             self.shape_drawn -= length;
-            self.current_shape += 1;
-            if self.current_shape >= self.shapes.len() {
-                self.current_shape = 0;
-            }
+            self.current_shape = next;
             length = self.shapes[self.current_shape].length() as f64;
         }
+
+        if self.blanking_samples > 0 && self.current_shape != previous_shape {
+            self.start_blanking(previous_shape);
+        }
+    }
+
+    /// Queue blanking if the current shape doesn't start where `from` ended.
+    fn start_blanking(&mut self, from: usize) {
+        let Some(previous) = self.shapes.get(from) else {
+            return;
+        };
+        // With a draw rate the beam leaves and re-enters mid-shape
+        let (exit, entry) = match self.draw_rate_hz {
+            Some(_) => (self.shape_phase, self.shape_phase),
+            None => (1.0, 0.0),
+        };
+        let end = previous.next_vector(self.directed(exit) as f32);
+        let start = self.shapes[self.current_shape].next_vector(self.directed(entry) as f32);
+        let gap = ((start.x - end.x).powi(2) + (start.y - end.y).powi(2)).sqrt();
+        if gap > CONTIGUOUS_EPSILON {
+            self.blanking_remaining = self.blanking_samples;
+            self.blanking_point = blank(start);
+        }
     }
 }
 
+/// `point` with brightness and colour switched off.
+fn blank(point: Point) -> Point {
+    Point::with_rgb(point.x, point.y, 0.0, 0.0, 0.0, 0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,4 +563,201 @@ mod tests {
         r.set_shapes(vec![Box::new(line)]);
         assert!(r.frame_length() > 0.0);
     }
+
+    #[test]
+    fn test_blanking_between_separated_lines() {
+        let mut r = ShapeRenderer::new(1000.0, 10.0);
+        r.set_blanking_samples(4);
+        r.set_shapes(vec![
+            Box::new(Line::from_points(Point::new(-1.0, 0.0, 0.0), Point::new(-0.5, 0.0, 0.0))),
+            Box::new(Line::from_points(Point::new(0.5, 0.0, 0.0), Point::new(1.0, 0.0, 0.0))),
+        ]);
+
+        let points: Vec<Point> = (0..110).map(|_| r.next_vector()).collect();
+
+        let blanked: Vec<&Point> = points.iter().filter(|p| p.z == 0.0).collect();
+        // One jump into the second line and one back to the first on wrap
+        assert_eq!(blanked.len(), 8);
+        assert!(blanked.iter().all(|p| p.r == 0.0 && p.g == 0.0 && p.b == 0.0));
+        // Blanking parks the beam at the start of the next line
+        assert!(blanked[..4].iter().all(|p| (p.x - 0.5).abs() < 1e-6));
+
+        // Every lit sample lies on one of the lines, never in the gap
+        assert!(points
+            .iter()
+            .filter(|p| p.z > 0.0)
+            .all(|p| p.x <= -0.5 + 1e-3 || p.x >= 0.5 - 1e-3));
+    }
+
+    #[test]
+    fn test_no_blanking_between_joined_lines() {
+        let mut r = ShapeRenderer::new(1000.0, 10.0);
+        r.set_blanking_samples(4);
+        r.set_shapes(vec![
+            Box::new(Line::from_points(Point::new(-1.0, 0.0, 0.0), Point::new(0.0, 0.0, 0.0))),
+            Box::new(Line::from_points(Point::new(0.0, 0.0, 0.0), Point::new(1.0, 0.0, 0.0))),
+        ]);
+
+        // Stay within the first pass so the wrap back to the start isn't hit
+        assert!((0..90).all(|_| r.next_vector().z > 0.0));
+    }
+
+    #[test]
+    fn test_dwelling_points_render_as_dots() {
+        use osci_core::shape::PointShape;
+
+        let stars = [(-0.5, 0.5), (0.25, 0.0), (0.5, -0.75)];
+        let mut r = ShapeRenderer::new(1000.0, 10.0);
+        r.set_shapes(
+            stars
+                .iter()
+                .map(|&(x, y)| Box::new(PointShape::with_dwell(Point::new(x, y, 0.0), 0.1)) as Box<dyn Shape>)
+                .collect(),
+        );
+        assert!((r.frame_length() - 0.3).abs() < 1e-6);
+
+        // One frame is 100 samples, shared equally between the three dots
+        let points: Vec<Point> = (0..100).map(|_| r.next_vector()).collect();
+        assert!(points.iter().all(|p| p.z > 0.0 && (p.x != 0.0 || p.y != 0.0)));
+        for &(x, y) in &stars {
+            let hits = points.iter().filter(|p| p.x == x && p.y == y).count();
+            assert!((32..=35).contains(&hits), "{hits} samples on ({x}, {y})");
+        }
+    }
+
+    /// Frames completed and zero crossings of x over one second.
+    fn trace_one_second(r: &mut ShapeRenderer, sample_rate: usize, increment: f64) -> (usize, usize) {
+        let mut frames = 0;
+        let mut crossings = 0;
+        let mut last_x = r.next_vector_with_increment(increment).x;
+        for _ in 1..sample_rate {
+            let x = r.next_vector_with_increment(increment).x;
+            if (last_x < 0.0) != (x < 0.0) {
+                crossings += 1;
+            }
+            last_x = x;
+            if r.frame_complete() {
+                frames += 1;
+                r.reset_frame_drawn();
+            }
+        }
+        (frames, crossings)
+    }
+
+    #[test]
+    fn test_draw_rate_sets_frame_rate_but_not_pitch() {
+        use osci_core::shape::CircleArc;
+        use std::f32::consts::TAU;
+
+        let sample_rate = 48000;
+        let pitch = 200.0;
+        let circles = || -> Vec<Box<dyn Shape>> {
+            vec![
+                Box::new(CircleArc::new(0.0, 0.0, 0.5, 0.5, 0.0, TAU)),
+                Box::new(CircleArc::new(0.0, 0.0, 0.25, 0.25, 0.0, TAU)),
+            ]
+        };
+
+        let mut results = Vec::new();
+        for draw_rate in [25.0, 50.0] {
+            let mut r = ShapeRenderer::new(sample_rate as f64, pitch);
+            r.set_draw_rate_hz(draw_rate);
+            r.set_shapes(circles());
+            let increment = r.frame_length() * pitch / sample_rate as f64;
+            results.push(trace_one_second(&mut r, sample_rate, increment));
+        }
+
+        // Frame completions follow the draw rate...
+        assert!((results[0].0 as i64 - 25).abs() <= 1, "{results:?}");
+        assert!((results[1].0 as i64 - 50).abs() <= 1, "{results:?}");
+        // ...while each circle still crosses zero twice per pitch period
+        for (_, crossings) in &results {
+            assert!((*crossings as f64 - 2.0 * pitch).abs() <= 2.0, "{results:?}");
+        }
+    }
+
+    #[test]
+    fn test_ping_pong_retraces_the_frame_backward() {
+        // A corner: right along the bottom, then up. 10 samples per frame.
+        let mut r = ShapeRenderer::new(44100.0, 4410.0);
+        r.set_shapes(vec![
+            Box::new(Line::new_2d(-1.0, 0.0, 0.0, 0.0)),
+            Box::new(Line::new_2d(0.0, 0.0, 0.0, 1.0)),
+        ]);
+        r.set_ping_pong(true);
+
+        // Point `s` of the way along the 2-unit path
+        let along = |s: f32| if s <= 1.0 { (s - 1.0, 0.0) } else { (0.0, s - 1.0) };
+        for k in 0..30 {
+            let s = match k {
+                0..=10 => 0.2 * k as f32,
+                11..=20 => 2.0 - 0.2 * (k - 10) as f32,
+                _ => 0.2 * (k - 20) as f32,
+            };
+            let (x, y) = along(s);
+            let p = r.next_vector();
+            assert!((p.x - x).abs() < 1e-4 && (p.y - y).abs() < 1e-4, "sample {k}: ({}, {}) vs ({x}, {y})", p.x, p.y);
+        }
+
+        // Without ping-pong the beam jumps back to the start instead
+        r.set_ping_pong(false);
+        r.seek(0.0);
+        let xs: Vec<f32> = (0..12).map(|_| r.next_vector().x).collect();
+        assert_eq!(xs[11], -0.8);
+    }
+
+    #[test]
+    fn test_reversed_traces_the_frame_from_its_end() {
+        // The same corner as above, 10 samples per frame
+        let mut r = ShapeRenderer::new(44100.0, 4410.0);
+        r.set_shapes(vec![
+            Box::new(Line::new_2d(-1.0, 0.0, 0.0, 0.0)),
+            Box::new(Line::new_2d(0.0, 0.0, 0.0, 1.0)),
+        ]);
+        r.set_reversed(true);
+        r.seek(0.0);
+
+        let along = |s: f32| if s <= 1.0 { (s - 1.0, 0.0) } else { (0.0, s - 1.0) };
+        for k in 0..12 {
+            // Back to the end of the frame after reaching its start
+            let s = if k <= 10 { 2.0 - 0.2 * k as f32 } else { 1.8 };
+            let (x, y) = along(s);
+            let p = r.next_vector();
+            assert!((p.x - x).abs() < 1e-4 && (p.y - y).abs() < 1e-4, "sample {k}: ({}, {}) vs ({x}, {y})", p.x, p.y);
+        }
+
+        // Turning it off carries on from the same point, now forward
+        r.set_reversed(false);
+        let p = r.next_vector();
+        assert!(p.x.abs() < 1e-4 && (p.y - 0.6).abs() < 1e-4, "({}, {})", p.x, p.y);
+        let p = r.next_vector();
+        assert!(p.x.abs() < 1e-4 && (p.y - 0.8).abs() < 1e-4, "({}, {})", p.x, p.y);
+    }
+
+    #[test]
+    fn test_speed_curve_dwells_in_slow_region() {
+        use osci_core::EnvCurve;
+
+        // 1000 samples per frame along a single line
+        let trace = |curve: Option<Env>| {
+            let mut r = ShapeRenderer::new(1000.0, 1.0);
+            r.set_shapes(vec![Box::new(Line::new_2d(-1.0, 0.0, 1.0, 0.0))]);
+            r.set_speed_curve(curve);
+            let xs: Vec<f32> = (0..1000).map(|_| r.next_vector().x).collect();
+            xs
+        };
+        let ends = |xs: &[f32]| xs.iter().filter(|x| x.abs() > 0.8).count();
+
+        // Slow at both ends of the frame, fast through the middle
+        let ease = Env::new(vec![0.2, 1.0, 0.2], vec![0.5, 0.5], vec![EnvCurve::sine(); 2], -1, -1);
+        let uniform = trace(None);
+        let eased = trace(Some(ease));
+
+        assert!((ends(&uniform) as i64 - 200).abs() <= 2, "{}", ends(&uniform));
+        assert!(ends(&eased) > 350, "{}", ends(&eased));
+
+        // The frame still takes one period: the trace ends near where it began
+        let last = eased.iter().rposition(|x| *x > 0.9).unwrap();
+        assert!(last > 950, "frame wrapped at sample {last}");
+    }
 }
@@ -0,0 +1,18 @@
+use osci_core::EnvelopeFollower;
+
+/// Default attack time of the sidechain follower, in milliseconds.
+pub const DEFAULT_SIDECHAIN_ATTACK_MS: f32 = 5.0;
+/// Default release time of the sidechain follower, in milliseconds.
+pub const DEFAULT_SIDECHAIN_RELEASE_MS: f32 = 150.0;
+
+/// Envelope follower for an external sidechain signal (e.g. a kick drum).
+///
+/// The plugin feeds it the aux input once per block and hands the resulting
+/// level to [`crate::Synthesizer::set_sidechain_level`].
+pub fn sidechain_follower(sample_rate: f32) -> EnvelopeFollower {
+    EnvelopeFollower::new(
+        DEFAULT_SIDECHAIN_ATTACK_MS,
+        DEFAULT_SIDECHAIN_RELEASE_MS,
+        sample_rate,
+    )
+}
@@ -1,5 +1,8 @@
 use crossbeam::channel::{bounded, Receiver, Sender, TryRecvError};
-use osci_core::shape::Shape;
+use std::collections::VecDeque;
+use osci_core::shape::{clone_shapes_into, Shape};
+
+pub use osci_core::shape::isolate_shape;
 
 /// A frame is a collection of shapes representing one animation frame.
 pub type Frame = Vec<Box<dyn Shape>>;
@@ -8,39 +11,92 @@ pub type Frame = Vec<Box<dyn Shape>>;
 ///
 /// Mirrors the C++ `ShapeSound` class. Frames are produced by a parser/producer
 /// on a background thread and consumed by the voice on the audio thread.
+///
+/// When a new frame replaces the current one, the old frame is handed back
+/// through a recycling channel (see [`ShapeSound::recycler`]) so the producer
+/// can refill its vector instead of allocating a new one, and so the shapes
+/// are freed off the audio thread.
 pub struct ShapeSound {
     frame_rx: Receiver<Frame>,
     frame_tx: Sender<Frame>,
+    recycle_rx: Receiver<Frame>,
+    recycle_tx: Sender<Frame>,
     current_frame: Frame,
     frame_length: f64,
+    advance_on_frame_complete: bool,
+    // Frames taken off the queue ahead of time in frame-complete mode
+    lookahead: VecDeque<Frame>,
+    capacity: usize,
 }
 
 impl ShapeSound {
     /// Create a new ShapeSound with the given queue capacity.
     pub fn new(capacity: usize) -> Self {
         let (tx, rx) = bounded(capacity);
+        // Room for every queued frame plus the one being refilled
+        let (recycle_tx, recycle_rx) = bounded(capacity + 1);
         Self {
             frame_rx: rx,
             frame_tx: tx,
+            recycle_rx,
+            recycle_tx,
             current_frame: Vec::new(),
             frame_length: 0.0,
+            advance_on_frame_complete: false,
+            lookahead: VecDeque::with_capacity(capacity),
+            capacity,
         }
     }
 
+    /// Step through queued frames once per traced cycle instead of once per
+    /// audio block.
+    ///
+    /// For flip-book animations synced to the beam: every time the drawing
+    /// voice finishes tracing a frame it moves on to the next one in the
+    /// queue, so the animation advances by exactly one frame per cycle
+    /// however the audio is split into blocks. Up to the queue capacity of
+    /// frames are taken ahead at the start of each block, which bounds how
+    /// many cycles a single block can step through.
+    pub fn set_advance_on_frame_complete(&mut self, enabled: bool) {
+        self.advance_on_frame_complete = enabled;
+    }
+
+    /// Whether frames advance once per traced cycle.
+    pub fn advances_on_frame_complete(&self) -> bool {
+        self.advance_on_frame_complete
+    }
+
     /// Get a sender handle for the frame producer.
     pub fn sender(&self) -> Sender<Frame> {
         self.frame_tx.clone()
     }
 
+    /// Get a receiver for frames the sound has finished with.
+    ///
+    /// Frames arrive still holding their old shapes; the producer should clear
+    /// and refill them. If nobody drains this channel, retired frames are
+    /// simply dropped once it fills up.
+    pub fn recycler(&self) -> Receiver<Frame> {
+        self.recycle_rx.clone()
+    }
+
     /// Try to update the current frame from the queue.
     ///
     /// Returns the total length of the new frame's shapes. If no new frame is
-    /// available, returns the length of the current frame.
+    /// available, returns the length of the current frame. Frames already
+    /// taken by [`ShapeSound::prefetch`] come first.
     pub fn update_frame(&mut self) -> f64 {
+        if !self.lookahead.is_empty() {
+            self.advance_frames(1);
+            return self.frame_length;
+        }
         match self.frame_rx.try_recv() {
             Ok(frame) => {
                 self.frame_length = osci_core::shape::total_length(&frame) as f64;
-                self.current_frame = frame;
+                let retired = std::mem::replace(&mut self.current_frame, frame);
+                if retired.capacity() > 0 {
+                    let _ = self.recycle_tx.try_send(retired);
+                }
                 self.frame_length
             }
             Err(TryRecvError::Empty) => self.frame_length,
@@ -48,7 +104,51 @@ impl ShapeSound {
         }
     }
 
-    /// Clone the current frame's shapes for use by a voice.
+    /// Take queued frames into the lookahead until it holds the queue
+    /// capacity, so they can be handed out mid-block by
+    /// [`ShapeSound::copy_upcoming_frame`].
+    pub fn prefetch(&mut self) {
+        while self.lookahead.len() < self.capacity {
+            match self.frame_rx.try_recv() {
+                Ok(frame) => {
+                    self.lookahead.push_back(frame);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Copy the frame `n` steps after the current one (0 is the next),
+    /// or the last one available if the lookahead is shorter, into `target`,
+    /// reusing the boxes it already holds.
+    pub fn copy_upcoming_frame(&self, n: usize, target: &mut Frame) {
+        let frame = match self.lookahead.len() {
+            0 => &self.current_frame,
+            len => &self.lookahead[n.min(len - 1)],
+        };
+        clone_shapes_into(frame, target);
+    }
+
+    /// Make the frame `count` steps ahead in the lookahead current,
+    /// stopping early if the lookahead runs out.
+    pub fn advance_frames(&mut self, count: usize) {
+        for _ in 0..count {
+            let Some(frame) = self.lookahead.pop_front() else { break };
+            self.frame_length = osci_core::shape::total_length(&frame) as f64;
+            let retired = std::mem::replace(&mut self.current_frame, frame);
+            if retired.capacity() > 0 {
+                let _ = self.recycle_tx.try_send(retired);
+            }
+        }
+    }
+
+    /// Copy the current frame's shapes into `target` for use by a voice,
+    /// reusing the boxes it already holds.
+    pub fn copy_frame(&self, target: &mut Frame) {
+        clone_shapes_into(&self.current_frame, target);
+    }
+
+    /// Clone the current frame's shapes into a new frame.
     pub fn clone_frame(&self) -> Frame {
         self.current_frame
             .iter()
@@ -61,6 +161,11 @@ impl ShapeSound {
         self.frame_length
     }
 
+    /// Number of shapes in the current frame.
+    pub fn shape_count(&self) -> usize {
+        self.current_frame.len()
+    }
+
     /// Check if the current frame is empty.
     pub fn is_empty(&self) -> bool {
         self.current_frame.is_empty()
@@ -90,6 +195,27 @@ mod tests {
         assert!(!sound.is_empty());
     }
 
+    #[test]
+    fn test_isolate_shape_keeps_only_that_shape() {
+        let frame: Frame = (0..4)
+            .map(|i| Box::new(Line::new_2d(0.1 * i as f32, 0.0, 0.1 * i as f32, 0.5)) as Box<dyn Shape>)
+            .collect();
+
+        let isolated = isolate_shape(&frame, 2);
+        assert_eq!(isolated.len(), 1);
+        for t in [0.0, 0.5, 1.0] {
+            assert_eq!(isolated[0].next_vector(t), frame[2].next_vector(t));
+        }
+        assert!(isolate_shape(&frame, 4).is_empty());
+
+        // Drawn on its own, the voice traces nothing but that shape
+        let mut sound = ShapeSound::new(4);
+        sound.sender().send(isolated).unwrap();
+        let len = sound.update_frame();
+        assert_eq!(sound.shape_count(), 1);
+        assert!((len - 0.5).abs() < 1e-6);
+    }
+
     #[test]
     fn test_sound_empty() {
         let mut sound = ShapeSound::new(4);
@@ -97,4 +223,22 @@ mod tests {
         assert!((len).abs() < 0.001);
         assert!(sound.is_empty());
     }
+
+    #[test]
+    fn test_sound_recycles_retired_frame() {
+        let mut sound = ShapeSound::new(4);
+        let tx = sound.sender();
+        let recycled = sound.recycler();
+
+        let first: Frame = vec![Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0))];
+        let first_ptr = first.as_ptr();
+        tx.send(first).unwrap();
+        sound.update_frame();
+        assert!(recycled.try_recv().is_err());
+
+        tx.send(vec![Box::new(Line::new_2d(0.0, 0.0, 0.0, 1.0))]).unwrap();
+        sound.update_frame();
+        let retired = recycled.try_recv().unwrap();
+        assert_eq!(retired.as_ptr(), first_ptr);
+    }
 }
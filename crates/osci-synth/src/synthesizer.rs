@@ -1,9 +1,26 @@
+use crate::frame_producer::ShapeMorph;
 use crate::sound::ShapeSound;
-use crate::voice::{ShapeVoice, VoiceEffect};
+use crate::tuning::Tuning;
+use crate::voice::{MorphBlock, ShapeVoice, VoiceBlock, VoiceEffect};
 use osci_core::envelope::Env;
+use osci_core::parameter::derive_seed;
+use osci_core::DcBlocker;
+use osci_effects::soft_clip::soft_clip;
 
-/// Maximum number of simultaneous voices.
-const DEFAULT_MAX_VOICES: usize = 16;
+/// Number of voices a synthesizer starts with.
+pub const DEFAULT_MAX_VOICES: usize = 16;
+
+/// Upper limit for [`Synthesizer::set_max_voices`].
+pub const MAX_VOICES: usize = 64;
+
+/// Cutoff of the optional DC blocker. Low enough that a 20 Hz traversal is
+/// shifted by under 6 degrees, so shapes keep their form.
+const DC_BLOCK_CUTOFF_HZ: f32 = 2.0;
+
+/// Width of the output limiter's soft knee as a fraction of the ceiling.
+/// The knee starts at the ceiling, so samples within it pass through
+/// untouched, and levels off `knee * ceiling` beyond it.
+const LIMITER_KNEE: f32 = 0.2;
 
 /// MIDI event types used by the synthesizer.
 #[derive(Debug, Clone, Copy)]
@@ -24,6 +41,24 @@ pub struct Synthesizer {
     adsr: Env,
     midi_enabled: bool,
     default_frequency: f64,
+    free_run: bool,
+    dc_block: bool,
+    // One blocker each for X and Y
+    dc_blockers: [DcBlocker; 2],
+    output_ceiling: Option<f32>,
+    oversample: usize,
+    max_frequency: Option<f64>,
+    seed: Option<u64>,
+    scale_quantize: Option<Tuning>,
+    voice_frame_offset_spread: f64,
+    profiling: bool,
+    morph: Option<ShapeMorph>,
+    morph_position: f32,
+    // Position the last block's morph ended at, where the next one starts
+    drawn_morph_position: f32,
+    // Which voices are rendering this block, kept to avoid allocating
+    #[cfg(feature = "parallel")]
+    rendering: Vec<bool>,
 }
 
 impl Synthesizer {
@@ -40,6 +75,21 @@ impl Synthesizer {
             adsr: Env::adsr(0.01, 0.3, 0.5, 1.0, 1.0, -4.0),
             midi_enabled: true,
             default_frequency: 440.0,
+            free_run: false,
+            dc_block: false,
+            dc_blockers: std::array::from_fn(|_| DcBlocker::new(DC_BLOCK_CUTOFF_HZ, sample_rate as f32)),
+            output_ceiling: None,
+            oversample: 1,
+            max_frequency: None,
+            seed: None,
+            scale_quantize: None,
+            voice_frame_offset_spread: 0.0,
+            profiling: false,
+            morph: None,
+            morph_position: 0.0,
+            drawn_morph_position: 0.0,
+            #[cfg(feature = "parallel")]
+            rendering: Vec::with_capacity(MAX_VOICES),
         }
     }
 
@@ -54,6 +104,9 @@ impl Synthesizer {
         for voice in &mut self.voices {
             voice.set_sample_rate(sample_rate);
         }
+        for blocker in &mut self.dc_blockers {
+            blocker.set_sample_rate(sample_rate as f32);
+        }
     }
 
     /// Set the ADSR envelope that new notes will use.
@@ -74,6 +127,291 @@ impl Synthesizer {
         self.default_frequency = frequency;
     }
 
+    /// Snap the default frequency to the nearest pitch of `tuning` before
+    /// it is used, so sweeping it steps through notes. `None` leaves it
+    /// continuous, which is the default. Notes played over MIDI are
+    /// unaffected.
+    pub fn set_scale_quantize(&mut self, tuning: Option<Tuning>) {
+        self.scale_quantize = tuning;
+        let frequency = self.quantized_default_frequency();
+        for voice in &mut self.voices {
+            voice.retune_free_run(frequency);
+        }
+    }
+
+    /// The tuning the default frequency snaps to, if any.
+    pub fn scale_quantize(&self) -> Option<Tuning> {
+        self.scale_quantize
+    }
+
+    /// Start each note part of the way through the frame according to the
+    /// voice it lands on: voice `i` begins `i * fraction` of the frame in,
+    /// wrapping past the end. A chord then traces the same frame as a
+    /// canon, each note at a different point. 0, the default, starts every
+    /// note at the beginning.
+    pub fn set_voice_frame_offset_spread(&mut self, fraction: f64) {
+        self.voice_frame_offset_spread = fraction;
+    }
+
+    /// Fraction of the frame between the start points of adjacent voices.
+    pub fn voice_frame_offset_spread(&self) -> f64 {
+        self.voice_frame_offset_spread
+    }
+
+    /// Keep the current frame on screen while no notes are playing.
+    ///
+    /// When enabled and every note has finished, one voice traces the frame
+    /// at the default frequency with no envelope, as if MIDI were disabled.
+    /// It is cut as soon as a note starts and comes back once the notes have
+    /// all ended, so with MIDI disabled it simply keeps the scope drawing.
+    pub fn set_free_run(&mut self, enabled: bool) {
+        self.free_run = enabled;
+        if !enabled {
+            for voice in &mut self.voices {
+                if voice.is_free_running() {
+                    voice.stop_note(false);
+                }
+            }
+        }
+    }
+
+    /// Whether free-run mode is enabled.
+    pub fn free_run(&self) -> bool {
+        self.free_run
+    }
+
+    /// Remove any DC offset from the X and Y outputs.
+    ///
+    /// Shapes that aren't centred on the origin put a constant offset on the
+    /// output, which is hard on speakers and pushes the picture off-centre.
+    /// While enabled, a gentle high-pass filter pulls both axes back to a
+    /// zero mean over a fraction of a second. Z carries brightness, so it is
+    /// left alone.
+    pub fn set_dc_block(&mut self, enabled: bool) {
+        if enabled && !self.dc_block {
+            for blocker in &mut self.dc_blockers {
+                blocker.reset();
+            }
+        }
+        self.dc_block = enabled;
+    }
+
+    /// Whether the DC blocker is enabled.
+    pub fn dc_block(&self) -> bool {
+        self.dc_block
+    }
+
+    /// Soft-limit the X and Y outputs to `ceiling` after the effect chain.
+    ///
+    /// Guards against effect chains (a large Scale, Bulge) that throw the beam
+    /// far outside the screen. Samples inside the ceiling, including a
+    /// full-scale frame at a ceiling of 1, pass through unchanged; beyond it
+    /// they bend smoothly over, never going more than a fifth of the
+    /// ceiling past it. Zero, a negative or a non-finite ceiling disables
+    /// the limiter, which is the default.
+    pub fn set_output_ceiling(&mut self, ceiling: f32) {
+        self.output_ceiling = (ceiling > 0.0 && ceiling.is_finite()).then_some(ceiling);
+    }
+
+    /// The limiter ceiling, or `None` when the limiter is off.
+    pub fn output_ceiling(&self) -> Option<f32> {
+        self.output_ceiling
+    }
+
+    /// Limit the drawing frequency of every voice to `max_frequency` Hz,
+    /// whether it comes from a note or the default frequency.
+    ///
+    /// Lower frequencies are unaffected. Zero, a negative or a non-finite
+    /// limit removes it, which is the default.
+    pub fn set_max_frequency(&mut self, max_frequency: f64) {
+        self.max_frequency = (max_frequency > 0.0 && max_frequency.is_finite()).then_some(max_frequency);
+        for voice in &mut self.voices {
+            voice.set_max_frequency(self.max_frequency);
+        }
+    }
+
+    /// The scan rate limit, or `None` when frequencies aren't limited.
+    pub fn max_frequency(&self) -> Option<f64> {
+        self.max_frequency
+    }
+
+    /// Set the external sidechain level for every voice.
+    ///
+    /// Parameters with sidechain enabled follow this level (expected in
+    /// `[0, 1]`) instead of each voice's own envelope. Pass `None` to return
+    /// to self-sidechaining.
+    pub fn set_sidechain_level(&mut self, level: Option<f32>) {
+        for voice in &mut self.voices {
+            voice.set_sidechain_level(level);
+        }
+    }
+
+    /// Time every effect in every voice; see [`ShapeVoice::set_profiling`].
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiling = enabled;
+        for voice in &mut self.voices {
+            voice.set_profiling(enabled);
+        }
+    }
+
+    /// Whether effects are being timed.
+    pub fn profiling(&self) -> bool {
+        self.profiling
+    }
+
+    /// Average processing time of the effect at `idx`, in nanoseconds per
+    /// sample, summed over the sounding voices. `None` unless profiling.
+    pub fn effect_cost(&self, idx: usize) -> Option<f64> {
+        if !self.profiling {
+            return None;
+        }
+        Some(
+            self.voices
+                .iter()
+                .filter(|v| v.is_active())
+                .filter_map(|v| v.effects.get(idx))
+                .map(|e| e.cost.ns_per_sample())
+                .sum(),
+        )
+    }
+
+    /// Set the number of blanked samples inserted between disconnected shapes.
+    pub fn set_blanking_samples(&mut self, samples: usize) {
+        for voice in &mut self.voices {
+            voice.set_blanking_samples(samples);
+        }
+    }
+
+    /// Trace frames at `hz` on every voice, independent of pitch. Zero or
+    /// less goes back to tracing one frame per period.
+    pub fn set_draw_rate_hz(&mut self, hz: f64) {
+        for voice in &mut self.voices {
+            voice.set_draw_rate_hz(hz);
+        }
+    }
+
+    /// Trace frames alternately forward and backward on every voice; see
+    /// [`ShapeRenderer::set_ping_pong`](crate::ShapeRenderer::set_ping_pong).
+    pub fn set_ping_pong(&mut self, ping_pong: bool) {
+        for voice in &mut self.voices {
+            voice.set_ping_pong(ping_pong);
+        }
+    }
+
+    /// Draw frames backwards on every voice: shapes in reverse order, each
+    /// from end to start; see
+    /// [`ShapeRenderer::set_reversed`](crate::ShapeRenderer::set_reversed).
+    pub fn set_reversed(&mut self, reversed: bool) {
+        for voice in &mut self.voices {
+            voice.set_reversed(reversed);
+        }
+    }
+
+    /// Draw `morph` instead of the frames queued on the sound, blended by
+    /// [`Synthesizer::set_morph_position`]. Build the morph with
+    /// [`ShapeMorph::new`] off the audio thread, since it resamples both
+    /// frames.
+    pub fn set_morph(&mut self, morph: ShapeMorph) {
+        self.morph = Some(morph);
+        self.drawn_morph_position = self.morph_position;
+    }
+
+    /// Go back to drawing the frame that was playing before the morph.
+    pub fn clear_morph(&mut self) {
+        self.morph = None;
+    }
+
+    /// Whether a morph set by [`Synthesizer::set_morph`] is playing.
+    pub fn has_morph(&self) -> bool {
+        self.morph.is_some()
+    }
+
+    /// Blend between the morph frames, from 0 (all `a`) to 1 (all `b`).
+    /// Over the next block voices glide from the previous position to this
+    /// one, sample by sample.
+    pub fn set_morph_position(&mut self, position: f32) {
+        self.morph_position = if position.is_nan() { 0.0 } else { position.clamp(0.0, 1.0) };
+    }
+
+    pub fn morph_position(&self) -> f32 {
+        self.morph_position
+    }
+
+    /// Ease the beam along the frame on every voice, dwelling where `curve`
+    /// is low; see [`ShapeRenderer::set_speed_curve`](crate::ShapeRenderer::set_speed_curve).
+    /// `None` traces at constant speed.
+    pub fn set_speed_curve(&mut self, curve: Option<Env>) {
+        for voice in &mut self.voices {
+            voice.set_speed_curve(curve.clone());
+        }
+    }
+
+    /// Trace `factor` points per output sample on every voice; see
+    /// [`ShapeVoice::set_oversample`]. 1 turns oversampling off.
+    pub fn set_oversample(&mut self, factor: usize) {
+        for voice in &mut self.voices {
+            voice.set_oversample(factor);
+        }
+        self.oversample = factor.clamp(1, crate::voice::MAX_OVERSAMPLE);
+    }
+
+    /// The oversampling factor, 1 when off.
+    pub fn oversample(&self) -> usize {
+        self.oversample
+    }
+
+    /// Seed every noise source in the effect chain from `seed`.
+    ///
+    /// Each voice, effect and parameter gets its own stream derived from
+    /// `seed`, so voices still differ from one another but the same patch
+    /// and seed render identically every time. Calling this again restarts
+    /// the streams. The seed is reapplied whenever the effect template or
+    /// voice count changes.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+        self.apply_seed();
+    }
+
+    /// Stop reapplying the seed. Noise sources carry on from where they are.
+    pub fn clear_seed(&mut self) {
+        self.seed = None;
+    }
+
+    /// The seed set with [`Synthesizer::set_seed`], if any.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Change the number of voices to `num_voices`, clamped to
+    /// 1..=[`MAX_VOICES`].
+    ///
+    /// New voices copy the settings and effect chain of the existing ones.
+    /// When shrinking, idle voices go first so sounding notes carry on; only
+    /// if more notes are playing than fit are the last ones cut.
+    pub fn set_max_voices(&mut self, num_voices: usize) {
+        let num_voices = num_voices.clamp(1, MAX_VOICES);
+
+        let mut excess = self.voices.len().saturating_sub(num_voices);
+        self.voices.retain(|voice| {
+            let remove = excess > 0 && !voice.is_active();
+            if remove {
+                excess -= 1;
+            }
+            !remove
+        });
+        self.voices.truncate(num_voices);
+
+        self.voices.reserve(num_voices - self.voices.len());
+        while self.voices.len() < num_voices {
+            let voice = match self.voices.first() {
+                Some(existing) => existing.idle_copy(),
+                None => ShapeVoice::new(self.sample_rate),
+            };
+            self.voices.push(voice);
+        }
+        self.apply_seed();
+    }
+
     /// Get a mutable reference to a voice by index.
     pub fn voice_mut(&mut self, index: usize) -> Option<&mut ShapeVoice> {
         self.voices.get_mut(index)
@@ -84,6 +422,15 @@ impl Synthesizer {
         self.voices.iter().filter(|v| v.is_active()).count()
     }
 
+    /// Frequency the frame is being drawn at: that of the first sounding
+    /// voice, or the default frequency while no voice is active.
+    pub fn current_frequency(&self) -> f64 {
+        self.voices
+            .iter()
+            .find(|v| v.is_active())
+            .map_or(self.quantized_default_frequency(), |v| v.frequency())
+    }
+
     /// Get the total number of voice slots.
     pub fn num_voices(&self) -> usize {
         self.voices.len()
@@ -97,6 +444,53 @@ impl Synthesizer {
         for voice in &mut self.voices {
             voice.effects = template.iter().map(|e| e.clone_voice_effect()).collect();
         }
+        self.apply_seed();
+    }
+
+    /// Solo or unsolo the effect at `idx` in every voice's chain.
+    ///
+    /// Unlike [`Synthesizer::set_effect_template`] this leaves each effect's
+    /// animation state alone, so un-soloing picks up where the chain left off.
+    pub fn set_effect_solo(&mut self, idx: usize, solo: bool) {
+        for voice in &mut self.voices {
+            if let Some(effect) = voice.effects.get_mut(idx) {
+                effect.solo = solo;
+            }
+        }
+    }
+
+    /// Choose whether the effect at `idx` clears its state when a voice
+    /// starts a note, in every voice's chain.
+    pub fn set_effect_reset_on_note(&mut self, idx: usize, reset_on_note: bool) {
+        for voice in &mut self.voices {
+            if let Some(effect) = voice.effects.get_mut(idx) {
+                effect.reset_on_note = reset_on_note;
+            }
+        }
+    }
+
+    /// Set the value of parameter `param_idx` of the effect at `effect_idx`
+    /// in every voice's chain.
+    ///
+    /// Like [`Synthesizer::set_effect_solo`] this leaves each effect's
+    /// animation state alone, so it can be called every block.
+    pub fn set_effect_param_value(&mut self, effect_idx: usize, param_idx: usize, value: f32) {
+        for voice in &mut self.voices {
+            if let Some(param) = voice
+                .effects
+                .get_mut(effect_idx)
+                .and_then(|e| e.parameters.get_mut(param_idx))
+            {
+                param.value = value;
+            }
+        }
+    }
+
+    /// Bypass the whole effect chain in every voice, or bring it back.
+    pub fn set_effects_bypassed(&mut self, bypassed: bool) {
+        for voice in &mut self.voices {
+            voice.set_effects_bypassed(bypassed);
+        }
     }
 
     /// Process a MIDI event.
@@ -118,11 +512,33 @@ impl Synthesizer {
         }
     }
 
+    /// Silence every voice at once, without a release tail.
+    ///
+    /// The panic button for stuck notes. Free-running voices are stopped
+    /// too but start again on the next block while free-run is on.
+    pub fn all_notes_off(&mut self) {
+        for voice in &mut self.voices {
+            if voice.is_active() {
+                voice.stop_note(false);
+            }
+            voice.reset_effects();
+        }
+    }
+
     /// Render the next block of audio from all active voices.
     ///
     /// The output is written to `output_x`, `output_y`, `output_z`.
     /// These buffers are cleared before rendering, then all active voices
     /// are mixed additively.
+    ///
+    /// The queued frames are taken into `sound`'s lookahead up front, and
+    /// each voice moves through them as it wraps round its current frame;
+    /// afterwards the sound advances as far as the first sounding voice got.
+    /// Unless frames advance once per traced cycle, the sound also moves on
+    /// to the next queued frame at the start of every block. With the
+    /// `parallel` feature each voice renders into its own scratch buffers on
+    /// the rayon thread pool and the buffers are then summed in voice order,
+    /// so the result is bit-identical to serial rendering.
     pub fn render_next_block(
         &mut self,
         output_x: &mut [f32],
@@ -138,18 +554,125 @@ impl Synthesizer {
             output_z[i] = 0.0;
         }
 
-        // Render each active voice into the output
+        // Queue up the frames the voices will move on to during the block,
+        // so voices only need shared access to the sound
+        let tracing = self.morph.is_none();
+        if tracing {
+            if !sound.advances_on_frame_complete() {
+                sound.update_frame();
+            }
+            sound.prefetch();
+            if sound.is_empty() {
+                sound.update_frame();
+            }
+        }
+        if self.free_run {
+            self.update_free_run(sound);
+        }
+
+        #[cfg(feature = "parallel")]
+        self.render_voices_parallel(output_x, output_y, output_z, num_samples, sound);
+        #[cfg(not(feature = "parallel"))]
+        self.render_voices_serial(output_x, output_y, output_z, num_samples, sound);
+        self.drawn_morph_position = self.morph_position;
+
+        // The animation follows the first sounding voice
+        if tracing {
+            let completed = self
+                .voices
+                .iter()
+                .find(|v| v.is_active())
+                .map_or(0, |v| v.frames_completed());
+            sound.advance_frames(completed);
+        }
+
+        if self.dc_block {
+            self.dc_blockers[0].process(&mut output_x[..num_samples]);
+            self.dc_blockers[1].process(&mut output_y[..num_samples]);
+        }
+
+        if let Some(ceiling) = self.output_ceiling {
+            let knee = ceiling * LIMITER_KNEE;
+            for sample in output_x[..num_samples].iter_mut().chain(&mut output_y[..num_samples]) {
+                *sample = soft_clip(*sample, ceiling + knee, knee);
+            }
+        }
+    }
+
+    /// Render each active voice in turn, mixing straight into the output.
+    pub fn render_voices_serial(
+        &mut self,
+        output_x: &mut [f32],
+        output_y: &mut [f32],
+        output_z: &mut [f32],
+        num_samples: usize,
+        sound: &ShapeSound,
+    ) {
+        let (start, end) = (self.drawn_morph_position, self.morph_position);
+        let block = VoiceBlock {
+            num_samples,
+            sound,
+            morph: self.morph.as_ref().map(|morph| MorphBlock { morph, start, end }),
+            midi_enabled: self.midi_enabled,
+            default_frequency: self.quantized_default_frequency(),
+        };
         for voice in &mut self.voices {
-            if voice.is_active() {
-                voice.render_next_block(
-                    output_x,
-                    output_y,
-                    output_z,
-                    num_samples,
-                    sound,
-                    self.midi_enabled,
-                    self.default_frequency,
-                );
+            voice.render_next_block(output_x, output_y, output_z, block);
+        }
+    }
+
+    /// Render active voices concurrently, then sum them into the output.
+    #[cfg(feature = "parallel")]
+    pub fn render_voices_parallel(
+        &mut self,
+        output_x: &mut [f32],
+        output_y: &mut [f32],
+        output_z: &mut [f32],
+        num_samples: usize,
+        sound: &ShapeSound,
+    ) {
+        use rayon::prelude::*;
+
+        let (start, end) = (self.drawn_morph_position, self.morph_position);
+        let block = VoiceBlock {
+            num_samples,
+            sound,
+            morph: self.morph.as_ref().map(|morph| MorphBlock { morph, start, end }),
+            midi_enabled: self.midi_enabled,
+            default_frequency: self.quantized_default_frequency(),
+        };
+
+        // Voices can stop partway through the block, so note which ones to mix
+        self.rendering.clear();
+        self.rendering.extend(self.voices.iter().map(|v| v.is_active()));
+
+        self.voices
+            .par_iter_mut()
+            .zip(&self.rendering)
+            .filter(|(_, rendering)| **rendering)
+            .for_each(|(voice, _)| {
+                voice.render_to_scratch(block);
+            });
+
+        let rendered = self.voices.iter().zip(&self.rendering).filter(|(_, rendering)| **rendering);
+        for (voice, _) in rendered {
+            voice.mix_into(output_x, output_y, output_z, num_samples);
+        }
+    }
+
+    /// The default frequency, snapped to the scale when quantizing.
+    fn quantized_default_frequency(&self) -> f64 {
+        match self.scale_quantize {
+            Some(tuning) => tuning.snap(self.default_frequency),
+            None => self.default_frequency,
+        }
+    }
+
+    /// Give each voice its own stream of the seed, if one is set.
+    fn apply_seed(&mut self) {
+        if let Some(seed) = self.seed {
+            for (i, voice) in self.voices.iter_mut().enumerate() {
+                voice.set_seed(derive_seed(seed, i as u64));
             }
         }
     }
@@ -157,6 +680,7 @@ impl Synthesizer {
     fn note_on(&mut self, note: u8, velocity: f32, sound: &mut ShapeSound) {
         // Find a free voice, or steal the oldest
         let voice_idx = self.find_free_voice().unwrap_or_else(|| self.steal_voice());
+        let default_frequency = self.quantized_default_frequency();
 
         let voice = &mut self.voices[voice_idx];
         voice.start_note(
@@ -165,13 +689,34 @@ impl Synthesizer {
             sound,
             self.adsr.clone(),
             self.midi_enabled,
-            self.default_frequency,
+            default_frequency,
         );
+        if self.voice_frame_offset_spread != 0.0 {
+            voice.seek_frame(voice_idx as f64 * self.voice_frame_offset_spread);
+        }
+    }
+
+    /// Start the free-run voice during silence, and cut it while notes play.
+    fn update_free_run(&mut self, sound: &mut ShapeSound) {
+        let notes_playing = self.voices.iter().any(|v| v.is_active() && !v.is_free_running());
+        let free_voice = self.voices.iter().position(|v| v.is_free_running());
+
+        match (notes_playing, free_voice) {
+            (true, Some(idx)) => self.voices[idx].stop_note(false),
+            (false, None) => {
+                if let Some(idx) = self.find_free_voice() {
+                    let adsr = self.adsr.clone();
+                    let default_frequency = self.quantized_default_frequency();
+                    self.voices[idx].start_free_run(sound, adsr, default_frequency);
+                }
+            }
+            _ => {}
+        }
     }
 
     fn note_off(&mut self, note: u8) {
         for voice in &mut self.voices {
-            if voice.is_active() && voice.note == note {
+            if voice.is_active() && !voice.is_free_running() && voice.note == note {
                 voice.stop_note(true);
             }
         }
@@ -196,6 +741,7 @@ impl Synthesizer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sound::Frame;
     use osci_core::shape::Line;
     use osci_core::Point;
 
@@ -232,6 +778,189 @@ mod tests {
         // Voice may still be active (in release phase) depending on tail-off
     }
 
+    #[test]
+    fn test_current_frequency_follows_voice() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        let mut sound = make_sound_with_line();
+        synth.set_default_frequency(100.0);
+        assert_eq!(synth.current_frequency(), 100.0);
+
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 81, velocity: 1.0 }, &mut sound);
+        let (mut x, mut y, mut z) = (vec![0.0f32; 64], vec![0.0f32; 64], vec![0.0f32; 64]);
+        synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+        assert!((synth.current_frequency() - 880.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_scale_quantize_snaps_default_frequency() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        let mut sound = make_sound_with_line();
+        synth.set_default_frequency(445.0);
+        synth.set_scale_quantize(Some(Tuning::default()));
+        assert!((synth.current_frequency() - 440.0).abs() < 1e-9);
+
+        synth.set_free_run(true);
+        let (mut x, mut y, mut z) = (vec![0.0f32; 64], vec![0.0f32; 64], vec![0.0f32; 64]);
+        synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+        assert!((synth.voice_mut(0).unwrap().frequency() - 440.0).abs() < 1e-9);
+
+        synth.set_scale_quantize(None);
+        assert_eq!(synth.current_frequency(), 445.0);
+    }
+
+    #[test]
+    fn test_morph_position_blends_between_frames() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        let mut sound = make_sound_with_line();
+        // One drawing of the frame every 64 samples
+        synth.set_midi_enabled(false);
+        synth.set_default_frequency(44100.0 / 64.0);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 60, velocity: 1.0 }, &mut sound);
+
+        let a: Frame = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 1.0, 0.0)),
+            Box::new(Line::new_2d(1.0, 0.0, 1.0, 1.0)),
+        ];
+        let b: Frame = vec![
+            Box::new(Line::new_2d(0.0, 0.0, 0.0, -1.0)),
+            Box::new(Line::new_2d(0.0, -1.0, -1.0, -1.0)),
+        ];
+        synth.set_morph(ShapeMorph::new(&a, &b));
+        assert!(synth.has_morph());
+
+        // Where the frame is drawn at sample `i` of a cycle
+        let traced = |frame: &Frame, i: usize| -> Point {
+            let t = (i % 32) as f32 / 32.0;
+            frame[i / 32].next_vector(t)
+        };
+        let (mut x, mut y, mut z) = (vec![0.0f32; 64], vec![0.0f32; 64], vec![0.0f32; 64]);
+        for position in [0.0, 1.0, 0.5] {
+            // The first block glides over from the previous position
+            synth.set_morph_position(position);
+            synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+            synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+            for i in 0..64 {
+                let (from, to) = (traced(&a, i), traced(&b, i));
+                let expected = from + (to - from) * position;
+                assert!(Point::xy(x[i], y[i]).approx_eq(&expected), "sample {i} at {position}");
+            }
+        }
+
+        // Clearing goes back to the line the sound was playing
+        synth.clear_morph();
+        assert!(!synth.has_morph());
+        synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+        assert!(y.iter().all(|v| v.abs() < 1e-6));
+        assert!(x.iter().any(|v| v.abs() > 0.5));
+    }
+
+    #[test]
+    fn test_max_frequency_caps_drawing_frequency() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        let mut sound = make_sound_with_line();
+        synth.set_max_frequency(1000.0);
+        assert_eq!(synth.max_frequency(), Some(1000.0));
+        let (mut x, mut y, mut z) = (vec![0.0f32; 64], vec![0.0f32; 64], vec![0.0f32; 64]);
+
+        // MIDI note 105 is 3520 Hz, above the limit
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 105, velocity: 1.0 }, &mut sound);
+        synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+        assert_eq!(synth.current_frequency(), 1000.0);
+
+        // Note 69 is 440 Hz and passes unchanged
+        synth.handle_midi_event(MidiEvent::NoteOff { note: 105, velocity: 0.0 }, &mut sound);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 69, velocity: 1.0 }, &mut sound);
+        synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+        let frequencies: Vec<f64> = synth
+            .voices
+            .iter()
+            .filter(|v| v.is_active() && v.note == 69)
+            .map(|v| v.frequency())
+            .collect();
+        assert_eq!(frequencies.len(), 1);
+        assert!((frequencies[0] - 440.0).abs() < 1e-9);
+
+        synth.set_max_frequency(0.0);
+        assert_eq!(synth.max_frequency(), None);
+    }
+
+    #[test]
+    fn test_voice_frame_offset_spread_staggers_voices() {
+        let mut synth = Synthesizer::new(2, 44100.0);
+        let mut sound = make_sound_with_line();
+        synth.set_midi_enabled(false);
+        synth.set_default_frequency(100.0);
+        synth.set_voice_frame_offset_spread(0.5);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 60, velocity: 1.0 }, &mut sound);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 64, velocity: 1.0 }, &mut sound);
+
+        // Render each voice alone to see where it is on the line
+        let mut starts = Vec::new();
+        for voice in &mut synth.voices {
+            let (mut x, mut y, mut z) = (vec![0.0f32; 16], vec![0.0f32; 16], vec![0.0f32; 16]);
+            voice.render_next_block(&mut x, &mut y, &mut z, VoiceBlock {
+                num_samples: 16,
+                sound: &sound,
+                morph: None,
+                midi_enabled: false,
+                default_frequency: 100.0,
+            });
+            starts.push(x[0]);
+        }
+        // The line runs from x = -1 to 1, so halfway along is the middle
+        assert!((starts[0] + 1.0).abs() < 0.01, "first voice at {}", starts[0]);
+        assert!(starts[1].abs() < 0.01, "second voice at {}", starts[1]);
+    }
+
+    #[test]
+    fn test_all_notes_off_silences_every_voice() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        let mut sound = make_sound_with_line();
+        for note in 60..64 {
+            synth.handle_midi_event(MidiEvent::NoteOn { note, velocity: 1.0 }, &mut sound);
+        }
+        assert_eq!(synth.active_voice_count(), 4);
+
+        // No release tail, even with a long release on the envelope
+        synth.all_notes_off();
+        assert_eq!(synth.active_voice_count(), 0);
+    }
+
+    #[test]
+    fn test_set_max_voices_keeps_notes_and_adds_polyphony() {
+        let mut synth = Synthesizer::new(8, 44100.0);
+        let mut sound = make_sound_with_line();
+        synth.set_oversample(2);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 60, velocity: 1.0 }, &mut sound);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 64, velocity: 1.0 }, &mut sound);
+
+        // Shrinking below the slot count but above the active count keeps both notes
+        synth.set_max_voices(4);
+        assert_eq!(synth.num_voices(), 4);
+        assert_eq!(synth.active_voice_count(), 2);
+        let (mut x, mut y, mut z) = (vec![0.0f32; 64], vec![0.0f32; 64], vec![0.0f32; 64]);
+        synth.render_next_block(&mut x, &mut y, &mut z, 64, &mut sound);
+        assert_eq!(synth.active_voice_count(), 2);
+
+        // Four slots cap polyphony at four
+        for note in 65..69 {
+            synth.handle_midi_event(MidiEvent::NoteOn { note, velocity: 1.0 }, &mut sound);
+        }
+        assert_eq!(synth.active_voice_count(), 4);
+
+        // Growing makes room for more, with settings carried over
+        synth.set_max_voices(12);
+        assert_eq!(synth.num_voices(), 12);
+        for note in 70..78 {
+            synth.handle_midi_event(MidiEvent::NoteOn { note, velocity: 1.0 }, &mut sound);
+        }
+        assert_eq!(synth.active_voice_count(), 12);
+        assert!(synth.voices.iter().all(|v| v.oversample() == 2));
+
+        synth.set_max_voices(0);
+        assert_eq!(synth.num_voices(), 1);
+    }
+
     #[test]
     fn test_render_block() {
         let mut synth = Synthesizer::new(4, 44100.0);
@@ -269,4 +998,400 @@ mod tests {
         synth.handle_midi_event(MidiEvent::NoteOn { note: 67, velocity: 1.0 }, &mut sound);
         assert_eq!(synth.active_voice_count(), 2);
     }
+
+    #[test]
+    fn test_external_sidechain_drives_parameters() {
+        let mut synth = Synthesizer::new(1, 44100.0);
+        let mut sound = make_sound_with_line();
+
+        let entry = osci_effects::registry::find_effect("translate").unwrap();
+        let mut params = (entry.parameters)();
+        params[0].sidechain_enabled = true;
+        params[0].smooth_value_change = 1.0;
+        synth.set_effect_template(&[VoiceEffect::new("translate", (entry.constructor)(), params)]);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 69, velocity: 1.0 }, &mut sound);
+
+        let n = 64;
+        let (mut x, mut y, mut z) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+
+        // Translate X spans [-1, 1], so a level of 0.75 maps to 0.5
+        synth.set_sidechain_level(Some(0.75));
+        synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        let value = synth.voice_mut(0).unwrap().effects[0].values()[0];
+        assert!((value - 0.5).abs() < 1e-6);
+
+        // Without an external level the voice's own envelope takes over again
+        synth.set_sidechain_level(None);
+        synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        let value = synth.voice_mut(0).unwrap().effects[0].values()[0];
+        assert!(value < 0.5);
+    }
+
+    #[test]
+    fn test_frames_advance_once_per_cycle() {
+        let mut synth = Synthesizer::new(1, 44100.0);
+        // 100 samples per cycle
+        synth.set_default_frequency(441.0);
+        synth.set_free_run(true);
+
+        let mut sound = ShapeSound::new(16);
+        sound.set_advance_on_frame_complete(true);
+        for k in 0..16 {
+            let y = k as f32 * 0.05;
+            let line = Line::from_points(Point::new(-0.5, y, 0.0), Point::new(0.5, y, 0.0));
+            sound.sender().send(vec![Box::new(line)]).unwrap();
+        }
+        let current = |sound: &ShapeSound| (sound.clone_frame()[0].next_vector(0.0).y / 0.05).round() as usize;
+
+        let n = 450;
+        let (mut x, mut y, mut z) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+        let mut total = 0;
+        for _ in 0..2 {
+            synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+            let completed = synth.voice_mut(0).unwrap().frames_completed();
+            assert!(completed >= 3, "{completed} cycles in a block");
+            total += completed;
+            assert_eq!(current(&sound), total);
+        }
+
+        // Several frames were drawn within the last block, one per cycle
+        let mut levels: Vec<i32> = y.iter().map(|v| (v / 0.05).round() as i32).collect();
+        levels.dedup();
+        assert!(levels.len() >= 3, "{levels:?}");
+    }
+
+    #[test]
+    fn test_dc_block_centres_offset_frame() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        let mut sound = ShapeSound::new(4);
+        let line = Line::from_points(Point::new(-0.5, 0.5, 0.0), Point::new(0.5, 0.5, 0.0));
+        sound.sender().send(vec![Box::new(line)]).unwrap();
+        sound.update_frame();
+
+        synth.set_free_run(true);
+        synth.set_dc_block(true);
+
+        let n = 441;
+        let (mut x, mut y, mut z) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+        let mean = |s: &[f32]| s.iter().sum::<f32>() / s.len() as f32;
+
+        synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        let first = mean(&y);
+        assert!(first > 0.4, "first block mean {first}");
+
+        // Two seconds in, the 0.5 offset on Y has decayed away
+        for _ in 0..199 {
+            synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        }
+        assert!(mean(&y).abs() < 0.01, "mean {}", mean(&y));
+    }
+
+    /// Render one second of a square of half-side `half`, free running.
+    fn render_square(synth: &mut Synthesizer, half: f32) -> (Vec<f32>, Vec<f32>) {
+        let corners = [(-half, -half), (half, -half), (half, half), (-half, half)];
+        let square: Vec<Box<dyn osci_core::Shape>> = (0..4)
+            .map(|i| {
+                let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                Box::new(Line::from_points(Point::new(a.0, a.1, 0.0), Point::new(b.0, b.1, 0.0)))
+                    as Box<dyn osci_core::Shape>
+            })
+            .collect();
+        let mut sound = ShapeSound::new(4);
+        sound.sender().send(square).unwrap();
+        sound.update_frame();
+
+        synth.set_free_run(true);
+        let n = 44100;
+        let (mut x, mut y, mut z) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+        synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        (x, y)
+    }
+
+    #[test]
+    fn test_output_limiter() {
+        let limited = || {
+            let mut synth = Synthesizer::new(4, 44100.0);
+            synth.set_output_ceiling(1.0);
+            synth
+        };
+
+        // A full-scale square reaches the ceiling but passes through unchanged
+        let full = render_square(&mut Synthesizer::new(4, 44100.0), 1.0);
+        assert!(full.0.iter().chain(&full.1).any(|v| v.abs() == 1.0));
+        assert_eq!(render_square(&mut limited(), 1.0), full);
+
+        // Scaled further, it is bent over within the knee past the ceiling
+        let (x, y) = render_square(&mut Synthesizer::new(4, 44100.0), 2.5);
+        assert!(x.iter().chain(&y).any(|v| v.abs() > 2.0));
+        let (x, y) = render_square(&mut limited(), 2.5);
+        assert!(x.iter().chain(&y).all(|v| v.abs() <= 1.0 + LIMITER_KNEE));
+        assert!(x.iter().chain(&y).any(|v| v.abs() > 1.15));
+    }
+
+    /// Spectral power of `signal` at `hz`, under a Hann window.
+    fn power_at(signal: &[f32], hz: f64, sample_rate: f64) -> f64 {
+        let n = signal.len() as f64;
+        let (mut re, mut im) = (0.0, 0.0);
+        for (i, s) in signal.iter().enumerate() {
+            let window = 0.5 - 0.5 * (std::f64::consts::TAU * i as f64 / n).cos();
+            let phase = std::f64::consts::TAU * hz * i as f64 / sample_rate;
+            re += *s as f64 * window * phase.cos();
+            im -= *s as f64 * window * phase.sin();
+        }
+        re * re + im * im
+    }
+
+    /// Power folded back below Nyquist by harmonics above it, relative to
+    /// the power of the harmonics that fit.
+    fn alias_ratio(signal: &[f32], frequency: f64, sample_rate: f64) -> f64 {
+        let nyquist = sample_rate / 2.0;
+        let (mut harmonics, mut aliases) = (0.0, 0.0);
+        for k in 1..40 {
+            let hz = k as f64 * frequency;
+            let folded = (hz % sample_rate).min(sample_rate - hz % sample_rate);
+            if hz < nyquist {
+                harmonics += power_at(signal, hz, sample_rate);
+            } else {
+                aliases += power_at(signal, folded, sample_rate);
+            }
+        }
+        aliases / harmonics
+    }
+
+    #[test]
+    fn test_oversampling_reduces_aliasing() {
+        // A square drawn at a non-integer division of the sample rate, so its
+        // upper harmonics fold onto frequencies it doesn't contain
+        let sample_rate = 44100.0;
+        let frequency = sample_rate / 9.3;
+        let ratio = |oversample: usize| {
+            let mut synth = Synthesizer::new(4, sample_rate);
+            synth.set_default_frequency(frequency);
+            synth.set_oversample(oversample);
+            let (x, _) = render_square(&mut synth, 0.5);
+            alias_ratio(&x, frequency, sample_rate)
+        };
+
+        let plain = ratio(1);
+        let oversampled = ratio(4);
+        assert!(oversampled < plain * 0.5, "aliasing {plain} at 1x, {oversampled} at 4x");
+
+        let mut synth = Synthesizer::new(4, sample_rate);
+        synth.set_oversample(100);
+        assert_eq!(synth.oversample(), crate::voice::MAX_OVERSAMPLE);
+    }
+
+    #[test]
+    fn test_free_run_draws_without_notes() {
+        let mut synth = Synthesizer::new(4, 44100.0);
+        let mut sound = make_sound_with_line();
+        let n = 256;
+        let (mut x, mut y, mut z) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+
+        synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        assert!(x.iter().all(|v| *v == 0.0));
+
+        // MIDI stays enabled: the free-run voice ignores it
+        synth.set_free_run(true);
+        synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        assert_eq!(synth.active_voice_count(), 1);
+        assert!(x.iter().any(|v| v.abs() > 0.1));
+        assert!((synth.voice_mut(0).unwrap().frequency() - 440.0).abs() < 1e-9);
+
+        // A note takes over; a note off for MIDI note 0 doesn't touch the free-run voice
+        synth.handle_midi_event(MidiEvent::NoteOff { note: 0, velocity: 0.0 }, &mut sound);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 60, velocity: 1.0 }, &mut sound);
+        synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+        assert_eq!(synth.active_voice_count(), 1);
+        assert!(!synth.voice_mut(1).unwrap().is_free_running());
+
+        synth.set_free_run(false);
+        synth.handle_midi_event(MidiEvent::NoteOff { note: 60, velocity: 0.0 }, &mut sound);
+        let released = (0..n).any(|_| {
+            synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+            synth.active_voice_count() == 0
+        });
+        assert!(released, "release should finish without free-run restarting");
+    }
+
+    /// Render `blocks` blocks of one note through `chain`, letting `setup`
+    /// adjust the synth once the template is in place.
+    fn render_chain(chain: &[(&str, bool)], setup: impl Fn(&mut Synthesizer), blocks: usize) -> Vec<f32> {
+        let mut synth = Synthesizer::new(1, 44100.0);
+        let mut sound = make_sound_with_line();
+        let template: Vec<VoiceEffect> = chain
+            .iter()
+            .map(|&(id, enabled)| {
+                let entry = osci_effects::registry::find_effect(id).unwrap();
+                let mut effect = VoiceEffect::new(id, (entry.constructor)(), (entry.parameters)());
+                effect.enabled = enabled;
+                effect
+            })
+            .collect();
+        synth.set_effect_template(&template);
+        setup(&mut synth);
+        synth.handle_midi_event(MidiEvent::NoteOn { note: 69, velocity: 1.0 }, &mut sound);
+
+        let n = 128;
+        let (mut x, mut y, mut z) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+        let mut out = Vec::new();
+        for _ in 0..blocks {
+            synth.render_next_block(&mut x, &mut y, &mut z, n, &mut sound);
+            out.extend(x.iter().chain(&y).chain(&z));
+        }
+        out
+    }
+
+    #[test]
+    fn test_solo_matches_chain_with_only_that_effect() {
+        let chain = ["rotate", "bitcrush", "scale"];
+
+        let soloed = render_chain(
+            &chain.map(|id| (id, true)),
+            |synth| synth.set_effect_solo(1, true),
+            4,
+        );
+        let alone = render_chain(&chain.map(|id| (id, id == "bitcrush")), |_| {}, 4);
+        assert_eq!(soloed, alone);
+
+        let full = render_chain(&chain.map(|id| (id, true)), |_| {}, 4);
+        assert_ne!(soloed, full);
+    }
+
+    #[test]
+    fn test_bypass_all_matches_empty_chain() {
+        let bypassed = render_chain(
+            &[("rotate", true), ("bitcrush", true)],
+            |synth| synth.set_effects_bypassed(true),
+            4,
+        );
+        let dry = render_chain(&[], |_| {}, 4);
+        assert_eq!(bypassed, dry);
+    }
+
+    #[test]
+    fn test_seed_makes_noise_reproducible() {
+        let render = |seed: u64| {
+            let mut synth = Synthesizer::new(2, 44100.0);
+            let mut sound = make_sound_with_line();
+            let entry = osci_effects::registry::find_effect("jitter").unwrap();
+            let mut params = (entry.parameters)();
+            params[0].value = 0.2;
+            params[1].lfo_type = osci_core::LfoType::Noise;
+            synth.set_effect_template(&[VoiceEffect::new("jitter", (entry.constructor)(), params)]);
+            synth.set_seed(seed);
+            synth.handle_midi_event(MidiEvent::NoteOn { note: 69, velocity: 1.0 }, &mut sound);
+            synth.handle_midi_event(MidiEvent::NoteOn { note: 69, velocity: 1.0 }, &mut sound);
+
+            let n = 256;
+            let mut voices = vec![Vec::<f32>::new(); 2];
+            for _ in 0..4 {
+                for (i, out) in voices.iter_mut().enumerate() {
+                    let (mut x, mut y, mut z) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+                    synth.voices[i].render_next_block(&mut x, &mut y, &mut z, VoiceBlock {
+                        num_samples: n,
+                        sound: &sound,
+                        morph: None,
+                        midi_enabled: true,
+                        default_frequency: 440.0,
+                    });
+                    out.extend(x.iter().chain(&y));
+                }
+            }
+            voices
+        };
+
+        let a = render(7);
+        assert_eq!(a, render(7));
+        assert_ne!(a, render(8));
+        // Both voices play the same note yet get different noise
+        assert_ne!(a[0], a[1]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        fn make_synth() -> Synthesizer {
+            let mut synth = Synthesizer::new(8, 44100.0);
+            // Jitter's PRNG starts from a fixed seed in every instance
+            let entry = osci_effects::registry::find_effect("jitter").unwrap();
+            let mut params = (entry.parameters)();
+            params[0].value = 0.2;
+            let template = vec![VoiceEffect::new("jitter", (entry.constructor)(), params)];
+            synth.set_effect_template(&template);
+            synth
+        }
+
+        let mut serial = make_synth();
+        let mut parallel = make_synth();
+        let mut serial_sound = make_sound_with_line();
+        let mut parallel_sound = make_sound_with_line();
+
+        for note in [48, 55, 60, 64, 67, 72] {
+            let event = MidiEvent::NoteOn { note, velocity: 0.8 };
+            serial.handle_midi_event(event, &mut serial_sound);
+            parallel.handle_midi_event(event, &mut parallel_sound);
+        }
+
+        let n = 256;
+        for _ in 0..4 {
+            let mut sx = vec![0.0f32; n];
+            let mut sy = vec![0.0f32; n];
+            let mut sz = vec![0.0f32; n];
+            let mut px = vec![0.0f32; n];
+            let mut py = vec![0.0f32; n];
+            let mut pz = vec![0.0f32; n];
+
+            serial.render_voices_serial(&mut sx, &mut sy, &mut sz, n, &serial_sound);
+            parallel.render_voices_parallel(&mut px, &mut py, &mut pz, n, &parallel_sound);
+
+            assert!(sx.iter().any(|v| v.abs() > 0.001));
+            for i in 0..n {
+                assert_eq!(sx[i].to_bits(), px[i].to_bits());
+                assert_eq!(sy[i].to_bits(), py[i].to_bits());
+                assert_eq!(sz[i].to_bits(), pz[i].to_bits());
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial_across_frame_changes() {
+        fn make_sound() -> ShapeSound {
+            let mut sound = ShapeSound::new(8);
+            let tx = sound.sender();
+            for k in 0..8 {
+                let y = k as f32 * 0.1;
+                tx.send(vec![Box::new(Line::new_2d(-1.0, y, 1.0, y))]).unwrap();
+            }
+            sound.update_frame();
+            sound
+        }
+
+        let mut serial = Synthesizer::new(4, 44100.0);
+        let mut parallel = Synthesizer::new(4, 44100.0);
+        let mut serial_sound = make_sound();
+        let mut parallel_sound = make_sound();
+        for note in [69, 76] {
+            let event = MidiEvent::NoteOn { note, velocity: 1.0 };
+            serial.handle_midi_event(event, &mut serial_sound);
+            parallel.handle_midi_event(event, &mut parallel_sound);
+        }
+
+        // Each voice wraps a couple of times per block, moving on a frame each time
+        let n = 256;
+        let (mut sx, mut sy, mut sz) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+        let (mut px, mut py, mut pz) = (vec![0.0f32; n], vec![0.0f32; n], vec![0.0f32; n]);
+        serial_sound.prefetch();
+        parallel_sound.prefetch();
+        serial.render_voices_serial(&mut sx, &mut sy, &mut sz, n, &serial_sound);
+        parallel.render_voices_parallel(&mut px, &mut py, &mut pz, n, &parallel_sound);
+
+        assert!(sy[n - 1] > sy[0] + 0.15, "frames did not change mid-block");
+        for i in 0..n {
+            assert_eq!(sx[i].to_bits(), px[i].to_bits());
+            assert_eq!(sy[i].to_bits(), py[i].to_bits());
+            assert_eq!(sz[i].to_bits(), pz[i].to_bits());
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Host transport state relevant to beat-synced animation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransportInfo {
+    pub playing: bool,
+    /// Song position in quarter-note beats, if the host reports one.
+    pub pos_beats: Option<f64>,
+}
+
+/// Transport state published by the audio thread once per block and read
+/// by frame sources on other threads without locking.
+#[derive(Debug, Default)]
+pub struct TransportClock {
+    playing: AtomicBool,
+    has_position: AtomicBool,
+    pos_beats: AtomicU64,
+}
+
+impl TransportClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&self, transport: TransportInfo) {
+        if let Some(pos) = transport.pos_beats {
+            self.pos_beats.store(pos.to_bits(), Ordering::Relaxed);
+        }
+        self.has_position.store(transport.pos_beats.is_some(), Ordering::Relaxed);
+        self.playing.store(transport.playing, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> TransportInfo {
+        TransportInfo {
+            playing: self.playing.load(Ordering::Relaxed),
+            pos_beats: self
+                .has_position
+                .load(Ordering::Relaxed)
+                .then(|| f64::from_bits(self.pos_beats.load(Ordering::Relaxed))),
+        }
+    }
+}
+
+/// Maps the transport position onto animation frames, one frame per
+/// `beats_per_frame` beats.
+///
+/// The frame is derived from the song position rather than counted, so
+/// host loops and seeks land on the same frame every time.
+#[derive(Debug, Clone, Copy)]
+pub struct BeatSync {
+    beats_per_frame: f64,
+}
+
+impl BeatSync {
+    /// `beats_per_frame` is the division: 1.0 advances every beat, 4.0
+    /// every bar of 4/4 and 0.5 every eighth note.
+    pub fn new(beats_per_frame: f64) -> Self {
+        Self {
+            beats_per_frame: beats_per_frame.max(1e-3),
+        }
+    }
+
+    pub fn beats_per_frame(&self) -> f64 {
+        self.beats_per_frame
+    }
+
+    /// Frame to show out of `frame_count`, or `None` to hold the current
+    /// one because the transport is stopped or has no position.
+    pub fn frame_index(&self, transport: &TransportInfo, frame_count: usize) -> Option<usize> {
+        if !transport.playing || frame_count == 0 {
+            return None;
+        }
+        let step = (transport.pos_beats? / self.beats_per_frame).floor() as i64;
+        Some(step.rem_euclid(frame_count as i64) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(playing: bool, beats: f64) -> TransportInfo {
+        TransportInfo {
+            playing,
+            pos_beats: Some(beats),
+        }
+    }
+
+    #[test]
+    fn test_one_frame_per_beat_holding_while_stopped() {
+        let sync = BeatSync::new(1.0);
+        let positions = [
+            at(true, 0.0),
+            at(true, 0.5),
+            at(true, 1.0),
+            at(true, 2.75),
+            at(false, 3.2),
+            at(true, 4.0),
+            // Host loop back to the start
+            at(true, 0.25),
+        ];
+        let frames: Vec<Option<usize>> = positions.iter().map(|t| sync.frame_index(t, 3)).collect();
+        assert_eq!(frames, [Some(0), Some(0), Some(1), Some(2), None, Some(1), Some(0)]);
+
+        let unknown = TransportInfo {
+            playing: true,
+            pos_beats: None,
+        };
+        assert_eq!(sync.frame_index(&unknown, 3), None);
+    }
+
+    #[test]
+    fn test_division_sets_frames_per_beat() {
+        let eighths = BeatSync::new(0.5);
+        assert_eq!(eighths.frame_index(&at(true, 1.5), 8), Some(3));
+
+        let bars = BeatSync::new(4.0);
+        assert_eq!(bars.frame_index(&at(true, 7.9), 8), Some(1));
+    }
+
+    #[test]
+    fn test_clock_round_trips_transport() {
+        let clock = TransportClock::new();
+        assert_eq!(clock.get(), TransportInfo::default());
+
+        clock.update(at(true, 12.5));
+        assert_eq!(clock.get(), at(true, 12.5));
+    }
+}
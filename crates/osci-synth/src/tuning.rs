@@ -0,0 +1,61 @@
+/// An equal-tempered tuning: `divisions` equal steps per octave, laid out
+/// so that `reference_hz` is one of the pitches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    reference_hz: f64,
+    divisions: u32,
+}
+
+impl Default for Tuning {
+    /// Standard 12-tone equal temperament with A4 at 440 Hz.
+    fn default() -> Self {
+        Self::equal_temperament(12, 440.0)
+    }
+}
+
+impl Tuning {
+    /// `divisions` equal steps per octave through `reference_hz`. At least
+    /// one division is used.
+    pub fn equal_temperament(divisions: u32, reference_hz: f64) -> Self {
+        Self {
+            reference_hz,
+            divisions: divisions.max(1),
+        }
+    }
+
+    /// The pitch in this tuning nearest to `hz`, measured in cents rather
+    /// than Hz so a frequency halfway between two notes by ear rounds the
+    /// way it sounds. Non-positive and non-finite frequencies pass through.
+    pub fn snap(&self, hz: f64) -> f64 {
+        if !(hz > 0.0 && hz.is_finite()) {
+            return hz;
+        }
+        let divisions = self.divisions as f64;
+        let steps = (divisions * (hz / self.reference_hz).log2()).round();
+        self.reference_hz * 2.0_f64.powf(steps / divisions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_twelve_tet_snaps_to_nearest_note() {
+        let tuning = Tuning::default();
+        assert_eq!(tuning.snap(440.0), 440.0);
+        assert!((tuning.snap(445.0) - 440.0).abs() < 1e-9);
+        // Just over a quarter tone above A4 goes up to A#4
+        assert!((tuning.snap(455.0) - 466.1638).abs() < 1e-3);
+        assert!((tuning.snap(250.0) - 246.9417).abs() < 1e-3);
+        assert_eq!(tuning.snap(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_other_divisions() {
+        // Whole-tone scale from A
+        let tuning = Tuning::equal_temperament(6, 440.0);
+        assert!((tuning.snap(470.0) - 493.8833).abs() < 1e-3);
+        assert!((tuning.snap(880.0) - 880.0).abs() < 1e-9);
+    }
+}
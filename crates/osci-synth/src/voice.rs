@@ -1,23 +1,76 @@
 use osci_core::effect::EffectApplication;
 use osci_core::envelope::Env;
-use osci_core::parameter::{animate_parameter, EffectParameter};
+use osci_core::parameter::{animate_parameter, derive_seed, EffectParameter};
 use osci_core::Point;
+use osci_effects::frequency::limit_frequency;
+use std::time::{Duration, Instant};
 
 use crate::renderer::ShapeRenderer;
-use crate::sound::ShapeSound;
+use crate::frame_producer::ShapeMorph;
+use crate::sound::{Frame, ShapeSound};
 
 const MIN_LENGTH_INCREMENT: f64 = 0.000001;
 
+/// Weight each new block gets in an [`EffectCost`] average.
+const COST_SMOOTHING: f64 = 0.05;
+
+/// Moving average of how long an effect takes to process, in nanoseconds
+/// per sample. Each block's timing moves the average [`COST_SMOOTHING`] of
+/// the way towards it, so one slow block doesn't make it jump.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EffectCost {
+    ns_per_sample: f64,
+    // False until the first block, which sets the average outright
+    primed: bool,
+}
+
+impl EffectCost {
+    /// Fold in a block of `samples` samples that took `elapsed`.
+    pub fn record(&mut self, elapsed: Duration, samples: usize) {
+        if samples == 0 {
+            return;
+        }
+        let ns = elapsed.as_nanos() as f64 / samples as f64;
+        if self.primed {
+            self.ns_per_sample += (ns - self.ns_per_sample) * COST_SMOOTHING;
+        } else {
+            self.ns_per_sample = ns;
+            self.primed = true;
+        }
+    }
+
+    /// The average so far; 0 before any block was recorded.
+    pub fn ns_per_sample(&self) -> f64 {
+        self.ns_per_sample
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Highest oversampling factor [`ShapeVoice::set_oversample`] accepts.
+pub const MAX_OVERSAMPLE: usize = 8;
+
 /// Per-voice effect instance: an effect application paired with its parameters.
 pub struct VoiceEffect {
     pub id: String,
     pub application: Box<dyn EffectApplication>,
     pub parameters: Vec<EffectParameter>,
     pub enabled: bool,
+    /// While any enabled effect in the chain is soloed, only soloed effects apply.
+    pub solo: bool,
+    /// Clear the application's state, e.g. a delay line, whenever the voice
+    /// starts a note. Off lets echoes and trails carry over retriggers.
+    pub reset_on_note: bool,
+    /// Time spent processing this effect, measured while profiling is on.
+    pub cost: EffectCost,
 
     // Per-parameter animation state
     animated_values: Vec<f32>,
     current_values: Vec<f32>,
+    // Scratch buffer for `animate`, grown to the largest block seen
+    animation_buffer: Vec<f32>,
 }
 
 impl VoiceEffect {
@@ -32,8 +85,12 @@ impl VoiceEffect {
             application,
             parameters,
             enabled: true,
+            solo: false,
+            reset_on_note: true,
+            cost: EffectCost::default(),
             animated_values: vec![0.0; n],
             current_values: vec![0.0; n],
+            animation_buffer: Vec::new(),
         }
     }
 
@@ -42,9 +99,12 @@ impl VoiceEffect {
     /// After this call, `animated_values` contains the last sample's value
     /// for each parameter (suitable for per-sample effect processing).
     pub fn animate(&mut self, block_size: usize, sample_rate: f32, volume_buffer: Option<&[f32]>) {
-        let mut buf = vec![0.0f32; block_size];
+        if self.animation_buffer.len() < block_size {
+            self.animation_buffer.resize(block_size, 0.0);
+        }
+        let buf = &mut self.animation_buffer[..block_size];
         for (i, param) in self.parameters.iter_mut().enumerate() {
-            animate_parameter(param, &mut buf, sample_rate, &mut self.current_values[i], volume_buffer);
+            animate_parameter(param, buf, sample_rate, &mut self.current_values[i], volume_buffer);
             self.animated_values[i] = buf[block_size - 1];
         }
     }
@@ -54,22 +114,64 @@ impl VoiceEffect {
         &self.animated_values
     }
 
+    /// Restart every random source in this effect from `seed`.
+    ///
+    /// The application and each parameter's noise LFO get their own stream
+    /// derived from `seed`, so no two of them move in lockstep.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.application.set_seed(derive_seed(seed, 0));
+        for (i, param) in self.parameters.iter_mut().enumerate() {
+            param.set_seed(derive_seed(seed, i as u64 + 1));
+        }
+    }
+
     /// Create a fresh copy of this effect for another voice.
     ///
-    /// Clones the effect application, parameters, and enabled state,
-    /// but resets per-voice animation state to zeroes.
+    /// Clones the effect application, parameters, and enabled, solo and
+    /// reset-on-note state, but resets per-voice animation state to zeroes.
     pub fn clone_voice_effect(&self) -> Self {
         Self {
             id: self.id.clone(),
             application: self.application.clone_effect(),
             parameters: self.parameters.clone(),
             enabled: self.enabled,
+            solo: self.solo,
+            reset_on_note: self.reset_on_note,
+            cost: EffectCost::default(),
             animated_values: vec![0.0; self.parameters.len()],
             current_values: vec![0.0; self.parameters.len()],
+            animation_buffer: Vec::new(),
         }
     }
 }
 
+/// A morph for voices to trace during one block, with its position moving
+/// from `start` to `end` across the block.
+#[derive(Clone, Copy)]
+pub struct MorphBlock<'a> {
+    pub morph: &'a ShapeMorph,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl MorphBlock<'_> {
+    /// The morph position for sample `index` of a block of `len` samples.
+    fn position_at(&self, index: usize, len: usize) -> f32 {
+        let fraction = (index + 1) as f32 / len.max(1) as f32;
+        self.start + (self.end - self.start) * fraction
+    }
+}
+
+/// The inputs every voice shares for one block.
+#[derive(Clone, Copy)]
+pub struct VoiceBlock<'a> {
+    pub num_samples: usize,
+    pub sound: &'a ShapeSound,
+    pub morph: Option<MorphBlock<'a>>,
+    pub midi_enabled: bool,
+    pub default_frequency: f64,
+}
+
 /// A single synthesizer voice — renders shapes to audio samples.
 ///
 /// Mirrors the C++ `ShapeVoice`. Each voice has:
@@ -97,16 +199,51 @@ pub struct ShapeVoice {
     // Voice state
     active: bool,
     sample_rate: f64,
+    // Started by the synth's free-run mode rather than a note
+    free_running: bool,
 
     // Per-voice effects
     pub effects: Vec<VoiceEffect>,
 
+    // External sidechain level; `None` falls back to the voice's own envelope
+    sidechain_level: Option<f32>,
+
+    // Skip the whole effect chain without touching it
+    effects_bypassed: bool,
+
+    // Time each effect into its `cost`
+    profiling: bool,
+
+    // Scan rate limit applied to the drawing frequency
+    max_frequency: Option<f64>,
+
+    // Sub-samples traced per output sample, and the blanking length in
+    // output samples, which the renderer counts in sub-samples
+    oversample: usize,
+    blanking_samples: usize,
+
+    // Frames finished tracing during the last block
+    frames_completed: usize,
+
+    // How far through the morph the beam is, from 0 to 1
+    morph_progress: f64,
+
+    // The frame before the renderer's current one, refilled with the next
+    // so moving on to it reuses the shapes' boxes
+    spare_frame: Frame,
+
     // Working buffers
     voice_x: Vec<f32>,
     voice_y: Vec<f32>,
     voice_z: Vec<f32>,
+    voice_r: Vec<f32>,
+    voice_g: Vec<f32>,
+    voice_b: Vec<f32>,
     frequency_buffer: Vec<f32>,
     volume_buffer: Vec<f32>,
+    sidechain_buffer: Vec<f32>,
+    effect_input: Vec<Point>,
+    effect_output: Vec<Point>,
 }
 
 impl ShapeVoice {
@@ -125,20 +262,72 @@ impl ShapeVoice {
             waiting_for_release: false,
             active: false,
             sample_rate,
+            free_running: false,
             effects: Vec::new(),
+            sidechain_level: None,
+            effects_bypassed: false,
+            profiling: false,
+            max_frequency: None,
+            oversample: 1,
+            blanking_samples: 0,
+            frames_completed: 0,
+            morph_progress: 0.0,
+            spare_frame: Vec::new(),
             voice_x: Vec::new(),
             voice_y: Vec::new(),
             voice_z: Vec::new(),
+            voice_r: Vec::new(),
+            voice_g: Vec::new(),
+            voice_b: Vec::new(),
             frequency_buffer: Vec::new(),
             volume_buffer: Vec::new(),
+            sidechain_buffer: Vec::new(),
+            effect_input: Vec::new(),
+            effect_output: Vec::new(),
+        }
+    }
+
+    /// An idle voice with the same settings and effect chain, with fresh
+    /// effect state.
+    pub fn idle_copy(&self) -> Self {
+        Self {
+            renderer: self.renderer.idle_copy(),
+            adsr: self.adsr.clone(),
+            effects: self.effects.iter().map(|e| e.clone_voice_effect()).collect(),
+            sidechain_level: self.sidechain_level,
+            effects_bypassed: self.effects_bypassed,
+            profiling: self.profiling,
+            max_frequency: self.max_frequency,
+            oversample: self.oversample,
+            blanking_samples: self.blanking_samples,
+            ..Self::new(self.sample_rate)
         }
     }
 
+    /// Restart the random sources of the whole effect chain from `seed`,
+    /// with a separate stream for each effect.
+    pub fn set_seed(&mut self, seed: u64) {
+        for (i, effect) in self.effects.iter_mut().enumerate() {
+            effect.set_seed(derive_seed(seed, i as u64));
+        }
+    }
+
+    /// How many times the voice finished tracing the frame during the last
+    /// rendered block.
+    pub fn frames_completed(&self) -> usize {
+        self.frames_completed
+    }
+
     /// Check if this voice is currently active (playing a note).
     pub fn is_active(&self) -> bool {
         self.active
     }
 
+    /// Check if this voice is the synthesizer's free-run voice.
+    pub fn is_free_running(&self) -> bool {
+        self.free_running
+    }
+
     /// Get the current frequency.
     pub fn frequency(&self) -> f64 {
         self.actual_frequency
@@ -147,7 +336,85 @@ impl ShapeVoice {
     /// Set the sample rate.
     pub fn set_sample_rate(&mut self, sample_rate: f64) {
         self.sample_rate = sample_rate;
-        self.renderer.set_sample_rate(sample_rate);
+        self.renderer.set_sample_rate(sample_rate * self.oversample as f64);
+    }
+
+    /// Trace `factor` points per output sample and average them down.
+    ///
+    /// At high drawing frequencies each sample jumps a long way along the
+    /// frame, so sharp corners alias into stair-steps. Oversampling traces
+    /// the frame at `factor` times the sample rate and box-filters each group
+    /// of points into one sample, smoothing those steps at the cost of
+    /// `factor` times the tracing work. Effects still run once per output
+    /// sample. The factor is clamped to `1..=MAX_OVERSAMPLE`; 1 disables it.
+    pub fn set_oversample(&mut self, factor: usize) {
+        self.oversample = factor.clamp(1, MAX_OVERSAMPLE);
+        self.renderer.set_sample_rate(self.sample_rate * self.oversample as f64);
+        self.renderer.set_blanking_samples(self.blanking_samples * self.oversample);
+    }
+
+    /// Sub-samples traced per output sample.
+    pub fn oversample(&self) -> usize {
+        self.oversample
+    }
+
+    /// Drive sidechained parameters from an external level in `[0, 1]`.
+    ///
+    /// With `None` they follow this voice's own envelope instead.
+    pub fn set_sidechain_level(&mut self, level: Option<f32>) {
+        self.sidechain_level = level;
+    }
+
+    /// Time each effect as it runs, averaging into [`VoiceEffect::cost`].
+    /// Off by default, as reading the clock around every effect isn't free.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiling = enabled;
+        if !enabled {
+            for effect in &mut self.effects {
+                effect.cost.reset();
+            }
+        }
+    }
+
+    /// Blank the beam for `samples` samples before each disconnected shape.
+    pub fn set_blanking_samples(&mut self, samples: usize) {
+        self.blanking_samples = samples;
+        self.renderer.set_blanking_samples(samples * self.oversample);
+    }
+
+    /// Trace the frame at `hz` independent of the note's pitch; see
+    /// [`ShapeRenderer::set_draw_rate_hz`].
+    pub fn set_draw_rate_hz(&mut self, hz: f64) {
+        self.renderer.set_draw_rate_hz(hz);
+    }
+
+    /// Trace the frame forward then backward; see
+    /// [`ShapeRenderer::set_ping_pong`].
+    pub fn set_ping_pong(&mut self, ping_pong: bool) {
+        self.renderer.set_ping_pong(ping_pong);
+    }
+
+    /// Trace frames from their end to their start; see
+    /// [`ShapeRenderer::set_reversed`].
+    pub fn set_reversed(&mut self, reversed: bool) {
+        self.renderer.set_reversed(reversed);
+    }
+
+    /// Cap the drawing frequency at `max_frequency`; see
+    /// [`limit_frequency`]. `None` removes the cap.
+    pub fn set_max_frequency(&mut self, max_frequency: Option<f64>) {
+        self.max_frequency = max_frequency;
+    }
+
+    /// Warp the beam's speed along the frame; see
+    /// [`ShapeRenderer::set_speed_curve`].
+    pub fn set_speed_curve(&mut self, curve: Option<Env>) {
+        self.renderer.set_speed_curve(curve);
+    }
+
+    /// Skip every effect in the chain, leaving the effects and their state as they are.
+    pub fn set_effects_bypassed(&mut self, bypassed: bool) {
+        self.effects_bypassed = bypassed;
     }
 
     /// Set the ADSR envelope parameters.
@@ -168,6 +435,13 @@ impl ShapeVoice {
         self.velocity = velocity;
         self.note = midi_note;
         self.active = true;
+        self.free_running = false;
+
+        for effect in &mut self.effects {
+            if effect.reset_on_note {
+                effect.application.reset();
+            }
+        }
 
         // Load initial frame
         let mut tries = 0;
@@ -176,9 +450,10 @@ impl ShapeVoice {
             tries += 1;
         }
 
-        let frame = sound.clone_frame();
-        let frame_length = osci_core::shape::total_length(&frame);
-        self.renderer.set_shapes(frame);
+        sound.copy_frame(&mut self.spare_frame);
+        let frame_length = osci_core::shape::total_length(&self.spare_frame);
+        self.spare_frame = self.renderer.set_shapes(std::mem::take(&mut self.spare_frame));
+        self.morph_progress = 0.0;
 
         // Set up envelope
         self.adsr = adsr;
@@ -206,6 +481,38 @@ impl ShapeVoice {
         let _ = frame_length; // frame_length is used by the renderer internally
     }
 
+    /// Clear the state of every effect in the chain, e.g. delay lines, as
+    /// if the voice had just been created. Unlike a new note this ignores
+    /// [`VoiceEffect::reset_on_note`].
+    pub fn reset_effects(&mut self) {
+        for effect in &mut self.effects {
+            effect.application.reset();
+        }
+    }
+
+    /// Start drawing from `fraction` of the way through the frame rather
+    /// than its beginning. Call it right after [`ShapeVoice::start_note`].
+    pub fn seek_frame(&mut self, fraction: f64) {
+        self.renderer.seek(fraction);
+    }
+
+    /// Start tracing the current frame without a note.
+    ///
+    /// The voice plays at `default_frequency` with full level and no
+    /// envelope, whether or not MIDI is enabled, until it is stopped.
+    pub fn start_free_run(&mut self, sound: &mut ShapeSound, adsr: Env, default_frequency: f64) {
+        self.start_note(0, 1.0, sound, adsr, false, default_frequency);
+        self.free_running = true;
+    }
+
+    /// Move a free-running voice to `default_frequency` straight away,
+    /// rather than at its next block.
+    pub fn retune_free_run(&mut self, default_frequency: f64) {
+        if self.free_running {
+            self.actual_frequency = limit_frequency(default_frequency, self.max_frequency);
+        }
+    }
+
     /// Stop the note (begin release phase or immediate stop).
     pub fn stop_note(&mut self, allow_tail_off: bool) {
         self.waiting_for_release = false;
@@ -228,26 +535,45 @@ impl ShapeVoice {
         output_x: &mut [f32],
         output_y: &mut [f32],
         output_z: &mut [f32],
-        num_samples: usize,
-        sound: &mut ShapeSound,
-        midi_enabled: bool,
-        default_frequency: f64,
+        block: VoiceBlock,
     ) {
         if !self.active {
             return;
         }
 
+        self.render_to_scratch(block);
+        self.mix_into(output_x, output_y, output_z, block.num_samples);
+    }
+
+    /// Render the next block into this voice's own working buffers.
+    ///
+    /// The enveloped samples are left in the voice's scratch buffers, touching
+    /// no shared state, so several voices can render concurrently. Use
+    /// [`ShapeVoice::mix_into`] to add the result to an output.
+    ///
+    /// Each time the voice wraps round its frame it moves on to the next one
+    /// in `sound`'s lookahead, so the caller is responsible for calling
+    /// [`ShapeSound::prefetch`] beforehand. With a `morph` the voice traces
+    /// that instead of its frame.
+    pub fn render_to_scratch(&mut self, block: VoiceBlock) {
+        let VoiceBlock { num_samples, sound, morph, midi_enabled, default_frequency } = block;
+
+        // A free-run voice has no note, so it always plays like MIDI is off
+        let midi_enabled = midi_enabled && !self.free_running;
+
         // Determine frequency
-        if midi_enabled {
-            self.actual_frequency = self.frequency * self.pitch_wheel_adjustment;
+        let frequency = if midi_enabled {
+            self.frequency * self.pitch_wheel_adjustment
         } else {
-            self.actual_frequency = default_frequency;
-        }
+            default_frequency
+        };
+        self.actual_frequency = limit_frequency(frequency, self.max_frequency);
 
         // Ensure working buffers are large enough
         self.resize_buffers(num_samples);
 
         let frame_length = self.renderer.frame_length();
+        self.frames_completed = 0;
 
         // First pass: generate raw samples + frequency/volume buffers
         for i in 0..num_samples {
@@ -257,10 +583,24 @@ impl ShapeVoice {
                 MIN_LENGTH_INCREMENT
             };
 
-            let point = self.renderer.next_vector_with_increment(length_increment);
+            let point = if let Some(morph) = &morph {
+                let position = morph.position_at(i, num_samples);
+                self.next_morph_point(morph.morph, position)
+            } else if self.oversample > 1 {
+                let step = length_increment / self.oversample as f64;
+                let sum = (0..self.oversample).fold(Point::ZERO, |sum, _| {
+                    sum + self.renderer.next_vector_with_increment(step)
+                });
+                sum / self.oversample as f32
+            } else {
+                self.renderer.next_vector_with_increment(length_increment)
+            };
             self.voice_x[i] = point.x;
             self.voice_y[i] = point.y;
             self.voice_z[i] = point.z;
+            self.voice_r[i] = point.r;
+            self.voice_g[i] = point.g;
+            self.voice_b[i] = point.b;
 
             self.frequency_buffer[i] = self.actual_frequency as f32;
 
@@ -285,6 +625,9 @@ impl ShapeVoice {
                     self.voice_x[j] = 0.0;
                     self.voice_y[j] = 0.0;
                     self.voice_z[j] = 0.0;
+                    self.voice_r[j] = 0.0;
+                    self.voice_g[j] = 0.0;
+                    self.voice_b[j] = 0.0;
                     self.frequency_buffer[j] = self.actual_frequency as f32;
                     self.volume_buffer[j] = 0.0;
                 }
@@ -293,18 +636,18 @@ impl ShapeVoice {
             }
 
             // Check for frame wrap-around
-            if self.renderer.frame_complete() {
-                sound.update_frame();
-                let new_frame = sound.clone_frame();
-                self.renderer.set_shapes(new_frame);
-                self.renderer.reset_frame_drawn();
+            if morph.is_none() && self.renderer.frame_complete() {
+                sound.copy_upcoming_frame(self.frames_completed, &mut self.spare_frame);
+                self.frames_completed += 1;
+                let next = std::mem::take(&mut self.spare_frame);
+                self.spare_frame = self.renderer.continue_with_shapes(next);
             }
         }
 
         // Apply per-voice effects
         self.apply_effects(num_samples);
 
-        // Apply ADSR envelope and mix into output
+        // Apply ADSR envelope
         for i in 0..num_samples {
             let gain = if midi_enabled {
                 self.volume_buffer[i] * self.velocity
@@ -312,37 +655,130 @@ impl ShapeVoice {
                 self.velocity.max(1.0) // Default velocity of 1 for non-MIDI
             };
 
-            output_x[i] += self.voice_x[i] * gain;
-            output_y[i] += self.voice_y[i] * gain;
-            output_z[i] += self.voice_z[i] * gain;
+            self.voice_x[i] *= gain;
+            self.voice_y[i] *= gain;
+            self.voice_z[i] *= gain;
+            self.voice_r[i] *= gain;
+            self.voice_g[i] *= gain;
+            self.voice_b[i] *= gain;
+        }
+    }
+
+    /// Trace the next sample of `morph` at `position`, averaging sub-samples
+    /// when oversampling.
+    fn next_morph_point(&mut self, morph: &ShapeMorph, position: f32) -> Point {
+        let step = if self.sample_rate > 0.0 {
+            self.actual_frequency / self.sample_rate / self.oversample as f64
+        } else {
+            0.0
+        };
+        let mut sum = Point::ZERO;
+        for _ in 0..self.oversample {
+            let progress = self.morph_progress as f32;
+            let progress = if self.renderer.reversed() { 1.0 - progress } else { progress };
+            sum += morph.point_at(progress, position);
+            self.morph_progress += step;
+            if self.morph_progress >= 1.0 {
+                self.morph_progress = self.morph_progress.fract();
+                self.frames_completed += 1;
+            }
+        }
+        sum / self.oversample as f32
+    }
+
+    /// Add the block last rendered by [`ShapeVoice::render_to_scratch`] into
+    /// the output buffers.
+    pub fn mix_into(
+        &self,
+        output_x: &mut [f32],
+        output_y: &mut [f32],
+        output_z: &mut [f32],
+        num_samples: usize,
+    ) {
+        for i in 0..num_samples {
+            output_x[i] += self.voice_x[i];
+            output_y[i] += self.voice_y[i];
+            output_z[i] += self.voice_z[i];
+        }
+    }
+
+    /// Add the colour of the block last rendered into the output buffers.
+    ///
+    /// Kept apart from [`ShapeVoice::mix_into`] as the synth's outputs only
+    /// carry X, Y and Z; this is how colour effects can be read back.
+    pub fn mix_colour_into(
+        &self,
+        output_r: &mut [f32],
+        output_g: &mut [f32],
+        output_b: &mut [f32],
+        num_samples: usize,
+    ) {
+        for i in 0..num_samples {
+            output_r[i] += self.voice_r[i];
+            output_g[i] += self.voice_g[i];
+            output_b[i] += self.voice_b[i];
         }
     }
 
     fn apply_effects(&mut self, num_samples: usize) {
+        if self.effects_bypassed {
+            return;
+        }
+
         let sample_rate = self.sample_rate as f32;
+        let freq = self.actual_frequency as f32;
+
+        let sidechain = match self.sidechain_level {
+            Some(level) => {
+                self.sidechain_buffer[..num_samples].fill(level.clamp(0.0, 1.0));
+                &self.sidechain_buffer[..num_samples]
+            }
+            None => &self.volume_buffer[..num_samples],
+        };
+
+        let soloing = self.effects.iter().any(|e| e.enabled && e.solo);
 
         for effect in &mut self.effects {
-            if !effect.enabled {
+            if !effect.enabled || (soloing && !effect.solo) {
+                effect.cost.reset();
                 continue;
             }
+            let start = self.profiling.then(Instant::now);
 
             // Animate parameters
-            effect.animate(num_samples, sample_rate, Some(&self.volume_buffer));
+            effect.animate(num_samples, sample_rate, Some(sidechain));
 
-            // Copy values to avoid borrow conflict with application
-            let values: Vec<f32> = effect.animated_values.clone();
-            let freq = self.actual_frequency as f32;
-
-            // Apply effect per-sample
             for i in 0..num_samples {
-                let input = Point::new(self.voice_x[i], self.voice_y[i], self.voice_z[i]);
-                let external = Point::ZERO;
+                self.effect_input[i] = Point::with_rgb(
+                    self.voice_x[i],
+                    self.voice_y[i],
+                    self.voice_z[i],
+                    self.voice_r[i],
+                    self.voice_g[i],
+                    self.voice_b[i],
+                );
+            }
 
-                let output = effect.application.apply(i, input, external, &values, sample_rate, freq);
+            // Apply effect over the whole block
+            effect.application.apply_block(
+                &self.effect_input[..num_samples],
+                &mut self.effect_output[..num_samples],
+                &effect.animated_values,
+                sample_rate,
+                freq,
+            );
 
+            for (i, output) in self.effect_output[..num_samples].iter().enumerate() {
                 self.voice_x[i] = output.x;
                 self.voice_y[i] = output.y;
                 self.voice_z[i] = output.z;
+                self.voice_r[i] = output.r;
+                self.voice_g[i] = output.g;
+                self.voice_b[i] = output.b;
+            }
+
+            if let Some(start) = start {
+                effect.cost.record(start.elapsed(), num_samples);
             }
         }
     }
@@ -352,13 +788,20 @@ impl ShapeVoice {
             self.voice_x.resize(num_samples, 0.0);
             self.voice_y.resize(num_samples, 0.0);
             self.voice_z.resize(num_samples, 0.0);
+            self.voice_r.resize(num_samples, 0.0);
+            self.voice_g.resize(num_samples, 0.0);
+            self.voice_b.resize(num_samples, 0.0);
             self.frequency_buffer.resize(num_samples, 0.0);
             self.volume_buffer.resize(num_samples, 0.0);
+            self.sidechain_buffer.resize(num_samples, 0.0);
+            self.effect_input.resize(num_samples, Point::ZERO);
+            self.effect_output.resize(num_samples, Point::ZERO);
         }
     }
 
     fn note_stopped(&mut self) {
         self.active = false;
+        self.free_running = false;
     }
 }
 
@@ -371,6 +814,28 @@ pub fn midi_note_to_hz(note: u8) -> f64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_effect_cost_moving_average() {
+        let mut cost = EffectCost::default();
+        assert_eq!(cost.ns_per_sample(), 0.0);
+
+        // The first block sets the average, empty blocks are ignored
+        cost.record(Duration::from_nanos(64_000), 64);
+        cost.record(Duration::from_nanos(5_000), 0);
+        assert!((cost.ns_per_sample() - 1000.0).abs() < 1e-9);
+
+        // Each later block moves it part of the way
+        cost.record(Duration::from_nanos(3_000 * 64), 64);
+        assert!((cost.ns_per_sample() - (1000.0 + 2000.0 * COST_SMOOTHING)).abs() < 1e-9);
+        for _ in 0..500 {
+            cost.record(Duration::from_nanos(3_000 * 128), 128);
+        }
+        assert!((cost.ns_per_sample() - 3000.0).abs() < 1.0);
+
+        cost.reset();
+        assert_eq!(cost, EffectCost::default());
+    }
+
     #[test]
     fn test_midi_note_to_hz() {
         let hz = midi_note_to_hz(69); // A4
@@ -404,4 +869,143 @@ mod tests {
         voice.stop_note(false);
         assert!(!voice.is_active());
     }
+
+    #[test]
+    fn test_blanking_reaches_voice_z() {
+        use osci_core::shape::Line;
+
+        let mut voice = ShapeVoice::new(44100.0);
+        voice.set_blanking_samples(4);
+        let mut sound = ShapeSound::new(4);
+        sound.sender().send(vec![
+            Box::new(Line::new_2d(-1.0, 0.0, 0.0, 0.0)),
+            Box::new(Line::new_2d(0.0, 0.5, 1.0, 0.5)),
+        ]).unwrap();
+        sound.update_frame();
+        voice.start_note(69, 1.0, &mut sound, Env::adsr(0.0, 0.0, 1.0, 0.0, 1.0, -4.0), false, 441.0);
+
+        let (mut x, mut y, mut z) = (vec![0.0; 200], vec![0.0; 200], vec![0.0; 200]);
+        voice.render_next_block(&mut x, &mut y, &mut z, VoiceBlock {
+            num_samples: 200,
+            sound: &sound,
+            morph: None,
+            midi_enabled: false,
+            default_frequency: 441.0,
+        });
+
+        let lit = z.iter().filter(|&&z| z > 0.99).count();
+        let blanked = z.iter().filter(|&&z| z == 0.0).count();
+        assert!(lit > 150, "only {lit} lit samples");
+        // Into the second line and back to the first, at least once each
+        assert!(blanked >= 8, "only {blanked} blanked samples");
+    }
+
+    #[test]
+    fn test_colour_effects_reach_voice_colour() {
+        use osci_core::shape::Line;
+
+        let mut voice = ShapeVoice::new(44100.0);
+        let entry = osci_effects::registry::find_effect("chromaSplit").unwrap();
+        let mut params = (entry.parameters)();
+        params[0].value = 1.0;
+        voice.effects.push(VoiceEffect::new("chromaSplit", (entry.constructor)(), params));
+        let mut sound = ShapeSound::new(4);
+        let line = Line::new_2d(-1.0, 0.0, 1.0, 0.0).with_colour(1.0, 1.0, 1.0);
+        sound.sender().send(vec![Box::new(line)]).unwrap();
+        sound.update_frame();
+        voice.start_note(69, 1.0, &mut sound, Env::adsr(0.0, 0.0, 1.0, 0.0, 1.0, -4.0), false, 441.0);
+
+        let (mut x, mut y, mut z) = (vec![0.0; 200], vec![0.0; 200], vec![0.0; 200]);
+        voice.render_next_block(&mut x, &mut y, &mut z, VoiceBlock {
+            num_samples: 200,
+            sound: &sound,
+            morph: None,
+            midi_enabled: false,
+            default_frequency: 441.0,
+        });
+        let (mut r, mut g, mut b) = (vec![0.0; 200], vec![0.0; 200], vec![0.0; 200]);
+        voice.mix_colour_into(&mut r, &mut g, &mut b, 200);
+
+        // Tracing along the split axis pushes the colour towards red
+        let red = (0..200).filter(|&i| r[i] > 1.1 && b[i] < 0.9).count();
+        assert!(red > 190, "only {red} red samples");
+    }
+
+    #[test]
+    fn test_dahdsr_release_time_from_release_node() {
+        let mut voice = ShapeVoice::new(44100.0);
+        let mut sound = ShapeSound::new(4);
+        let adsr = Env::dahdsr(0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 1.0, -4.0);
+
+        voice.start_note(60, 1.0, &mut sound, adsr, true, 440.0);
+
+        // Sustain begins after delay + attack + hold + decay
+        assert!((voice.release_time - 1.0).abs() < 1e-9);
+        assert!((voice.end_time - 1.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_delay_keeps_echoes_across_retrigger_without_reset() {
+        use osci_effects::delay::DelayEffect;
+
+        let echo_after_retrigger = |reset_on_note: bool| {
+            let mut voice = ShapeVoice::new(1000.0);
+            let mut sound = ShapeSound::new(4);
+            let mut effect = VoiceEffect::new("delay", Box::new(DelayEffect::new()), Vec::new());
+            assert!(effect.reset_on_note);
+            effect.reset_on_note = reset_on_note;
+            voice.effects.push(effect);
+
+            // Full decay, 10 sample delay at 1 kHz
+            let values = [1.0, 0.01];
+            let app = &mut voice.effects[0].application;
+            for i in 0..10 {
+                app.apply(i, Point::xy(1.0, 1.0), Point::ZERO, &values, 1000.0, 440.0);
+            }
+
+            let adsr = Env::adsr(0.01, 0.3, 0.5, 1.0, 1.0, -4.0);
+            voice.start_note(60, 1.0, &mut sound, adsr, true, 440.0);
+
+            let app = &mut voice.effects[0].application;
+            (0..10)
+                .map(|i| app.apply(i, Point::ZERO, Point::ZERO, &values, 1000.0, 440.0).x)
+                .sum::<f32>()
+        };
+
+        assert!((echo_after_retrigger(false) - 10.0).abs() < 1e-6);
+        assert_eq!(echo_after_retrigger(true), 0.0);
+    }
+
+    #[test]
+    fn test_animate_reuses_buffer_without_changing_values() {
+        use osci_core::parameter::LfoType;
+
+        let mut param = EffectParameter::new("Test", "", "test", 0.5, 0.0, 1.0);
+        param.lfo_type = LfoType::Sine;
+        param.lfo_rate = 3.0;
+        param.lfo_enabled = true;
+
+        let app = osci_effects::translate::Translate::new();
+        let mut effect = VoiceEffect::new("translate", Box::new(app), vec![param.clone()]);
+
+        // Reference: the old behaviour, a fresh buffer for every block
+        let mut reference_param = param;
+        let mut reference_current = 0.0;
+        let mut expected = Vec::new();
+        for block_size in [256, 64] {
+            let mut buf = vec![0.0f32; block_size];
+            animate_parameter(&mut reference_param, &mut buf, 44100.0, &mut reference_current, None);
+            expected.push(buf[block_size - 1]);
+        }
+
+        effect.animate(256, 44100.0, None);
+        let first = effect.values()[0];
+        effect.animate(64, 44100.0, None);
+        let second = effect.values()[0];
+
+        assert_eq!(first.to_bits(), expected[0].to_bits());
+        assert_eq!(second.to_bits(), expected[1].to_bits());
+        assert_ne!(first, second);
+        assert_eq!(effect.animation_buffer.len(), 256);
+    }
 }
@@ -0,0 +1,124 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use osci_core::shape::{CubicBezierCurve, Line, Shape};
+use osci_synth::frame_producer::{AnimatedFrameSource, Frame, FrameSource};
+use osci_synth::{MidiEvent, ShapeSound, StaticFrameSource, Synthesizer};
+
+// ── Allocation counting ──────────────────────────────────────────
+
+/// Counts the allocations made by each thread, so tests running in
+/// parallel don't see each other's.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Number of allocations `f` makes on this thread.
+fn allocations_in(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+// ── Helpers ──────────────────────────────────────────────────────
+
+const SAMPLE_RATE: f64 = 44100.0;
+const BLOCK_SIZE: usize = 512;
+
+/// A frame mixing plain, reversed and coloured shapes, offset by `y`.
+fn mixed_frame(y: f32) -> Frame {
+    let curve = CubicBezierCurve::new(-1.0, y, -0.5, 1.0, 0.5, -1.0, 1.0, y);
+    vec![
+        Box::new(Line::new_2d(-1.0, y, 1.0, y)),
+        curve.reversed(),
+        Box::new(Line::new_2d(1.0, y, -1.0, -y).with_colour(1.0, 0.0, 0.0)),
+    ]
+}
+
+// ── Tests ────────────────────────────────────────────────────────
+
+#[test]
+fn refilling_recycled_frames_does_not_allocate() {
+    let mut source = StaticFrameSource::new(mixed_frame(0.25));
+    let mut frame = source.next_frame().unwrap();
+
+    let count = allocations_in(|| {
+        for _ in 0..100 {
+            assert!(source.fill_frame(&mut frame));
+        }
+    });
+    assert_eq!(count, 0, "refilling a recycled frame allocated {count} times");
+    assert_eq!(frame.len(), 3);
+}
+
+#[test]
+fn stepping_through_an_animation_does_not_allocate() {
+    let frames = vec![mixed_frame(0.0), mixed_frame(0.5)];
+    let mut source = AnimatedFrameSource::new(frames, 120.0);
+    let mut frame = source.next_frame().unwrap();
+
+    let count = allocations_in(|| {
+        for _ in 0..4 {
+            assert!(source.fill_frame(&mut frame));
+        }
+    });
+    assert_eq!(count, 0, "stepping the animation allocated {count} times");
+}
+
+#[test]
+fn rendering_changing_frames_does_not_allocate() {
+    let mut synth = Synthesizer::new(4, SAMPLE_RATE);
+    let mut sound = ShapeSound::new(4);
+    let tx = sound.sender();
+    let recycler = sound.recycler();
+    let mut sources = [
+        StaticFrameSource::new(mixed_frame(0.0)),
+        StaticFrameSource::new(mixed_frame(0.5)),
+    ];
+    for i in 0..4 {
+        tx.send(sources[i % 2].next_frame().unwrap()).unwrap();
+    }
+    synth.handle_midi_event(MidiEvent::NoteOn { note: 69, velocity: 1.0 }, &mut sound);
+
+    // Each block wraps round the frame several times, moving on to a new one
+    // each time; the frames the sound is done with are refilled and sent back
+    let mut x = vec![0.0f32; BLOCK_SIZE];
+    let mut y = vec![0.0f32; BLOCK_SIZE];
+    let mut z = vec![0.0f32; BLOCK_SIZE];
+    let mut next = 0;
+    let mut render = |blocks: usize| {
+        for _ in 0..blocks {
+            synth.render_next_block(&mut x, &mut y, &mut z, BLOCK_SIZE, &mut sound);
+            while let Ok(mut frame) = recycler.try_recv() {
+                sources[next % 2].fill_frame(&mut frame);
+                next += 1;
+                let _ = tx.try_send(frame);
+            }
+        }
+    };
+
+    render(8);
+    let count = allocations_in(|| render(32));
+    assert_eq!(count, 0, "rendering allocated {count} times");
+}
@@ -15,18 +15,23 @@ pub struct Compositor {
     loc_glow_amount: glow::UniformLocation,
     loc_scatter_amount: glow::UniformLocation,
     loc_overexposure: glow::UniformLocation,
+    loc_tone_curve: glow::UniformLocation,
+    loc_gamma: glow::UniformLocation,
     loc_saturation: glow::UniformLocation,
     loc_ambient: glow::UniformLocation,
     loc_noise: glow::UniformLocation,
     loc_time: glow::UniformLocation,
     loc_reflection_mode: glow::UniformLocation,
-    loc_goniometer: glow::UniformLocation,
+    loc_transparent: glow::UniformLocation,
     frame_count: u32,
+    copy_program: glow::Program,
+    loc_copy_texture: glow::UniformLocation,
 }
 
 impl Compositor {
     pub fn new(gl: &glow::Context) -> Self {
         let program = compile_fullscreen_program(gl, shaders::COMPOSITE_FRAGMENT);
+        let copy_program = compile_fullscreen_program(gl, shaders::COPY_FRAGMENT);
 
         unsafe {
             let loc = |name: &str| gl.get_uniform_location(program, name).expect(name);
@@ -40,18 +45,32 @@ impl Compositor {
                 loc_glow_amount: loc("u_glow_amount"),
                 loc_scatter_amount: loc("u_scatter_amount"),
                 loc_overexposure: loc("u_overexposure"),
+                loc_tone_curve: loc("u_tone_curve"),
+                loc_gamma: loc("u_gamma"),
                 loc_saturation: loc("u_saturation"),
                 loc_ambient: loc("u_ambient"),
                 loc_noise: loc("u_noise"),
                 loc_time: loc("u_time"),
                 loc_reflection_mode: loc("u_reflection_mode"),
-                loc_goniometer: loc("u_goniometer"),
+                loc_transparent: loc("u_transparent"),
                 frame_count: 0,
+                copy_program,
+                loc_copy_texture: gl
+                    .get_uniform_location(copy_program, "u_texture")
+                    .expect("u_texture"),
             }
         }
     }
 
+    /// Restart the noise grain sequence from the first frame.
+    pub fn reset(&mut self) {
+        self.frame_count = 0;
+    }
+
     /// Render the final composited image to the currently bound FBO.
+    ///
+    /// With `settings.transparent_background` on, the premultiplied output
+    /// is blended over what the FBO already holds; otherwise it replaces it.
     pub fn render(
         &mut self,
         gl: &glow::Context,
@@ -65,7 +84,7 @@ impl Compositor {
 
         unsafe {
             gl.use_program(Some(self.program));
-            gl.disable(glow::BLEND);
+            set_blend(gl, settings);
 
             // Bind textures
             gl.active_texture(glow::TEXTURE0);
@@ -86,12 +105,14 @@ impl Compositor {
             gl.uniform_1_f32(Some(&self.loc_glow_amount), settings.glow_amount);
             gl.uniform_1_f32(Some(&self.loc_scatter_amount), settings.scatter_amount);
             gl.uniform_1_f32(Some(&self.loc_overexposure), settings.overexposure);
+            gl.uniform_1_i32(Some(&self.loc_tone_curve), settings.tone_curve.index() as i32);
+            gl.uniform_1_f32(Some(&self.loc_gamma), settings.gamma.max(0.01));
             gl.uniform_1_f32(Some(&self.loc_saturation), settings.saturation);
             gl.uniform_1_f32(Some(&self.loc_ambient), settings.ambient);
             gl.uniform_1_f32(Some(&self.loc_noise), settings.noise);
             gl.uniform_1_f32(Some(&self.loc_time), self.frame_count as f32 * 0.0167);
             gl.uniform_1_i32(Some(&self.loc_reflection_mode), settings.reflection_mode as i32);
-            gl.uniform_1_i32(Some(&self.loc_goniometer), if settings.goniometer { 1 } else { 0 });
+            gl.uniform_1_i32(Some(&self.loc_transparent), settings.transparent_background as i32);
 
             quad.draw(gl);
 
@@ -100,8 +121,49 @@ impl Compositor {
         }
     }
 
+    /// Draw `composited`, a texture [`Compositor::render`] has already
+    /// filled, to the currently bound FBO, blending the same way.
+    ///
+    /// Shows a frame that was composited elsewhere without compositing it
+    /// again, so the noise grain doesn't step twice.
+    pub fn present(
+        &self,
+        gl: &glow::Context,
+        composited: glow::Texture,
+        settings: &VisualiserSettings,
+        quad: &FullscreenQuad,
+    ) {
+        unsafe {
+            gl.use_program(Some(self.copy_program));
+            set_blend(gl, settings);
+
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(composited));
+            gl.uniform_1_i32(Some(&self.loc_copy_texture), 0);
+
+            quad.draw(gl);
+
+            gl.use_program(None);
+        }
+    }
+
     pub fn destroy(&self, gl: &glow::Context) {
-        unsafe { gl.delete_program(self.program); }
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_program(self.copy_program);
+        }
+    }
+}
+
+/// Blend premultiplied output over the FBO with a transparent background,
+/// otherwise replace it.
+unsafe fn set_blend(gl: &glow::Context, settings: &VisualiserSettings) {
+    if settings.transparent_background {
+        gl.enable(glow::BLEND);
+        gl.blend_equation(glow::FUNC_ADD);
+        gl.blend_func(glow::ONE, glow::ONE_MINUS_SRC_ALPHA);
+    } else {
+        gl.disable(glow::BLEND);
     }
 }
 
@@ -2,6 +2,7 @@ pub mod bloom;
 pub mod compositor;
 pub mod fbo;
 pub mod line_renderer;
+pub mod offline;
 pub mod persistence;
 pub mod quad;
 pub mod recorder;
@@ -9,5 +10,5 @@ pub mod renderer;
 pub mod settings;
 pub mod shaders;
 
-pub use renderer::OsciRenderer;
-pub use settings::VisualiserSettings;
+pub use renderer::{OsciRenderer, RenderStats};
+pub use settings::{focus_for_frequency, AfterglowCurve, BlendMode, ToneCurve, VisualiserSettings};
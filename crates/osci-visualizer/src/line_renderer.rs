@@ -1,7 +1,12 @@
 use glow::HasContext;
 
+use crate::settings::BlendMode;
 use crate::shaders;
 
+/// Most segments the buffers will grow to hold (about 25 MB of vertices).
+/// Longer paths are truncated.
+pub const SEGMENT_LIMIT: usize = 1 << 18;
+
 /// Renders line segments as Gaussian beams using quad-per-segment geometry.
 pub struct LineRenderer {
     program: glow::Program,
@@ -10,22 +15,36 @@ pub struct LineRenderer {
     ibo: glow::Buffer,
     loc_sigma: glow::UniformLocation,
     loc_intensity: glow::UniformLocation,
-    max_segments: usize,
+    loc_flip: glow::UniformLocation,
+    loc_max_blend: glow::UniformLocation,
+    /// Segments the vertex and index buffers currently have room for.
+    capacity: usize,
+    /// Set once a path has been cut short at [`SEGMENT_LIMIT`].
+    warned_truncation: bool,
+}
+
+/// Buffer capacity needed for `needed` segments: unchanged if there is
+/// room, otherwise the next power of two, capped at `limit`.
+pub fn grown_capacity(capacity: usize, needed: usize, limit: usize) -> usize {
+    if needed <= capacity {
+        capacity
+    } else {
+        needed.next_power_of_two().min(limit).max(capacity)
+    }
 }
 
 impl LineRenderer {
-    pub fn new(gl: &glow::Context, max_segments: usize) -> Self {
+    /// Create a renderer with room for `initial_segments` segments. The
+    /// buffers grow when a longer path comes in.
+    pub fn new(gl: &glow::Context, initial_segments: usize) -> Self {
         let program = compile_program(gl, shaders::LINE_VERTEX, shaders::LINE_FRAGMENT);
 
         let loc_sigma = unsafe { gl.get_uniform_location(program, "u_sigma").expect("u_sigma") };
         let loc_intensity = unsafe { gl.get_uniform_location(program, "u_intensity").expect("u_intensity") };
+        let loc_flip = unsafe { gl.get_uniform_location(program, "u_flip").expect("u_flip") };
+        let loc_max_blend = unsafe { gl.get_uniform_location(program, "u_max_blend").expect("u_max_blend") };
 
-        // Pre-allocate vertex buffer for max_segments * 4 vertices
-        // Each vertex: pos(2) + other(2) + perp(1) + along(1) = 6 floats
-        let vbo_size = max_segments * 4 * 6 * std::mem::size_of::<f32>();
-
-        // Pre-allocate index buffer for max_segments * 6 indices
-        let ibo_size = max_segments * 6 * std::mem::size_of::<u32>();
+        let capacity = initial_segments.clamp(1, SEGMENT_LIMIT);
 
         unsafe {
             let vao = gl.create_vertex_array().expect("create vao");
@@ -35,7 +54,6 @@ impl LineRenderer {
             gl.bind_vertex_array(Some(vao));
 
             gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
-            gl.buffer_data_size(glow::ARRAY_BUFFER, vbo_size as i32, glow::DYNAMIC_DRAW);
 
             let stride = 6 * std::mem::size_of::<f32>() as i32;
             // a_pos: location 0
@@ -52,7 +70,7 @@ impl LineRenderer {
             gl.vertex_attrib_pointer_f32(3, 1, glow::FLOAT, false, stride, 20);
 
             gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(ibo));
-            gl.buffer_data_size(glow::ELEMENT_ARRAY_BUFFER, ibo_size as i32, glow::DYNAMIC_DRAW);
+            allocate_buffers(gl, capacity);
 
             gl.bind_vertex_array(None);
             gl.bind_buffer(glow::ARRAY_BUFFER, None);
@@ -65,20 +83,54 @@ impl LineRenderer {
                 ibo,
                 loc_sigma,
                 loc_intensity,
-                max_segments,
+                loc_flip,
+                loc_max_blend,
+                capacity,
+                warned_truncation: false,
             }
         }
     }
 
+    /// Segments the buffers currently have room for.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Render line segments from x/y sample arrays into the currently bound FBO.
-    /// Samples are in [-1, 1] and get mapped to [0, 1] UV space.
-    pub fn render(&self, gl: &glow::Context, x_samples: &[f32], y_samples: &[f32], sigma: f32, intensity: f32) {
+    /// Samples are in [-1, 1] and get mapped to [0, 1] UV space. `flip`
+    /// mirrors the drawing horizontally and vertically, as `[x, y]`.
+    /// `blend_mode` sets how overlapping segments combine.
+    ///
+    /// The buffers grow to fit the path, up to [`SEGMENT_LIMIT`] segments.
+    /// Returns the number of segments drawn.
+    pub fn render(&mut self, gl: &glow::Context, x_samples: &[f32], y_samples: &[f32], sigma: f32, intensity: f32, flip: [bool; 2], blend_mode: BlendMode) -> usize {
         let n = x_samples.len().min(y_samples.len());
         if n < 2 {
-            return;
+            return 0;
+        }
+
+        let needed = n - 1;
+        let capacity = grown_capacity(self.capacity, needed, SEGMENT_LIMIT);
+        if capacity != self.capacity {
+            log::debug!("Growing line buffers from {} to {} segments", self.capacity, capacity);
+            unsafe {
+                gl.bind_vertex_array(Some(self.vao));
+                gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+                allocate_buffers(gl, capacity);
+                gl.bind_vertex_array(None);
+            }
+            self.capacity = capacity;
+        }
+        if needed > self.capacity && !self.warned_truncation {
+            log::warn!(
+                "Path of {} segments truncated to the line renderer limit of {}",
+                needed,
+                self.capacity
+            );
+            self.warned_truncation = true;
         }
 
-        let num_segments = (n - 1).min(self.max_segments);
+        let num_segments = needed.min(self.capacity);
 
         // Build vertex data: 4 vertices per segment, 6 floats each
         let mut vertices = Vec::with_capacity(num_segments * 4 * 6);
@@ -111,6 +163,9 @@ impl LineRenderer {
             gl.use_program(Some(self.program));
             gl.uniform_1_f32(Some(&self.loc_sigma), sigma);
             gl.uniform_1_f32(Some(&self.loc_intensity), intensity);
+            let sign = |flipped: bool| if flipped { -1.0 } else { 1.0 };
+            gl.uniform_2_f32(Some(&self.loc_flip), sign(flip[0]), sign(flip[1]));
+            gl.uniform_1_i32(Some(&self.loc_max_blend), (blend_mode == BlendMode::Max) as i32);
 
             gl.bind_vertex_array(Some(self.vao));
 
@@ -122,15 +177,20 @@ impl LineRenderer {
             gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.ibo));
             gl.buffer_sub_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, 0, cast_slice_u32(&indices));
 
-            // Additive blending
             gl.enable(glow::BLEND);
             gl.blend_func(glow::ONE, glow::ONE);
+            match blend_mode {
+                BlendMode::Additive => gl.blend_equation(glow::FUNC_ADD),
+                BlendMode::Max => gl.blend_equation(glow::MAX),
+            }
 
             gl.draw_elements(glow::TRIANGLES, (num_segments * 6) as i32, glow::UNSIGNED_INT, 0);
 
             gl.bind_vertex_array(None);
             gl.use_program(None);
         }
+
+        num_segments
     }
 
     pub fn destroy(&self, gl: &glow::Context) {
@@ -143,6 +203,16 @@ impl LineRenderer {
     }
 }
 
+/// (Re)allocate the bound vertex and index buffers for `segments` segments.
+unsafe fn allocate_buffers(gl: &glow::Context, segments: usize) {
+    // Each vertex: pos(2) + other(2) + perp(1) + along(1) = 6 floats, 4 per segment
+    let vbo_size = segments * 4 * 6 * std::mem::size_of::<f32>();
+    // Two triangles, 6 indices per segment
+    let ibo_size = segments * 6 * std::mem::size_of::<u32>();
+    gl.buffer_data_size(glow::ARRAY_BUFFER, vbo_size as i32, glow::DYNAMIC_DRAW);
+    gl.buffer_data_size(glow::ELEMENT_ARRAY_BUFFER, ibo_size as i32, glow::DYNAMIC_DRAW);
+}
+
 fn compile_program(gl: &glow::Context, vert_src: &str, frag_src: &str) -> glow::Program {
     unsafe {
         let program = gl.create_program().expect("create program");
@@ -189,3 +259,17 @@ fn cast_slice_u32(data: &[u32]) -> &[u8] {
         std::slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * std::mem::size_of::<u32>())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_grows_to_fit_up_to_limit() {
+        assert_eq!(grown_capacity(2048, 100, SEGMENT_LIMIT), 2048);
+        assert_eq!(grown_capacity(2048, 2048, SEGMENT_LIMIT), 2048);
+        assert_eq!(grown_capacity(2048, 2049, SEGMENT_LIMIT), 4096);
+        assert_eq!(grown_capacity(2048, 10_000, SEGMENT_LIMIT), 16384);
+        assert_eq!(grown_capacity(2048, SEGMENT_LIMIT * 3, SEGMENT_LIMIT), SEGMENT_LIMIT);
+    }
+}
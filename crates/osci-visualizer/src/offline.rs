@@ -0,0 +1,715 @@
+//! Offline rendering of frame sets to pixel buffers, for exporting shape
+//! files to images or video without a host or a window.
+//!
+//! The GL side needs the `headless` feature, which creates an offscreen EGL
+//! context and drives the same [`OsciRenderer`](crate::OsciRenderer) the
+//! editor uses.
+
+use osci_core::Frame;
+
+use crate::recorder::CapturedFrame;
+
+/// An RGBA8 frame produced by offline rendering, rows ordered top to bottom.
+pub type RgbaFrame = CapturedFrame;
+
+/// Samples traced through each frame, matching the renderer's initial segment budget.
+pub const OFFLINE_SAMPLES_PER_FRAME: usize = 2048;
+
+/// Trace `count` beam positions through `frame`, spaced evenly along its
+/// total path length the way the synthesizer would draw it.
+///
+/// Returns the x and y sample buffers the renderer consumes. An empty frame
+/// leaves the beam parked at the centre.
+pub fn sample_frame(frame: &Frame, count: usize) -> (Vec<f32>, Vec<f32>) {
+    let mut x = Vec::with_capacity(count);
+    let mut y = Vec::with_capacity(count);
+    if frame.shapes.is_empty() || frame.total_length <= 0.0 {
+        x.resize(count, 0.0);
+        y.resize(count, 0.0);
+        return (x, y);
+    }
+
+    let step = frame.total_length / count as f32;
+    let mut shape_index = 0;
+    let mut shape_start = 0.0;
+    for i in 0..count {
+        let distance = i as f32 * step;
+        while shape_index + 1 < frame.shapes.len()
+            && distance >= shape_start + frame.shapes[shape_index].length()
+        {
+            shape_start += frame.shapes[shape_index].length();
+            shape_index += 1;
+        }
+        let shape = &frame.shapes[shape_index];
+        let progress = if shape.length() > 0.0 {
+            ((distance - shape_start) / shape.length()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let point = shape.next_vector(progress);
+        x.push(point.x);
+        y.push(point.y);
+    }
+    (x, y)
+}
+
+#[cfg(feature = "headless")]
+pub use headless::{render_offline, HeadlessContext, OfflineFrames};
+
+#[cfg(feature = "headless")]
+mod headless {
+    use glow::HasContext;
+    use khronos_egl as egl;
+    use osci_core::Frame;
+
+    use super::{sample_frame, RgbaFrame, OFFLINE_SAMPLES_PER_FRAME};
+    use crate::{OsciRenderer, VisualiserSettings};
+    #[cfg(test)]
+    use crate::{AfterglowCurve, BlendMode, ToneCurve};
+
+    /// `EGL_PLATFORM_SURFACELESS_MESA`, from the EGL_MESA_platform_surfaceless extension.
+    const PLATFORM_SURFACELESS_MESA: egl::Enum = 0x31DD;
+
+    /// An offscreen EGL pbuffer with a current OpenGL 3.3 core context.
+    pub struct HeadlessContext {
+        egl: egl::Instance<egl::Static>,
+        display: egl::Display,
+        surface: egl::Surface,
+        context: egl::Context,
+        gl: glow::Context,
+    }
+
+    impl HeadlessContext {
+        /// Create the context with a `width`x`height` pbuffer as its
+        /// default framebuffer, and make it current on this thread.
+        pub fn new(width: u32, height: u32) -> Result<Self, String> {
+            let egl = egl::Instance::new(egl::Static);
+            let display = surfaceless_display(&egl)
+                .or_else(|| {
+                    let display = unsafe { egl.get_display(egl::DEFAULT_DISPLAY) }?;
+                    egl.initialize(display).ok().map(|_| display)
+                })
+                .ok_or("No EGL display could be initialized")?;
+
+            let config_attribs = [
+                egl::SURFACE_TYPE,
+                egl::PBUFFER_BIT,
+                egl::RENDERABLE_TYPE,
+                egl::OPENGL_BIT,
+                egl::RED_SIZE,
+                8,
+                egl::GREEN_SIZE,
+                8,
+                egl::BLUE_SIZE,
+                8,
+                egl::ALPHA_SIZE,
+                8,
+                egl::NONE,
+            ];
+            let config = egl
+                .choose_first_config(display, &config_attribs)
+                .map_err(|e| format!("EGL config query failed: {}", e))?
+                .ok_or("No EGL config supports offscreen OpenGL")?;
+
+            egl.bind_api(egl::OPENGL_API)
+                .map_err(|e| format!("EGL could not bind OpenGL: {}", e))?;
+
+            let surface_attribs = [
+                egl::WIDTH,
+                width as egl::Int,
+                egl::HEIGHT,
+                height as egl::Int,
+                egl::NONE,
+            ];
+            let surface = egl
+                .create_pbuffer_surface(display, config, &surface_attribs)
+                .map_err(|e| format!("EGL pbuffer creation failed: {}", e))?;
+
+            let context_attribs = [
+                egl::CONTEXT_MAJOR_VERSION,
+                3,
+                egl::CONTEXT_MINOR_VERSION,
+                3,
+                egl::CONTEXT_OPENGL_PROFILE_MASK,
+                egl::CONTEXT_OPENGL_CORE_PROFILE_BIT,
+                egl::NONE,
+            ];
+            let context = egl
+                .create_context(display, config, None, &context_attribs)
+                .map_err(|e| format!("EGL context creation failed: {}", e))?;
+            egl.make_current(display, Some(surface), Some(surface), Some(context))
+                .map_err(|e| format!("EGL make current failed: {}", e))?;
+
+            let gl = unsafe {
+                glow::Context::from_loader_function(|name| {
+                    egl.get_proc_address(name)
+                        .map_or(std::ptr::null(), |f| f as *const std::ffi::c_void)
+                })
+            };
+
+            Ok(Self {
+                egl,
+                display,
+                surface,
+                context,
+                gl,
+            })
+        }
+
+        /// The OpenGL functions for this context.
+        pub fn gl(&self) -> &glow::Context {
+            &self.gl
+        }
+    }
+
+    /// Mesa's surfaceless platform, which works without a display server.
+    fn surfaceless_display(egl: &egl::Instance<egl::Static>) -> Option<egl::Display> {
+        let display = unsafe {
+            egl.get_platform_display(
+                PLATFORM_SURFACELESS_MESA,
+                egl::DEFAULT_DISPLAY,
+                &[egl::ATTRIB_NONE],
+            )
+        }
+        .ok()?;
+        egl.initialize(display).ok().map(|_| display)
+    }
+
+    impl Drop for HeadlessContext {
+        fn drop(&mut self) {
+            let _ = self.egl.make_current(self.display, None, None, None);
+            let _ = self.egl.destroy_context(self.display, self.context);
+            let _ = self.egl.destroy_surface(self.display, self.surface);
+            let _ = self.egl.terminate(self.display);
+        }
+    }
+
+    /// Iterator over offline-rendered frames. Owns the GL context, which is
+    /// torn down when the iterator is dropped.
+    pub struct OfflineFrames {
+        renderer: OsciRenderer,
+        frames: Vec<Frame>,
+        settings: VisualiserSettings,
+        width: u32,
+        height: u32,
+        index: usize,
+        total: usize,
+        warm_up: usize,
+        // Declared last so it is dropped after the renderer is destroyed
+        context: HeadlessContext,
+    }
+
+    impl OfflineFrames {
+        /// Render `frames` extra frames before the first capture, so the
+        /// persistence trails have built up the way they would on screen
+        /// after watching the animation loop for a while.
+        pub fn with_warm_up(mut self, frames: usize) -> Self {
+            self.warm_up = frames;
+            self
+        }
+
+        fn render_frame(&mut self, index: usize) {
+            let (x, y) = sample_frame(&self.frames[index], OFFLINE_SAMPLES_PER_FRAME);
+            let viewport = [0, 0, self.width as i32, self.height as i32];
+            let gl = &self.context.gl;
+            // Start from transparent black, as a transparent composite blends over it
+            unsafe {
+                gl.clear_color(0.0, 0.0, 0.0, 0.0);
+                gl.clear(glow::COLOR_BUFFER_BIT);
+            }
+            self.renderer.render(gl, &x, &y, 0.0, &self.settings, viewport);
+        }
+    }
+
+    impl Iterator for OfflineFrames {
+        type Item = RgbaFrame;
+
+        fn next(&mut self) -> Option<RgbaFrame> {
+            if self.index >= self.total {
+                return None;
+            }
+            if self.index == 0 {
+                // Play the frames leading up to the first one into the trails
+                let count = self.frames.len() as i64;
+                for i in -(self.warm_up as i64)..0 {
+                    self.render_frame(i.rem_euclid(count) as usize);
+                }
+            }
+            self.render_frame(self.index % self.frames.len());
+            self.index += 1;
+
+            let pixels = self.renderer.capture_frame(&self.context.gl, self.width, self.height);
+            Some(RgbaFrame {
+                pixels,
+                width: self.width,
+                height: self.height,
+            })
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let remaining = self.total - self.index;
+            (remaining, Some(remaining))
+        }
+    }
+
+    impl ExactSizeIterator for OfflineFrames {}
+
+    impl Drop for OfflineFrames {
+        fn drop(&mut self) {
+            self.renderer.destroy(&self.context.gl);
+        }
+    }
+
+    /// Render `frames` offscreen at `width`x`height`, producing
+    /// `fps * seconds` images.
+    ///
+    /// The frame set advances one frame per output image and loops, so a
+    /// single static frame renders as a still with persistence building up.
+    /// Persistence decays by exactly `1 / fps` per image starting from a
+    /// cleared history, so identical input always renders identically.
+    pub fn render_offline(
+        frames: Vec<Frame>,
+        settings: VisualiserSettings,
+        fps: u32,
+        seconds: f32,
+        width: u32,
+        height: u32,
+    ) -> Result<OfflineFrames, String> {
+        if frames.is_empty() {
+            return Err("No frames to render".to_string());
+        }
+        if fps == 0 {
+            return Err("Frame rate must be at least 1 fps".to_string());
+        }
+        if width == 0 || height == 0 {
+            return Err(format!("Invalid output size {}x{}", width, height));
+        }
+
+        let context = HeadlessContext::new(width, height)?;
+        let mut renderer = OsciRenderer::new(&context.gl);
+        renderer.set_frame_interval(Some(1.0 / fps as f32));
+        renderer.reset_persistence(&context.gl);
+        let total = (fps as f32 * seconds.max(0.0)).round() as usize;
+
+        Ok(OfflineFrames {
+            renderer,
+            frames,
+            settings,
+            width,
+            height,
+            index: 0,
+            total,
+            warm_up: 0,
+            context,
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use osci_core::shape::Shape;
+        use osci_core::Line;
+
+        #[test]
+        fn test_renders_one_tiny_frame() {
+            let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.5, 0.0, 0.5, 0.0))];
+            let frames = render_offline(
+                vec![Frame::new(shapes)],
+                VisualiserSettings::default(),
+                1,
+                1.0,
+                16,
+                16,
+            )
+            .expect("headless GL context");
+
+            let rendered: Vec<RgbaFrame> = frames.collect();
+            assert_eq!(rendered.len(), 1);
+            assert_eq!((rendered[0].width, rendered[0].height), (16, 16));
+            assert_eq!(rendered[0].pixels.len(), 16 * 16 * 4);
+            assert!(rendered[0].pixels.iter().any(|&p| p > 0), "frame is blank");
+        }
+
+        /// Width in pixels of the lit part of the middle row.
+        fn lit_width(frame: &RgbaFrame) -> usize {
+            let row = frame.height as usize / 2;
+            let start = row * frame.width as usize * 4;
+            frame.pixels[start..start + frame.width as usize * 4]
+                .chunks(4)
+                .filter(|px| px[1] > 64)
+                .count()
+        }
+
+        #[test]
+        fn test_zoom_scales_drawn_extent() {
+            let capture = |zoom: f32| {
+                let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.25, 0.0, 0.25, 0.0))];
+                let settings = VisualiserSettings {
+                    zoom,
+                    ..VisualiserSettings::default()
+                };
+                render_offline(vec![Frame::new(shapes)], settings, 1, 1.0, 128, 128)
+                    .expect("headless GL context")
+                    .next()
+                    .unwrap()
+            };
+
+            // The line spans a quarter of the width, half with zoom 2. The
+            // beam's glow adds the same margin to both.
+            let normal = lit_width(&capture(1.0)) as i32;
+            let zoomed = lit_width(&capture(2.0)) as i32;
+            assert!(normal > 0);
+            assert!((zoomed - normal - 32).abs() <= 4, "{normal} px -> {zoomed} px");
+        }
+
+        #[test]
+        fn test_goniometer_draws_mono_vertically() {
+            // X == Y: a diagonal on a plain XY scope
+            let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.5, -0.5, 0.5, 0.5))];
+            let settings = VisualiserSettings {
+                goniometer: true,
+                ..VisualiserSettings::default()
+            };
+            let frame = render_offline(vec![Frame::new(shapes)], settings, 1, 1.0, 64, 64)
+                .expect("headless GL context")
+                .next()
+                .unwrap();
+
+            // Every lit pixel sits in the centre columns, from well above to well below the middle
+            let lit: Vec<(usize, usize)> = frame
+                .pixels
+                .chunks(4)
+                .enumerate()
+                .filter(|(_, px)| px[1] > 64)
+                .map(|(i, _)| (i % 64, i / 64))
+                .collect();
+            assert!(!lit.is_empty());
+            assert!(lit.iter().all(|(x, _)| (29..=34).contains(x)), "{lit:?}");
+            let rows = lit.iter().map(|(_, y)| *y);
+            assert!(rows.clone().min().unwrap() < 16 && rows.max().unwrap() > 48);
+        }
+
+        #[test]
+        fn test_flip_y_mirrors_vertically() {
+            let capture = |flip_y: bool| {
+                // Off centre and slanted, so it isn't its own mirror image
+                let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.5, 0.25, 0.5, 0.6))];
+                let settings = VisualiserSettings {
+                    flip_y,
+                    noise: 0.0,
+                    ..VisualiserSettings::default()
+                };
+                render_offline(vec![Frame::new(shapes)], settings, 1, 1.0, 64, 64)
+                    .expect("headless GL context")
+                    .next()
+                    .unwrap()
+            };
+            let lit = |frame: &RgbaFrame, x: usize, y: usize| frame.pixels[(y * 64 + x) * 4 + 1] > 64;
+            // Pixels where `a` differs from `b` turned upside down
+            let mirror_mismatches = |a: &RgbaFrame, b: &RgbaFrame| {
+                (0..64)
+                    .flat_map(|y| (0..64).map(move |x| (x, y)))
+                    .filter(|&(x, y)| lit(a, x, y) != lit(b, x, 63 - y))
+                    .count()
+            };
+
+            let normal = capture(false);
+            let flipped = capture(true);
+            let lit_count = (0..64 * 64).filter(|i| lit(&normal, i % 64, i / 64)).count();
+            assert!(lit_count > 0);
+            assert!(mirror_mismatches(&normal, &normal) > lit_count / 2);
+            // Allow a few pixels at the threshold to round differently
+            let mismatches = mirror_mismatches(&flipped, &normal);
+            assert!(mismatches <= lit_count / 20, "{mismatches} of {lit_count} lit pixels");
+        }
+
+        #[test]
+        fn test_max_blend_keeps_overlaps_coloured() {
+            let capture = |blend_mode: BlendMode| {
+                // The same line traced fifty times over
+                let shapes: Vec<Box<dyn Shape>> = (0..50)
+                    .map(|_| Box::new(Line::new_2d(-0.5, 0.0, 0.5, 0.0)) as Box<dyn Shape>)
+                    .collect();
+                let settings = VisualiserSettings {
+                    blend_mode,
+                    // Wide enough that the pixel centres sit inside the beam
+                    focus: 0.02,
+                    color: [1.0, 0.0, 0.0],
+                    exposure: 1.0,
+                    overexposure: 1.0,
+                    glow_amount: 0.0,
+                    ambient: 0.0,
+                    noise: 0.0,
+                    ..VisualiserSettings::default()
+                };
+                render_offline(vec![Frame::new(shapes)], settings, 1, 1.0, 64, 64)
+                    .expect("headless GL context")
+                    .next()
+                    .unwrap()
+            };
+            // The centre of the line, as [r, g, b]
+            let centre = |frame: &RgbaFrame| {
+                let i = (32 * 64 + 32) * 4;
+                [frame.pixels[i], frame.pixels[i + 1], frame.pixels[i + 2]]
+            };
+
+            let [r, g, b] = centre(&capture(BlendMode::Additive));
+            assert!(r > 240 && g > 240 && b > 240, "additive ({r}, {g}, {b})");
+
+            let [r, g, b] = centre(&capture(BlendMode::Max));
+            assert!(r > 128 && g < 32 && b < 32, "max ({r}, {g}, {b})");
+        }
+
+        #[test]
+        fn test_line_buffers_grow_without_dropping_segments() {
+            use crate::line_renderer::LineRenderer;
+
+            let context = HeadlessContext::new(64, 64).expect("headless GL context");
+            let gl = &context.gl;
+            let mut lines = LineRenderer::new(gl, 16);
+
+            // 100 samples across the middle, well past the initial 16 segments
+            let x: Vec<f32> = (0..100).map(|i| -0.9 + 1.8 * i as f32 / 99.0).collect();
+            let y = vec![0.0; 100];
+            let mut row = vec![0u8; 64 * 4];
+            let drawn = unsafe {
+                gl.viewport(0, 0, 64, 64);
+                gl.clear_color(0.0, 0.0, 0.0, 0.0);
+                gl.clear(glow::COLOR_BUFFER_BIT);
+                let drawn = lines.render(gl, &x, &y, 0.01, 1.0, [false, false], BlendMode::Additive);
+                gl.read_pixels(
+                    0,
+                    32,
+                    64,
+                    1,
+                    glow::RGBA,
+                    glow::UNSIGNED_BYTE,
+                    glow::PixelPackData::Slice(Some(&mut row)),
+                );
+                drawn
+            };
+            lines.destroy(gl);
+
+            assert_eq!(drawn, 99);
+            assert!(lines.capacity() >= 99);
+            // The tail of the path reaches the right-hand side
+            assert!(row[58 * 4] > 0, "{:?}", &row[50 * 4..]);
+        }
+
+        #[test]
+        fn test_shared_output_leaves_screen_unchanged() {
+            // Noise steps with every composite, so compositing twice would show
+            let capture = |shared_output: bool| {
+                let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.5, 0.0, 0.5, 0.0))];
+                let settings = VisualiserSettings {
+                    shared_output,
+                    noise: 0.5,
+                    ..VisualiserSettings::default()
+                };
+                render_offline(vec![Frame::new(shapes)], settings, 3, 1.0, 64, 64)
+                    .expect("headless GL context")
+                    .last()
+                    .unwrap()
+            };
+
+            let plain = capture(false);
+            let shared = capture(true);
+            // The shared copy is stored at half precision before it's shown
+            let worst = plain.pixels.iter().zip(&shared.pixels).map(|(a, b)| a.abs_diff(*b)).max();
+            assert!(worst <= Some(1), "pixels differ by up to {worst:?}");
+        }
+
+        #[test]
+        fn test_transparent_background_keys_alpha_on_brightness() {
+            let capture = |transparent_background: bool| {
+                let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.5, 0.0, 0.5, 0.0))];
+                let settings = VisualiserSettings {
+                    transparent_background,
+                    ..VisualiserSettings::default()
+                };
+                render_offline(vec![Frame::new(shapes)], settings, 1, 1.0, 128, 128)
+                    .expect("headless GL context")
+                    .next()
+                    .unwrap()
+            };
+            let alpha = |frame: &RgbaFrame, x: usize, y: usize| frame.pixels[(y * 128 + x) * 4 + 3];
+
+            let transparent = capture(true);
+            // Background corners are see-through, the beam is not
+            assert!(alpha(&transparent, 2, 2) < 13);
+            assert!(alpha(&transparent, 125, 125) < 13);
+            let beam = (60..68).map(|y| alpha(&transparent, 64, y)).max().unwrap();
+            assert!(beam > 128, "beam alpha {beam}");
+            // Premultiplied: no channel exceeds alpha
+            assert!(transparent.pixels.chunks(4).all(|px| px[..3].iter().all(|c| *c <= px[3])));
+
+            let opaque = capture(false);
+            assert_eq!(alpha(&opaque, 2, 2), 255);
+        }
+
+        #[test]
+        fn test_afterglow_curve_changes_trail_length() {
+            // A dot-sized line in the centre for one frame, then only a line along the top
+            let trail = |afterglow_curve: AfterglowCurve| {
+                let line = |x1, y1, x2, y2| {
+                    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(x1, y1, x2, y2))];
+                    Frame::new(shapes)
+                };
+                let mut frames = vec![line(-0.05, 0.0, 0.05, 0.0)];
+                frames.extend((0..12).map(|_| line(-0.5, 0.9, 0.5, 0.9)));
+                let settings = VisualiserSettings {
+                    persistence: 1.0,
+                    afterglow_curve,
+                    ambient: 0.0,
+                    noise: 0.0,
+                    ..VisualiserSettings::default()
+                };
+                let brightness: Vec<u8> = render_offline(frames, settings, 60, 13.0 / 60.0, 64, 64)
+                    .expect("headless GL context")
+                    .map(|frame| {
+                        (30..34)
+                            .map(|y| frame.pixels[(y * 64 + 32) * 4 + 1])
+                            .max()
+                            .unwrap()
+                    })
+                    .collect();
+                // Frames until the dot has faded to black
+                brightness.iter().skip(1).take_while(|b| **b > 0).count()
+            };
+
+            let linear = trail(AfterglowCurve::Linear);
+            let two_stage = trail(AfterglowCurve::TwoStage);
+            let power = trail(AfterglowCurve::Power);
+            assert!(linear > 0);
+            assert!(two_stage > linear, "two-stage {two_stage} vs linear {linear}");
+            assert!(power < linear, "power {power} vs linear {linear}");
+        }
+
+        #[test]
+        fn test_gamma_lifts_midtones() {
+            let capture = |gamma: f32, tone_curve: ToneCurve| {
+                let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.5, 0.0, 0.5, 0.0))];
+                let settings = VisualiserSettings {
+                    color: [1.0, 1.0, 1.0],
+                    overexposure: 0.0,
+                    ambient: 0.0,
+                    noise: 0.0,
+                    tone_curve,
+                    gamma,
+                    ..VisualiserSettings::default()
+                };
+                render_offline(vec![Frame::new(shapes)], settings, 1, 1.0, 128, 128)
+                    .expect("headless GL context")
+                    .next()
+                    .unwrap()
+            };
+            let green = |frame: &RgbaFrame, y: usize| frame.pixels[(y * 128 + 64) * 4 + 1];
+
+            // A pixel in the glow falling off above the beam, neither black nor white
+            let linear = capture(1.0, ToneCurve::Exponential);
+            let row = (0..64)
+                .rev()
+                .find(|&y| (64..=192).contains(&green(&linear, y)))
+                .expect("no midtone in the glow");
+            let before = green(&linear, row) as f32;
+            let after = green(&capture(2.2, ToneCurve::Exponential), row) as f32;
+            let expected = 255.0 * (before / 255.0).powf(1.0 / 2.2);
+            assert!(after > before + 20.0, "{before} -> {after}");
+            assert!((after - expected).abs() <= 4.0, "{after} vs {expected}");
+
+            // The tone curve changes the same pixel too
+            assert_ne!(green(&capture(1.0, ToneCurve::Reinhard), row), green(&linear, row));
+        }
+
+        #[test]
+        fn test_red_persistence_outlasts_blue() {
+            let line = |y: f32| {
+                let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Line::new_2d(-0.05, y, 0.05, y))];
+                Frame::new(shapes)
+            };
+            let mut frames = vec![line(0.0)];
+            frames.extend((0..12).map(|_| line(0.9)));
+            // A white beam with no afterglow tint, red persisting longest
+            let settings = VisualiserSettings {
+                color: [1.0, 1.0, 1.0],
+                afterglow: 0.0,
+                persistence_rgb: Some([1.0, 0.5, 0.0]),
+                overexposure: 0.0,
+                ambient: 0.0,
+                noise: 0.0,
+                ..VisualiserSettings::default()
+            };
+            let captures: Vec<RgbaFrame> = render_offline(frames, settings, 60, 13.0 / 60.0, 64, 64)
+                .expect("headless GL context")
+                .collect();
+
+            // Frames after the first until a channel of the dot fades to black
+            let trail = |channel: usize| {
+                captures
+                    .iter()
+                    .skip(1)
+                    .take_while(|frame| (30..34).any(|y| frame.pixels[(y * 64 + 32) * 4 + channel] > 0))
+                    .count()
+            };
+            assert!(trail(0) > trail(2), "red {} frames, blue {} frames", trail(0), trail(2));
+        }
+
+        #[test]
+        fn test_warmed_up_renders_are_identical() {
+            let frames = || {
+                (0..3)
+                    .map(|i| {
+                        let y = i as f32 * 0.3 - 0.3;
+                        let shapes: Vec<Box<dyn Shape>> =
+                            vec![Box::new(Line::new_2d(-0.5, y, 0.5, -y))];
+                        Frame::new(shapes)
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let settings = VisualiserSettings {
+                persistence: 0.8,
+                afterglow: 0.8,
+                ..VisualiserSettings::default()
+            };
+            let render = || {
+                render_offline(frames(), settings.clone(), 30, 0.1, 32, 32)
+                    .expect("headless GL context")
+                    .with_warm_up(5)
+                    .last()
+                    .unwrap()
+            };
+
+            let first = render();
+            let second = render();
+            assert!(first.pixels.iter().any(|&p| p > 0), "frame is blank");
+            assert!(first.pixels == second.pixels, "warmed-up captures differ");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use osci_core::shape::Shape;
+    use osci_core::Line;
+
+    #[test]
+    fn test_samples_spread_along_path_length() {
+        // A long segment followed by a short one, end to end
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Line::new_2d(-1.0, 0.0, 0.5, 0.0)),
+            Box::new(Line::new_2d(0.5, 0.0, 0.5, 0.5)),
+        ];
+        let (x, y) = sample_frame(&Frame::new(shapes), 8);
+
+        assert_eq!(x.len(), 8);
+        // Three quarters of the samples land on the long segment
+        assert!(x[..6].iter().all(|&v| v < 0.5));
+        assert!((x[2] + 0.5).abs() < 1e-6);
+        assert_eq!((x[6], y[6]), (0.5, 0.0));
+        assert!((y[7] - 0.25).abs() < 1e-6);
+
+        let (x, y) = sample_frame(&Frame::empty(), 4);
+        assert_eq!((x, y), (vec![0.0; 4], vec![0.0; 4]));
+    }
+}
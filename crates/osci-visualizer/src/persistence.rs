@@ -3,6 +3,7 @@ use std::time::Instant;
 
 use crate::fbo::RenderTarget;
 use crate::quad::FullscreenQuad;
+use crate::settings::VisualiserSettings;
 use crate::shaders;
 
 /// Phosphor persistence via ping-pong FBOs with exponential decay.
@@ -11,11 +12,14 @@ pub struct PersistencePass {
     targets: [RenderTarget; 2],
     current_idx: usize,
     last_frame: Instant,
+    /// Fixed time step for the decay, instead of the measured frame time.
+    frame_interval: Option<f32>,
     loc_current: glow::UniformLocation,
     loc_previous: glow::UniformLocation,
     loc_fade: glow::UniformLocation,
     loc_afterglow_color: glow::UniformLocation,
     loc_afterglow: glow::UniformLocation,
+    loc_curve: glow::UniformLocation,
 }
 
 impl PersistencePass {
@@ -27,6 +31,7 @@ impl PersistencePass {
         let loc_fade = unsafe { gl.get_uniform_location(program, "u_fade").expect("u_fade") };
         let loc_afterglow_color = unsafe { gl.get_uniform_location(program, "u_afterglow_color").expect("u_afterglow_color") };
         let loc_afterglow = unsafe { gl.get_uniform_location(program, "u_afterglow").expect("u_afterglow") };
+        let loc_curve = unsafe { gl.get_uniform_location(program, "u_curve").expect("u_curve") };
 
         Self {
             program,
@@ -36,34 +41,60 @@ impl PersistencePass {
             ],
             current_idx: 0,
             last_frame: Instant::now(),
+            frame_interval: None,
             loc_current,
             loc_previous,
             loc_fade,
             loc_afterglow_color,
             loc_afterglow,
+            loc_curve,
         }
     }
 
-    /// Blend current line texture with previous frame.
+    /// Decay by a fixed `seconds` per frame rather than the wall-clock time
+    /// between calls, or go back to wall-clock timing with `None`.
+    pub fn set_frame_interval(&mut self, seconds: Option<f32>) {
+        self.frame_interval = seconds;
+    }
+
+    /// Clear the accumulated history so the next frame starts from black.
+    /// Leaves the last target bound.
+    pub fn reset(&mut self, gl: &glow::Context) {
+        unsafe {
+            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+            for target in &self.targets {
+                target.bind(gl);
+                gl.clear(glow::COLOR_BUFFER_BIT);
+            }
+        }
+        self.current_idx = 0;
+        self.last_frame = Instant::now();
+    }
+
+    /// Blend current line texture with previous frame, faded according to the
+    /// persistence and afterglow settings, per channel if
+    /// `settings.persistence_rgb` is set.
     /// Returns the persisted texture handle.
     pub fn render(
         &mut self,
         gl: &glow::Context,
         line_texture: glow::Texture,
-        persistence: f32,
-        afterglow: f32,
-        afterglow_color: &[f32; 3],
+        settings: &VisualiserSettings,
         quad: &FullscreenQuad,
     ) -> glow::Texture {
         let now = Instant::now();
-        let dt = now.duration_since(self.last_frame).as_secs_f32();
+        let dt = self
+            .frame_interval
+            .unwrap_or_else(|| now.duration_since(self.last_frame).as_secs_f32());
         self.last_frame = now;
 
         // Calculate fade factor: exponential decay scaled by frame time
         // At persistence=0.5, about 40% retained per frame at 60fps
         let fps_ref = 60.0;
-        let fade = (0.5f32).powf(1.0 - persistence) * 0.4 * (fps_ref * dt);
-        let fade = fade.clamp(0.0, 0.99);
+        let fade = |persistence: f32| {
+            ((0.5f32).powf(1.0 - persistence) * 0.4 * (fps_ref * dt)).clamp(0.0, 0.99)
+        };
+        let [r, g, b] = settings.persistence_rgb.unwrap_or([settings.persistence; 3]);
 
         let prev_idx = self.current_idx;
         let next_idx = 1 - self.current_idx;
@@ -86,14 +117,15 @@ impl PersistencePass {
             gl.bind_texture(glow::TEXTURE_2D, Some(self.targets[prev_idx].texture));
             gl.uniform_1_i32(Some(&self.loc_previous), 1);
 
-            gl.uniform_1_f32(Some(&self.loc_fade), fade);
+            gl.uniform_3_f32(Some(&self.loc_fade), fade(r), fade(g), fade(b));
             gl.uniform_3_f32(
                 Some(&self.loc_afterglow_color),
-                afterglow_color[0],
-                afterglow_color[1],
-                afterglow_color[2],
+                settings.afterglow_color[0],
+                settings.afterglow_color[1],
+                settings.afterglow_color[2],
             );
-            gl.uniform_1_f32(Some(&self.loc_afterglow), afterglow);
+            gl.uniform_1_f32(Some(&self.loc_afterglow), settings.afterglow);
+            gl.uniform_1_i32(Some(&self.loc_curve), settings.afterglow_curve.index() as i32);
 
             quad.draw(gl);
 
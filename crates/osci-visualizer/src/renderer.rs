@@ -1,4 +1,5 @@
 use glow::HasContext;
+use std::time::Instant;
 
 use crate::bloom::BloomPass;
 use crate::compositor::Compositor;
@@ -9,7 +10,9 @@ use crate::quad::FullscreenQuad;
 use crate::settings::VisualiserSettings;
 
 const LINE_FBO_SIZE: u32 = 1024;
-const MAX_SEGMENTS: usize = 2048;
+
+/// Segments the line buffers start with; they grow for longer paths.
+const INITIAL_SEGMENTS: usize = 2048;
 
 /// Saved OpenGL state so we can restore egui's GL context after custom rendering.
 struct SavedGlState {
@@ -126,6 +129,20 @@ impl SavedGlState {
     }
 }
 
+/// Weight of the newest frame interval in the smoothed FPS reading.
+const FPS_SMOOTHING: f32 = 0.1;
+
+/// Per-frame instrumentation from [`OsciRenderer::render`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderStats {
+    /// Frames per second, smoothed over recent frames.
+    pub fps: f32,
+    /// Line segments drawn in the last frame.
+    pub segments: usize,
+    /// CPU time spent issuing the last frame, in milliseconds.
+    pub frame_ms: f32,
+}
+
 /// Orchestrates the full GPU oscilloscope rendering pipeline.
 pub struct OsciRenderer {
     line_fbo: RenderTarget,
@@ -134,6 +151,10 @@ pub struct OsciRenderer {
     persistence: PersistencePass,
     compositor: Compositor,
     quad: FullscreenQuad,
+    /// Viewport-sized copy of the final composite, while sharing is enabled.
+    shared_target: Option<RenderTarget>,
+    stats: RenderStats,
+    last_frame: Option<Instant>,
 }
 
 impl OsciRenderer {
@@ -141,25 +162,81 @@ impl OsciRenderer {
     pub fn new(gl: &glow::Context) -> Self {
         Self {
             line_fbo: RenderTarget::new(gl, LINE_FBO_SIZE, LINE_FBO_SIZE),
-            line_renderer: LineRenderer::new(gl, MAX_SEGMENTS),
+            line_renderer: LineRenderer::new(gl, INITIAL_SEGMENTS),
             bloom: BloomPass::new(gl),
             persistence: PersistencePass::new(gl),
             compositor: Compositor::new(gl),
             quad: FullscreenQuad::new(gl),
+            shared_target: None,
+            stats: RenderStats::default(),
+            last_frame: None,
+        }
+    }
+
+    /// Stats for the most recently rendered frame.
+    pub fn stats(&self) -> RenderStats {
+        self.stats
+    }
+
+    /// Texture holding the last frame's composite, with its width and height,
+    /// while [`VisualiserSettings::shared_output`] is enabled.
+    pub fn shared_output(&self) -> Option<(glow::Texture, u32, u32)> {
+        self.shared_target
+            .as_ref()
+            .map(|target| (target.texture, target.width, target.height))
+    }
+
+    /// Clear the persistence history and restart the noise grain, so the
+    /// next frame renders as if the renderer were new.
+    ///
+    /// Persistence carries every frame forward into the next, so a capture
+    /// depends on everything rendered before it. Resetting, fixing the frame
+    /// interval and then rendering the same warm-up input makes captures
+    /// reproducible. With `settings.persistence` and `settings.afterglow` at
+    /// zero only the previous frame or so contributes; higher values need
+    /// more warm-up frames before the trails settle to what the scope shows.
+    pub fn reset_persistence(&mut self, gl: &glow::Context) {
+        unsafe {
+            let saved = SavedGlState::save(gl);
+            self.persistence.reset(gl);
+            saved.restore(gl);
         }
+        self.compositor.reset();
+    }
+
+    /// Decay persistence by a fixed `seconds` per rendered frame instead of
+    /// the measured time between calls. Offline renders should pass
+    /// `1 / fps`; `None` returns to wall-clock timing.
+    pub fn set_frame_interval(&mut self, seconds: Option<f32>) {
+        self.persistence.set_frame_interval(seconds);
     }
 
     /// Render the oscilloscope visualization.
     ///
+    /// `frequency` is the rate the frame is being drawn at in Hz, used by
+    /// auto-focus; pass 0 when it isn't known.
     /// `viewport` is [x, y, width, height] in physical pixels for the final output.
+    /// Returns the updated [`RenderStats`].
     pub fn render(
         &mut self,
         gl: &glow::Context,
         x_samples: &[f32],
         y_samples: &[f32],
+        frequency: f32,
         settings: &VisualiserSettings,
         viewport: [i32; 4],
-    ) {
+    ) -> RenderStats {
+        let start = Instant::now();
+        let segments;
+
+        let transformed;
+        let (x_samples, y_samples) = if settings.has_view_transform() {
+            transformed = settings.transform_samples(x_samples, y_samples);
+            (&transformed.0[..], &transformed.1[..])
+        } else {
+            (x_samples, y_samples)
+        };
+
         unsafe {
             // 1. Save egui's GL state
             let saved = SavedGlState::save(gl);
@@ -170,31 +247,81 @@ impl OsciRenderer {
             self.line_fbo.bind(gl);
             gl.clear_color(0.0, 0.0, 0.0, 0.0);
             gl.clear(glow::COLOR_BUFFER_BIT);
-            self.line_renderer.render(gl, x_samples, y_samples, settings.focus, settings.intensity);
-
-            // 3. Persistence: blend with previous frame
-            let persisted_tex = self.persistence.render(
+            segments = self.line_renderer.render(
                 gl,
-                self.line_fbo.texture,
-                settings.persistence,
-                settings.afterglow,
-                &settings.afterglow_color,
-                &self.quad,
+                x_samples,
+                y_samples,
+                settings.effective_focus(frequency),
+                settings.intensity,
+                [settings.flip_x, settings.flip_y],
+                settings.blend_mode,
             );
 
+            // 3. Persistence: blend with previous frame
+            let persisted_tex = self.persistence.render(gl, self.line_fbo.texture, settings, &self.quad);
+
             // 4. Bloom: tight + wide blur
             let (tight_tex, wide_tex) = self.bloom.render(gl, persisted_tex, &self.quad);
 
-            // 5. Restore egui's FBO, set viewport to target rect
+            // 5. Composite once into a copy for sharing, sized to match the
+            // on-screen scope
+            let width = viewport[2].max(1) as u32;
+            let height = viewport[3].max(1) as u32;
+            let shared = if settings.shared_output {
+                if self
+                    .shared_target
+                    .as_ref()
+                    .is_some_and(|t| t.width != width || t.height != height)
+                {
+                    if let Some(target) = self.shared_target.take() {
+                        target.destroy(gl);
+                    }
+                }
+                let target = self
+                    .shared_target
+                    .get_or_insert_with(|| RenderTarget::new(gl, width, height));
+                target.bind(gl);
+                gl.clear_color(0.0, 0.0, 0.0, 0.0);
+                gl.clear(glow::COLOR_BUFFER_BIT);
+                self.compositor.render(gl, persisted_tex, tight_tex, wide_tex, settings, &self.quad);
+                Some(target.texture)
+            } else {
+                if let Some(target) = self.shared_target.take() {
+                    target.destroy(gl);
+                }
+                None
+            };
+
+            // 6. Restore egui's FBO, set viewport to target rect
             gl.bind_framebuffer(glow::FRAMEBUFFER, saved.framebuffer);
             gl.viewport(viewport[0], viewport[1], viewport[2], viewport[3]);
 
-            // 6. Composite final image
-            self.compositor.render(gl, persisted_tex, tight_tex, wide_tex, settings, &self.quad);
+            // 7. Final image, over egui's background if transparent: the
+            // shared copy when there is one, otherwise composite it here
+            match shared {
+                Some(texture) => self.compositor.present(gl, texture, settings, &self.quad),
+                None => self.compositor.render(gl, persisted_tex, tight_tex, wide_tex, settings, &self.quad),
+            }
 
-            // 7. Restore all GL state
+            // 8. Restore all GL state
             saved.restore(gl);
         }
+
+        if let Some(last) = self.last_frame {
+            let interval = start.duration_since(last).as_secs_f32();
+            if interval > 0.0 {
+                let fps = 1.0 / interval;
+                self.stats.fps = if self.stats.fps > 0.0 {
+                    self.stats.fps + FPS_SMOOTHING * (fps - self.stats.fps)
+                } else {
+                    fps
+                };
+            }
+        }
+        self.last_frame = Some(start);
+        self.stats.segments = segments;
+        self.stats.frame_ms = start.elapsed().as_secs_f32() * 1000.0;
+        self.stats
     }
 
     /// Read pixels from the currently bound FBO. Returns RGBA8 data, flipped vertically.
@@ -229,5 +356,8 @@ impl OsciRenderer {
         self.persistence.destroy(gl);
         self.compositor.destroy(gl);
         self.quad.destroy(gl);
+        if let Some(target) = &self.shared_target {
+            target.destroy(gl);
+        }
     }
 }
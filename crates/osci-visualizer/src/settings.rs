@@ -1,14 +1,176 @@
+/// Drawing frequency at which auto-focus leaves the focus as set.
+pub const AUTO_FOCUS_REFERENCE_HZ: f32 = 440.0;
+
+/// Range auto-focus keeps the beam within, matching the focus slider.
+const AUTO_FOCUS_RANGE: (f32, f32) = (0.001, 0.02);
+
+/// Beam focus that keeps line weight steady when drawing at `frequency` Hz.
+///
+/// A frame drawn faster leaves less energy along each pixel of its path, so
+/// the line looks thinner. The beam widens with the square root of the
+/// frequency relative to [`AUTO_FOCUS_REFERENCE_HZ`] to make up for it, and
+/// tightens for slower drawing, within the focus slider's range. A
+/// non-positive or non-finite frequency leaves `focus` unchanged.
+pub fn focus_for_frequency(focus: f32, frequency: f32) -> f32 {
+    if !(frequency > 0.0 && frequency.is_finite()) {
+        return focus;
+    }
+    let scaled = focus * (frequency / AUTO_FOCUS_REFERENCE_HZ).sqrt();
+    scaled.clamp(AUTO_FOCUS_RANGE.0, AUTO_FOCUS_RANGE.1)
+}
+
+/// Shape of the persistence decay.
+///
+/// Every curve keeps the same fraction of a trail at full brightness each
+/// frame; they differ in how the dim end of the trail behaves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AfterglowCurve {
+    /// The same fraction of the trail survives every frame.
+    #[default]
+    Linear,
+    /// Like a P7 phosphor: the bright flash dies away quickly, leaving a
+    /// faint trail that lingers much longer.
+    TwoStage,
+    /// Dim light fades faster than bright light, so trails end crisply.
+    Power,
+}
+
+impl AfterglowCurve {
+    pub const ALL: [AfterglowCurve; 3] = [
+        AfterglowCurve::Linear,
+        AfterglowCurve::TwoStage,
+        AfterglowCurve::Power,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AfterglowCurve::Linear => "Linear",
+            AfterglowCurve::TwoStage => "Two-Stage (P7)",
+            AfterglowCurve::Power => "Power",
+        }
+    }
+
+    /// Index used by the persistence shader and saved projects.
+    pub fn index(self) -> u32 {
+        match self {
+            AfterglowCurve::Linear => 0,
+            AfterglowCurve::TwoStage => 1,
+            AfterglowCurve::Power => 2,
+        }
+    }
+
+    /// The curve saved as `index`, falling back to linear for unknown values.
+    pub fn from_index(index: u32) -> Self {
+        Self::ALL.get(index as usize).copied().unwrap_or_default()
+    }
+}
+
+/// How the compositor maps accumulated beam energy to display brightness.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToneCurve {
+    /// `1 - exp(-exposure * L)`: bright areas approach white smoothly.
+    #[default]
+    Exponential,
+    /// `x / (1 + x)`: a gentler shoulder that keeps more detail in highlights.
+    Reinhard,
+    /// A film-like S-curve with deeper shadows and a soft shoulder.
+    Filmic,
+    /// Exposure only, clipped at white.
+    Linear,
+}
+
+impl ToneCurve {
+    pub const ALL: [ToneCurve; 4] = [
+        ToneCurve::Exponential,
+        ToneCurve::Reinhard,
+        ToneCurve::Filmic,
+        ToneCurve::Linear,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ToneCurve::Exponential => "Exponential",
+            ToneCurve::Reinhard => "Reinhard",
+            ToneCurve::Filmic => "Filmic",
+            ToneCurve::Linear => "Linear",
+        }
+    }
+
+    /// Index used by the composite shader and saved projects.
+    pub fn index(self) -> u32 {
+        match self {
+            ToneCurve::Exponential => 0,
+            ToneCurve::Reinhard => 1,
+            ToneCurve::Filmic => 2,
+            ToneCurve::Linear => 3,
+        }
+    }
+
+    /// The curve saved as `index`, falling back to exponential for unknown values.
+    pub fn from_index(index: u32) -> Self {
+        Self::ALL.get(index as usize).copied().unwrap_or_default()
+    }
+}
+
+/// How overlapping passes of the beam combine in the line buffer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Passes add up, so dense overlaps grow brighter until they clip to
+    /// white.
+    #[default]
+    Additive,
+    /// Each pixel keeps its brightest pass, so overlaps hold the beam's
+    /// colour. Brightness no longer follows the beam's speed.
+    Max,
+}
+
+impl BlendMode {
+    pub const ALL: [BlendMode; 2] = [BlendMode::Additive, BlendMode::Max];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BlendMode::Additive => "Additive",
+            BlendMode::Max => "Max",
+        }
+    }
+
+    /// Index used by saved projects.
+    pub fn index(self) -> u32 {
+        match self {
+            BlendMode::Additive => 0,
+            BlendMode::Max => 1,
+        }
+    }
+
+    /// The mode saved as `index`, falling back to additive for unknown values.
+    pub fn from_index(index: u32) -> Self {
+        Self::ALL.get(index as usize).copied().unwrap_or_default()
+    }
+}
+
 /// Visual parameters for the oscilloscope renderer.
 #[derive(Clone)]
 pub struct VisualiserSettings {
     /// Beam focus (Gaussian sigma in UV space). Smaller = sharper. Range: 0.001..0.02
     pub focus: f32,
+    /// Adjust `focus` to the drawing frequency passed to
+    /// [`crate::OsciRenderer::render`]; see [`focus_for_frequency`].
+    pub auto_focus: bool,
     /// Beam intensity multiplier. Range: 0.1..5.0
     pub intensity: f32,
     /// Phosphor persistence (half-life in frames at 60fps). Range: 0.0..1.0
+    ///
+    /// Trails build up over previous frames, see
+    /// [`crate::OsciRenderer::reset_persistence`] for reproducible captures.
     pub persistence: f32,
+    /// Separate persistence for the red, green and blue channels, so one
+    /// colour can linger after the others have faded. `None` applies
+    /// `persistence` to all three. Range: 0.0..1.0 each
+    pub persistence_rgb: Option<[f32; 3]>,
     /// Afterglow color retention. Range: 0.0..1.0
     pub afterglow: f32,
+    /// How persisted trails fade from frame to frame
+    pub afterglow_curve: AfterglowCurve,
     /// Tight bloom (glow) amount. Range: 0.0..2.0
     pub glow_amount: f32,
     /// Wide scatter bloom amount. Range: 0.0..2.0
@@ -19,6 +181,11 @@ pub struct VisualiserSettings {
     pub exposure: f32,
     /// Overexposure white clipping. Range: 0.0..1.0
     pub overexposure: f32,
+    /// Curve that maps exposed beam energy to brightness
+    pub tone_curve: ToneCurve,
+    /// Output gamma; the final colour is raised to `1 / gamma`, so values
+    /// above 1 lift the midtones. Range: 0.5..3.0
+    pub gamma: f32,
     /// Color saturation. Range: 0.0..2.0
     pub saturation: f32,
     /// Ambient background tint amount. Range: 0.0..0.1
@@ -29,28 +196,182 @@ pub struct VisualiserSettings {
     pub afterglow_color: [f32; 3],
     /// Reflection mode: 0=off, 1=horizontal mirror, 2=vertical mirror, 3=quad
     pub reflection_mode: u32,
-    /// Goniometer mode: Mid/Side 45 degree rotation
+    /// Goniometer mode: show X/Y as Mid/Side, so mono signals draw a
+    /// vertical line and the left channel leans to the upper left
     pub goniometer: bool,
+    /// Display rotation in degrees, counter-clockwise. Range: -180.0..180.0
+    pub rotation: f32,
+    /// Display magnification about the centre. Range: 0.25..4.0
+    pub zoom: f32,
+    /// Display position offset [x, y], applied after rotation and zoom. Range: -1.0..1.0 each
+    pub offset: [f32; 2],
+    /// Mirror the display left to right, after the view transform
+    pub flip_x: bool,
+    /// Mirror the display top to bottom, for sources drawn Y-down
+    pub flip_y: bool,
+    /// How overlapping lines combine before tone mapping
+    pub blend_mode: BlendMode,
+    /// Output premultiplied alpha keyed on brightness, so dark areas are
+    /// transparent when layered over other content
+    pub transparent_background: bool,
+    /// Also composite into an offscreen texture that can be shared with
+    /// other apps (see [`crate::OsciRenderer::shared_output`]).
+    pub shared_output: bool,
 }
 
 impl Default for VisualiserSettings {
     fn default() -> Self {
         Self {
             focus: 0.004,
+            auto_focus: false,
             intensity: 1.0,
             persistence: 0.5,
+            persistence_rgb: None,
             afterglow: 0.5,
+            afterglow_curve: AfterglowCurve::Linear,
             glow_amount: 0.6,
             scatter_amount: 0.4,
             color: [0.2, 1.0, 0.3],
             exposure: 1.5,
             overexposure: 0.3,
+            tone_curve: ToneCurve::Exponential,
+            gamma: 1.0,
             saturation: 1.0,
             ambient: 0.02,
             noise: 0.01,
             afterglow_color: [0.2, 1.0, 0.3],
             reflection_mode: 0,
             goniometer: false,
+            rotation: 0.0,
+            zoom: 1.0,
+            offset: [0.0, 0.0],
+            flip_x: false,
+            flip_y: false,
+            blend_mode: BlendMode::Additive,
+            transparent_background: false,
+            shared_output: false,
+        }
+    }
+}
+
+impl VisualiserSettings {
+    /// The focus to draw with at `frequency` Hz: `focus` itself unless
+    /// auto-focus is on.
+    pub fn effective_focus(&self, frequency: f32) -> f32 {
+        if self.auto_focus {
+            focus_for_frequency(self.focus, frequency)
+        } else {
+            self.focus
+        }
+    }
+
+    /// Whether goniometer mode, rotation, zoom or offset move the trace at all.
+    pub fn has_view_transform(&self) -> bool {
+        self.goniometer || self.rotation != 0.0 || self.zoom != 1.0 || self.offset != [0.0, 0.0]
+    }
+
+    /// Map a sample to where it is drawn.
+    ///
+    /// In goniometer mode the X/Y (left/right) sample is first converted to
+    /// Side across and Mid up, each scaled by 1/√2. The result is then rotated
+    /// and zoomed about the centre and offset, like nudging the controls on a
+    /// real scope. Reflection modes mirror the finished image afterwards.
+    pub fn view_transform(&self, x: f32, y: f32) -> (f32, f32) {
+        let (x, y) = if self.goniometer {
+            (
+                (y - x) * std::f32::consts::FRAC_1_SQRT_2,
+                (x + y) * std::f32::consts::FRAC_1_SQRT_2,
+            )
+        } else {
+            (x, y)
+        };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let rx = x * cos - y * sin;
+        let ry = x * sin + y * cos;
+        (
+            rx * self.zoom + self.offset[0],
+            ry * self.zoom + self.offset[1],
+        )
+    }
+
+    /// Apply [`Self::view_transform`] to whole sample buffers.
+    pub fn transform_samples(&self, x_samples: &[f32], y_samples: &[f32]) -> (Vec<f32>, Vec<f32>) {
+        x_samples
+            .iter()
+            .zip(y_samples)
+            .map(|(&x, &y)| self.view_transform(x, y))
+            .unzip()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_transform_rotates_zooms_then_offsets() {
+        let settings = VisualiserSettings::default();
+        assert!(!settings.has_view_transform());
+        assert_eq!(settings.view_transform(0.3, -0.4), (0.3, -0.4));
+
+        let settings = VisualiserSettings {
+            rotation: 90.0,
+            zoom: 2.0,
+            offset: [0.1, 0.0],
+            ..VisualiserSettings::default()
+        };
+        assert!(settings.has_view_transform());
+        let (x, y) = settings.view_transform(0.5, 0.0);
+        assert!((x - 0.1).abs() < 1e-6 && (y - 1.0).abs() < 1e-6, "({x}, {y})");
+
+        let (xs, ys) = settings.transform_samples(&[0.0, 0.0], &[0.0, 0.25]);
+        assert!((xs[1] + 0.4).abs() < 1e-6 && ys[1].abs() < 1e-6);
+        assert_eq!((xs[0], ys[0]), (0.1, 0.0));
+    }
+
+    #[test]
+    fn test_focus_follows_frequency() {
+        let focus = 0.004;
+        assert_eq!(focus_for_frequency(focus, AUTO_FOCUS_REFERENCE_HZ), focus);
+
+        // Four times as fast doubles the beam width, a quarter halves it
+        assert!((focus_for_frequency(focus, 1760.0) - 0.008).abs() < 1e-6);
+        assert!((focus_for_frequency(focus, 110.0) - 0.002).abs() < 1e-6);
+        assert!(focus_for_frequency(focus, 880.0) > focus_for_frequency(focus, 660.0));
+
+        // Held to the slider range at the extremes
+        assert_eq!(focus_for_frequency(focus, 1.0), 0.001);
+        assert_eq!(focus_for_frequency(focus, 1.0e6), 0.02);
+
+        // An unknown frequency leaves the focus alone
+        assert_eq!(focus_for_frequency(focus, 0.0), focus);
+        assert_eq!(focus_for_frequency(focus, f32::NAN), focus);
+
+        let mut settings = VisualiserSettings::default();
+        assert_eq!(settings.effective_focus(1760.0), settings.focus);
+        settings.auto_focus = true;
+        assert!(settings.effective_focus(1760.0) > settings.focus);
+    }
+
+    #[test]
+    fn test_goniometer_shows_mid_side() {
+        let settings = VisualiserSettings {
+            goniometer: true,
+            ..VisualiserSettings::default()
+        };
+        assert!(settings.has_view_transform());
+
+        // In-phase mono is a vertical line
+        for v in [-0.8, -0.2, 0.5, 1.0] {
+            let (x, y) = settings.view_transform(v, v);
+            assert!(x.abs() < 1e-6);
+            assert!((y - v * std::f32::consts::SQRT_2).abs() < 1e-6);
         }
+
+        // Out of phase is horizontal; left only leans to the upper left
+        let (x, y) = settings.view_transform(0.5, -0.5);
+        assert!(y.abs() < 1e-6 && x < 0.0);
+        let (x, y) = settings.view_transform(1.0, 0.0);
+        assert!(x < 0.0 && y > 0.0 && (x + y).abs() < 1e-6);
     }
 }
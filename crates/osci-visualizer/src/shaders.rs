@@ -8,6 +8,7 @@ layout(location = 2) in float a_perp;  // -1 or +1
 layout(location = 3) in float a_along; // 0 or 1
 
 uniform float u_sigma;
+uniform vec2 u_flip;  // -1 on an axis drawn mirrored, 1 otherwise
 
 out vec2 v_pos;        // position in UV space
 out vec2 v_seg_a;      // segment start in UV space
@@ -15,20 +16,24 @@ out vec2 v_seg_b;      // segment end in UV space
 out float v_sigma;
 
 void main() {
-    vec2 dir = a_other - a_pos;
+    // Mirror about the centre of UV space
+    vec2 pos = 0.5 + (a_pos - 0.5) * u_flip;
+    vec2 other = 0.5 + (a_other - 0.5) * u_flip;
+
+    vec2 dir = other - pos;
     float seg_len = length(dir);
     vec2 tang = seg_len > 0.0001 ? dir / seg_len : vec2(1.0, 0.0);
     vec2 norm = vec2(-tang.y, tang.x);
 
     // Expand along segment + perpendicular by 4*sigma for Gaussian coverage
     float expand = 4.0 * u_sigma;
-    vec2 point = mix(a_pos, a_other, a_along);
+    vec2 point = mix(pos, other, a_along);
     point += tang * (a_along * 2.0 - 1.0) * expand; // extend past endpoints
     point += norm * a_perp * expand;
 
     v_pos = point;
-    v_seg_a = a_pos;
-    v_seg_b = a_other;
+    v_seg_a = pos;
+    v_seg_b = other;
     v_sigma = u_sigma;
 
     // Map from [0,1] UV to [-1,1] clip space
@@ -44,6 +49,7 @@ in vec2 v_seg_b;
 in float v_sigma;
 
 uniform float u_intensity;
+uniform bool u_max_blend;
 
 out vec4 frag_color;
 
@@ -81,13 +87,21 @@ void main() {
         // Gaussian perpendicular to line
         float gauss_y = exp(-0.5 * (perp * perp) / (v_sigma * v_sigma));
 
-        // erf integral along line (analytical Gaussian beam)
-        float inv_sigma_sqrt2 = 1.0 / (v_sigma * 1.41421356);
-        float erf_end = erf_approx((seg_len - along) * inv_sigma_sqrt2);
-        float erf_start = erf_approx(-along * inv_sigma_sqrt2);
-        float integral_x = 0.5 * (erf_end - erf_start);
-
-        brightness = gauss_y * integral_x;
+        if (u_max_blend) {
+            // Gaussian of the distance to the segment, so every pass peaks
+            // at full brightness however short its segments are
+            float past = max(-along, along - seg_len);
+            float gauss_x = past > 0.0 ? exp(-0.5 * (past * past) / (v_sigma * v_sigma)) : 1.0;
+            brightness = gauss_y * gauss_x;
+        } else {
+            // erf integral along line (analytical Gaussian beam)
+            float inv_sigma_sqrt2 = 1.0 / (v_sigma * 1.41421356);
+            float erf_end = erf_approx((seg_len - along) * inv_sigma_sqrt2);
+            float erf_start = erf_approx(-along * inv_sigma_sqrt2);
+            float integral_x = 0.5 * (erf_end - erf_start);
+
+            brightness = gauss_y * integral_x;
+        }
     }
 
     brightness *= u_intensity;
@@ -149,17 +163,34 @@ in vec2 v_uv;
 
 uniform sampler2D u_current;
 uniform sampler2D u_previous;
-uniform float u_fade;  // decay factor per frame
+uniform vec3 u_fade;   // decay factor per frame, per channel
 uniform vec3 u_afterglow_color;
 uniform float u_afterglow;
+uniform int u_curve;   // 0 = linear, 1 = two-stage, 2 = power
 
 out vec4 frag_color;
 
+// Level below which the two-stage curve switches to its slow tail
+const float TWO_STAGE_KNEE = 0.1;
+// Extra exponent the power curve applies below full brightness
+const float POWER_GAMMA = 0.5;
+
+vec3 decay(vec3 prev) {
+    if (u_curve == 1) {
+        vec3 tail = min(prev, vec3(TWO_STAGE_KNEE));
+        return (prev - tail) * u_fade * u_fade + tail * sqrt(u_fade);
+    }
+    if (u_curve == 2) {
+        return prev * u_fade * pow(clamp(prev, 0.0, 1.0), vec3(POWER_GAMMA));
+    }
+    return prev * u_fade;
+}
+
 void main() {
     vec4 cur = texture(u_current, v_uv);
     vec4 prev = texture(u_previous, v_uv);
     vec3 tint = mix(vec3(1.0), u_afterglow_color, u_afterglow);
-    vec3 faded = prev.rgb * u_fade * tint;
+    vec3 faded = decay(prev.rgb) * tint;
     frag_color = vec4(cur.rgb + faded, 1.0);
 }
 "#;
@@ -179,12 +210,14 @@ uniform float u_exposure;
 uniform float u_glow_amount;
 uniform float u_scatter_amount;
 uniform float u_overexposure;
+uniform int u_tone_curve;        // 0 = exponential, 1 = Reinhard, 2 = filmic, 3 = linear
+uniform float u_gamma;
 uniform float u_saturation;
 uniform float u_ambient;
 uniform float u_noise;
 uniform float u_time;
 uniform int u_reflection_mode;
-uniform bool u_goniometer;
+uniform bool u_transparent;
 
 out vec4 frag_color;
 
@@ -198,17 +231,6 @@ float hash(vec2 p) {
 void main() {
     vec2 uv = v_uv;
 
-    // Goniometer: 45 degree rotation around center (Mid/Side display)
-    if (u_goniometer) {
-        vec2 centered = uv - 0.5;
-        float cos45 = 0.70710678;
-        float sin45 = 0.70710678;
-        uv = vec2(
-            centered.x * cos45 - centered.y * sin45,
-            centered.x * sin45 + centered.y * cos45
-        ) + 0.5;
-    }
-
     // Reflection modes
     if (u_reflection_mode == 1) {
         // Horizontal mirror
@@ -221,35 +243,74 @@ void main() {
         uv = abs(uv - 0.5) + 0.5;
     }
 
-    float line_val = texture(u_persisted, uv).r;
-    float tight = texture(u_tight_blur, uv).r;
-    float wide = texture(u_wide_blur, uv).r;
+    // Fresh beam is equal in every channel; trails carry the afterglow
+    // tint and per-channel persistence
+    vec3 line_val = texture(u_persisted, uv).rgb;
+    vec3 tight = texture(u_tight_blur, uv).rgb;
+    vec3 wide = texture(u_wide_blur, uv).rgb;
 
     // Combine line + bloom
-    float bloom = u_glow_amount * (tight + u_scatter_amount * wide);
-    float L = line_val + bloom;
-
-    // Tone mapping: 1 - exp(-exposure * L)
-    float mapped = 1.0 - exp(-u_exposure * L);
+    vec3 bloom = u_glow_amount * (tight + u_scatter_amount * wide);
+    vec3 L = line_val + bloom;
+
+    // Tone mapping of the exposed energy
+    vec3 x = u_exposure * L;
+    vec3 mapped;
+    if (u_tone_curve == 1) {
+        mapped = x / (1.0 + x);
+    } else if (u_tone_curve == 2) {
+        // Narkowicz's fit of the ACES filmic curve
+        mapped = clamp((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14), 0.0, 1.0);
+    } else if (u_tone_curve == 3) {
+        mapped = clamp(x, 0.0, 1.0);
+    } else {
+        mapped = 1.0 - exp(-x);
+    }
 
     // Apply color
     vec3 col = u_color * mapped;
 
     // Overexposure: blend toward white at high intensity
-    float overex = smoothstep(0.7, 1.0, mapped) * u_overexposure;
+    float overex = smoothstep(0.7, 1.0, max(mapped.r, max(mapped.g, mapped.b))) * u_overexposure;
     col = mix(col, vec3(1.0), overex);
 
     // Saturation adjustment
     float lum = dot(col, vec3(0.299, 0.587, 0.114));
     col = mix(vec3(lum), col, u_saturation);
 
-    // Ambient tint
-    col += u_color * u_ambient;
+    // Ambient tint, which would veil a transparent background
+    if (!u_transparent) {
+        col += u_color * u_ambient;
+    }
 
     // Noise grain
     float n = hash(v_uv * 1000.0 + u_time) * u_noise;
     col += vec3(n);
 
-    frag_color = vec4(col, 1.0);
+    // Output gamma
+    col = pow(max(col, vec3(0.0)), vec3(1.0 / u_gamma));
+
+    if (u_transparent) {
+        // Premultiplied alpha keyed on brightness: black is fully transparent
+        float alpha = clamp(max(col.r, max(col.g, col.b)), 0.0, 1.0);
+        frag_color = vec4(min(col, vec3(alpha)), alpha);
+    } else {
+        frag_color = vec4(col, 1.0);
+    }
+}
+"#;
+
+// ── Copy shader ─────────────────────────────────────────────────────
+
+pub const COPY_FRAGMENT: &str = r#"#version 330 core
+
+in vec2 v_uv;
+
+uniform sampler2D u_texture;
+
+out vec4 frag_color;
+
+void main() {
+    frag_color = texture(u_texture, v_uv);
 }
 "#;
@@ -1,26 +1,47 @@
 use nih_plug::prelude::*;
 use nih_plug_egui::{create_egui_editor, egui, EguiState};
 use osci_gui::{GpuScopeState, VisBuffer};
-use osci_visualizer::VisualiserSettings;
+use osci_visualizer::{AfterglowCurve, BlendMode, ToneCurve, VisualiserSettings};
+use spectrum::SpectrumAnalyzer;
 use std::sync::{Arc, Mutex};
+use trigger::{Slope, TriggerSettings};
+
+pub mod spectrum;
+pub mod trigger;
 
 const VIS_BUFFER_SIZE: usize = 512;
 
 pub struct RusciPlugin {
     params: Arc<RusciParams>,
     vis_buffer: Arc<Mutex<VisBuffer>>,
+    // Copy of the raw input for each channel, blended back in by `mix`
+    dry: [Vec<f32>; 2],
+    /// Draw spectrum bars on the scope instead of the raw XY signal.
+    spectrum_mode: Arc<Mutex<bool>>,
+    spectrum: SpectrumAnalyzer,
+    /// Edge trigger that aligns the drawn window to the signal.
+    trigger: Arc<Mutex<TriggerSettings>>,
 }
 
 #[derive(Params)]
 struct RusciParams {
     #[persist = "editor-state"]
     editor_state: Arc<EguiState>,
+
+    // Blend between the raw input (0) and the processed signal (1)
+    #[id = "mix"]
+    mix: FloatParam,
 }
 
 impl Default for RusciParams {
     fn default() -> Self {
         Self {
             editor_state: EguiState::from_size(520, 850),
+
+            mix: FloatParam::new("Mix", 1.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_unit("%")
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
         }
     }
 }
@@ -30,6 +51,10 @@ impl Default for RusciPlugin {
         Self {
             params: Arc::new(RusciParams::default()),
             vis_buffer: Arc::new(Mutex::new(VisBuffer::default())),
+            dry: [Vec::new(), Vec::new()],
+            spectrum_mode: Arc::new(Mutex::new(false)),
+            spectrum: SpectrumAnalyzer::new(44100.0),
+            trigger: Arc::new(Mutex::new(TriggerSettings::default())),
         }
     }
 }
@@ -60,12 +85,16 @@ impl Plugin for RusciPlugin {
     fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
         let vis_buffer = self.vis_buffer.clone();
         let scope_state = Arc::new(Mutex::new(GpuScopeState::default()));
+        let editor_state = self.params.editor_state.clone();
+        let params = self.params.clone();
+        let spectrum_mode = self.spectrum_mode.clone();
+        let trigger = self.trigger.clone();
 
         create_egui_editor(
             self.params.editor_state.clone(),
             (),
             |_, _| {},
-            move |egui_ctx, _setter, _state| {
+            move |egui_ctx, setter, _state| {
                 osci_gui::theme::apply(egui_ctx);
 
                 // Snapshot the vis buffer for this frame
@@ -79,12 +108,22 @@ impl Plugin for RusciPlugin {
 
                 let scope = scope_state.clone();
 
-                egui::CentralPanel::default().show(egui_ctx, |ui| {
+                osci_gui::show_resizable(egui_ctx, &editor_state, |ui| {
                     egui::ScrollArea::vertical().show(ui, |ui| {
                         // XY Scope (GPU-rendered)
                         ui.heading("XY Scope");
                         ui.separator();
-                        osci_gui::scope::draw_gpu_scope(ui, &vis, scope.clone());
+                        osci_gui::scope::draw_gpu_scope(ui, &vis, 0.0, scope.clone(), false);
+
+                        ui.add_space(12.0);
+
+                        ui.add(nih_plug_egui::widgets::ParamSlider::for_param(&params.mix, setter));
+                        if let Ok(mut spectrum) = spectrum_mode.lock() {
+                            ui.checkbox(&mut spectrum, "Spectrum bars (FFT of the summed input)");
+                        }
+                        if let Ok(mut trigger) = trigger.lock() {
+                            draw_trigger_settings(ui, &mut trigger);
+                        }
 
                         ui.add_space(12.0);
 
@@ -101,9 +140,13 @@ impl Plugin for RusciPlugin {
     fn initialize(
         &mut self,
         _audio_io_layout: &AudioIOLayout,
-        _buffer_config: &BufferConfig,
+        buffer_config: &BufferConfig,
         _context: &mut impl InitContext<Self>,
     ) -> bool {
+        for channel in &mut self.dry {
+            channel.resize(buffer_config.max_buffer_size as usize, 0.0);
+        }
+        self.spectrum = SpectrumAnalyzer::new(buffer_config.sample_rate);
         true
     }
 
@@ -114,26 +157,79 @@ impl Plugin for RusciPlugin {
         _context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
         let num_samples = buffer.samples();
+        let mix = self.params.mix.value();
+
+        // nih-plug passes input data through to output for matching layouts,
+        // so the buffer starts out holding the raw input
+        let blend = mix < 1.0;
+        if blend {
+            for (dry, channel) in self.dry.iter_mut().zip(buffer.as_slice_immutable()) {
+                let len = dry.len().min(channel.len());
+                dry[..len].copy_from_slice(&channel[..len]);
+            }
+        }
+
+        // Processing of the wet signal goes here; for now it is the input as is
 
-        // Audio passthrough: input is already in the buffer, nothing to do.
-        // nih-plug passes input data through to output by default for matching layouts.
+        if blend {
+            for (dry, channel) in self.dry.iter().zip(buffer.as_slice()) {
+                mix_dry_wet(dry, channel, mix);
+            }
+        }
 
-        // Update vis buffer with the last VIS_BUFFER_SIZE samples
+        let spectrum_mode = self.spectrum_mode.lock().is_ok_and(|m| *m);
+        if spectrum_mode {
+            if let [x, y, ..] = buffer.as_slice_immutable() {
+                self.spectrum.push(x, y);
+            }
+        }
+
+        let trigger = self.trigger.lock().map(|t| *t).unwrap_or_default();
+
+        // Update vis buffer with the spectrum bars, or VIS_BUFFER_SIZE samples
+        // from the trigger point, falling back to the last ones in the block
+        // when no crossing leaves room for a whole window
         if let Ok(mut vis) = self.vis_buffer.lock() {
-            let output = buffer.as_slice();
-            let copy_len = num_samples.min(VIS_BUFFER_SIZE);
-            let src_start = num_samples.saturating_sub(VIS_BUFFER_SIZE);
-
-            vis.x.clear();
-            vis.y.clear();
-            vis.x.extend_from_slice(&output[0][src_start..src_start + copy_len]);
-            vis.y.extend_from_slice(&output[1][src_start..src_start + copy_len]);
+            let vis = &mut *vis;
+            if spectrum_mode {
+                self.spectrum.write_bars(&mut vis.x, &mut vis.y);
+            } else if let [x, y, ..] = buffer.as_slice_immutable() {
+                let src_start = trigger::window_start(&x[..num_samples], VIS_BUFFER_SIZE, &trigger);
+                let copy_len = (num_samples - src_start).min(VIS_BUFFER_SIZE);
+
+                vis.x.clear();
+                vis.y.clear();
+                vis.x.extend_from_slice(&x[src_start..src_start + copy_len]);
+                vis.y.extend_from_slice(&y[src_start..src_start + copy_len]);
+            }
         }
 
         ProcessStatus::Normal
     }
 }
 
+/// Blend `wet` towards `dry` in place: `mix` 0 gives `dry`, 1 leaves `wet`.
+/// Only the shorter of the two slices is processed.
+fn mix_dry_wet(dry: &[f32], wet: &mut [f32], mix: f32) {
+    for (w, d) in wet.iter_mut().zip(dry) {
+        *w = d + (*w - d) * mix;
+    }
+}
+
+fn draw_trigger_settings(ui: &mut egui::Ui, t: &mut TriggerSettings) {
+    ui.checkbox(&mut t.trigger_enabled, "Trigger (align to X crossing the level)");
+    ui.add_enabled_ui(t.trigger_enabled, |ui| {
+        ui.add(egui::Slider::new(&mut t.trigger_level, -1.0..=1.0).text("Trigger Level"));
+        egui::ComboBox::from_label("Slope")
+            .selected_text(t.slope.label())
+            .show_ui(ui, |ui| {
+                for slope in Slope::ALL {
+                    ui.selectable_value(&mut t.slope, slope, slope.label());
+                }
+            });
+    });
+}
+
 fn draw_visualizer_settings(ui: &mut egui::Ui, s: &mut VisualiserSettings) {
     // -- Beam --
     ui.heading("Beam");
@@ -155,7 +251,26 @@ fn draw_visualizer_settings(ui: &mut egui::Ui, s: &mut VisualiserSettings) {
     ui.add(egui::Slider::new(&mut s.glow_amount, 0.0..=2.0).text("Glow"));
     ui.add(egui::Slider::new(&mut s.scatter_amount, 0.0..=2.0).text("Scatter"));
     ui.add(egui::Slider::new(&mut s.persistence, 0.0..=1.0).text("Persistence"));
+    let mut per_channel = s.persistence_rgb.is_some();
+    if ui.checkbox(&mut per_channel, "Per-channel persistence").changed() {
+        s.persistence_rgb = per_channel.then_some([s.persistence; 3]);
+    }
+    if let Some(rgb) = &mut s.persistence_rgb {
+        ui.horizontal(|ui| {
+            ui.label("Persistence");
+            ui.add(egui::Slider::new(&mut rgb[0], 0.0..=1.0).text("R"));
+            ui.add(egui::Slider::new(&mut rgb[1], 0.0..=1.0).text("G"));
+            ui.add(egui::Slider::new(&mut rgb[2], 0.0..=1.0).text("B"));
+        });
+    }
     ui.add(egui::Slider::new(&mut s.afterglow, 0.0..=1.0).text("Afterglow"));
+    egui::ComboBox::from_label("Afterglow Curve")
+        .selected_text(s.afterglow_curve.label())
+        .show_ui(ui, |ui| {
+            for curve in AfterglowCurve::ALL {
+                ui.selectable_value(&mut s.afterglow_curve, curve, curve.label());
+            }
+        });
     ui.horizontal(|ui| {
         ui.label("Afterglow Color");
         ui.add(egui::Slider::new(&mut s.afterglow_color[0], 0.0..=1.0).text("R"));
@@ -170,6 +285,21 @@ fn draw_visualizer_settings(ui: &mut egui::Ui, s: &mut VisualiserSettings) {
     ui.separator();
     ui.add(egui::Slider::new(&mut s.exposure, 0.5..=5.0).text("Exposure"));
     ui.add(egui::Slider::new(&mut s.overexposure, 0.0..=1.0).text("Overexposure"));
+    egui::ComboBox::from_label("Tone Curve")
+        .selected_text(s.tone_curve.label())
+        .show_ui(ui, |ui| {
+            for curve in ToneCurve::ALL {
+                ui.selectable_value(&mut s.tone_curve, curve, curve.label());
+            }
+        });
+    egui::ComboBox::from_label("Blend")
+        .selected_text(s.blend_mode.label())
+        .show_ui(ui, |ui| {
+            for mode in BlendMode::ALL {
+                ui.selectable_value(&mut s.blend_mode, mode, mode.label());
+            }
+        });
+    ui.add(egui::Slider::new(&mut s.gamma, 0.5..=3.0).text("Gamma"));
     ui.add(egui::Slider::new(&mut s.saturation, 0.0..=2.0).text("Saturation"));
     ui.add(egui::Slider::new(&mut s.ambient, 0.0..=0.1).text("Ambient"));
     ui.add(egui::Slider::new(&mut s.noise, 0.0..=0.05).text("Noise"));
@@ -197,6 +327,26 @@ fn draw_visualizer_settings(ui: &mut egui::Ui, s: &mut VisualiserSettings) {
         });
 
     ui.checkbox(&mut s.goniometer, "Goniometer (Mid/Side rotation)");
+    ui.checkbox(&mut s.transparent_background, "Transparent background (alpha from brightness)");
+
+    ui.add_space(8.0);
+
+    // -- Position --
+    ui.heading("Position");
+    ui.separator();
+    ui.add(egui::Slider::new(&mut s.rotation, -180.0..=180.0).text("Rotation").suffix("°"));
+    ui.add(egui::Slider::new(&mut s.zoom, 0.25..=4.0).logarithmic(true).text("Zoom"));
+    ui.add(egui::Slider::new(&mut s.offset[0], -1.0..=1.0).text("X Offset"));
+    ui.add(egui::Slider::new(&mut s.offset[1], -1.0..=1.0).text("Y Offset"));
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut s.flip_x, "Flip X");
+        ui.checkbox(&mut s.flip_y, "Flip Y");
+    });
+    if ui.button("Reset Position").clicked() {
+        s.rotation = 0.0;
+        s.zoom = 1.0;
+        s.offset = [0.0, 0.0];
+    }
 }
 
 impl ClapPlugin for RusciPlugin {
@@ -222,3 +372,34 @@ impl Vst3Plugin for RusciPlugin {
 
 nih_export_clap!(RusciPlugin);
 nih_export_vst3!(RusciPlugin);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_blends_dry_into_wet() {
+        let dry = [1.0, -1.0, 0.5, 0.0];
+        let processed = [0.0, 1.0, 0.5, -0.8];
+
+        let mut wet = processed;
+        mix_dry_wet(&dry, &mut wet, 1.0);
+        assert_eq!(wet, processed);
+
+        let mut wet = processed;
+        mix_dry_wet(&dry, &mut wet, 0.0);
+        assert_eq!(wet, dry);
+
+        let mut wet = processed;
+        mix_dry_wet(&dry, &mut wet, 0.25);
+        let expected = [0.75, -0.5, 0.5, -0.2];
+        for (w, e) in wet.iter().zip(expected) {
+            assert!((w - e).abs() < 1e-6, "{w} != {e}");
+        }
+
+        // A short dry buffer leaves the rest of the wet one alone
+        let mut wet = processed;
+        mix_dry_wet(&dry[..2], &mut wet, 0.0);
+        assert_eq!(wet, [1.0, -1.0, 0.5, -0.8]);
+    }
+}
@@ -0,0 +1,207 @@
+//! Spectrum analyzer that turns the input into bars for the scope.
+//!
+//! The stereo input is summed to mono and kept in a sliding window. Every
+//! [`HOP_SIZE`] samples the latest [`FFT_SIZE`] are Hann windowed and
+//! transformed, so successive analyses overlap by three quarters however the
+//! host splits its blocks. Bins are grouped into log-spaced bars whose
+//! heights rise at once and fall back gradually, which keeps them from
+//! flickering between analyses.
+
+use osci_core::{Line, Shape};
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+/// Samples in each analysis window.
+pub const FFT_SIZE: usize = 2048;
+
+/// Samples between analyses.
+pub const HOP_SIZE: usize = FFT_SIZE / 4;
+
+/// Number of bars drawn across the scope.
+pub const NUM_BARS: usize = 48;
+
+/// Frequency at the left edge of the first bar.
+const LOWEST_HZ: f32 = 20.0;
+
+/// Level drawn as an empty bar; a full-scale sine reaches the top.
+const FLOOR_DB: f32 = -72.0;
+
+/// Fraction of a bar's height kept per analysis while it falls.
+const BAR_RELEASE: f32 = 0.85;
+
+pub struct SpectrumAnalyzer {
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    // Ring buffer of the last FFT_SIZE mono samples
+    history: Vec<f32>,
+    write_pos: usize,
+    since_analysis: usize,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    magnitudes: Vec<f32>,
+    bars: [f32; NUM_BARS],
+    sample_rate: f32,
+}
+
+impl SpectrumAnalyzer {
+    pub fn new(sample_rate: f32) -> Self {
+        let fft = FftPlanner::new().plan_fft_forward(FFT_SIZE);
+        let scratch = vec![Complex::default(); fft.get_inplace_scratch_len()];
+        let window = (0..FFT_SIZE)
+            .map(|i| 0.5 - 0.5 * (TAU * i as f32 / FFT_SIZE as f32).cos())
+            .collect();
+        Self {
+            fft,
+            window,
+            history: vec![0.0; FFT_SIZE],
+            write_pos: 0,
+            since_analysis: 0,
+            buffer: vec![Complex::default(); FFT_SIZE],
+            scratch,
+            magnitudes: vec![0.0; FFT_SIZE / 2],
+            bars: [0.0; NUM_BARS],
+            sample_rate,
+        }
+    }
+
+    /// Feed a block of stereo input, analyzing every [`HOP_SIZE`] samples.
+    pub fn push(&mut self, left: &[f32], right: &[f32]) {
+        for (l, r) in left.iter().zip(right) {
+            self.history[self.write_pos] = 0.5 * (l + r);
+            self.write_pos = (self.write_pos + 1) % FFT_SIZE;
+            self.since_analysis += 1;
+            if self.since_analysis >= HOP_SIZE {
+                self.since_analysis = 0;
+                self.analyze();
+            }
+        }
+    }
+
+    /// Amplitude of each bin from the last analysis, where a full-scale
+    /// sine centred on a bin reads 1.
+    pub fn magnitudes(&self) -> &[f32] {
+        &self.magnitudes
+    }
+
+    /// Bar heights in `[0, 1]`, lowest frequency first.
+    pub fn bars(&self) -> &[f32; NUM_BARS] {
+        &self.bars
+    }
+
+    /// One vertical line per bar, rising from the bottom of the scope.
+    pub fn bar_lines(&self) -> impl Iterator<Item = Line> + '_ {
+        self.bars.iter().enumerate().map(|(i, height)| {
+            let x = -1.0 + (i as f32 + 0.5) * 2.0 / NUM_BARS as f32;
+            Line::new_2d(x, -1.0, x, -1.0 + 2.0 * height)
+        })
+    }
+
+    /// Trace the bars into `x` and `y`, replacing their contents. The beam
+    /// goes up and back down each bar before moving on to the next.
+    pub fn write_bars(&self, x: &mut Vec<f32>, y: &mut Vec<f32>) {
+        x.clear();
+        y.clear();
+        for line in self.bar_lines() {
+            for t in [0.0, 1.0, 0.0] {
+                let point = line.next_vector(t);
+                x.push(point.x);
+                y.push(point.y);
+            }
+        }
+    }
+
+    fn analyze(&mut self) {
+        // Oldest sample first
+        for (i, value) in self.buffer.iter_mut().enumerate() {
+            let sample = self.history[(self.write_pos + i) % FFT_SIZE];
+            *value = Complex::new(sample * self.window[i], 0.0);
+        }
+        self.fft.process_with_scratch(&mut self.buffer, &mut self.scratch);
+
+        // A Hann window halves the amplitude, and half of it lands in the negative bins
+        let scale = 4.0 / FFT_SIZE as f32;
+        for (magnitude, bin) in self.magnitudes.iter_mut().zip(&self.buffer) {
+            *magnitude = bin.norm() * scale;
+        }
+
+        for (i, bar) in self.bars.iter_mut().enumerate() {
+            let (first, last) = bar_bins(i, self.sample_rate);
+            let peak = self.magnitudes[first..=last].iter().fold(0.0f32, |a, &b| a.max(b));
+            let height = ((20.0 * peak.max(1e-9).log10() - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0);
+            *bar = height.max(*bar * BAR_RELEASE);
+        }
+    }
+}
+
+/// The first and last FFT bin that bar `index` covers. Bars are spaced
+/// evenly in log frequency from [`LOWEST_HZ`] up to Nyquist, and each
+/// covers at least one bin.
+pub fn bar_bins(index: usize, sample_rate: f32) -> (usize, usize) {
+    let nyquist = sample_rate / 2.0;
+    let bin_hz = sample_rate / FFT_SIZE as f32;
+    let edge = |i: usize| LOWEST_HZ * (nyquist / LOWEST_HZ).powf(i as f32 / NUM_BARS as f32);
+    let last_bin = FFT_SIZE / 2 - 1;
+
+    let first = ((edge(index) / bin_hz).round() as usize).min(last_bin);
+    let last = ((edge(index + 1) / bin_hz).round() as usize)
+        .saturating_sub(1)
+        .clamp(first, last_bin);
+    (first, last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sine_makes_one_dominant_bar() {
+        let sample_rate = 48000.0f32;
+        let bin = 93;
+        let hz = bin as f64 * sample_rate as f64 / FFT_SIZE as f64;
+        // Long enough for the bars lit by the onset to have fallen away
+        let sine: Vec<f32> = (0..FFT_SIZE * 16)
+            .map(|n| 0.5 * (std::f64::consts::TAU * hz * n as f64 / sample_rate as f64).sin() as f32)
+            .collect();
+
+        let mut analyzer = SpectrumAnalyzer::new(sample_rate);
+        // Uneven blocks, as a host might send
+        for block in sine.chunks(300) {
+            analyzer.push(block, block);
+        }
+
+        let peak = analyzer
+            .magnitudes()
+            .iter()
+            .enumerate()
+            .fold((0, 0.0), |best, (i, &m)| if m > best.1 { (i, m) } else { best });
+        assert_eq!(peak.0, bin);
+        assert!((peak.1 - 0.5).abs() < 0.01, "amplitude {}", peak.1);
+
+        let expected = (0..NUM_BARS)
+            .find(|&i| {
+                let (first, last) = bar_bins(i, sample_rate);
+                (first..=last).contains(&bin)
+            })
+            .unwrap();
+        let bars = analyzer.bars();
+        for (i, height) in bars.iter().enumerate() {
+            if i == expected {
+                assert!(*height > 0.8, "bar {i} at {height}");
+            } else if i.abs_diff(expected) > 1 {
+                // Hann leakage reaches one bin either side at most
+                assert!(*height < 0.1, "bar {i} at {height}");
+            } else {
+                assert!(*height < bars[expected], "bar {i} at {height}");
+            }
+        }
+
+        let mut x = Vec::new();
+        let mut y = Vec::new();
+        analyzer.write_bars(&mut x, &mut y);
+        assert_eq!(x.len(), NUM_BARS * 3);
+        assert_eq!(y[expected * 3], -1.0);
+        assert!(y[expected * 3 + 1] > 0.5);
+    }
+}
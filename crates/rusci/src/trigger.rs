@@ -0,0 +1,106 @@
+//! Edge trigger that keeps periodic signals still on the scope.
+//!
+//! Without a trigger each block is drawn from wherever the host happened to
+//! split it, so a steady waveform drifts sideways. Like the trigger on a
+//! bench scope, this finds where the X channel crosses a level in the chosen
+//! direction and starts the drawn window there.
+
+/// Direction the signal must cross the trigger level in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slope {
+    Rising,
+    Falling,
+}
+
+impl Slope {
+    pub const ALL: [Slope; 2] = [Slope::Rising, Slope::Falling];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Slope::Rising => "Rising",
+            Slope::Falling => "Falling",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriggerSettings {
+    pub trigger_enabled: bool,
+    pub trigger_level: f32,
+    pub slope: Slope,
+}
+
+impl Default for TriggerSettings {
+    fn default() -> Self {
+        Self {
+            trigger_enabled: false,
+            trigger_level: 0.0,
+            slope: Slope::Rising,
+        }
+    }
+}
+
+/// Index of the first sample at or past `level` whose predecessor was on
+/// the other side of it, moving in the direction of `slope`. Returns `None`
+/// if the block never crosses.
+pub fn find_trigger(samples: &[f32], level: f32, slope: Slope) -> Option<usize> {
+    samples
+        .windows(2)
+        .position(|pair| match slope {
+            Slope::Rising => pair[0] < level && pair[1] >= level,
+            Slope::Falling => pair[0] > level && pair[1] <= level,
+        })
+        .map(|i| i + 1)
+}
+
+/// Start of the `window` samples of `samples` to draw: the first trigger
+/// point a whole window still fits after, or the last `window` samples when
+/// the trigger is off or there's no such crossing.
+pub fn window_start(samples: &[f32], window: usize, settings: &TriggerSettings) -> usize {
+    let last_start = samples.len().saturating_sub(window);
+    let triggered = if settings.trigger_enabled && samples.len() >= window {
+        find_trigger(&samples[..=last_start], settings.trigger_level, settings.slope)
+    } else {
+        None
+    };
+    triggered.unwrap_or(last_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::TAU;
+
+    #[test]
+    fn finds_crossing_in_sine() {
+        // Period of 100 samples, rising through zero halfway between 74 and 75
+        let sine: Vec<f32> = (0..400)
+            .map(|n| (TAU * (n as f32 - 74.5) / 100.0).sin())
+            .collect();
+
+        assert_eq!(find_trigger(&sine, 0.0, Slope::Rising), Some(75));
+        assert_eq!(find_trigger(&sine, 0.0, Slope::Falling), Some(25));
+        // sin reaches 0.5 a twelfth of a period after the zero crossing
+        assert_eq!(find_trigger(&sine, 0.5, Slope::Rising), Some(83));
+        assert_eq!(find_trigger(&sine, 1.5, Slope::Rising), None);
+    }
+
+    #[test]
+    fn window_always_fits_after_the_trigger() {
+        let sine: Vec<f32> = (0..400)
+            .map(|n| (TAU * (n as f32 - 74.5) / 100.0).sin())
+            .collect();
+        let settings = TriggerSettings {
+            trigger_enabled: true,
+            ..TriggerSettings::default()
+        };
+
+        assert_eq!(window_start(&sine, 300, &settings), 75);
+        // Crossings at 75 and 175 leave too little after them, so the
+        // window falls back to the end of the block
+        assert_eq!(window_start(&sine[..350], 300, &settings), 50);
+        assert_eq!(window_start(&sine[..200], 300, &settings), 0);
+        // With the trigger off it's always the end of the block
+        assert_eq!(window_start(&sine, 300, &TriggerSettings::default()), 100);
+    }
+}